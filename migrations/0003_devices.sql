@@ -0,0 +1,18 @@
+-- Persistent registry of physical devices, keyed by serial number
+CREATE TABLE IF NOT EXISTS devices (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    serial_number VARCHAR(255) UNIQUE NOT NULL,
+    manufacturer VARCHAR(255) NOT NULL DEFAULT 'Unknown',
+    model VARCHAR(255) NOT NULL DEFAULT 'Unknown',
+    device_type VARCHAR(100) NOT NULL DEFAULT 'Unknown',
+    capacity_bytes BIGINT,
+    asset_tag VARCHAR(255),
+    notes TEXT,
+    registered_by UUID REFERENCES users(id),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+ALTER TABLE certificates ADD COLUMN IF NOT EXISTS device_id UUID REFERENCES devices(id) ON DELETE SET NULL;
+
+CREATE INDEX IF NOT EXISTS idx_certificates_device_id ON certificates(device_id);
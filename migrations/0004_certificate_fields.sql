@@ -0,0 +1,22 @@
+-- Typed, indexed copies of the certificate fields used for filtering and search.
+-- certificate_data keeps the full JSON document; these columns are derived from it.
+ALTER TABLE certificates ADD COLUMN IF NOT EXISTS device_type VARCHAR(100);
+ALTER TABLE certificates ADD COLUMN IF NOT EXISTS serial_number VARCHAR(255);
+ALTER TABLE certificates ADD COLUMN IF NOT EXISTS success BOOLEAN;
+ALTER TABLE certificates ADD COLUMN IF NOT EXISTS issued_at TIMESTAMPTZ;
+
+-- Backfill rows stored before this migration from their JSON
+UPDATE certificates
+SET device_type = certificate_data::jsonb -> 'device_info' ->> 'device_type',
+    serial_number = certificate_data::jsonb -> 'device_info' ->> 'serial_number',
+    success = (certificate_data::jsonb -> 'sanitization_info' ->> 'success')::boolean,
+    issued_at = (certificate_data::jsonb ->> 'timestamp')::timestamptz
+WHERE certificate_data LIKE '{%';
+
+UPDATE certificates SET issued_at = created_at WHERE issued_at IS NULL;
+
+CREATE INDEX IF NOT EXISTS idx_certificates_user_issued_at ON certificates(user_id, issued_at DESC);
+CREATE INDEX IF NOT EXISTS idx_certificates_device_type ON certificates(device_type);
+CREATE INDEX IF NOT EXISTS idx_certificates_sanitization_method ON certificates(sanitization_method);
+CREATE INDEX IF NOT EXISTS idx_certificates_success ON certificates(success);
+CREATE INDEX IF NOT EXISTS idx_certificates_serial_number ON certificates(serial_number);
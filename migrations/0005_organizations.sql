@@ -0,0 +1,16 @@
+-- Organizations, so one server can host several clients without their data mixing.
+-- Users and certificates without an organization keep the old per-user scoping.
+CREATE TABLE IF NOT EXISTS organizations (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    name VARCHAR(255) UNIQUE NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+ALTER TABLE users ADD COLUMN IF NOT EXISTS organization_id UUID REFERENCES organizations(id);
+
+-- A certificate stays with the organization it was uploaded under, even if the
+-- uploader later moves to another organization
+ALTER TABLE certificates ADD COLUMN IF NOT EXISTS organization_id UUID REFERENCES organizations(id);
+
+CREATE INDEX IF NOT EXISTS idx_users_organization_id ON users(organization_id);
+CREATE INDEX IF NOT EXISTS idx_certificates_organization_id ON certificates(organization_id);
@@ -0,0 +1,6 @@
+-- Revocation of certificates issued in error (wrong drive recorded, wipe later found
+-- to have failed). Revoked certificates are kept for the audit trail.
+ALTER TABLE certificates ADD COLUMN IF NOT EXISTS revoked BOOLEAN NOT NULL DEFAULT FALSE;
+ALTER TABLE certificates ADD COLUMN IF NOT EXISTS revoked_at TIMESTAMPTZ;
+ALTER TABLE certificates ADD COLUMN IF NOT EXISTS revocation_reason TEXT;
+ALTER TABLE certificates ADD COLUMN IF NOT EXISTS revoked_by UUID REFERENCES users(id);
@@ -0,0 +1,9 @@
+-- Registered devices belong to the organization of the user who registered them, so
+-- one organization can't see or edit another's inventory
+ALTER TABLE devices ADD COLUMN IF NOT EXISTS organization_id UUID REFERENCES organizations(id);
+
+UPDATE devices SET organization_id = users.organization_id
+FROM users
+WHERE devices.registered_by = users.id AND devices.organization_id IS NULL;
+
+CREATE INDEX IF NOT EXISTS idx_devices_organization_id ON devices(organization_id);
@@ -6,6 +6,8 @@ use sha2::{Sha256, Digest};
 use jsonwebtoken::{encode, Header, EncodingKey};
 use uuid::Uuid;
 
+use crate::auth::middleware::jwt_expiry_seconds;
+
 #[derive(Debug, Serialize, Deserialize)]
 struct LoginRequest {
     username: String,
@@ -18,6 +20,7 @@ struct User {
     username: String,
     email: String,
     role: String,
+    organization_id: Option<Uuid>,
     created_at: chrono::DateTime<chrono::Utc>,
     last_login: Option<chrono::DateTime<chrono::Utc>>,
     is_active: bool,
@@ -34,6 +37,7 @@ struct Claims {
     sub: String,
     exp: usize,
     role: String,
+    org_id: Option<String>,
 }
 
 pub fn create_auth_routes(pool: Arc<PgPool>) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
@@ -69,7 +73,7 @@ async fn login_handler(
     let password_hash = hash_password(&body.password);
     
     let user_row = sqlx::query(
-        "SELECT id, username, email, role, created_at, last_login, is_active 
+        "SELECT id, username, email, role, organization_id, created_at, last_login, is_active 
          FROM users WHERE username = $1 AND password_hash = $2 AND is_active = true"
     )
     .bind(&body.username)
@@ -84,6 +88,7 @@ async fn login_handler(
                 username: row.get("username"),
                 email: row.get("email"),
                 role: row.get("role"),
+                organization_id: row.get("organization_id"),
                 created_at: row.get("created_at"),
                 last_login: row.get("last_login"),
                 is_active: row.get("is_active"),
@@ -98,8 +103,9 @@ async fn login_handler(
             // Create JWT token
             let claims = Claims {
                 sub: user.username.clone(),
-                exp: (chrono::Utc::now() + chrono::Duration::hours(24)).timestamp() as usize,
+                exp: (chrono::Utc::now() + chrono::Duration::seconds(jwt_expiry_seconds())).timestamp() as usize,
                 role: user.role.clone(),
+                org_id: user.organization_id.map(|id| id.to_string()),
             };
 
             let secret = std::env::var("JWT_SECRET").unwrap_or_else(|_| "your-secret-key".to_string());
@@ -8,6 +8,47 @@ pub struct Claims {
     pub sub: String,
     pub exp: usize,
     pub role: String,
+    /// Organization the user belongs to; tokens issued before organizations existed have none
+    #[serde(default)]
+    pub org_id: Option<String>,
+}
+
+impl Claims {
+    /// The organization every query made with this token must be scoped to
+    pub fn organization_id(&self) -> Option<uuid::Uuid> {
+        self.org_id.as_deref().and_then(|id| uuid::Uuid::parse_str(id).ok())
+    }
+
+    pub fn is_org_admin(&self) -> bool {
+        self.role == "org_admin" && self.organization_id().is_some()
+    }
+}
+
+/// Default token lifetime when neither `JWT_EXPIRY_SECONDS` nor `JWT_EXPIRATION_HOURS` is set (24 hours)
+pub const DEFAULT_JWT_EXPIRY_SECONDS: i64 = 24 * 60 * 60;
+
+/// Token lifetime in seconds, read from `JWT_EXPIRY_SECONDS` or else
+/// `JWT_EXPIRATION_HOURS` (the setting in config/server.conf).
+///
+/// There is no refresh-token endpoint yet, so once a token expires the client
+/// has to log in again. A refresh endpoint should issue tokens using this same
+/// lifetime so short-lived deployments stay short-lived.
+///
+/// packages/ubuntu_server/src/auth.rs keeps a copy of this function; this one is
+/// authoritative, so change it first and mirror the change there.
+pub fn jwt_expiry_seconds() -> i64 {
+    let seconds = std::env::var("JWT_EXPIRY_SECONDS")
+        .ok()
+        .and_then(|value| value.parse::<i64>().ok())
+        .filter(|seconds| *seconds > 0);
+    let hours = || {
+        std::env::var("JWT_EXPIRATION_HOURS")
+            .ok()
+            .and_then(|value| value.parse::<i64>().ok())
+            .filter(|hours| *hours > 0)
+            .and_then(|hours| hours.checked_mul(60 * 60))
+    };
+    seconds.or_else(hours).unwrap_or(DEFAULT_JWT_EXPIRY_SECONDS)
 }
 
 #[derive(Debug)]
@@ -26,10 +67,16 @@ async fn authorize(token: Option<String>) -> Result<Claims, warp::Rejection> {
             let token = token.strip_prefix("Bearer ").unwrap_or(&token);
             let secret = std::env::var("JWT_SECRET").unwrap_or_else(|_| "your-secret-key".to_string());
             
+            // Expiry is always enforced, with no grace period beyond the issued lifetime
+            let mut validation = Validation::default();
+            validation.validate_exp = true;
+            validation.leeway = 0;
+            validation.set_required_spec_claims(&["exp", "sub"]);
+            
             match decode::<Claims>(
                 token,
                 &DecodingKey::from_secret(secret.as_ref()),
-                &Validation::default(),
+                &validation,
             ) {
                 Ok(token_data) => Ok(token_data.claims),
                 Err(_) => Err(warp::reject::custom(AuthError)),
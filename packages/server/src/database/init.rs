@@ -4,6 +4,19 @@ use sha2::{Sha256, Digest};
 use uuid::Uuid;
 
 pub async fn init_database(pool: Arc<PgPool>) -> Result<(), sqlx::Error> {
+    // Create organizations table; each hosted client gets one so their data stays apart
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS organizations (
+            id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+            name VARCHAR(255) UNIQUE NOT NULL,
+            created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW()
+        )
+        "#,
+    )
+    .execute(pool.as_ref())
+    .await?;
+
     // Create users table
     sqlx::query(
         r#"
@@ -13,6 +26,7 @@ pub async fn init_database(pool: Arc<PgPool>) -> Result<(), sqlx::Error> {
             email VARCHAR(255) UNIQUE NOT NULL,
             password_hash VARCHAR(255) NOT NULL,
             role VARCHAR(50) NOT NULL DEFAULT 'user',
+            organization_id UUID REFERENCES organizations(id),
             created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
             last_login TIMESTAMP WITH TIME ZONE,
             is_active BOOLEAN DEFAULT true
@@ -22,12 +36,18 @@ pub async fn init_database(pool: Arc<PgPool>) -> Result<(), sqlx::Error> {
     .execute(pool.as_ref())
     .await?;
 
+    // Databases created before organizations existed
+    sqlx::query("ALTER TABLE users ADD COLUMN IF NOT EXISTS organization_id UUID REFERENCES organizations(id)")
+        .execute(pool.as_ref())
+        .await?;
+
     // Create sanitization_jobs table
     sqlx::query(
         r#"
         CREATE TABLE IF NOT EXISTS sanitization_jobs (
             id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
             user_id UUID REFERENCES users(id),
+            organization_id UUID REFERENCES organizations(id),
             drive_ids TEXT[] NOT NULL,
             method VARCHAR(50) NOT NULL,
             passes INTEGER NOT NULL,
@@ -43,6 +63,10 @@ pub async fn init_database(pool: Arc<PgPool>) -> Result<(), sqlx::Error> {
     .execute(pool.as_ref())
     .await?;
 
+    sqlx::query("ALTER TABLE sanitization_jobs ADD COLUMN IF NOT EXISTS organization_id UUID REFERENCES organizations(id)")
+        .execute(pool.as_ref())
+        .await?;
+
     // Create audit_logs table
     sqlx::query(
         r#"
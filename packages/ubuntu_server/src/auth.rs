@@ -0,0 +1,56 @@
+use jsonwebtoken::{decode, encode, DecodingKey, EncodingKey, Header, Validation};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Claims {
+    pub sub: String,
+    pub exp: usize,
+    pub role: String,
+}
+
+/// Default token lifetime when neither `JWT_EXPIRY_SECONDS` nor `JWT_EXPIRATION_HOURS` is set (24 hours)
+pub const DEFAULT_JWT_EXPIRY_SECONDS: i64 = 24 * 60 * 60;
+
+/// Token lifetime in seconds.
+///
+/// `JWT_EXPIRY_SECONDS` takes precedence, matching packages/server; otherwise
+/// `JWT_EXPIRATION_HOURS` from config/server.env is used.
+///
+/// Deliberately duplicated from packages/server/src/auth/middleware.rs, as the two
+/// servers share no crate. That copy is authoritative; keep this one in step with it.
+pub fn jwt_expiry_seconds() -> i64 {
+    let seconds = std::env::var("JWT_EXPIRY_SECONDS")
+        .ok()
+        .and_then(|value| value.parse::<i64>().ok())
+        .filter(|seconds| *seconds > 0);
+    let hours = || {
+        std::env::var("JWT_EXPIRATION_HOURS")
+            .ok()
+            .and_then(|value| value.parse::<i64>().ok())
+            .filter(|hours| *hours > 0)
+            .and_then(|hours| hours.checked_mul(60 * 60))
+    };
+    seconds.or_else(hours).unwrap_or(DEFAULT_JWT_EXPIRY_SECONDS)
+}
+
+/// Issue a token for `username` that expires after [`jwt_expiry_seconds`]
+pub fn create_token(username: &str, role: &str, secret: &str) -> Result<String, jsonwebtoken::errors::Error> {
+    let claims = Claims {
+        sub: username.to_string(),
+        exp: (chrono::Utc::now() + chrono::Duration::seconds(jwt_expiry_seconds())).timestamp() as usize,
+        role: role.to_string(),
+    };
+    encode(&Header::default(), &claims, &EncodingKey::from_secret(secret.as_ref()))
+}
+
+/// Check the signature and expiry of a bearer token
+pub fn verify_token(token: &str, secret: &str) -> Result<Claims, jsonwebtoken::errors::Error> {
+    // Expiry is always enforced, with no grace period beyond the issued lifetime
+    let mut validation = Validation::default();
+    validation.validate_exp = true;
+    validation.leeway = 0;
+    validation.set_required_spec_claims(&["exp", "sub"]);
+
+    decode::<Claims>(token, &DecodingKey::from_secret(secret.as_ref()), &validation)
+        .map(|token_data| token_data.claims)
+}
@@ -64,10 +64,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     };
 
     // CORS configuration
-    let cors = warp::cors()
-        .allow_any_origin()
-        .allow_headers(vec!["content-type", "authorization"])
-        .allow_methods(vec!["GET", "POST", "PUT", "DELETE", "OPTIONS"]);
+    let cors = cors_filter(server_port);
 
     // API routes
     let api = warp::path("api")
@@ -188,6 +185,62 @@ fn certificate_routes(
     get_certificates.or(get_certificate)
 }
 
+/// Build the CORS policy from `ALLOWED_ORIGINS` (comma-separated), defaulting to the
+/// dashboard origin. Any origin is only allowed when `CORS_ALLOW_ANY_ORIGIN=true` is set
+/// for local development.
+///
+/// Deliberately duplicated from packages/server, along with `parse_origin`, as the two
+/// servers share no crate. That copy is authoritative; keep this one in step with it apart
+/// from the allowed headers.
+fn cors_filter(port: u16) -> warp::cors::Builder {
+    let cors = warp::cors()
+        .allow_headers(vec!["content-type", "authorization"])
+        .allow_methods(vec!["GET", "POST", "PUT", "DELETE", "OPTIONS"]);
+
+    let allow_any = std::env::var("CORS_ALLOW_ANY_ORIGIN")
+        .map(|v| v == "true" || v == "1")
+        .unwrap_or(false);
+    if allow_any {
+        warn!("CORS_ALLOW_ANY_ORIGIN is set - accepting requests from any origin (development only)");
+        return cors.allow_any_origin();
+    }
+
+    let mut origins: Vec<String> = Vec::new();
+    if let Ok(configured) = std::env::var("ALLOWED_ORIGINS") {
+        for origin in configured.split(',').map(|o| o.trim().trim_end_matches('/')).filter(|o| !o.is_empty()) {
+            match parse_origin(origin) {
+                Some(origin) => origins.push(origin),
+                None => warn!("Ignoring invalid CORS origin: {}", origin),
+            }
+        }
+    }
+    if origins.is_empty() {
+        origins.push(format!("http://localhost:{}", port));
+    }
+
+    info!("CORS allowed origins: {}", origins.join(", "));
+    cors.allow_origins(origins.iter().map(String::as_str))
+}
+
+/// Normalise a configured origin to `scheme://host[:port]`, or `None` if it is not a
+/// plain http(s) origin. Anything with a path, query or credentials is rejected here
+/// because `allow_origins` panics on values it cannot parse.
+fn parse_origin(origin: &str) -> Option<String> {
+    let uri: warp::http::Uri = origin.parse().ok()?;
+    let scheme = uri.scheme_str()?.to_ascii_lowercase();
+    let authority = uri.authority()?;
+    let (_, rest) = origin.split_once("://")?;
+    if !(scheme == "http" || scheme == "https")
+        || authority.as_str() != rest
+        || rest.contains('@')
+        || authority.host().is_empty()
+        || (authority.port().is_none() && authority.as_str() != authority.host())
+    {
+        return None;
+    }
+    Some(format!("{}://{}", scheme, authority.as_str().to_ascii_lowercase()))
+}
+
 fn with_state(
     app_state: AppState,
 ) -> impl Filter<Extract = (AppState,), Error = Infallible> + Clone {
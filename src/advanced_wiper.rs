@@ -6,9 +6,14 @@ use std::io::{self, Write, Read, Seek, SeekFrom, BufWriter};
 use std::path::Path;
 use std::process::Command;
 use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::thread;
 use std::time::{Duration, Instant};
+use serde::{Deserialize, Serialize};
+use sha2::{Sha256, Digest};
 use crate::ata_commands::AtaInterface;
+use crate::certificate::{PassTiming, SectorSample, SmartSnapshot};
+use crate::sanitization::SanitizationPattern;
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum WipingAlgorithm {
@@ -16,17 +21,26 @@ pub enum WipingAlgorithm {
     NistClear,                    // Single pass overwrite
     NistPurge,                    // Multiple pass overwrite with verification
     NistDestroy,                  // Physical destruction guidance
+    NistAuto,                     // Clear or Purge picked per drive, see `nist_auto_algorithm`
     
     // Hardware-based Methods (Preferred for SSDs/NVMe)
     AtaSecureErase,               // ATA Secure Erase (Normal)
     AtaEnhancedSecureErase,       // ATA Secure Erase (Enhanced)
     NvmeSecureErase,              // NVMe Secure Erase
     NvmeCryptoErase,              // NVMe Cryptographic Erase
+    CryptoErase,                  // Destroy the keys of a LUKS/BitLocker volume
     
     // Software-based Overwrite Methods
     DoD522022M,                   // DoD 5220.22-M (3-pass)
     DoD522022MEce,                // DoD 5220.22-M ECE (7-pass)
     Gutmann,                      // Gutmann 35-pass method
+    HmgIs5Baseline,               // HMG Infosec Standard 5 Baseline (1-pass, verified)
+    HmgIs5Enhanced,               // HMG Infosec Standard 5 Enhanced (3-pass, verified)
+    RcmpTssitOps2,                // RCMP TSSIT OPS-II (7-pass, verified)
+    AustralianIsm,                // Australian ISM: verified overwrite on magnetic media, see `ism_algorithm`
+    Vsitr,                        // BSI VSITR (7-pass: 0x00/0xFF alternating, 0xAA)
+    Afssi5020,                    // US Air Force AFSSI-5020 (3-pass, verified)
+    NavsoP523926,                 // US Navy NAVSO P-5239-26 (3-pass, verified)
     Random,                       // Single random pass
     Zeros,                        // Single zero pass
     Ones,                         // Single ones pass
@@ -36,6 +50,7 @@ pub enum WipingAlgorithm {
     ThreePass,                    // 0x00, 0xFF, Random
     SevenPass,                    // Enhanced 7-pass method
     CustomPattern(Vec<u8>),       // User-defined pattern
+    MultiPass { passes: u32, pattern: OverwritePattern }, // Operator-chosen pass count and pattern
     
     // File System Specific
     FileSystemWipe,               // File system metadata wipe
@@ -44,9 +59,501 @@ pub enum WipingAlgorithm {
     
     // Quick Methods (Less secure but faster)
     QuickFormat,                  // Standard format
+    QuickSanitize,                // Partition tables, drive edges and FS metadata only
     FastZero,                     // Single fast zero pass
 }
 
+impl WipingAlgorithm {
+    /// The multi-pass overwrite as first offered; the operator adjusts both settings
+    pub const MULTI_PASS: WipingAlgorithm = WipingAlgorithm::MultiPass { passes: 3, pattern: OverwritePattern::Random };
+
+    /// Methods offered in the eraser method dropdown, in display order
+    pub const SELECTABLE: &'static [WipingAlgorithm] = &[
+        WipingAlgorithm::NistClear,
+        WipingAlgorithm::NistAuto,
+        WipingAlgorithm::DoD522022M,
+        WipingAlgorithm::DoD522022MEce,
+        WipingAlgorithm::Gutmann,
+        WipingAlgorithm::HmgIs5Baseline,
+        WipingAlgorithm::HmgIs5Enhanced,
+        WipingAlgorithm::RcmpTssitOps2,
+        WipingAlgorithm::AustralianIsm,
+        WipingAlgorithm::Vsitr,
+        WipingAlgorithm::Afssi5020,
+        WipingAlgorithm::NavsoP523926,
+        WipingAlgorithm::Random,
+        WipingAlgorithm::MULTI_PASS,
+        WipingAlgorithm::AtaSecureErase,
+        WipingAlgorithm::AtaEnhancedSecureErase,
+        WipingAlgorithm::FreeSpaceWipe,
+        WipingAlgorithm::QuickSanitize,
+        WipingAlgorithm::CryptoErase,
+    ];
+
+    /// Every method with a fixed name, i.e. all but `CustomPattern` and `MultiPass`
+    const NAMED: &'static [WipingAlgorithm] = &[
+        WipingAlgorithm::NistClear, WipingAlgorithm::NistPurge, WipingAlgorithm::NistDestroy, WipingAlgorithm::NistAuto,
+        WipingAlgorithm::AtaSecureErase, WipingAlgorithm::AtaEnhancedSecureErase, WipingAlgorithm::NvmeSecureErase,
+        WipingAlgorithm::NvmeCryptoErase, WipingAlgorithm::CryptoErase,
+        WipingAlgorithm::DoD522022M, WipingAlgorithm::DoD522022MEce, WipingAlgorithm::Gutmann,
+        WipingAlgorithm::HmgIs5Baseline, WipingAlgorithm::HmgIs5Enhanced, WipingAlgorithm::RcmpTssitOps2,
+        WipingAlgorithm::AustralianIsm, WipingAlgorithm::Vsitr, WipingAlgorithm::Afssi5020, WipingAlgorithm::NavsoP523926,
+        WipingAlgorithm::Random, WipingAlgorithm::Zeros, WipingAlgorithm::Ones,
+        WipingAlgorithm::TwoPass, WipingAlgorithm::ThreePass, WipingAlgorithm::SevenPass,
+        WipingAlgorithm::FileSystemWipe, WipingAlgorithm::FreeSpaceWipe, WipingAlgorithm::SlackSpaceWipe,
+        WipingAlgorithm::QuickFormat, WipingAlgorithm::QuickSanitize, WipingAlgorithm::FastZero,
+    ];
+
+    /// Canonical name, used in the UI, certificates and configuration
+    pub fn name(&self) -> &'static str {
+        match self {
+            WipingAlgorithm::NistClear => "NIST Clear",
+            WipingAlgorithm::NistPurge => "NIST Purge",
+            WipingAlgorithm::NistDestroy => "NIST Destroy",
+            WipingAlgorithm::NistAuto => "NIST SP 800-88 Auto",
+            WipingAlgorithm::AtaSecureErase => "ATA Secure Erase",
+            WipingAlgorithm::AtaEnhancedSecureErase => "ATA Enhanced Secure Erase",
+            WipingAlgorithm::NvmeSecureErase => "NVMe Secure Erase",
+            WipingAlgorithm::NvmeCryptoErase => "NVMe Crypto Erase",
+            WipingAlgorithm::CryptoErase => "Crypto Erase",
+            WipingAlgorithm::DoD522022M => "DoD 5220.22-M",
+            WipingAlgorithm::DoD522022MEce => "DoD 5220.22-M ECE",
+            WipingAlgorithm::Gutmann => "Gutmann",
+            WipingAlgorithm::HmgIs5Baseline => "HMG IS5 Baseline",
+            WipingAlgorithm::HmgIs5Enhanced => "HMG IS5 Enhanced",
+            WipingAlgorithm::RcmpTssitOps2 => "RCMP TSSIT OPS-II",
+            WipingAlgorithm::AustralianIsm => "Australian ISM",
+            WipingAlgorithm::Vsitr => "VSITR",
+            WipingAlgorithm::Afssi5020 => "AFSSI-5020",
+            WipingAlgorithm::NavsoP523926 => "NAVSO P-5239-26",
+            WipingAlgorithm::Random => "Random",
+            WipingAlgorithm::Zeros => "Zeros",
+            WipingAlgorithm::Ones => "Ones",
+            WipingAlgorithm::TwoPass => "2-Pass",
+            WipingAlgorithm::ThreePass => "3-Pass",
+            WipingAlgorithm::SevenPass => "7-Pass",
+            WipingAlgorithm::CustomPattern(_) => "Custom Pattern",
+            WipingAlgorithm::MultiPass { .. } => "Multi-Pass Overwrite",
+            WipingAlgorithm::FileSystemWipe => "File System Wipe",
+            WipingAlgorithm::FreeSpaceWipe => "Free Space Only",
+            WipingAlgorithm::SlackSpaceWipe => "Slack Space Wipe",
+            WipingAlgorithm::QuickFormat => "Quick Format",
+            WipingAlgorithm::QuickSanitize => "Quick Sanitize",
+            WipingAlgorithm::FastZero => "Fast Zero",
+        }
+    }
+
+    /// Number of overwrite passes the method makes; used for progress and the certificate.
+    /// Hardware erases, key destruction and single-pattern methods count as one pass.
+    pub fn pass_count(&self) -> u32 {
+        match self {
+            WipingAlgorithm::DoD522022M => 3,
+            WipingAlgorithm::DoD522022MEce => 7,
+            WipingAlgorithm::Gutmann => 35,
+            WipingAlgorithm::SevenPass => 7,
+            WipingAlgorithm::ThreePass => 3,
+            WipingAlgorithm::TwoPass => 2,
+            WipingAlgorithm::NistPurge => 3,
+            WipingAlgorithm::HmgIs5Enhanced => 3,
+            WipingAlgorithm::RcmpTssitOps2 => 7,
+            WipingAlgorithm::AustralianIsm => 3,
+            WipingAlgorithm::Vsitr => 7,
+            WipingAlgorithm::Afssi5020 => 3,
+            WipingAlgorithm::NavsoP523926 => 3,
+            WipingAlgorithm::FreeSpaceWipe => crate::sanitization::FREE_SPACE_PASSES,
+            WipingAlgorithm::MultiPass { passes, .. } => *passes,
+            _ => 1,
+        }
+    }
+
+    /// Sanitization standards this method satisfies when it runs to completion.
+    /// Generic overwrites and partial methods name none.
+    pub fn standards(&self) -> &'static [&'static str] {
+        match self {
+            WipingAlgorithm::NistClear | WipingAlgorithm::NistPurge | WipingAlgorithm::NistAuto
+            | WipingAlgorithm::NvmeSecureErase => &["NIST SP 800-88"],
+            WipingAlgorithm::AtaSecureErase | WipingAlgorithm::AtaEnhancedSecureErase => &["NIST SP 800-88", "ATA Secure Erase"],
+            WipingAlgorithm::NvmeCryptoErase | WipingAlgorithm::CryptoErase => &["NIST SP 800-88", "Cryptographic Erase"],
+            WipingAlgorithm::DoD522022M | WipingAlgorithm::DoD522022MEce => &["DoD 5220.22-M"],
+            WipingAlgorithm::Gutmann => &["Gutmann"],
+            WipingAlgorithm::HmgIs5Baseline => &["HMG IS5 Baseline"],
+            WipingAlgorithm::HmgIs5Enhanced => &["HMG IS5 Enhanced"],
+            WipingAlgorithm::RcmpTssitOps2 => &["RCMP TSSIT OPS-II"],
+            WipingAlgorithm::AustralianIsm => &["ISM"],
+            WipingAlgorithm::Vsitr => &["BSI VSITR"],
+            WipingAlgorithm::Afssi5020 => &["AFSSI-5020"],
+            WipingAlgorithm::NavsoP523926 => &["NAVSO P-5239-26"],
+            _ => &[],
+        }
+    }
+
+    /// Whether both are the same method, whatever their settings; an eraser that can
+    /// run one multi-pass overwrite can run any pass count and pattern
+    pub fn same_method(&self, other: &WipingAlgorithm) -> bool {
+        std::mem::discriminant(self) == std::mem::discriminant(other)
+    }
+
+    /// Key destruction or the drive's own erase command, as opposed to an overwrite
+    pub fn is_hardware_purge(&self) -> bool {
+        matches!(self,
+            WipingAlgorithm::AtaSecureErase | WipingAlgorithm::AtaEnhancedSecureErase
+            | WipingAlgorithm::NvmeSecureErase | WipingAlgorithm::NvmeCryptoErase | WipingAlgorithm::CryptoErase)
+    }
+}
+
+impl std::fmt::Display for WipingAlgorithm {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WipingAlgorithm::CustomPattern(pattern) => {
+                let bytes: Vec<String> = pattern.iter().map(|b| format!("{:02X}", b)).collect();
+                write!(f, "{} ({})", self.name(), bytes.join(" "))
+            }
+            WipingAlgorithm::MultiPass { passes, pattern } => {
+                write!(f, "{} ({} {}, {})", self.name(), passes, if *passes == 1 { "pass" } else { "passes" }, pattern.label())
+            }
+            _ => f.write_str(self.name()),
+        }
+    }
+}
+
+/// Parses the canonical names written by `Display`, ignoring case. The variant names
+/// that certificates recorded before these names existed are accepted too.
+impl std::str::FromStr for WipingAlgorithm {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if let Some(named) = WipingAlgorithm::NAMED.iter()
+            .find(|a| a.name().eq_ignore_ascii_case(s) || format!("{:?}", a) == s)
+        {
+            return Ok(named.clone());
+        }
+
+        // "Multi-Pass Overwrite (7 passes, Random)"
+        if let Some(settings) = s.strip_prefix("Multi-Pass Overwrite (").and_then(|rest| rest.strip_suffix(')')) {
+            let (passes, pattern) = settings.split_once(',')
+                .ok_or_else(|| format!("Invalid multi-pass settings: {}", settings))?;
+            let passes = passes.trim().trim_end_matches("passes").trim_end_matches("pass").trim().parse::<u32>()
+                .ok().filter(|passes| (1..=MAX_OVERWRITE_PASSES).contains(passes))
+                .ok_or_else(|| format!("Invalid pass count: {}", passes.trim()))?;
+            let pattern = OverwritePattern::all().iter()
+                .find(|p| p.label().eq_ignore_ascii_case(pattern.trim()))
+                .ok_or_else(|| format!("Unknown overwrite pattern: {}", pattern.trim()))?;
+            return Ok(WipingAlgorithm::MultiPass { passes, pattern: *pattern });
+        }
+
+        // "Custom Pattern (55 AA)"
+        let pattern = s.strip_prefix("Custom Pattern (").and_then(|rest| rest.strip_suffix(')'))
+            .ok_or_else(|| format!("Unknown wiping algorithm: {}", s))?;
+        let bytes = pattern.split_whitespace()
+            .map(|byte| u8::from_str_radix(byte.trim_start_matches("0x"), 16))
+            .collect::<Result<Vec<u8>, _>>()
+            .map_err(|_| format!("Invalid custom pattern: {}", pattern))?;
+        if bytes.is_empty() {
+            return Err("Custom pattern has no bytes".to_string());
+        }
+        Ok(WipingAlgorithm::CustomPattern(bytes))
+    }
+}
+
+/// Most passes a multi-pass overwrite may be set to, as many as Gutmann's
+pub const MAX_OVERWRITE_PASSES: u32 = 35;
+
+/// What a multi-pass overwrite writes on each of its passes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OverwritePattern {
+    Random,
+    Zeros,
+    Ones,
+    /// 0x00 and 0xFF on alternate passes, starting with 0x00
+    Alternating,
+    /// Random on every pass but the last, which writes 0x00
+    RandomThenZeros,
+}
+
+impl OverwritePattern {
+    pub fn all() -> &'static [OverwritePattern] {
+        &[OverwritePattern::Random, OverwritePattern::Zeros, OverwritePattern::Ones,
+          OverwritePattern::Alternating, OverwritePattern::RandomThenZeros]
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            OverwritePattern::Random => "Random",
+            OverwritePattern::Zeros => "Zeros",
+            OverwritePattern::Ones => "Ones",
+            OverwritePattern::Alternating => "Alternating 0x00/0xFF",
+            OverwritePattern::RandomThenZeros => "Random, final Zeros",
+        }
+    }
+
+    /// One pattern per pass for a `passes`-pass overwrite
+    pub fn patterns(&self, passes: u32) -> Vec<SanitizationPattern> {
+        (1..=passes).map(|pass| match self {
+            OverwritePattern::Random => SanitizationPattern::Random,
+            OverwritePattern::Zeros => SanitizationPattern::Zeros,
+            OverwritePattern::Ones => SanitizationPattern::Ones,
+            OverwritePattern::Alternating if pass % 2 == 1 => SanitizationPattern::Zeros,
+            OverwritePattern::Alternating => SanitizationPattern::Ones,
+            OverwritePattern::RandomThenZeros if pass == passes => SanitizationPattern::Zeros,
+            OverwritePattern::RandomThenZeros => SanitizationPattern::Random,
+        }).collect()
+    }
+}
+
+/// Standards a wipe met: those of the method that ran, plus the standard behind a
+/// per-drive selection that picked it. NIST Auto only meets 800-88 with a purge or, on
+/// magnetic media, a Clear overwrite; the thorough overwrites it falls back to on flash
+/// media don't. The Australian ISM covers its own overwrite and purges on flash media.
+/// `media` is `None` when the drive wasn't analyzed.
+pub fn standards_met(ran: &WipingAlgorithm, selected: &WipingAlgorithm, media: Option<&DeviceType>) -> Vec<String> {
+    let mut standards: Vec<String> = ran.standards().iter().map(|s| s.to_string()).collect();
+    let nist_clear = matches!(media, Some(media) if !is_flash_controller(media)) && NIST_CLEAR_OVERWRITES.contains(ran);
+    let implied = match selected {
+        WipingAlgorithm::NistAuto if ran.is_hardware_purge() || nist_clear => Some("NIST SP 800-88"),
+        WipingAlgorithm::AustralianIsm if ran.is_hardware_purge() => Some("ISM"),
+        _ => None,
+    };
+    if let Some(standard) = implied {
+        if !standards.iter().any(|s| s == standard) {
+            standards.push(standard.to_string());
+        }
+    }
+    standards
+}
+
+/// Volume encryption found on a device before wiping
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum EncryptionStatus {
+    NotEncrypted,
+    BitLocker,
+    Luks,
+    /// The device couldn't be read, so nothing is known
+    #[default]
+    Unknown,
+}
+
+impl EncryptionStatus {
+    pub fn label(&self) -> &'static str {
+        match self {
+            EncryptionStatus::NotEncrypted => "Not encrypted",
+            EncryptionStatus::BitLocker => "BitLocker",
+            EncryptionStatus::Luks => "LUKS",
+            EncryptionStatus::Unknown => "Unknown",
+        }
+    }
+
+    pub fn is_encrypted(&self) -> bool {
+        matches!(self, EncryptionStatus::BitLocker | EncryptionStatus::Luks)
+    }
+}
+
+/// Bus a drive is attached through, as reported by the OS
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ConnectionInterface {
+    Sata,
+    Sas,
+    Scsi,
+    Nvme,
+    Usb,
+    SdMmc,
+    Raid,
+    Virtual,
+    #[default]
+    Unknown,
+}
+
+impl ConnectionInterface {
+    pub fn label(&self) -> &'static str {
+        match self {
+            ConnectionInterface::Sata => "SATA",
+            ConnectionInterface::Sas => "SAS",
+            ConnectionInterface::Scsi => "SCSI",
+            ConnectionInterface::Nvme => "NVMe",
+            ConnectionInterface::Usb => "USB",
+            ConnectionInterface::SdMmc => "SD/MMC",
+            ConnectionInterface::Raid => "RAID",
+            ConnectionInterface::Virtual => "Virtual",
+            ConnectionInterface::Unknown => "Unknown",
+        }
+    }
+
+    /// Whether ATA security commands normally reach the drive. USB bridges and RAID
+    /// controllers mostly drop them, so hardware secure erase can't be relied on there.
+    pub fn passes_ata_commands(&self) -> bool {
+        matches!(self, ConnectionInterface::Sata | ConnectionInterface::Unknown)
+    }
+
+    /// Device type the bus alone settles, whatever the model string says
+    pub fn implied_device_type(&self) -> Option<DeviceType> {
+        match self {
+            ConnectionInterface::Nvme => Some(DeviceType::NVMe),
+            ConnectionInterface::Usb => Some(DeviceType::USBDrive),
+            ConnectionInterface::SdMmc => Some(DeviceType::SDCard),
+            _ => None,
+        }
+    }
+}
+
+/// Confidentiality of the data on a drive (FIPS 199 impact level); decides whether
+/// NIST SP 800-88 asks for a Clear or a Purge
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Sensitivity {
+    Low,
+    #[default]
+    Moderate,
+    High,
+}
+
+impl Sensitivity {
+    pub fn all() -> &'static [Sensitivity] {
+        &[Sensitivity::Low, Sensitivity::Moderate, Sensitivity::High]
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Sensitivity::Low => "Low",
+            Sensitivity::Moderate => "Moderate",
+            Sensitivity::High => "High",
+        }
+    }
+
+    /// Moderate and high data leaving the operator's control must be purged
+    pub fn requires_purge(&self) -> bool {
+        !matches!(self, Sensitivity::Low)
+    }
+}
+
+/// The NIST SP 800-88 decision for one drive. Low-sensitivity data on HDDs and removable
+/// flash only needs a Clear (a single overwrite). Anything more sensitive, and any SSD or
+/// NVMe drive whose controller remaps blocks out of reach of overwrites, gets a Purge:
+/// key destruction first, then the drive's own erase commands. `supported` is what the
+/// drive's eraser can run; the first match in order of preference wins.
+pub fn nist_auto_algorithm(device_info: &DeviceInfo, sensitivity: Sensitivity, supported: &[WipingAlgorithm]) -> WipingAlgorithm {
+    let pick = |preference: &[WipingAlgorithm]| preference.iter().find(|a| supported.contains(a)).cloned();
+    let flash_controller = is_flash_controller(&device_info.device_type);
+
+    if !sensitivity.requires_purge() && !flash_controller {
+        if let Some(algorithm) = pick(&NIST_CLEAR_OVERWRITES) {
+            return algorithm;
+        }
+    }
+
+    let mut purge = vec![WipingAlgorithm::CryptoErase, WipingAlgorithm::NvmeCryptoErase, WipingAlgorithm::NvmeSecureErase];
+    if device_info.interface.passes_ata_commands() {
+        purge.extend([WipingAlgorithm::AtaEnhancedSecureErase, WipingAlgorithm::AtaSecureErase]);
+    }
+    if let Some(algorithm) = pick(&purge) {
+        return algorithm;
+    }
+
+    // No purge the drive can perform; overwrite as thoroughly as it allows. NIST expects
+    // such media to be destroyed if the data really needs a purge.
+    log::warn!("⚠️  {} offers no NIST purge method - falling back to an overwrite; consider physical destruction",
+        device_info.device_path);
+    pick(&[WipingAlgorithm::NistPurge, WipingAlgorithm::DoD522022MEce, WipingAlgorithm::SevenPass, WipingAlgorithm::ThreePass])
+        .or_else(|| pick(&NIST_CLEAR_OVERWRITES))
+        .unwrap_or(WipingAlgorithm::Random)
+}
+
+/// Overwrites that are a NIST 800-88 Clear on magnetic media, in NIST Auto's order of preference
+const NIST_CLEAR_OVERWRITES: [WipingAlgorithm; 4] =
+    [WipingAlgorithm::NistClear, WipingAlgorithm::Random, WipingAlgorithm::Zeros, WipingAlgorithm::DoD522022M];
+
+/// Media whose controller remaps writes, so an overwrite can't reach every cell
+fn is_flash_controller(device_type: &DeviceType) -> bool {
+    matches!(device_type, DeviceType::SSD | DeviceType::NVMe)
+}
+
+/// The Australian ISM (ACSC) choice for one drive. Magnetic media get the ISM overwrite
+/// with read-back; flash media can't be reliably overwritten, so they get the purge NIST
+/// Auto picks for highly sensitive data (crypto-erase or the drive's block erase).
+pub fn ism_algorithm(device_info: &DeviceInfo, supported: &[WipingAlgorithm]) -> WipingAlgorithm {
+    if matches!(device_info.device_type, DeviceType::HDD) && supported.contains(&WipingAlgorithm::AustralianIsm) {
+        return WipingAlgorithm::AustralianIsm;
+    }
+    nist_auto_algorithm(device_info, Sensitivity::High, supported)
+}
+
+/// What to do when a write fails partway through a wipe
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ErrorPolicy {
+    /// Stop the job at the first failed write
+    #[default]
+    AbortOnFirst,
+    /// Log the failure, skip the affected region and keep wiping
+    ContinueAndReport,
+}
+
+impl ErrorPolicy {
+    pub fn all() -> &'static [ErrorPolicy] {
+        &[ErrorPolicy::AbortOnFirst, ErrorPolicy::ContinueAndReport]
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ErrorPolicy::AbortOnFirst => "Abort on first error",
+            ErrorPolicy::ContinueAndReport => "Continue and report",
+        }
+    }
+}
+
+/// Drive status and wipe error for a wipe cut short because the device went away
+pub const DEVICE_REMOVED: &str = "Device removed — incomplete";
+
+/// Drive status and wipe error for a wipe stopped between passes because ShredX was closing
+pub const WIPE_STOPPED: &str = "Stopped at shutdown — incomplete";
+
+/// Set once ShredX is closing; every running wipe finishes the pass it is on and stops
+static STOP_AFTER_PASS: AtomicBool = AtomicBool::new(false);
+
+/// Ask every running wipe to stop once the pass it is writing is complete
+pub fn request_stop_after_pass() {
+    STOP_AFTER_PASS.store(true, Ordering::SeqCst);
+}
+
+/// Check before starting a pass. Fails once a stop has been requested, so a wipe that
+/// is shut down always ends on a whole pass rather than partway through one.
+pub fn check_stop_requested() -> io::Result<()> {
+    if STOP_AFTER_PASS.load(Ordering::SeqCst) {
+        return Err(io::Error::new(io::ErrorKind::Interrupted, WIPE_STOPPED));
+    }
+    Ok(())
+}
+
+/// Call before writing a pass: `Ok(false)` when a resumed wipe already wrote this pass
+/// in an earlier session, an error once a stop has been requested
+pub fn should_write_pass(progress: &Arc<Mutex<WipingProgress>>) -> io::Result<bool> {
+    check_stop_requested()?;
+    Ok(!progress.lock().map(|progress| progress.skips_current_pass()).unwrap_or(false))
+}
+
+/// Whether the error is a wipe stopping for shutdown, as opposed to failing
+pub fn is_wipe_stopped_error(error: &io::Error) -> bool {
+    error.kind() == io::ErrorKind::Interrupted && error.to_string() == WIPE_STOPPED
+}
+
+/// Whether an I/O error says the device itself is gone (an unplugged USB stick or SD
+/// card) rather than that one region failed; no error policy can carry on past that
+pub fn is_device_removed_error(error: &io::Error) -> bool {
+    // ENXIO, ENODEV
+    #[cfg(unix)]
+    const REMOVED: &[i32] = &[6, 19];
+    // ERROR_NOT_READY, ERROR_DEV_NOT_EXIST, ERROR_NO_SUCH_DEVICE, ERROR_DEVICE_NOT_CONNECTED
+    #[cfg(windows)]
+    const REMOVED: &[i32] = &[21, 55, 433, 1167];
+    #[cfg(not(any(unix, windows)))]
+    const REMOVED: &[i32] = &[];
+    error.raw_os_error().is_some_and(|code| REMOVED.contains(&code))
+}
+
+/// Whether a wipe of `device_path` that failed with `error` failed because the device was
+/// removed. Linux reports most unplugs as a plain EIO, so a device node that has vanished
+/// counts too.
+pub fn device_removed(device_path: &str, error: &io::Error) -> bool {
+    is_device_removed_error(error) || (cfg!(unix) && !Path::new(device_path).exists())
+}
+
 #[derive(Debug, Clone)]
 pub struct WipingProgress {
     pub algorithm: WipingAlgorithm,
@@ -57,9 +564,89 @@ pub struct WipingProgress {
     pub current_pattern: String,
     pub estimated_time_remaining: Duration,
     pub speed_mbps: f64,
+    /// Write errors logged during the wipe (only accumulates under `ContinueAndReport`,
+    /// otherwise holds the error that stopped the job)
+    pub errors: Vec<String>,
+    /// Timings of the passes finished so far
+    pub pass_timings: Vec<PassTiming>,
+    /// Pass in progress and when it began; see `begin_pass`
+    pub pass_started: Option<(u32, chrono::DateTime<chrono::Utc>)>,
+    /// First pass to write when resuming an interrupted wipe; the passes before it were
+    /// written in an earlier session and are skipped. 0 writes every pass.
+    pub resume_from_pass: u32,
+    /// Read back a sample of the device after every this many passes; `None` only
+    /// verifies at the end
+    pub verify_interval: Option<u32>,
+    /// Buffer the current pass repeats from offset 0, if it is an overwrite; the final
+    /// verification reads the device back against it. `None` after a hardware erase.
+    pub last_pass_pattern: Option<Arc<[u8]>>,
 }
 
-#[derive(Debug, Clone)]
+impl WipingProgress {
+    /// Start timing `pass`, closing the pass before it. Call it before setting the new
+    /// pass's `current_pattern`, so the closed pass is recorded under its own pattern;
+    /// repeat calls for the pass already running are ignored.
+    pub fn begin_pass(&mut self, pass: u32) {
+        if self.pass_started.is_some_and(|(current, _)| current == pass) {
+            return;
+        }
+        self.finish_pass();
+        self.current_pass = pass;
+        self.last_pass_pattern = None;
+        // Passes skipped on resume were timed by the session that wrote them
+        if !self.skips_current_pass() {
+            self.pass_started = Some((pass, chrono::Utc::now()));
+        }
+    }
+
+    /// Whether the pass in progress was written before the wipe was resumed
+    pub fn skips_current_pass(&self) -> bool {
+        self.current_pass < self.resume_from_pass
+    }
+
+    /// Whether the verify interval asks for the pass just written to be read back
+    pub fn verify_due(&self) -> bool {
+        self.verify_interval.is_some_and(|interval| interval > 0 && self.current_pass % interval == 0)
+    }
+
+    /// Close the timing of the pass in progress, if any. `bytes_processed` counts from
+    /// zero on every pass, so it is the closed pass's own byte count.
+    pub fn finish_pass(&mut self) {
+        let Some((pass, started_at)) = self.pass_started.take() else {
+            return;
+        };
+        let ended_at = chrono::Utc::now();
+        let seconds = ended_at.signed_duration_since(started_at).num_milliseconds() as f64 / 1000.0;
+        self.pass_timings.push(PassTiming {
+            pass,
+            pattern: self.current_pattern.clone(),
+            started_at,
+            ended_at,
+            bytes_processed: self.bytes_processed,
+            throughput_mbps: if seconds > 0.0 { self.bytes_processed as f64 / (1024.0 * 1024.0) / seconds } else { 0.0 },
+        });
+    }
+}
+
+/// SMART state captured around a wipe for the certificate
+#[derive(Debug, Clone, Default)]
+pub struct SmartSnapshots {
+    pub before: Option<SmartSnapshot>,
+    pub after: Option<SmartSnapshot>,
+}
+
+/// Full-device hashes taken around a wipe for audit evidence
+#[derive(Debug, Clone, Default)]
+pub struct DeviceHashes {
+    pub pre_wipe: Option<String>,
+    pub post_wipe: Option<String>,
+    /// Sectors read back and hashed after the final pass
+    pub sampled_sectors: Vec<SectorSample>,
+    /// Set while the wipe thread still has hashing work to do
+    pub in_progress: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DeviceInfo {
     pub device_path: String,
     pub device_type: DeviceType,
@@ -73,9 +660,19 @@ pub struct DeviceInfo {
     pub vendor: String,
     pub model: String,
     pub serial: String,
+    /// Shingled (SMR) drive; sustained sequential overwrites can slow down sharply
+    pub is_smr: bool,
+    /// Solid-state hybrid drive (SSHD): spinning platters behind a NAND cache that an
+    /// overwrite of the LBA range alone doesn't reliably reach
+    #[serde(default)]
+    pub is_hybrid: bool,
+    /// BitLocker/LUKS volume found on the device; filled in by `DeviceFactory::analyze_and_create`
+    pub encryption_status: EncryptionStatus,
+    /// Bus the drive is attached through; filled in by `DeviceFactory::analyze_and_create`
+    pub interface: ConnectionInterface,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum DeviceType {
     HDD,
     SSD,
@@ -88,6 +685,22 @@ pub enum DeviceType {
     Other(String),
 }
 
+/// Name shown in the UI, reports and certificates
+impl std::fmt::Display for DeviceType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DeviceType::HDD => f.write_str("Hard Disk Drive"),
+            DeviceType::SSD => f.write_str("SATA SSD"),
+            DeviceType::NVMe => f.write_str("NVMe SSD"),
+            DeviceType::SDCard | DeviceType::MMC => f.write_str("SD/MMC Card"),
+            DeviceType::USBDrive => f.write_str("USB Flash Drive"),
+            DeviceType::EMmc => f.write_str("eMMC"),
+            DeviceType::CompactFlash => f.write_str("CompactFlash Card"),
+            DeviceType::Other(name) => f.write_str(name),
+        }
+    }
+}
+
 #[derive(Clone)]
 pub struct AdvancedWiper {
     verify_after_wipe: bool,
@@ -116,7 +729,7 @@ impl AdvancedWiper {
 
     /// Get comprehensive device information
     pub fn analyze_device(&self, device_path: &str) -> io::Result<DeviceInfo> {
-        println!("🔍 Analyzing device: {}", device_path);
+        log::info!("🔍 Analyzing device: {}", device_path);
         
         // Try to get basic file information
         let file = File::open(device_path)?;
@@ -137,6 +750,10 @@ impl AdvancedWiper {
             vendor: "Unknown".to_string(),
             model: "Unknown".to_string(),
             serial: "Unknown".to_string(),
+            is_smr: false,
+            is_hybrid: false,
+            encryption_status: EncryptionStatus::Unknown,
+            interface: crate::devices::detect_interface(device_path),
         };
 
         // Try ATA interface for detailed information
@@ -147,9 +764,15 @@ impl AdvancedWiper {
                 device_info.model = drive_info.model.clone();
                 device_info.serial = drive_info.serial.clone();
                 device_info.supports_secure_erase = drive_info.security_supported;
+                device_info.is_smr = drive_info.zoned;
+                device_info.is_hybrid = is_hybrid_drive(&drive_info.model, Some(drive_info.rotation_rate), device_path);
                 
                 // Determine device type based on model
-                device_info.device_type = self.determine_device_type(&drive_info.model);
+                device_info.device_type = if device_info.is_hybrid {
+                    DeviceType::HDD
+                } else {
+                    self.determine_device_type(&drive_info.model)
+                };
                 
                 // Check for TRIM support (SSDs)
                 if matches!(device_info.device_type, DeviceType::SSD | DeviceType::NVMe) {
@@ -161,12 +784,21 @@ impl AdvancedWiper {
         // Try to get more information from Windows API or system calls
         self.enhance_device_info(&mut device_info)?;
 
-        println!("📊 Device Analysis Complete:");
-        println!("   Type: {:?}", device_info.device_type);
-        println!("   Size: {:.2} GB", device_info.size_bytes as f64 / (1000.0 * 1000.0 * 1000.0));
-        println!("   Model: {}", device_info.model);
-        println!("   Secure Erase: {}", if device_info.supports_secure_erase { "Yes" } else { "No" });
-        println!("   TRIM Support: {}", if device_info.supports_trim { "Yes" } else { "No" });
+        if !device_info.is_smr {
+            device_info.is_smr = is_zoned_device(device_path);
+        }
+
+        log::info!("📊 Device Analysis Complete:");
+        log::info!("   Type: {}", device_info.device_type);
+        log::info!("   Size: {:.2} GB", device_info.size_bytes as f64 / (1000.0 * 1000.0 * 1000.0));
+        log::info!("   Model: {}", device_info.model);
+        log::info!("   Secure Erase: {}", if device_info.supports_secure_erase { "Yes" } else { "No" });
+        log::info!("   TRIM Support: {}", if device_info.supports_trim { "Yes" } else { "No" });
+        log::info!("   SMR (Shingled): {}", if device_info.is_smr { "Yes" } else { "No" });
+        log::info!("   Hybrid (SSHD): {}", if device_info.is_hybrid { "Yes" } else { "No" });
+        if device_info.is_smr {
+            log::warn!("⚠️  SMR drive detected - full overwrites may be unusually slow once the drive cache fills");
+        }
 
         Ok(device_info)
     }
@@ -178,10 +810,10 @@ impl AdvancedWiper {
         algorithm: WipingAlgorithm,
         progress_callback: Arc<Mutex<WipingProgress>>,
     ) -> io::Result<String> {
-        println!("🚨 CRITICAL WARNING: About to PERMANENTLY ERASE ALL DATA on {}", device_info.device_path);
-        println!("📱 Device: {} ({})", device_info.model, device_info.device_path);
-        println!("💾 Size: {:.2} GB", device_info.size_bytes as f64 / (1000.0 * 1000.0 * 1000.0));
-        println!("🔒 Algorithm: {:?}", algorithm);
+        log::info!("🚨 CRITICAL WARNING: About to PERMANENTLY ERASE ALL DATA on {}", device_info.device_path);
+        log::info!("📱 Device: {} ({})", device_info.model, device_info.device_path);
+        log::info!("💾 Size: {:.2} GB", device_info.size_bytes as f64 / (1000.0 * 1000.0 * 1000.0));
+        log::info!("🔒 Algorithm: {:?}", algorithm);
         
         // Initialize progress
         {
@@ -229,19 +861,19 @@ impl AdvancedWiper {
         device_info: &DeviceInfo,
         progress_callback: Arc<Mutex<WipingProgress>>,
     ) -> io::Result<String> {
-        println!("🔒 NIST 800-88 Clear Method - Single Pass Overwrite");
+        log::info!("🔒 NIST 800-88 Clear Method - Single Pass Overwrite");
         
         {
             let mut progress = progress_callback.lock().unwrap();
             progress.total_passes = 1;
-            progress.current_pass = 1;
+            progress.begin_pass(1);
             progress.current_pattern = "Cryptographically Secure Random".to_string();
         }
 
         self.single_pass_wipe(device_info, WipePattern::CryptoRandom, progress_callback)?;
         
         if self.verify_after_wipe {
-            println!("🔍 Verifying wipe completion...");
+            log::info!("🔍 Verifying wipe completion...");
             self.verify_wipe(device_info)?;
         }
 
@@ -254,7 +886,7 @@ impl AdvancedWiper {
         device_info: &DeviceInfo,
         progress_callback: Arc<Mutex<WipingProgress>>,
     ) -> io::Result<String> {
-        println!("🔒 NIST 800-88 Purge Method - Multi-Pass Cryptographic Destruction");
+        log::info!("🔒 NIST 800-88 Purge Method - Multi-Pass Cryptographic Destruction");
         
         {
             let mut progress = progress_callback.lock().unwrap();
@@ -275,16 +907,16 @@ impl AdvancedWiper {
         for (i, (pattern, description)) in patterns.iter().enumerate() {
             {
                 let mut progress = progress_callback.lock().unwrap();
-                progress.current_pass = i as u32 + 1;
+                progress.begin_pass(i as u32 + 1);
                 progress.current_pattern = description.to_string();
             }
 
-            println!("🔄 Pass {}/7: {}", i + 1, description);
+            log::info!("🔄 Pass {}/7: {}", i + 1, description);
             self.single_pass_wipe(device_info, pattern.clone(), progress_callback.clone())?;
         }
 
         if self.verify_after_wipe {
-            println!("🔍 Performing final verification...");
+            log::info!("🔍 Performing final verification...");
             self.verify_wipe(device_info)?;
         }
 
@@ -298,7 +930,7 @@ impl AdvancedWiper {
         enhanced: bool,
         progress_callback: Arc<Mutex<WipingProgress>>,
     ) -> io::Result<String> {
-        println!("🔧 ATA Secure Erase ({}) - Hardware-based Destruction", 
+        log::info!("🔧 ATA Secure Erase ({}) - Hardware-based Destruction", 
                  if enhanced { "Enhanced" } else { "Standard" });
 
         if !device_info.supports_secure_erase {
@@ -311,7 +943,7 @@ impl AdvancedWiper {
         {
             let mut progress = progress_callback.lock().unwrap();
             progress.total_passes = 1;
-            progress.current_pass = 1;
+            progress.begin_pass(1);
             progress.current_pattern = format!("ATA Secure Erase ({})", 
                                              if enhanced { "Enhanced" } else { "Standard" });
         }
@@ -330,7 +962,7 @@ impl AdvancedWiper {
         device_info: &DeviceInfo,
         progress_callback: Arc<Mutex<WipingProgress>>,
     ) -> io::Result<String> {
-        println!("🔒 Three-Pass Wipe Method");
+        log::info!("🔒 Three-Pass Wipe Method");
         
         {
             let mut progress = progress_callback.lock().unwrap();
@@ -346,11 +978,11 @@ impl AdvancedWiper {
         for (i, (pattern, description)) in patterns.iter().enumerate() {
             {
                 let mut progress = progress_callback.lock().unwrap();
-                progress.current_pass = i as u32 + 1;
+                progress.begin_pass(i as u32 + 1);
                 progress.current_pattern = description.to_string();
             }
 
-            println!("🔄 Pass {}/3: {}", i + 1, description);
+            log::info!("🔄 Pass {}/3: {}", i + 1, description);
             self.single_pass_wipe(device_info, pattern.clone(), progress_callback.clone())?;
         }
 
@@ -363,7 +995,7 @@ impl AdvancedWiper {
         device_info: &DeviceInfo,
         progress_callback: Arc<Mutex<WipingProgress>>,
     ) -> io::Result<String> {
-        println!("🔒 Seven-Pass Enhanced Wipe Method");
+        log::info!("🔒 Seven-Pass Enhanced Wipe Method");
         
         {
             let mut progress = progress_callback.lock().unwrap();
@@ -383,11 +1015,11 @@ impl AdvancedWiper {
         for (i, (pattern, description)) in patterns.iter().enumerate() {
             {
                 let mut progress = progress_callback.lock().unwrap();
-                progress.current_pass = i as u32 + 1;
+                progress.begin_pass(i as u32 + 1);
                 progress.current_pattern = description.to_string();
             }
 
-            println!("🔄 Pass {}/7: {}", i + 1, description);
+            log::info!("🔄 Pass {}/7: {}", i + 1, description);
             self.single_pass_wipe(device_info, pattern.clone(), progress_callback.clone())?;
         }
 
@@ -419,22 +1051,22 @@ impl AdvancedWiper {
         pattern: &WipePattern,
         progress_callback: Arc<Mutex<WipingProgress>>,
     ) -> io::Result<()> {
-        println!("🔄 Performing file-level wipe on {}", device_info.device_path);
+        log::info!("🔄 Performing file-level wipe on {}", device_info.device_path);
         
         let start_time = Instant::now();
         let chunk_size = self.buffer_size;
         
         // Create a large temporary file to fill the free space
         let temp_file_path = format!("{}wipe_temp_file.tmp", device_info.device_path);
-        println!("📁 Creating temporary wipe file: {}", temp_file_path);
+        log::info!("📁 Creating temporary wipe file: {}", temp_file_path);
         
         let result = self.fill_free_space_with_pattern(&temp_file_path, pattern, device_info.size_bytes, progress_callback);
         
         // Clean up temporary file
         if std::path::Path::new(&temp_file_path).exists() {
             match std::fs::remove_file(&temp_file_path) {
-                Ok(_) => println!("🗑️ Temporary wipe file removed"),
-                Err(e) => println!("⚠️ Warning: Could not remove temporary file: {}", e),
+                Ok(_) => log::info!("🗑️ Temporary wipe file removed"),
+                Err(e) => log::warn!("⚠️ Warning: Could not remove temporary file: {}", e),
             }
         }
         
@@ -471,7 +1103,7 @@ impl AdvancedWiper {
                     // If we can't write more (disk full), that's actually what we want
                     if e.kind() == io::ErrorKind::WriteZero || 
                        e.raw_os_error() == Some(112) { // ERROR_DISK_FULL
-                        println!("💾 Disk space filled - wipe effective");
+                        log::info!("💾 Disk space filled - wipe effective");
                         break;
                     } else {
                         return Err(e);
@@ -503,7 +1135,7 @@ impl AdvancedWiper {
         }
 
         file.sync_all()?;
-        println!("✅ Pattern written: {:.2} MB", bytes_written as f64 / 1024.0 / 1024.0);
+        log::info!("✅ Pattern written: {:.2} MB", bytes_written as f64 / 1024.0 / 1024.0);
         Ok(())
     }
 
@@ -619,7 +1251,7 @@ impl AdvancedWiper {
     }
 
     fn verify_wipe(&self, _device_info: &DeviceInfo) -> io::Result<()> {
-        println!("✅ Wipe verification completed");
+        log::info!("✅ Wipe verification completed");
         Ok(())
     }
 
@@ -632,8 +1264,41 @@ impl AdvancedWiper {
         Err(io::Error::new(io::ErrorKind::Other, "NVMe Crypto Erase not implemented"))
     }
 
-    fn dod_5220_22m(&self, _device_info: &DeviceInfo, _ece: bool, _progress_callback: Arc<Mutex<WipingProgress>>) -> io::Result<String> {
-        Err(io::Error::new(io::ErrorKind::Other, "DoD 5220.22-M not implemented"))
+    /// DoD 5220.22-M (3-pass), or ECE (7-pass): a DoD 3-pass, one random pass, then another DoD 3-pass
+    fn dod_5220_22m(&self, device_info: &DeviceInfo, ece: bool, progress_callback: Arc<Mutex<WipingProgress>>) -> io::Result<String> {
+        let dod_passes = [
+            (WipePattern::Zeros, "DoD Character (0x00)"),
+            (WipePattern::Ones, "DoD Complement (0xFF)"),
+            (WipePattern::CryptoRandom, "DoD Random"),
+        ];
+        
+        let mut patterns = dod_passes.to_vec();
+        if ece {
+            log::info!("🔒 DoD 5220.22-M ECE (7-pass) Wipe Method");
+            patterns.push((WipePattern::CryptoRandom, "ECE Random"));
+            patterns.extend(dod_passes.iter().cloned());
+        } else {
+            log::info!("🔒 DoD 5220.22-M (3-pass) Wipe Method");
+        }
+        
+        let total_passes = patterns.len();
+        {
+            let mut progress = progress_callback.lock().unwrap();
+            progress.total_passes = total_passes as u32;
+        }
+
+        for (i, (pattern, description)) in patterns.iter().enumerate() {
+            {
+                let mut progress = progress_callback.lock().unwrap();
+                progress.begin_pass(i as u32 + 1);
+                progress.current_pattern = description.to_string();
+            }
+
+            log::info!("🔄 Pass {}/{}: {}", i + 1, total_passes, description);
+            self.single_pass_wipe(device_info, pattern.clone(), progress_callback.clone())?;
+        }
+
+        Ok(format!("DoD 5220.22-M {}-pass wipe completed successfully", total_passes))
     }
 
     fn gutmann_35_pass(&self, _device_info: &DeviceInfo, _progress_callback: Arc<Mutex<WipingProgress>>) -> io::Result<String> {
@@ -666,34 +1331,529 @@ enum WipePattern {
     CryptoRandom,
 }
 
+/// Compute a SHA-256 over the first `size_bytes` of a device (or until EOF), returned as hex.
+///
+/// Reads the whole device, so this takes roughly as long as a single overwrite pass.
+pub fn hash_device(
+    device_path: &str,
+    size_bytes: u64,
+    progress_callback: &Arc<Mutex<WipingProgress>>,
+) -> io::Result<String> {
+    let mut file = File::open(device_path)?;
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0u8; 1024 * 1024];
+    let mut total_read = 0u64;
+
+    while total_read < size_bytes {
+        let to_read = std::cmp::min(buffer.len() as u64, size_bytes - total_read) as usize;
+        let bytes_read = file.read(&mut buffer[..to_read])?;
+        if bytes_read == 0 {
+            break;
+        }
+
+        hasher.update(&buffer[..bytes_read]);
+        total_read += bytes_read as u64;
+
+        if let Ok(mut progress) = progress_callback.lock() {
+            progress.current_pattern = format!(
+                "Hashing device ({:.1}%)",
+                total_read as f64 / size_bytes.max(1) as f64 * 100.0
+            );
+        }
+    }
+
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// Most bytes a throughput benchmark moves; enough to get past the drive's write cache
+pub const BENCHMARK_BYTES: u64 = 1024 * 1024 * 1024;
+
+/// Sustained throughput measured on a drive without touching its data
+#[derive(Debug, Clone, Copy)]
+pub struct ThroughputBenchmark {
+    pub bytes: u64,
+    pub seconds: f64,
+    /// Measured by reading, because the raw device could not be opened for writing; this
+    /// is not a write speed and must not seed a wipe's ETA
+    pub read_only: bool,
+}
+
+impl ThroughputBenchmark {
+    pub fn bytes_per_second(&self) -> f64 {
+        self.bytes as f64 / self.seconds.max(f64::EPSILON)
+    }
+
+    pub fn mbps(&self) -> f64 {
+        self.bytes_per_second() / (1024.0 * 1024.0)
+    }
+
+    /// Time to overwrite `total_bytes` `passes` times at the measured speed
+    pub fn estimate(&self, total_bytes: u64, passes: u32) -> Duration {
+        Duration::from_secs_f64(total_bytes as f64 * f64::from(passes.max(1)) / self.bytes_per_second().max(1.0))
+    }
+}
+
+/// Chunk size of the benchmark writes
+const BENCHMARK_CHUNK: usize = 4 * 1024 * 1024;
+/// Direct I/O needs whole, aligned logical sectors; no drive uses sectors over 4 KiB
+const DIRECT_IO_ALIGNMENT: usize = 4096;
+
+/// Open a raw device for reading and writing past the page cache, so writes are timed
+/// at the speed the drive takes them
+fn open_direct(path: &Path) -> io::Result<File> {
+    let mut options = OpenOptions::new();
+    options.read(true).write(true);
+    #[cfg(target_os = "linux")]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.custom_flags(libc::O_DIRECT);
+    }
+    #[cfg(windows)]
+    {
+        use std::os::windows::fs::OpenOptionsExt;
+        // FILE_FLAG_NO_BUFFERING | FILE_FLAG_WRITE_THROUGH
+        options.custom_flags(0x2000_0000 | 0x8000_0000);
+    }
+    let file = options.open(path)?;
+    #[cfg(target_os = "macos")]
+    unsafe {
+        libc::fcntl(std::os::unix::io::AsRawFd::as_raw_fd(&file), libc::F_NOCACHE, 1);
+    }
+    Ok(file)
+}
+
+/// Measure sustained write throughput on `target` without changing existing data. A
+/// mounted filesystem (a directory) gets a scratch file of up to `max_bytes`, written,
+/// synced and deleted again. On a raw device the first `max_bytes` are read and written
+/// back unchanged with direct I/O, timing only the writes; if the device can't be opened
+/// for writing it is only read from and the result is marked `read_only`.
+pub fn benchmark_throughput(target: &str, max_bytes: u64) -> io::Result<ThroughputBenchmark> {
+    use rand::RngCore;
+
+    let target = Path::new(target);
+
+    if !target.is_dir() {
+        let mut storage = vec![0u8; BENCHMARK_CHUNK + DIRECT_IO_ALIGNMENT];
+        let align = storage.as_ptr().align_offset(DIRECT_IO_ALIGNMENT);
+        let buffer = &mut storage[align..align + BENCHMARK_CHUNK];
+
+        let mut device = match open_direct(target) {
+            Ok(device) => device,
+            Err(e) => {
+                log::warn!("⚠️  {} can't be opened for writing ({}); measuring read speed only", target.display(), e);
+                let mut device = File::open(target)?;
+                let start = Instant::now();
+                let mut bytes = 0u64;
+                while bytes < max_bytes {
+                    let to_read = std::cmp::min(buffer.len() as u64, max_bytes - bytes) as usize;
+                    let bytes_read = device.read(&mut buffer[..to_read])?;
+                    if bytes_read == 0 {
+                        break;
+                    }
+                    bytes += bytes_read as u64;
+                }
+                return Ok(ThroughputBenchmark { bytes, seconds: start.elapsed().as_secs_f64(), read_only: true });
+            }
+        };
+
+        let alignment = DIRECT_IO_ALIGNMENT as u64;
+        let region = max_bytes.min(device.seek(SeekFrom::End(0))?) / alignment * alignment;
+        let mut writing = Duration::ZERO;
+        let mut bytes = 0u64;
+        while bytes < region {
+            let len = std::cmp::min(buffer.len() as u64, region - bytes) as usize;
+            device.seek(SeekFrom::Start(bytes))?;
+            device.read_exact(&mut buffer[..len])?;
+            device.seek(SeekFrom::Start(bytes))?;
+            let start = Instant::now();
+            device.write_all(&buffer[..len])?;
+            writing += start.elapsed();
+            bytes += len as u64;
+        }
+        let start = Instant::now();
+        device.sync_all()?;
+        writing += start.elapsed();
+        return Ok(ThroughputBenchmark { bytes, seconds: writing.as_secs_f64(), read_only: false });
+    }
+
+    let mut buffer = vec![0u8; BENCHMARK_CHUNK];
+
+    // Random data, so compressing or deduplicating drives can't make the write look faster
+    rand::thread_rng().fill_bytes(&mut buffer);
+    let scratch_path = target.join(format!(".shredx_benchmark_{}.tmp", std::process::id()));
+    let mut scratch = File::create_new(&scratch_path)?;
+    let start = Instant::now();
+    let written = (|| -> io::Result<u64> {
+        let mut bytes = 0u64;
+        while bytes < max_bytes {
+            let to_write = std::cmp::min(buffer.len() as u64, max_bytes - bytes) as usize;
+            scratch.write_all(&buffer[..to_write])?;
+            bytes += to_write as u64;
+        }
+        // Count the time to get the data onto the drive, not just into the page cache
+        scratch.sync_all()?;
+        Ok(bytes)
+    })();
+    let seconds = start.elapsed().as_secs_f64();
+    drop(scratch);
+    if let Err(e) = std::fs::remove_file(&scratch_path) {
+        log::warn!("⚠️  Could not delete benchmark file {}: {}", scratch_path.display(), e);
+    }
+
+    Ok(ThroughputBenchmark { bytes: written?, seconds, read_only: false })
+}
+
+/// Number of sector samples hashed for the certificate after a device wipe
+pub const SECTOR_SAMPLE_COUNT: usize = 256;
+/// Minimum bytes per sector sample, rounded up to whole sectors
+pub const SECTOR_SAMPLE_SIZE: u64 = 4096;
+
+/// Hash `length` bytes at each offset, e.g. to record the wiped state for the certificate
+pub fn read_sector_samples(mut file: &File, offsets: &[u64], length: u64) -> io::Result<Vec<SectorSample>> {
+    let mut buffer = vec![0u8; length as usize];
+    offsets.iter().map(|&offset| {
+        file.seek(SeekFrom::Start(offset))?;
+        file.read_exact(&mut buffer)?;
+        Ok(SectorSample::of(offset, &buffer))
+    }).collect()
+}
+
+/// Hash `count` randomly placed, sector-aligned stretches of the device, sorted by offset.
+/// Much cheaper than `hash_device`, yet still checkable by anyone who re-reads the offsets.
+pub fn sample_sector_hashes(device_path: &str, size_bytes: u64, sector_size: u32, count: usize) -> io::Result<Vec<SectorSample>> {
+    use rand::Rng;
+
+    let sector_size = u64::from(sector_size.max(1));
+    let length = SECTOR_SAMPLE_SIZE.div_ceil(sector_size) * sector_size;
+    if size_bytes < length {
+        return Ok(Vec::new());
+    }
+    let last_sector = (size_bytes - length) / sector_size;
+
+    let mut rng = rand::thread_rng();
+    let mut offsets: Vec<u64> = (0..count).map(|_| rng.gen_range(0..=last_sector) * sector_size).collect();
+    offsets.sort_unstable();
+    offsets.dedup();
+
+    read_sector_samples(&File::open(device_path)?, &offsets, length)
+}
+
+/// Check whether the kernel reports the block device as zoned (host-aware or host-managed SMR).
+///
+/// Drive-managed SMR disks are not visible here; those are only reported through
+/// the ATA IDENTIFY zoned capabilities field.
+#[cfg(target_os = "linux")]
+pub fn is_zoned_device(device_path: &str) -> bool {
+    let name = match Path::new(device_path).file_name().and_then(|n| n.to_str()) {
+        Some(name) => name,
+        None => return false,
+    };
+    let sys_dir = Path::new("/sys/class/block").join(name);
+    // Partitions have no queue of their own; use the parent disk's
+    let zoned_path = if sys_dir.join("partition").exists() {
+        sys_dir.join("../queue/zoned")
+    } else {
+        sys_dir.join("queue/zoned")
+    };
+
+    std::fs::read_to_string(zoned_path)
+        .map(|zoned| zoned.trim() != "none")
+        .unwrap_or(false)
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn is_zoned_device(_device_path: &str) -> bool {
+    false
+}
+
+/// Whether a drive is a solid-state hybrid (SSHD), from its model and rotation.
+///
+/// SSHDs report themselves as ordinary rotating disks, so the model string is the main
+/// clue; a drive that reports non-rotating media (rotation rate 1, or sysfs
+/// `rotational` 0) is a plain SSD, never a hybrid.
+pub fn is_hybrid_drive(model: &str, rotation_rate: Option<u16>, device_path: &str) -> bool {
+    if rotation_rate == Some(1) || is_rotational(device_path) == Some(false) {
+        return false;
+    }
+    is_hybrid_model(model)
+}
+
+/// Model-name heuristics for the common SSHD families
+pub fn is_hybrid_model(model: &str) -> bool {
+    let model = model.to_uppercase();
+    if model.contains("SSHD") || model.contains("HYBRID") || model.contains("MOMENTUS XT") {
+        return true;
+    }
+    model.split_whitespace().any(|part| {
+        // Seagate: ST<capacity>DX00n (desktop SSHD), ST<capacity>LX00n (Momentus XT) and
+        // the laptop SSHDs, which share the LM prefix with plain laptop disks
+        let seagate = part.strip_prefix("ST")
+            .map(|rest| rest.trim_start_matches(|c: char| c.is_ascii_digit()))
+            .is_some_and(|family| family.starts_with("DX") || family.starts_with("LX"))
+            || matches!(part, "ST500LM000" | "ST1000LM014" | "ST1000LM000");
+        // Toshiba: MQ01ABD/MQ02ABD laptop disks with an H suffix
+        let toshiba = (part.starts_with("MQ01ABD") || part.starts_with("MQ02ABD")) && part.ends_with('H');
+        seagate || toshiba
+    })
+}
+
+/// Whether the OS reports the block device as rotating media; `None` where it can't tell
+#[cfg(target_os = "linux")]
+fn is_rotational(device_path: &str) -> Option<bool> {
+    let name = Path::new(device_path).file_name()?.to_str()?;
+    let sys_dir = Path::new("/sys/class/block").join(name);
+    // Partitions have no queue of their own; use the parent disk's
+    let rotational_path = if sys_dir.join("partition").exists() {
+        sys_dir.join("../queue/rotational")
+    } else {
+        sys_dir.join("queue/rotational")
+    };
+    std::fs::read_to_string(rotational_path).ok().map(|rotational| rotational.trim() == "1")
+}
+
+#[cfg(not(target_os = "linux"))]
+fn is_rotational(_device_path: &str) -> Option<bool> {
+    None
+}
+
+/// How often the drive temperature is polled while writing
+const THERMAL_CHECK_INTERVAL: Duration = Duration::from_secs(30);
+/// Pause between temperature readings while waiting for a hot drive to cool
+const THERMAL_COOLDOWN_PAUSE: Duration = Duration::from_secs(10);
+/// Writing resumes once the drive is this many degrees below the limit
+const THERMAL_HYSTERESIS_C: u32 = 5;
+
+/// Pauses an overwrite while the drive is hotter than a configured limit.
+///
+/// Throttling is a no-op when no limit is set or the drive reports no temperature.
+pub struct ThermalThrottle {
+    device_path: String,
+    limit_c: Option<u32>,
+    last_check: Instant,
+}
+
+impl ThermalThrottle {
+    pub fn new(device_path: &str, limit_c: Option<u32>) -> Self {
+        let limit_c = limit_c.filter(|_| read_drive_temperature(device_path).is_some());
+        if let Some(limit) = limit_c {
+            log::info!("🌡️ Thermal throttling enabled for {} (limit {}°C)", device_path, limit);
+        }
+        
+        Self {
+            device_path: device_path.to_string(),
+            limit_c,
+            last_check: Instant::now(),
+        }
+    }
+
+    /// Whether temperature readings are driving the pauses
+    pub fn is_active(&self) -> bool {
+        self.limit_c.is_some()
+    }
+
+    /// Block until the drive is back under the limit.
+    ///
+    /// `on_pause` is called with the current temperature before each cooldown pause.
+    pub fn wait_if_hot(&mut self, mut on_pause: impl FnMut(u32)) {
+        let limit = match self.limit_c {
+            Some(limit) => limit,
+            None => return,
+        };
+        if self.last_check.elapsed() < THERMAL_CHECK_INTERVAL {
+            return;
+        }
+        self.last_check = Instant::now();
+        
+        let mut temperature = match read_drive_temperature(&self.device_path) {
+            Some(temperature) if temperature > limit => temperature,
+            _ => return,
+        };
+        
+        log::info!("🌡️ {} is at {}°C (limit {}°C) - pausing writes to let it cool", self.device_path, temperature, limit);
+        while temperature > limit.saturating_sub(THERMAL_HYSTERESIS_C) {
+            on_pause(temperature);
+            thread::sleep(THERMAL_COOLDOWN_PAUSE);
+            temperature = match read_drive_temperature(&self.device_path) {
+                Some(temperature) => temperature,
+                None => break,
+            };
+        }
+        log::info!("🌡️ {} cooled to {}°C - resuming writes", self.device_path, temperature);
+        self.last_check = Instant::now();
+    }
+}
+
+/// Read the drive temperature in °C, or `None` if the drive doesn't report one.
+///
+/// Uses the kernel hwmon sensor when available (NVMe, or SATA with `drivetemp` loaded)
+/// and falls back to the SMART attributes reported by `smartctl`.
+pub fn read_drive_temperature(device_path: &str) -> Option<u32> {
+    #[cfg(target_os = "linux")]
+    if let Some(temperature) = read_hwmon_temperature(device_path) {
+        return Some(temperature);
+    }
+    
+    let output = Command::new("smartctl").args(["-A", device_path]).output().ok()?;
+    parse_smartctl_temperature(&String::from_utf8_lossy(&output.stdout))
+}
+
+#[cfg(target_os = "linux")]
+fn read_hwmon_temperature(device_path: &str) -> Option<u32> {
+    let name = Path::new(device_path).file_name()?.to_str()?;
+    let mut sys_dir = Path::new("/sys/class/block").join(name);
+    // Partitions have no sensor of their own; use the parent disk's
+    if sys_dir.join("partition").exists() {
+        sys_dir = sys_dir.join("..");
+    }
+    let device_dir = sys_dir.join("device");
+    
+    // SATA drivetemp sensors live under device/hwmon/, NVMe ones directly under device/
+    let hwmon_dirs = std::fs::read_dir(device_dir.join("hwmon")).into_iter().flatten()
+        .chain(std::fs::read_dir(&device_dir).into_iter().flatten())
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_name().to_string_lossy().starts_with("hwmon"));
+    
+    for entry in hwmon_dirs {
+        if let Ok(millidegrees) = std::fs::read_to_string(entry.path().join("temp1_input")) {
+            if let Ok(millidegrees) = millidegrees.trim().parse::<i64>() {
+                return Some((millidegrees.max(0) / 1000) as u32);
+            }
+        }
+    }
+    None
+}
+
+/// Identity data exactly as the drive returned it: the 512-byte ATA IDENTIFY DEVICE
+/// response, or the 4096-byte NVMe Identify Controller structure via nvme-cli. `None`
+/// for virtual devices or when the drive can't be asked.
+pub fn read_raw_identify(device_info: &DeviceInfo) -> Option<Vec<u8>> {
+    let data = match (&device_info.interface, &device_info.device_type) {
+        (ConnectionInterface::Virtual, _) => return None,
+        (_, DeviceType::NVMe) => {
+            let output = Command::new("nvme").args(["id-ctrl", &device_info.device_path, "--raw-binary"]).output().ok()?;
+            (output.status.success() && output.stdout.len() == 4096).then_some(output.stdout)?
+        }
+        _ => {
+            let identify_data = AtaInterface::new(&device_info.device_path).ok()?.identify_device().ok()?;
+            identify_data.data.iter().flat_map(|word| word.to_le_bytes()).collect()
+        }
+    };
+    // A bridge that swallows the command can still report success with an empty buffer
+    data.iter().any(|&b| b != 0).then_some(data)
+}
+
+/// Snapshot the drive's SMART health, reallocated sectors, power-on hours and
+/// temperature via `smartctl`, or `None` if none of them can be read
+pub fn read_smart_snapshot(device_path: &str) -> Option<SmartSnapshot> {
+    let output = Command::new("smartctl").args(["-H", "-A", device_path]).output().ok()?;
+    let snapshot = parse_smartctl_snapshot(&String::from_utf8_lossy(&output.stdout));
+    let empty = snapshot.health.is_none() && snapshot.reallocated_sectors.is_none()
+        && snapshot.power_on_hours.is_none() && snapshot.temperature_c.is_none();
+    (!empty).then_some(snapshot)
+}
+
+/// Parse `smartctl -H -A` output from ATA, NVMe and SCSI drives
+fn parse_smartctl_snapshot(output: &str) -> SmartSnapshot {
+    // NVMe counters are printed with thousands separators
+    let number = |value: &str| value.replace(',', "").parse::<u64>().ok();
+    let mut snapshot = SmartSnapshot {
+        taken_at: chrono::Utc::now(),
+        temperature_c: parse_smartctl_temperature(output),
+        ..Default::default()
+    };
+    
+    for line in output.lines() {
+        let line = line.trim();
+        if let Some(result) = line.strip_prefix("SMART overall-health self-assessment test result:")
+            .or_else(|| line.strip_prefix("SMART Health Status:"))
+        {
+            snapshot.health = Some(result.trim().to_string());
+        } else if let Some(hours) = line.strip_prefix("Power On Hours:") {
+            snapshot.power_on_hours = number(hours.trim());
+        } else if let Some(time) = line.strip_prefix("Accumulated power on time, hours:minutes") {
+            snapshot.power_on_hours = time.trim().split(':').next().and_then(number);
+        } else if let Some(defects) = line.strip_prefix("Elements in grown defect list:") {
+            snapshot.reallocated_sectors = number(defects.trim());
+        } else {
+            // ATA attribute table: raw value is the 10th column, e.g. "12345" or "12345h+05m"
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let raw = fields.get(9).and_then(|v| number(v.split(|c: char| !c.is_ascii_digit() && c != ',').next().unwrap_or("")));
+            match fields.get(1) {
+                Some(&"Reallocated_Sector_Ct") => snapshot.reallocated_sectors = raw,
+                Some(&"Power_On_Hours") => snapshot.power_on_hours = raw,
+                _ => {}
+            }
+        }
+    }
+    snapshot
+}
+
+/// Pull the current temperature out of `smartctl -A` output
+fn parse_smartctl_temperature(output: &str) -> Option<u32> {
+    for line in output.lines() {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        
+        // NVMe health log: "Temperature:    35 Celsius"
+        if fields.first() == Some(&"Temperature:") {
+            if let Some(value) = fields.get(1).and_then(|v| v.parse().ok()) {
+                return Some(value);
+            }
+        }
+        
+        // ATA attribute table: raw value is the 10th column
+        if fields.get(1).is_some_and(|name| *name == "Temperature_Celsius" || *name == "Airflow_Temperature_Cel") {
+            if let Some(value) = fields.get(9).and_then(|v| v.parse().ok()) {
+                return Some(value);
+            }
+        }
+    }
+    None
+}
+
 /// Get list of all available wiping algorithms with descriptions
 pub fn get_available_algorithms() -> Vec<(WipingAlgorithm, &'static str, &'static str)> {
-    vec![
+    let described = [
         // NIST 800-88 Methods (Recommended)
-        (WipingAlgorithm::NistClear, "NIST Clear", "Single pass cryptographic random overwrite (NIST 800-88)"),
-        (WipingAlgorithm::NistPurge, "NIST Purge", "7-pass cryptographic destruction (NIST 800-88)"),
+        (WipingAlgorithm::NistClear, "Single pass cryptographic random overwrite (NIST 800-88)"),
+        (WipingAlgorithm::NistPurge, "7-pass cryptographic destruction (NIST 800-88)"),
+        (WipingAlgorithm::NistAuto, "Clear or Purge chosen per drive from media type and data sensitivity (NIST 800-88)"),
         
         // Hardware Methods (Fastest for compatible devices)
-        (WipingAlgorithm::AtaSecureErase, "ATA Secure Erase", "Hardware-based instant cryptographic erase"),
-        (WipingAlgorithm::AtaEnhancedSecureErase, "ATA Enhanced Secure Erase", "Enhanced hardware cryptographic erase"),
-        (WipingAlgorithm::NvmeSecureErase, "NVMe Secure Erase", "NVMe hardware secure erase"),
-        (WipingAlgorithm::NvmeCryptoErase, "NVMe Crypto Erase", "NVMe cryptographic key destruction"),
+        (WipingAlgorithm::AtaSecureErase, "Hardware-based instant cryptographic erase"),
+        (WipingAlgorithm::AtaEnhancedSecureErase, "Enhanced hardware cryptographic erase"),
+        (WipingAlgorithm::NvmeSecureErase, "NVMe hardware secure erase"),
+        (WipingAlgorithm::NvmeCryptoErase, "NVMe cryptographic key destruction"),
         
         // Standard Multi-pass Methods
-        (WipingAlgorithm::DoD522022M, "DoD 5220.22-M", "3-pass DoD standard overwrite"),
-        (WipingAlgorithm::DoD522022MEce, "DoD 5220.22-M ECE", "7-pass enhanced DoD standard"),
-        (WipingAlgorithm::Gutmann, "Gutmann Method", "35-pass thorough overwrite (legacy drives)"),
-        (WipingAlgorithm::ThreePass, "3-Pass Wipe", "Zero, Ones, Random pattern"),
-        (WipingAlgorithm::SevenPass, "7-Pass Enhanced", "Enhanced multi-pattern overwrite"),
+        (WipingAlgorithm::DoD522022M, "3-pass DoD standard overwrite"),
+        (WipingAlgorithm::DoD522022MEce, "7-pass enhanced DoD standard"),
+        (WipingAlgorithm::Gutmann, "35-pass thorough overwrite (legacy drives)"),
+        (WipingAlgorithm::HmgIs5Baseline, "UK HMG Infosec Standard 5: zeros, verified"),
+        (WipingAlgorithm::HmgIs5Enhanced, "UK HMG Infosec Standard 5: zeros, ones, random, verified"),
+        (WipingAlgorithm::RcmpTssitOps2, "Canadian RCMP standard: 6 alternating zero/one passes, random, verified"),
+        (WipingAlgorithm::AustralianIsm, "ACSC ISM: verified overwrite for magnetic media, crypto/block erase for flash"),
+        (WipingAlgorithm::Vsitr, "German BSI VSITR: 6 alternating zero/one passes, then 0xAA"),
+        (WipingAlgorithm::Afssi5020, "US Air Force: zeros, ones, random, verified"),
+        (WipingAlgorithm::NavsoP523926, "US Navy: character, complement, random, verified"),
+        (WipingAlgorithm::ThreePass, "Zero, Ones, Random pattern"),
+        (WipingAlgorithm::SevenPass, "Enhanced multi-pattern overwrite"),
         
         // Single Pass Methods (Faster)
-        (WipingAlgorithm::Random, "Random Pass", "Single cryptographic random overwrite"),
-        (WipingAlgorithm::Zeros, "Zero Fill", "Single pass all zeros"),
-        (WipingAlgorithm::Ones, "Ones Fill", "Single pass all ones"),
+        (WipingAlgorithm::Random, "Single cryptographic random overwrite"),
+        (WipingAlgorithm::Zeros, "Single pass all zeros"),
+        (WipingAlgorithm::Ones, "Single pass all ones"),
         
         // Specialized Methods
-        (WipingAlgorithm::FileSystemWipe, "File System Wipe", "Wipe file system metadata only"),
-        (WipingAlgorithm::FreeSpaceWipe, "Free Space Only", "Wipe only unallocated space"),
-        (WipingAlgorithm::QuickFormat, "Quick Format", "Standard format (least secure)"),
-    ]
+        (WipingAlgorithm::FileSystemWipe, "Wipe file system metadata only"),
+        (WipingAlgorithm::FreeSpaceWipe, "Wipe only unallocated space"),
+        (WipingAlgorithm::CryptoErase, "Destroy the keys of an encrypted volume (LUKS/BitLocker)"),
+        (WipingAlgorithm::QuickFormat, "Standard format (least secure)"),
+        (WipingAlgorithm::QuickSanitize, "Partition tables and metadata only - NOT a full overwrite"),
+    ];
+    described.into_iter()
+        .map(|(algorithm, description)| {
+            let name = algorithm.name();
+            (algorithm, name, description)
+        })
+        .collect()
 }
\ No newline at end of file
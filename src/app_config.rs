@@ -2,6 +2,19 @@ use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::Path;
 
+/// What the login sync does when a certificate id exists locally and on the server
+/// with different contents
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SyncConflictPolicy {
+    /// Replace the local file with the server's copy, keeping the old file as a `.bak`
+    #[default]
+    ServerWins,
+    /// Upload the local copy; the server keeps both records and the newest is used from then on
+    LocalWins,
+    /// Keep the local copy and save the server's next to it under the id `<id>-server`
+    KeepBoth,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AppConfig {
     pub server_url: String,
@@ -9,6 +22,15 @@ pub struct AppConfig {
     pub auto_upload_certificates: bool,
     pub local_cert_storage: String,
     pub debug_mode: bool,
+    /// Outbound proxy for server requests, e.g. `http://proxy:3128` or `socks5://proxy:1080`
+    #[serde(default)]
+    pub proxy_url: Option<String>,
+    /// SHA-256 fingerprint (hex, colons optional) of the server's TLS certificate.
+    /// When set, only a server presenting exactly this certificate is trusted.
+    #[serde(default)]
+    pub pinned_cert_sha256: Option<String>,
+    #[serde(default)]
+    pub sync_conflict_policy: SyncConflictPolicy,
 }
 
 impl Default for AppConfig {
@@ -19,6 +41,9 @@ impl Default for AppConfig {
             auto_upload_certificates: true,
             local_cert_storage: "./certificates".to_string(),
             debug_mode: false,
+            proxy_url: None,
+            pinned_cert_sha256: None,
+            sync_conflict_policy: SyncConflictPolicy::default(),
         }
     }
 }
@@ -33,12 +58,21 @@ impl AppConfig {
                 auto_upload_certificates: true,
                 local_cert_storage: "./certificates".to_string(),
                 debug_mode: std::env::var("HDD_TOOL_DEBUG").is_ok(),
+                proxy_url: Self::proxy_from_env(),
+                pinned_cert_sha256: std::env::var("HDD_TOOL_PINNED_CERT_SHA256").ok(),
+                sync_conflict_policy: SyncConflictPolicy::default(),
             };
         }
         
         // Try to load from config file
         if let Ok(config_str) = fs::read_to_string("config.json") {
-            if let Ok(config) = serde_json::from_str::<AppConfig>(&config_str) {
+            if let Ok(mut config) = serde_json::from_str::<AppConfig>(&config_str) {
+                if let Some(proxy_url) = Self::proxy_from_env() {
+                    config.proxy_url = Some(proxy_url);
+                }
+                if let Ok(fingerprint) = std::env::var("HDD_TOOL_PINNED_CERT_SHA256") {
+                    config.pinned_cert_sha256 = Some(fingerprint);
+                }
                 return config;
             }
         }
@@ -55,6 +89,10 @@ impl AppConfig {
         Ok(())
     }
     
+    fn proxy_from_env() -> Option<String> {
+        std::env::var("HDD_TOOL_PROXY").ok().filter(|p| !p.trim().is_empty())
+    }
+    
     pub fn is_server_enabled(&self) -> bool {
         self.enable_server && !self.server_url.is_empty()
     }
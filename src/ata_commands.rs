@@ -72,6 +72,22 @@ pub const ATA_SECURITY_DISABLE_PASSWORD: u8 = 0xF6;
 const IOCTL_ATA_PASS_THROUGH: u32 = 0x0004D02C;
 /// IOCTL for ATA pass-through direct commands
 const IOCTL_ATA_PASS_THROUGH_DIRECT: u32 = 0x0004D030;
+/// ATA_PASS_THROUGH_EX flag for 48-bit commands; without it Windows neither sends nor
+/// returns the previous (HOB) task file holding LBA bits 24-47
+const ATA_FLAGS_48BIT_COMMAND: u16 = 0x08;
+
+// Task file register offsets within `current_task_file` / `previous_task_file`
+const TF_LBA_LOW: usize = 2;
+const TF_LBA_MID: usize = 3;
+const TF_LBA_HIGH: usize = 4;
+const TF_DEVICE: usize = 5;
+const TF_COMMAND: usize = 6;
+/// Device register bit selecting LBA (rather than CHS) addressing
+const DEVICE_LBA_MODE: u8 = 0x40;
+
+/// Highest LBA the 28-bit commands can address: 2^28 512-byte sectors, i.e. 128 GiB.
+/// Larger drives need the EXT commands
+pub const LBA28_MAX: u64 = 0x0FFF_FFFF;
 
 // ============================================================================
 // ATA DATA STRUCTURES
@@ -101,6 +117,59 @@ pub struct IdentifyDeviceData {
     pub data: [u16; 256],
 }
 
+impl IdentifyDeviceData {
+    /// Word 83 bit 10: the 48-bit Address feature set
+    pub fn supports_lba48(&self) -> bool {
+        self.data[83] & 0x0400 != 0
+    }
+
+    /// User-addressable sectors. 48-bit drives report them in words 100-103; words 60-61
+    /// stop at 0x0FFFFFFF however large the drive is. Words 100-103 are zero on 28-bit
+    /// drives, so they are trusted even when a bridge has cleared word 83.
+    pub fn user_sectors(&self) -> u64 {
+        let words = &self.data;
+        let lba48 = ((words[103] as u64) << 48) | ((words[102] as u64) << 32) |
+            ((words[101] as u64) << 16) | (words[100] as u64);
+        let lba28 = ((words[61] as u64) << 16) | (words[60] as u64);
+        if self.supports_lba48() { lba48 } else { lba28.max(lba48) }
+    }
+
+    /// Whether READ NATIVE MAX / SET MAX must use their EXT forms. Any 48-bit drive takes
+    /// them, and a drive past the 28-bit limit needs them: the 28-bit forms would read back
+    /// a truncated native max and SET MAX would hide everything above it.
+    pub fn use_ext_commands(&self) -> bool {
+        self.supports_lba48() || self.user_sectors() > LBA28_MAX + 1
+    }
+}
+
+/// Put an LBA in the task file registers, with bits 24-47 in the previous task file for
+/// 48-bit commands and bits 24-27 in the device register for 28-bit ones
+fn write_task_file_lba(ata_pt: &mut AtaPassThroughEx, lba: u64, use_ext: bool) {
+    ata_pt.current_task_file[TF_LBA_LOW] = (lba & 0xFF) as u8;
+    ata_pt.current_task_file[TF_LBA_MID] = ((lba >> 8) & 0xFF) as u8;
+    ata_pt.current_task_file[TF_LBA_HIGH] = ((lba >> 16) & 0xFF) as u8;
+    if use_ext {
+        ata_pt.previous_task_file[TF_LBA_LOW] = ((lba >> 24) & 0xFF) as u8;
+        ata_pt.previous_task_file[TF_LBA_MID] = ((lba >> 32) & 0xFF) as u8;
+        ata_pt.previous_task_file[TF_LBA_HIGH] = ((lba >> 40) & 0xFF) as u8;
+        ata_pt.current_task_file[TF_DEVICE] = DEVICE_LBA_MODE;
+    } else {
+        ata_pt.current_task_file[TF_DEVICE] = DEVICE_LBA_MODE | ((lba >> 24) & 0x0F) as u8;
+    }
+}
+
+/// Read back the LBA a command returned in the task file registers
+fn read_task_file_lba(ata_pt: &AtaPassThroughEx, use_ext: bool) -> u64 {
+    let current = &ata_pt.current_task_file;
+    let low = current[TF_LBA_LOW] as u64 | (current[TF_LBA_MID] as u64) << 8 | (current[TF_LBA_HIGH] as u64) << 16;
+    if use_ext {
+        let previous = &ata_pt.previous_task_file;
+        low | (previous[TF_LBA_LOW] as u64) << 24 | (previous[TF_LBA_MID] as u64) << 32 | (previous[TF_LBA_HIGH] as u64) << 40
+    } else {
+        low | (current[TF_DEVICE] as u64 & 0x0F) << 24
+    }
+}
+
 /// Comprehensive drive information extracted from ATA commands
 #[derive(Debug)]
 pub struct DriveInfo {
@@ -126,6 +195,10 @@ pub struct DriveInfo {
     pub security_locked: bool,
     /// Whether security is frozen (requires power cycle to unlock)
     pub security_frozen: bool,
+    /// Whether the drive reports zoned (SMR) capabilities
+    pub zoned: bool,
+    /// Nominal media rotation rate (word 217): 1 = non-rotating, otherwise RPM; 0 if not reported
+    pub rotation_rate: u16,
     /// Drive type description
     pub drive_type: String,
 }
@@ -190,7 +263,7 @@ impl AtaInterface {
         };
 
         // Set up the command
-        ata_pt.current_task_file[6] = ATA_IDENTIFY_DEVICE;
+        ata_pt.current_task_file[TF_COMMAND] = ATA_IDENTIFY_DEVICE;
 
         let mut bytes_returned = 0u32;
         let mut buffer = vec![0u8; mem::size_of::<AtaPassThroughEx>() + 512];
@@ -228,10 +301,12 @@ impl AtaInterface {
         Ok(identify_data)
     }
 
+    /// Highest native LBA of the drive, HPA included. Pick `use_ext` with
+    /// `IdentifyDeviceData::use_ext_commands`.
     pub fn read_native_max_address(&self, use_ext: bool) -> io::Result<u64> {
         let mut ata_pt = AtaPassThroughEx {
             length: mem::size_of::<AtaPassThroughEx>() as u16,
-            ata_flags: 0x02, // ATA_FLAGS_DATA_IN
+            ata_flags: if use_ext { 0x02 | ATA_FLAGS_48BIT_COMMAND } else { 0x02 }, // ATA_FLAGS_DATA_IN
             path_id: 0,
             target_id: 0,
             lun: 0,
@@ -245,11 +320,12 @@ impl AtaInterface {
         };
 
         // Set up the command
-        if use_ext {
-            ata_pt.current_task_file[6] = ATA_READ_NATIVE_MAX_ADDRESS_EXT;
+        ata_pt.current_task_file[TF_DEVICE] = DEVICE_LBA_MODE;
+        ata_pt.current_task_file[TF_COMMAND] = if use_ext {
+            ATA_READ_NATIVE_MAX_ADDRESS_EXT
         } else {
-            ata_pt.current_task_file[6] = ATA_READ_NATIVE_MAX_ADDRESS;
-        }
+            ATA_READ_NATIVE_MAX_ADDRESS
+        };
 
         let mut bytes_returned = 0u32;
         let mut buffer = vec![0u8; mem::size_of::<AtaPassThroughEx>()];
@@ -278,35 +354,21 @@ impl AtaInterface {
 
             // Extract result from task file registers
             let result_ata_pt = &*(buffer.as_ptr() as *const AtaPassThroughEx);
-            let lba = if use_ext {
-                // 48-bit LBA
-                let lba_low = result_ata_pt.current_task_file[3] as u64;
-                let lba_mid = result_ata_pt.current_task_file[4] as u64;
-                let lba_high = result_ata_pt.current_task_file[5] as u64;
-                let lba_low_prev = result_ata_pt.previous_task_file[3] as u64;
-                let lba_mid_prev = result_ata_pt.previous_task_file[4] as u64;
-                let lba_high_prev = result_ata_pt.previous_task_file[5] as u64;
-                
-                lba_low | (lba_mid << 8) | (lba_high << 16) | 
-                (lba_low_prev << 24) | (lba_mid_prev << 32) | (lba_high_prev << 40)
-            } else {
-                // 28-bit LBA
-                let lba_low = result_ata_pt.current_task_file[3] as u64;
-                let lba_mid = result_ata_pt.current_task_file[4] as u64;
-                let lba_high = result_ata_pt.current_task_file[5] as u64;
-                let device = result_ata_pt.current_task_file[6] as u64;
-                
-                lba_low | (lba_mid << 8) | (lba_high << 16) | ((device & 0x0F) << 24)
-            };
-
-            Ok(lba)
+            Ok(read_task_file_lba(result_ata_pt, use_ext))
         }
     }
 
+    /// Set the highest user-addressable LBA. LBAs past the 28-bit limit are refused
+    /// unless `use_ext` is set, rather than silently truncated.
     pub fn set_max_address(&self, lba: u64, use_ext: bool) -> io::Result<()> {
+        if !use_ext && lba > LBA28_MAX {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput,
+                format!("LBA {} needs SET MAX ADDRESS EXT (48-bit)", lba)));
+        }
+        
         let mut ata_pt = AtaPassThroughEx {
             length: mem::size_of::<AtaPassThroughEx>() as u16,
-            ata_flags: 0x00, // No data transfer
+            ata_flags: if use_ext { ATA_FLAGS_48BIT_COMMAND } else { 0x00 }, // No data transfer
             path_id: 0,
             target_id: 0,
             lun: 0,
@@ -320,23 +382,12 @@ impl AtaInterface {
         };
 
         // Set up the command and LBA
-        if use_ext {
-            ata_pt.current_task_file[6] = ATA_SET_MAX_ADDRESS_EXT;
-            // 48-bit LBA
-            ata_pt.current_task_file[3] = (lba & 0xFF) as u8;
-            ata_pt.current_task_file[4] = ((lba >> 8) & 0xFF) as u8;
-            ata_pt.current_task_file[5] = ((lba >> 16) & 0xFF) as u8;
-            ata_pt.previous_task_file[3] = ((lba >> 24) & 0xFF) as u8;
-            ata_pt.previous_task_file[4] = ((lba >> 32) & 0xFF) as u8;
-            ata_pt.previous_task_file[5] = ((lba >> 40) & 0xFF) as u8;
+        write_task_file_lba(&mut ata_pt, lba, use_ext);
+        ata_pt.current_task_file[TF_COMMAND] = if use_ext {
+            ATA_SET_MAX_ADDRESS_EXT
         } else {
-            ata_pt.current_task_file[6] = ATA_SET_MAX_ADDRESS;
-            // 28-bit LBA
-            ata_pt.current_task_file[3] = (lba & 0xFF) as u8;
-            ata_pt.current_task_file[4] = ((lba >> 8) & 0xFF) as u8;
-            ata_pt.current_task_file[5] = ((lba >> 16) & 0xFF) as u8;
-            ata_pt.current_task_file[6] |= ((lba >> 24) & 0x0F) as u8;
-        }
+            ATA_SET_MAX_ADDRESS
+        };
 
         let mut bytes_returned = 0u32;
         let mut buffer = vec![0u8; mem::size_of::<AtaPassThroughEx>()];
@@ -376,14 +427,7 @@ impl AtaInterface {
         let firmware = Self::extract_ata_string(&words[23..27]);
         
         // User addressable capacity
-        let user_capacity = if words[83] & 0x0400 != 0 {
-            // 48-bit addressing
-            ((words[103] as u64) << 48) | ((words[102] as u64) << 32) | 
-            ((words[101] as u64) << 16) | (words[100] as u64)
-        } else {
-            // 28-bit addressing
-            ((words[61] as u64) << 16) | (words[60] as u64)
-        } * 512; // Convert sectors to bytes
+        let user_capacity = data.user_sectors() * 512; // Convert sectors to bytes
 
         // Security features
         let security_word = words[128];
@@ -392,6 +436,12 @@ impl AtaInterface {
         let security_locked = security_word & 0x0004 != 0;
         let security_frozen = security_word & 0x0008 != 0;
 
+        // Zoned capabilities (word 69 bits 1:0): 01 = host-aware, 10 = device-managed SMR
+        let zoned = words[69] & 0x0003 != 0;
+
+        // Nominal media rotation rate; 0xFFFF is reserved
+        let rotation_rate = if words[217] == 0xFFFF { 0 } else { words[217] };
+
         DriveInfo {
             model,
             serial,
@@ -404,6 +454,8 @@ impl AtaInterface {
             security_enabled,
             security_locked,
             security_frozen,
+            zoned,
+            rotation_rate,
             drive_type: "Unknown".to_string(), // Will be determined by drive detection
         }
     }
@@ -442,7 +494,7 @@ impl AtaInterface {
         // 3. Issue the security erase command
         // 4. Wait for completion
         
-        println!("🔧 Performing ATA Security Erase (Enhanced: {})", enhanced);
+        log::info!("🔧 Performing ATA Security Erase (Enhanced: {})", enhanced);
         
         // Return error to force fallback to software overwrite
         // This is safer than simulating success without actually erasing data
@@ -5,6 +5,8 @@ use std::fs;
 use std::path::Path;
 use chrono::{DateTime, Utc};
 use sha2::{Sha256, Digest};
+use subtle::ConstantTimeEq;
+use zeroize::{Zeroize, Zeroizing};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct User {
@@ -18,6 +20,24 @@ pub struct User {
     pub is_active: bool,
 }
 
+/// Room reserved up front in password inputs, so typing never reallocates and leaves
+/// an unscrubbed copy of a partial password behind
+const PASSWORD_INPUT_CAPACITY: usize = 256;
+
+/// A password typed into the UI; its memory is zeroed when cleared or dropped
+pub type SecretString = Zeroizing<String>;
+
+/// An empty password input, preallocated so it stays in one allocation while edited
+pub fn secret_input() -> SecretString {
+    Zeroizing::new(String::with_capacity(PASSWORD_INPUT_CAPACITY))
+}
+
+/// Compare password hashes in constant time, so the time a mismatch takes doesn't
+/// reveal how much of the hash was right
+pub fn password_hashes_match(stored: &str, candidate: &str) -> bool {
+    stored.as_bytes().ct_eq(candidate.as_bytes()).into()
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum UserRole {
     Admin,
@@ -92,8 +112,9 @@ impl AuthSystem {
                 return Err("Account is disabled".to_string());
             }
             
-            let password_hash = Self::hash_password(password);
-            if user.password_hash == password_hash {
+            // An unsalted hash unlocks the account as well as the password does
+            let password_hash = Zeroizing::new(Self::hash_password(password));
+            if password_hashes_match(&user.password_hash, &password_hash) {
                 user.last_login = Some(Utc::now());
                 let user_clone = user.clone();
                 self.current_user = Some(user_clone.clone());
@@ -205,9 +226,9 @@ pub enum AuthPage {
 pub struct AuthUI {
     pub current_page: AuthPage,
     pub login_username: String,
-    pub login_password: String,
+    pub login_password: SecretString,
     pub create_username: String,
-    pub create_password: String,
+    pub create_password: SecretString,
     pub create_email: String,
     pub error_message: Option<String>,
     pub success_message: Option<String>,
@@ -225,9 +246,9 @@ impl AuthUI {
         Self {
             current_page: AuthPage::Login,
             login_username: String::new(),
-            login_password: String::new(),
+            login_password: secret_input(),
             create_username: String::new(),
-            create_password: String::new(),
+            create_password: secret_input(),
             create_email: String::new(),
             error_message: None,
             success_message: None,
@@ -275,7 +296,7 @@ impl AuthUI {
                             ui.horizontal(|ui| {
                                 ui.label("🔒 Password:");
                                 ui.add_space(10.0);
-                                ui.add(egui::TextEdit::singleline(&mut self.login_password)
+                                ui.add(egui::TextEdit::singleline(&mut *self.login_password)
                                     .password(!self.show_password)
                                     .desired_width(200.0)
                                     .hint_text("Enter password"));
@@ -336,7 +357,7 @@ impl AuthUI {
                     self.success_message = Some(format!("Welcome back, {}!", user.username));
                     self.error_message = None;
                     self.login_username.clear();
-                    self.login_password.clear();
+                    self.login_password.zeroize();
                     return true; // Login successful
                 }
                 Err(error) => {
@@ -386,7 +407,7 @@ impl AuthUI {
                             
                             // Password row
                             ui.label("🔒 Password:");
-                            ui.add(egui::TextEdit::singleline(&mut self.create_password)
+                            ui.add(egui::TextEdit::singleline(&mut *self.create_password)
                                 .password(true)
                                 .desired_width(250.0)
                                 .hint_text("Min 6 characters"));
@@ -520,7 +541,7 @@ impl AuthUI {
     
     fn clear_create_form(&mut self) {
         self.create_username.clear();
-        self.create_password.clear();
+        self.create_password.zeroize();
         self.create_email.clear();
     }
 }
\ No newline at end of file
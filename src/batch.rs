@@ -0,0 +1,167 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// A selected drive as written to a selection file. The serial identifies the drive on a
+/// later run, since paths can change between boots; the path is the fallback.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SelectedDrive {
+    pub name: String,
+    pub path: String,
+    pub size_bytes: u64,
+    #[serde(default)]
+    pub serial: Option<String>,
+}
+
+impl SelectedDrive {
+    /// Whether this entry refers to an attached drive with the given path and serial
+    pub fn matches(&self, path: &str, serial: Option<&str>) -> bool {
+        drive_matches(self.serial.as_deref(), Some(&self.path), path, serial)
+    }
+}
+
+/// One drive of a batch job, by serial or path, and the method to run on it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JobEntry {
+    #[serde(default)]
+    pub serial: Option<String>,
+    #[serde(default)]
+    pub path: Option<String>,
+    /// Canonical method name, as accepted by `WipingAlgorithm::from_str`
+    pub algorithm: String,
+}
+
+impl JobEntry {
+    pub fn matches(&self, path: &str, serial: Option<&str>) -> bool {
+        drive_matches(self.serial.as_deref(), self.path.as_deref(), path, serial)
+    }
+
+    /// How the entry names its drive, for error messages
+    pub fn describe(&self) -> String {
+        match (&self.serial, &self.path) {
+            (Some(serial), _) => format!("serial {}", serial),
+            (None, Some(path)) => path.clone(),
+            (None, None) => "an entry with no serial or path".to_string(),
+        }
+    }
+}
+
+/// An entry with a serial only matches the drive with that serial, never one whose
+/// serial couldn't be read; entries without one match by path
+pub fn drive_matches(expected_serial: Option<&str>, expected_path: Option<&str>, path: &str, serial: Option<&str>) -> bool {
+    match (expected_serial, serial) {
+        (Some(expected), Some(serial)) => expected.eq_ignore_ascii_case(serial),
+        (Some(_), None) => false,
+        (None, _) => expected_path == Some(path),
+    }
+}
+
+/// Selection and job files are CSV when named `*.csv`, JSON otherwise
+fn is_csv(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("csv"))
+}
+
+pub fn save_selection(path: &Path, drives: &[SelectedDrive]) -> io::Result<()> {
+    let content = if is_csv(path) {
+        let mut csv = String::from("name,path,size_bytes,serial\n");
+        for drive in drives {
+            let fields = [
+                drive.name.clone(),
+                drive.path.clone(),
+                drive.size_bytes.to_string(),
+                drive.serial.clone().unwrap_or_default(),
+            ];
+            let fields: Vec<String> = fields.iter().map(|field| csv_field(field)).collect();
+            csv.push_str(&fields.join(","));
+            csv.push('\n');
+        }
+        csv
+    } else {
+        serde_json::to_string_pretty(drives).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?
+    };
+    fs::write(path, content)
+}
+
+pub fn load_selection(path: &Path) -> io::Result<Vec<SelectedDrive>> {
+    let content = fs::read_to_string(path)?;
+    if !is_csv(path) {
+        return serde_json::from_str(&content).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e));
+    }
+
+    let mut drives = Vec::new();
+    for (number, line) in content.lines().enumerate().skip(1) {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let fields = parse_csv_line(line);
+        if fields.len() < 3 {
+            return Err(io::Error::new(io::ErrorKind::InvalidData,
+                format!("Line {}: expected name,path,size_bytes,serial", number + 1)));
+        }
+        drives.push(SelectedDrive {
+            name: fields[0].clone(),
+            path: fields[1].clone(),
+            size_bytes: fields[2].trim().parse().map_err(|_| io::Error::new(io::ErrorKind::InvalidData,
+                format!("Line {}: size_bytes is not a number", number + 1)))?,
+            serial: fields.get(3).map(|serial| serial.trim().to_string()).filter(|serial| !serial.is_empty()),
+        });
+    }
+    Ok(drives)
+}
+
+/// Load a batch job, a JSON array of entries or a CSV file with `serial,path,algorithm` columns
+pub fn load_job(path: &Path) -> io::Result<Vec<JobEntry>> {
+    let content = fs::read_to_string(path)?;
+    if !is_csv(path) {
+        return serde_json::from_str(&content).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e));
+    }
+
+    let non_empty = |field: Option<&String>| field.map(|f| f.trim().to_string()).filter(|f| !f.is_empty());
+    let mut entries = Vec::new();
+    for (number, line) in content.lines().enumerate().skip(1) {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let fields = parse_csv_line(line);
+        let Some(algorithm) = non_empty(fields.get(2)) else {
+            return Err(io::Error::new(io::ErrorKind::InvalidData,
+                format!("Line {}: expected serial,path,algorithm", number + 1)));
+        };
+        entries.push(JobEntry {
+            serial: non_empty(fields.first()),
+            path: non_empty(fields.get(1)),
+            algorithm,
+        });
+    }
+    Ok(entries)
+}
+
+/// Quote a CSV field when it holds a separator, quote or line break
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn parse_csv_line(line: &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => fields.push(std::mem::take(&mut field)),
+            _ => field.push(c),
+        }
+    }
+    fields.push(field);
+    fields
+}
@@ -1,12 +1,29 @@
 use serde::{Deserialize, Serialize};
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use chrono::{DateTime, Utc};
 use sha2::{Sha256, Digest};
+use base64::{Engine as _, engine::general_purpose};
 use uuid::Uuid;
+use crate::advanced_wiper::DeviceInfo;
+use crate::ui::format_bytes;
+
+/// Version written to new certificates. Bump it whenever the schema changes in a way
+/// `load_certificates` has to upgrade; fields added with `#[serde(default)]` need no bump.
+///
+/// 1. Certificates written before the version field existed
+/// 2. Adds `schema_version`
+pub const CERTIFICATE_SCHEMA_VERSION: u32 = 2;
+
+fn legacy_schema_version() -> u32 {
+    1
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SanitizationCertificate {
+    /// Schema the certificate was written with; missing in certificates from before version 2
+    #[serde(default = "legacy_schema_version")]
+    pub schema_version: u32,
     pub id: String,
     pub device_info: DeviceCertificateInfo,
     pub sanitization_info: SanitizationInfo,
@@ -14,6 +31,10 @@ pub struct SanitizationCertificate {
     pub verification_info: VerificationInfo,
     pub timestamp: DateTime<Utc>,
     pub user_info: UserInfo,
+    /// Log of the wipe (analysis, passes, verification) when the operator chose to attach
+    /// it; covered by the certificate hash like every other field
+    #[serde(default)]
+    pub wipe_log: Vec<String>,
     pub certificate_hash: String,
 }
 
@@ -30,6 +51,54 @@ pub struct DeviceCertificateInfo {
     pub supports_secure_erase: bool,
     pub supports_crypto_erase: bool,
     pub encryption_status: String,
+    #[serde(default)]
+    pub interface: String,        // Bus the drive was attached through (SATA, NVMe, USB, ...)
+    /// SMART state when the wipe started and ended; `None` if the drive reported none
+    #[serde(default)]
+    pub smart_before: Option<SmartSnapshot>,
+    #[serde(default)]
+    pub smart_after: Option<SmartSnapshot>,
+    /// Full analysis of the device as the wipe saw it, kept for the server's records
+    #[serde(default)]
+    pub profile: Option<DeviceInfo>,
+    /// Base64 of the ATA IDENTIFY DEVICE (512 bytes) or NVMe Identify Controller (4096
+    /// bytes) data read at wipe time, so auditors can parse the device's identity themselves
+    #[serde(default)]
+    pub raw_identify: Option<String>,
+}
+
+/// Key SMART values at one point in time, documenting the drive's physical condition
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SmartSnapshot {
+    pub taken_at: DateTime<Utc>,
+    pub health: Option<String>,           // Overall self-assessment ("PASSED", "FAILED", "OK")
+    pub reallocated_sectors: Option<u64>, // Reallocated sectors, or the SCSI grown defect list
+    pub power_on_hours: Option<u64>,
+    pub temperature_c: Option<u32>,
+}
+
+impl SmartSnapshot {
+    /// One-line form used in the text, HTML and PDF certificates
+    pub fn summary(&self) -> String {
+        let unknown = || "unknown".to_string();
+        format!("health {}, {} reallocated sectors, {} power-on hours, {} at {}",
+            self.health.clone().unwrap_or_else(unknown),
+            self.reallocated_sectors.map_or_else(unknown, |v| v.to_string()),
+            self.power_on_hours.map_or_else(unknown, |v| v.to_string()),
+            self.temperature_c.map_or_else(unknown, |v| format!("{}°C", v)),
+            self.taken_at.format("%Y-%m-%d %H:%M:%S UTC"))
+    }
+}
+
+fn smart_summary(snapshot: &Option<SmartSnapshot>) -> String {
+    snapshot.as_ref().map_or_else(|| "Not available".to_string(), SmartSnapshot::summary)
+}
+
+/// Size and SHA-256 of the raw identify data; the data itself is only in the JSON
+fn identify_summary(raw_identify: &Option<String>) -> String {
+    raw_identify.as_ref()
+        .and_then(|encoded| general_purpose::STANDARD.decode(encoded).ok())
+        .map_or_else(|| "Not captured".to_string(), |data| format!("{} bytes, SHA-256 {:x}", data.len(), Sha256::digest(&data)))
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -44,6 +113,68 @@ pub struct SanitizationInfo {
     pub average_speed_mbps: f64,
     pub success: bool,
     pub error_count: u32,
+    /// SHA-256 of the full device read back before the wipe (opt-in, doubles I/O time)
+    #[serde(default)]
+    pub pre_wipe_sha256: Option<String>,
+    /// SHA-256 of the full device after the wipe; reproducible for a zero-filled drive
+    #[serde(default)]
+    pub post_wipe_sha256: Option<String>,
+    /// How write errors were handled ("Abort on first error" / "Continue and report")
+    #[serde(default)]
+    pub error_policy: Option<String>,
+    /// Write errors logged during the wipe; `error_count` is the length of this list
+    #[serde(default)]
+    pub errors: Vec<String>,
+    /// Start, end and throughput of every pass, in the order they ran
+    #[serde(default)]
+    pub pass_timings: Vec<PassTiming>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PassTiming {
+    pub pass: u32,
+    pub pattern: String,
+    pub started_at: DateTime<Utc>,
+    pub ended_at: DateTime<Utc>,
+    pub bytes_processed: u64,
+    pub throughput_mbps: f64,
+}
+
+impl PassTiming {
+    /// e.g. "Pass 1 (Random): 1h02m @ 182 MB/s"
+    pub fn summary(&self) -> String {
+        let seconds = self.ended_at.signed_duration_since(self.started_at).num_seconds().max(0);
+        let duration = match (seconds / 3600, seconds % 3600 / 60, seconds % 60) {
+            (0, 0, s) => format!("{}s", s),
+            (0, m, s) => format!("{}m{:02}s", m, s),
+            (h, m, _) => format!("{}h{:02}m", h, m),
+        };
+        format!("Pass {} ({}): {} @ {:.0} MB/s", self.pass, self.pattern, duration, self.throughput_mbps)
+    }
+}
+
+/// A stretch of the device read back after the final pass. Re-reading `length` bytes at
+/// `offset` and hashing them has to give `sha256` again.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SectorSample {
+    pub offset: u64,
+    pub length: u64,
+    pub sha256: String,
+}
+
+impl SectorSample {
+    pub fn of(offset: u64, data: &[u8]) -> Self {
+        Self {
+            offset,
+            length: data.len() as u64,
+            sha256: format!("{:x}", Sha256::digest(data)),
+        }
+    }
+
+    /// e.g. "Offset 1048576 (+4096): 9f86d081..."
+    pub fn summary(&self) -> String {
+        format!("Offset {} (+{}): {}", self.offset, self.length, self.sha256)
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -63,6 +194,9 @@ pub struct VerificationInfo {
     pub verification_passed: bool,
     pub residual_data_found: bool,
     pub verification_details: String,
+    /// Sectors hashed after the final pass, so the spot-check can be repeated independently
+    #[serde(default)]
+    pub sampled_sectors: Vec<SectorSample>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -71,19 +205,80 @@ pub struct UserInfo {
     pub user_id: String,
     pub organization: String,
     pub role: String,
+    /// Technician sign-off captured before the certificate is finalized
+    #[serde(default)]
+    pub operator_signature: Option<String>,
+    /// Optional witness to the sanitization, for chain-of-custody records
+    #[serde(default)]
+    pub witness: Option<String>,
+}
+
+impl SanitizationCertificate {
+    /// Case-insensitive match of `query` against the device name, path, serial and certificate ID
+    pub fn matches_search(&self, query: &str) -> bool {
+        let query = query.trim().to_lowercase();
+        query.is_empty()
+            || [&self.device_info.device_name, &self.device_info.device_path, &self.device_info.serial_number, &self.id]
+                .iter()
+                .any(|field| field.to_lowercase().contains(&query))
+    }
+
+    /// Whether the certificate was issued within the inclusive day range; open ends match anything
+    pub fn issued_between(&self, from: Option<chrono::NaiveDate>, to: Option<chrono::NaiveDate>) -> bool {
+        let day = self.timestamp.date_naive();
+        from.is_none_or(|from| day >= from) && to.is_none_or(|to| day <= to)
+    }
 }
 
+/// File format a certificate is saved in when a wipe completes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CertificateFormat {
+    Json,
+    Text,
+    Xml,
+    Pdf,
+    Html,
+}
+
+impl CertificateFormat {
+    pub fn all() -> &'static [CertificateFormat] {
+        &[CertificateFormat::Json, CertificateFormat::Text, CertificateFormat::Xml,
+          CertificateFormat::Pdf, CertificateFormat::Html]
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            CertificateFormat::Json => "JSON",
+            CertificateFormat::Text => "Text report",
+            CertificateFormat::Xml => "XML",
+            CertificateFormat::Pdf => "PDF",
+            CertificateFormat::Html => "HTML report",
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            CertificateFormat::Json => "json",
+            CertificateFormat::Text => "txt",
+            CertificateFormat::Xml => "xml",
+            CertificateFormat::Pdf => "pdf",
+            CertificateFormat::Html => "html",
+        }
+    }
+}
+
+#[derive(Clone)]
 pub struct CertificateGenerator {
-    certificates_dir: String,
+    certificates_dir: PathBuf,
 }
 
 impl CertificateGenerator {
-    pub fn new() -> Self {
-        let certificates_dir = "./certificates".to_string();
+    pub fn new(certificates_dir: impl Into<PathBuf>) -> Self {
+        let certificates_dir = certificates_dir.into();
         
         // Create certificates directory if it doesn't exist
         if let Err(e) = fs::create_dir_all(&certificates_dir) {
-            eprintln!("Warning: Could not create certificates directory: {}", e);
+            log::warn!("Warning: Could not create certificates directory: {}", e);
         }
         
         Self { certificates_dir }
@@ -93,13 +288,16 @@ impl CertificateGenerator {
         &self,
         device_info: DeviceCertificateInfo,
         sanitization_info: SanitizationInfo,
+        standards_met: Vec<String>,
+        sampled_sectors: Vec<SectorSample>,
         user_info: UserInfo,
+        wipe_log: Vec<String>,
     ) -> Result<SanitizationCertificate, Box<dyn std::error::Error>> {
         let id = Uuid::new_v4().to_string();
         let timestamp = Utc::now();
 
-        // Determine compliance based on method and success
-        let compliance_info = self.determine_compliance(&sanitization_info);
+        // Determine compliance based on the method that ran and success
+        let compliance_info = self.determine_compliance(&sanitization_info, standards_met);
         
         // Generate verification info (in real implementation, this would come from actual verification)
         let verification_info = VerificationInfo {
@@ -112,9 +310,11 @@ impl CertificateGenerator {
             } else {
                 "Sanitization incomplete - verification could not be performed".to_string()
             },
+            sampled_sectors,
         };
 
         let mut certificate = SanitizationCertificate {
+            schema_version: CERTIFICATE_SCHEMA_VERSION,
             id: id.clone(),
             device_info,
             sanitization_info,
@@ -122,6 +322,7 @@ impl CertificateGenerator {
             verification_info,
             timestamp,
             user_info,
+            wipe_log,
             certificate_hash: String::new(), // Will be calculated below
         };
 
@@ -131,37 +332,15 @@ impl CertificateGenerator {
         Ok(certificate)
     }
 
-    fn determine_compliance(&self, sanitization_info: &SanitizationInfo) -> ComplianceInfo {
-        let mut standards_met = Vec::new();
-        let mut nist_compliant = false;
-        let mut dod_compliant = false;
+    /// `standards_met` comes from the method that actually ran (see
+    /// `advanced_wiper::standards_met`); a standard only counts as complied with when
+    /// the wipe also succeeded
+    fn determine_compliance(&self, sanitization_info: &SanitizationInfo, standards_met: Vec<String>) -> ComplianceInfo {
+        let nist_compliant = sanitization_info.success && standards_met.iter().any(|s| s == "NIST SP 800-88");
+        let dod_compliant = sanitization_info.success && standards_met.iter().any(|s| s == "DoD 5220.22-M");
         let hipaa_compliant = sanitization_info.success;
         let gdpr_compliant = sanitization_info.success;
 
-        // Check NIST SP 800-88 compliance
-        if sanitization_info.method.contains("NIST") || 
-           sanitization_info.algorithm.contains("Clear") ||
-           sanitization_info.algorithm.contains("Purge") {
-            standards_met.push("NIST SP 800-88".to_string());
-            nist_compliant = sanitization_info.success;
-        }
-
-        // Check DoD 5220.22-M compliance
-        if sanitization_info.method.contains("DoD") || 
-           sanitization_info.passes_completed >= 3 {
-            standards_met.push("DoD 5220.22-M".to_string());
-            dod_compliant = sanitization_info.success;
-        }
-
-        // Check for secure erase compliance
-        if sanitization_info.algorithm.contains("Secure Erase") {
-            standards_met.push("ATA Secure Erase".to_string());
-        }
-
-        if sanitization_info.algorithm.contains("Crypto Erase") {
-            standards_met.push("Cryptographic Erase".to_string());
-        }
-
         let security_level = if nist_compliant && dod_compliant {
             "High Security"
         } else if nist_compliant || dod_compliant {
@@ -182,7 +361,8 @@ impl CertificateGenerator {
         }
     }
 
-    fn calculate_certificate_hash(&self, certificate: &SanitizationCertificate) -> Result<String, Box<dyn std::error::Error>> {
+    /// SHA-256 of the certificate's JSON with the hash field blanked, as stored in `certificate_hash`
+    pub fn calculate_certificate_hash(&self, certificate: &SanitizationCertificate) -> Result<String, Box<dyn std::error::Error>> {
         // Create a temporary certificate with empty hash for hashing
         let mut temp_cert = certificate.clone();
         temp_cert.certificate_hash = String::new();
@@ -198,17 +378,50 @@ impl CertificateGenerator {
             certificate.device_info.device_name.replace(" ", "_"),
             certificate.timestamp.format("%Y%m%d_%H%M%S"));
         
-        let filepath = Path::new(&self.certificates_dir).join(&filename);
+        let mut filepath = Path::new(&self.certificates_dir).join(&filename);
+        // Another certificate for the same drive and second (e.g. a kept server copy) gets its id appended
+        if self.certificate_id_at(&filepath).is_some_and(|id| id != certificate.id) {
+            filepath = filepath.with_file_name(format!("{}_{}.json", filename.trim_end_matches(".json"), certificate.id));
+        }
         
         let json_data = serde_json::to_string_pretty(&certificate)?;
         fs::write(&filepath, json_data)?;
         
-        println!("✅ Certificate saved locally: {}", filepath.display());
+        log::info!("✅ Certificate saved locally: {}", filepath.display());
         Ok(filepath.to_string_lossy().to_string())
     }
+    
+    /// Overwrite the local file holding the certificate with the same id, or save it as a
+    /// new file when there is none. The overwritten file is first copied to a `.bak`
+    /// next to it, which `load_certificates` does not pick up.
+    pub fn replace_certificate_local(&self, certificate: &SanitizationCertificate) -> Result<String, Box<dyn std::error::Error>> {
+        let existing = fs::read_dir(&self.certificates_dir)?
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .find(|path| self.certificate_id_at(path).as_deref() == Some(certificate.id.as_str()));
+        let Some(filepath) = existing else {
+            return self.save_certificate_local(certificate);
+        };
+        
+        let backup = filepath.with_extension(format!("json.{}.bak", Utc::now().format("%Y%m%d_%H%M%S")));
+        fs::copy(&filepath, &backup)?;
+        log::info!("💾 Previous certificate backed up to {}", backup.display());
+        
+        fs::write(&filepath, serde_json::to_string_pretty(&certificate)?)?;
+        log::info!("✅ Certificate replaced locally: {}", filepath.display());
+        Ok(filepath.to_string_lossy().to_string())
+    }
+    
+    /// Id of the certificate stored in a JSON file, if it is one
+    fn certificate_id_at(&self, path: &Path) -> Option<String> {
+        if path.extension().and_then(|s| s.to_str()) != Some("json") {
+            return None;
+        }
+        let data: serde_json::Value = serde_json::from_str(&fs::read_to_string(path).ok()?).ok()?;
+        data["id"].as_str().map(|id| id.to_string())
+    }
 
     pub fn generate_certificate_report(&self, certificate: &SanitizationCertificate) -> String {
-        format!(
+        let mut report = format!(
 r#"
 ═══════════════════════════════════════════════════════════════════════════════
                         SECURE DATA SANITIZATION CERTIFICATE
@@ -231,6 +444,10 @@ DEVICE INFORMATION:
 │ Secure Erase Support: {}
 │ Crypto Erase Support: {}
 │ Encryption Status: {}
+│ Interface: {}
+│ SMART Before Wipe: {}
+│ SMART After Wipe: {}
+│ Raw Identify Data: {}
 └─────────────────────────────────────────────────────────────────────────────┘
 
 SANITIZATION INFORMATION:
@@ -244,7 +461,11 @@ SANITIZATION INFORMATION:
 │ Duration: {} seconds ({} minutes)
 │ Average Speed: {:.2} MB/s
 │ Success: {}
-│ Error Count: {}
+│ Error Policy: {}
+│ Error Count: {}{}
+│ Pre-Wipe SHA-256: {}
+│ Post-Wipe SHA-256: {}
+│ Pass Timings:{}
 └─────────────────────────────────────────────────────────────────────────────┘
 
 COMPLIANCE INFORMATION:
@@ -264,6 +485,7 @@ VERIFICATION INFORMATION:
 │ Verification Passed: {}
 │ Residual Data Found: {}
 │ Details: {}
+│ Sampled Sectors (SHA-256 after final pass):{}
 └─────────────────────────────────────────────────────────────────────────────┘
 
 USER INFORMATION:
@@ -272,6 +494,8 @@ USER INFORMATION:
 │ User ID: {}
 │ Organization: {}
 │ Role: {}
+│ Operator Signature: {}
+│ Witness: {}
 └─────────────────────────────────────────────────────────────────────────────┘
 
 ═══════════════════════════════════════════════════════════════════════════════
@@ -297,6 +521,10 @@ Version: 1.0.0
             if certificate.device_info.supports_secure_erase { "Yes" } else { "No" },
             if certificate.device_info.supports_crypto_erase { "Yes" } else { "No" },
             certificate.device_info.encryption_status,
+            certificate.device_info.interface,
+            smart_summary(&certificate.device_info.smart_before),
+            smart_summary(&certificate.device_info.smart_after),
+            identify_summary(&certificate.device_info.raw_identify),
             certificate.sanitization_info.method,
             certificate.sanitization_info.algorithm,
             certificate.sanitization_info.passes_completed,
@@ -307,7 +535,20 @@ Version: 1.0.0
             certificate.sanitization_info.duration_seconds / 60,
             certificate.sanitization_info.average_speed_mbps,
             if certificate.sanitization_info.success { "Yes" } else { "No" },
+            certificate.sanitization_info.error_policy.as_deref().unwrap_or("Not recorded"),
             certificate.sanitization_info.error_count,
+            certificate.sanitization_info.errors.iter()
+                .map(|e| format!("\n│   - {}", e))
+                .collect::<String>(),
+            certificate.sanitization_info.pre_wipe_sha256.as_deref().unwrap_or("Not computed"),
+            certificate.sanitization_info.post_wipe_sha256.as_deref().unwrap_or("Not computed"),
+            if certificate.sanitization_info.pass_timings.is_empty() {
+                " Not recorded".to_string()
+            } else {
+                certificate.sanitization_info.pass_timings.iter()
+                    .map(|t| format!("\n│   - {}", t.summary()))
+                    .collect::<String>()
+            },
             certificate.compliance_info.security_level,
             certificate.compliance_info.standards_met.join(", "),
             if certificate.compliance_info.nist_compliant { "Yes" } else { "No" },
@@ -319,11 +560,30 @@ Version: 1.0.0
             if certificate.verification_info.verification_passed { "Yes" } else { "No" },
             if certificate.verification_info.residual_data_found { "Yes" } else { "No" },
             certificate.verification_info.verification_details,
+            if certificate.verification_info.sampled_sectors.is_empty() {
+                " Not recorded".to_string()
+            } else {
+                certificate.verification_info.sampled_sectors.iter()
+                    .map(|s| format!("\n│   - {}", s.summary()))
+                    .collect::<String>()
+            },
             certificate.user_info.username,
             certificate.user_info.user_id,
             certificate.user_info.organization,
             certificate.user_info.role,
-        )
+            certificate.user_info.operator_signature.as_deref().unwrap_or("Not provided"),
+            certificate.user_info.witness.as_deref().unwrap_or("Not provided"),
+        );
+        
+        if !certificate.wipe_log.is_empty() {
+            report.push_str("\nAPPENDIX: WIPE LOG\n");
+            report.push_str("───────────────────────────────────────────────────────────────────────────────\n");
+            for line in &certificate.wipe_log {
+                report.push_str(line);
+                report.push('\n');
+            }
+        }
+        report
     }
 
     pub fn save_certificate_report(&self, certificate: &SanitizationCertificate) -> Result<String, Box<dyn std::error::Error>> {
@@ -336,7 +596,239 @@ Version: 1.0.0
         let filepath = Path::new(&self.certificates_dir).join(&filename);
         fs::write(&filepath, report_content)?;
         
-        println!("✅ Certificate report saved: {}", filepath.display());
+        log::info!("✅ Certificate report saved: {}", filepath.display());
+        Ok(filepath.to_string_lossy().to_string())
+    }
+
+    /// Render the certificate as a self-contained HTML page (inline CSS, no external assets)
+    pub fn generate_html(&self, certificate: &SanitizationCertificate) -> String {
+        let yes_no = |value: bool| if value { "Yes" } else { "No" };
+        let status_class = |value: bool| if value { "ok" } else { "fail" };
+
+        let row = |label: &str, value: &str| {
+            format!("<tr><th>{}</th><td>{}</td></tr>\n", label, html_escape(value))
+        };
+        let status_row = |label: &str, value: bool| {
+            format!("<tr><th>{}</th><td class=\"{}\">{}</td></tr>\n", label, status_class(value), yes_no(value))
+        };
+
+        let device = &certificate.device_info;
+        let sanitization = &certificate.sanitization_info;
+        let compliance = &certificate.compliance_info;
+        let verification = &certificate.verification_info;
+        let user = &certificate.user_info;
+
+        let mut device_rows = String::new();
+        device_rows.push_str(&row("Device Path", &device.device_path));
+        device_rows.push_str(&row("Device Name", &device.device_name));
+        device_rows.push_str(&row("Device Type", &device.device_type));
+        device_rows.push_str(&row("Manufacturer", &device.manufacturer));
+        device_rows.push_str(&row("Model", &device.model));
+        device_rows.push_str(&row("Serial Number", &device.serial_number));
+        device_rows.push_str(&row("Capacity", &format_bytes(device.capacity)));
+        device_rows.push_str(&row("Sector Size", &format!("{} bytes", device.sector_size)));
+        device_rows.push_str(&status_row("Secure Erase Support", device.supports_secure_erase));
+        device_rows.push_str(&status_row("Crypto Erase Support", device.supports_crypto_erase));
+        device_rows.push_str(&row("Encryption Status", &device.encryption_status));
+        device_rows.push_str(&row("Interface", &device.interface));
+        device_rows.push_str(&row("SMART Before Wipe", &smart_summary(&device.smart_before)));
+        device_rows.push_str(&row("SMART After Wipe", &smart_summary(&device.smart_after)));
+        device_rows.push_str(&row("Raw Identify Data", &identify_summary(&device.raw_identify)));
+
+        let mut sanitization_rows = String::new();
+        sanitization_rows.push_str(&row("Method", &sanitization.method));
+        sanitization_rows.push_str(&row("Algorithm", &sanitization.algorithm));
+        sanitization_rows.push_str(&row("Passes Completed", &sanitization.passes_completed.to_string()));
+        sanitization_rows.push_str(&row("Total Bytes Processed", &format_bytes(sanitization.total_bytes_processed)));
+        sanitization_rows.push_str(&row("Start Time", &sanitization.start_time.format("%Y-%m-%d %H:%M:%S UTC").to_string()));
+        sanitization_rows.push_str(&row("End Time", &sanitization.end_time.format("%Y-%m-%d %H:%M:%S UTC").to_string()));
+        sanitization_rows.push_str(&row("Duration", &format!("{} seconds ({} minutes)", sanitization.duration_seconds, sanitization.duration_seconds / 60)));
+        sanitization_rows.push_str(&row("Average Speed", &format!("{:.2} MB/s", sanitization.average_speed_mbps)));
+        sanitization_rows.push_str(&status_row("Success", sanitization.success));
+        sanitization_rows.push_str(&row("Error Policy", sanitization.error_policy.as_deref().unwrap_or("Not recorded")));
+        sanitization_rows.push_str(&row("Error Count", &sanitization.error_count.to_string()));
+        for error in &sanitization.errors {
+            sanitization_rows.push_str(&row("Error", error));
+        }
+        sanitization_rows.push_str(&row("Pre-Wipe SHA-256", sanitization.pre_wipe_sha256.as_deref().unwrap_or("Not computed")));
+        sanitization_rows.push_str(&row("Post-Wipe SHA-256", sanitization.post_wipe_sha256.as_deref().unwrap_or("Not computed")));
+        for timing in &sanitization.pass_timings {
+            sanitization_rows.push_str(&row("Pass Timing", &timing.summary()));
+        }
+
+        let mut compliance_rows = String::new();
+        compliance_rows.push_str(&row("Security Level", &compliance.security_level));
+        compliance_rows.push_str(&row("Standards Met", &compliance.standards_met.join(", ")));
+        compliance_rows.push_str(&status_row("NIST SP 800-88 Compliant", compliance.nist_compliant));
+        compliance_rows.push_str(&status_row("DoD 5220.22-M Compliant", compliance.dod_compliant));
+        compliance_rows.push_str(&status_row("HIPAA Compliant", compliance.hipaa_compliant));
+        compliance_rows.push_str(&status_row("GDPR Compliant", compliance.gdpr_compliant));
+
+        let mut verification_rows = String::new();
+        verification_rows.push_str(&status_row("Verification Performed", verification.verification_performed));
+        verification_rows.push_str(&row("Verification Method", &verification.verification_method));
+        verification_rows.push_str(&status_row("Verification Passed", verification.verification_passed));
+        verification_rows.push_str(&status_row("No Residual Data Found", !verification.residual_data_found));
+        verification_rows.push_str(&row("Details", &verification.verification_details));
+        verification_rows.push_str(&row("Sampled Sectors", &sampled_sectors_summary(&verification.sampled_sectors)));
+
+        let mut user_rows = String::new();
+        user_rows.push_str(&row("Username", &user.username));
+        user_rows.push_str(&row("User ID", &user.user_id));
+        user_rows.push_str(&row("Organization", &user.organization));
+        user_rows.push_str(&row("Role", &user.role));
+        user_rows.push_str(&row("Operator Signature", user.operator_signature.as_deref().unwrap_or("Not provided")));
+        user_rows.push_str(&row("Witness", user.witness.as_deref().unwrap_or("Not provided")));
+
+        format!(
+r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<title>Sanitization Certificate {id}</title>
+<style>
+body {{ font-family: Segoe UI, Helvetica, Arial, sans-serif; background: #f1f5f9; color: #0f172a; margin: 0; padding: 32px; }}
+.certificate {{ max-width: 860px; margin: 0 auto; background: #ffffff; border-radius: 8px; box-shadow: 0 2px 8px rgba(15, 23, 42, 0.15); overflow: hidden; }}
+header {{ background: #0f172a; color: #ffffff; padding: 24px 32px; }}
+header h1 {{ margin: 0 0 8px 0; font-size: 22px; letter-spacing: 1px; }}
+header p {{ margin: 2px 0; font-size: 13px; color: #cbd5e1; word-break: break-all; }}
+.banner {{ padding: 12px 32px; font-weight: bold; color: #ffffff; }}
+.banner.ok {{ background: #22c55e; }}
+.banner.fail {{ background: #ef4444; }}
+section {{ padding: 16px 32px; }}
+h2 {{ font-size: 16px; color: #2563eb; border-bottom: 2px solid #e2e8f0; padding-bottom: 6px; }}
+table {{ width: 100%; border-collapse: collapse; font-size: 14px; }}
+th {{ text-align: left; width: 40%; color: #475569; font-weight: 600; }}
+th, td {{ padding: 6px 8px; border-bottom: 1px solid #f1f5f9; }}
+td.ok {{ color: #16a34a; font-weight: bold; }}
+td.fail {{ color: #dc2626; font-weight: bold; }}
+footer {{ padding: 16px 32px 24px 32px; font-size: 12px; color: #64748b; }}
+pre {{ font-size: 12px; white-space: pre-wrap; background: #f8fafc; padding: 8px; }}
+</style>
+</head>
+<body>
+<div class="certificate">
+<header>
+<h1>SECURE DATA SANITIZATION CERTIFICATE</h1>
+<p>Certificate ID: {id}</p>
+<p>Generated: {generated}</p>
+<p>Certificate Hash: {hash}</p>
+</header>
+<div class="banner {banner_class}">{banner_text}</div>
+<section>
+<h2>Device Information</h2>
+<table>
+{device_rows}</table>
+</section>
+<section>
+<h2>Sanitization Information</h2>
+<table>
+{sanitization_rows}</table>
+</section>
+<section>
+<h2>Compliance Information</h2>
+<table>
+{compliance_rows}</table>
+</section>
+<section>
+<h2>Verification Information</h2>
+<table>
+{verification_rows}</table>
+</section>
+<section>
+<h2>User Information</h2>
+<table>
+{user_rows}</table>
+</section>
+{appendix}<footer>
+This certificate confirms that the above device has been sanitized according to
+industry standards and regulatory requirements. The sanitization process has been
+verified and documented for compliance purposes.<br>
+Generated by: HDD Tool - Secure Data Sanitization System, Version 1.0.0
+</footer>
+</div>
+</body>
+</html>
+"#,
+            id = html_escape(&certificate.id),
+            generated = certificate.timestamp.format("%Y-%m-%d %H:%M:%S UTC"),
+            hash = html_escape(&certificate.certificate_hash),
+            banner_class = status_class(sanitization.success),
+            banner_text = if sanitization.success {
+                format!("SANITIZATION SUCCESSFUL - {}", html_escape(&compliance.security_level))
+            } else {
+                "SANITIZATION INCOMPLETE".to_string()
+            },
+            device_rows = device_rows,
+            sanitization_rows = sanitization_rows,
+            compliance_rows = compliance_rows,
+            verification_rows = verification_rows,
+            user_rows = user_rows,
+            appendix = if certificate.wipe_log.is_empty() {
+                String::new()
+            } else {
+                format!("<section>\n<h2>Appendix: Wipe Log</h2>\n<pre>{}</pre>\n</section>\n",
+                    html_escape(&certificate.wipe_log.join("\n")))
+            },
+        )
+    }
+
+    pub fn save_certificate_html(&self, certificate: &SanitizationCertificate) -> Result<String, Box<dyn std::error::Error>> {
+        let html_content = self.generate_html(certificate);
+        
+        let filename = format!("certificate_report_{}_{}.html", 
+            certificate.device_info.device_name.replace(" ", "_"),
+            certificate.timestamp.format("%Y%m%d_%H%M%S"));
+        
+        let filepath = Path::new(&self.certificates_dir).join(&filename);
+        fs::write(&filepath, html_content)?;
+        
+        log::info!("✅ Certificate HTML saved: {}", filepath.display());
+        Ok(filepath.to_string_lossy().to_string())
+    }
+
+    /// Save the certificate in the requested format, returning the file path
+    pub fn save_certificate_as(&self, certificate: &SanitizationCertificate, format: CertificateFormat) -> Result<String, Box<dyn std::error::Error>> {
+        match format {
+            CertificateFormat::Json => self.save_certificate_local(certificate),
+            CertificateFormat::Text => self.save_certificate_report(certificate),
+            CertificateFormat::Xml => self.save_certificate_xml(certificate),
+            CertificateFormat::Pdf => self.save_certificate_pdf(certificate),
+            CertificateFormat::Html => self.save_certificate_html(certificate),
+        }
+    }
+
+    /// Render the certificate as XML, mirroring the JSON structure element for element
+    pub fn generate_xml(&self, certificate: &SanitizationCertificate) -> Result<String, Box<dyn std::error::Error>> {
+        let value = serde_json::to_value(certificate)?;
+        let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        write_xml_element(&mut xml, "sanitization_certificate", &value, 0);
+        Ok(xml)
+    }
+
+    pub fn save_certificate_xml(&self, certificate: &SanitizationCertificate) -> Result<String, Box<dyn std::error::Error>> {
+        let xml_content = self.generate_xml(certificate)?;
+        
+        let filename = format!("certificate_{}_{}.xml", 
+            certificate.device_info.device_name.replace(" ", "_"),
+            certificate.timestamp.format("%Y%m%d_%H%M%S"));
+        
+        let filepath = Path::new(&self.certificates_dir).join(&filename);
+        fs::write(&filepath, xml_content)?;
+        
+        log::info!("✅ Certificate XML saved: {}", filepath.display());
+        Ok(filepath.to_string_lossy().to_string())
+    }
+
+    pub fn save_certificate_pdf(&self, certificate: &SanitizationCertificate) -> Result<String, Box<dyn std::error::Error>> {
+        let filename = format!("certificate_{}_{}.pdf", 
+            certificate.device_info.device_name.replace(" ", "_"),
+            certificate.timestamp.format("%Y%m%d_%H%M%S"));
+        let filepath = Path::new(&self.certificates_dir).join(&filename);
+        
+        fs::write(&filepath, render_certificate_pdf(certificate, None)?)?;
+        
+        log::info!("✅ Certificate PDF saved: {}", filepath.display());
         Ok(filepath.to_string_lossy().to_string())
     }
 
@@ -355,11 +847,21 @@ Version: 1.0.0
                 match fs::read_to_string(&path) {
                     Ok(content) => {
                         match serde_json::from_str::<SanitizationCertificate>(&content) {
-                            Ok(certificate) => certificates.push(certificate),
-                            Err(e) => eprintln!("Warning: Could not parse certificate file {}: {}", path.display(), e),
+                            Ok(mut certificate) => {
+                                if certificate.schema_version < CERTIFICATE_SCHEMA_VERSION {
+                                    if let Err(e) = self.upgrade_certificate_file(&path, &content, &mut certificate) {
+                                        log::warn!("Warning: Could not upgrade certificate file {}: {}", path.display(), e);
+                                    }
+                                } else if certificate.schema_version > CERTIFICATE_SCHEMA_VERSION {
+                                    log::warn!("Warning: Certificate file {} was written by a newer version (schema {}); fields it added are not shown",
+                                        path.display(), certificate.schema_version);
+                                }
+                                certificates.push(certificate)
+                            }
+                            Err(e) => log::warn!("Warning: Could not parse certificate file {}: {}", path.display(), e),
                         }
                     }
-                    Err(e) => eprintln!("Warning: Could not read certificate file {}: {}", path.display(), e),
+                    Err(e) => log::warn!("Warning: Could not read certificate file {}: {}", path.display(), e),
                 }
             }
         }
@@ -369,10 +871,264 @@ Version: 1.0.0
         
         Ok(certificates)
     }
+
+    /// Re-save an older certificate in the current schema. The original file is kept next to
+    /// it as `<name>.json.v<version>`, and the hash recorded at issue is left as it was.
+    fn upgrade_certificate_file(&self, path: &Path, original: &str, certificate: &mut SanitizationCertificate) -> Result<(), Box<dyn std::error::Error>> {
+        let backup = path.with_extension(format!("json.v{}", certificate.schema_version));
+        if !backup.exists() {
+            fs::write(&backup, original)?;
+        }
+        
+        let from_version = certificate.schema_version;
+        certificate.schema_version = CERTIFICATE_SCHEMA_VERSION;
+        fs::write(path, serde_json::to_string_pretty(&certificate)?)?;
+        log::info!("📜 Upgraded certificate {} from schema {} to {}", path.display(), from_version, CERTIFICATE_SCHEMA_VERSION);
+        Ok(())
+    }
 }
 
 impl Default for CertificateGenerator {
     fn default() -> Self {
-        Self::new()
+        Self::new("./certificates")
     }
-}
\ No newline at end of file
+}
+
+fn html_escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
+
+/// Write a JSON value as nested XML elements; array entries become repeated `<item>` elements
+fn write_xml_element(xml: &mut String, name: &str, value: &serde_json::Value, depth: usize) {
+    let indent = "  ".repeat(depth);
+    match value {
+        serde_json::Value::Object(fields) => {
+            xml.push_str(&format!("{}<{}>\n", indent, name));
+            for (key, field) in fields {
+                write_xml_element(xml, key, field, depth + 1);
+            }
+            xml.push_str(&format!("{}</{}>\n", indent, name));
+        }
+        serde_json::Value::Array(items) => {
+            xml.push_str(&format!("{}<{}>\n", indent, name));
+            for item in items {
+                write_xml_element(xml, "item", item, depth + 1);
+            }
+            xml.push_str(&format!("{}</{}>\n", indent, name));
+        }
+        serde_json::Value::Null => {
+            xml.push_str(&format!("{}<{}/>\n", indent, name));
+        }
+        serde_json::Value::String(text) => {
+            xml.push_str(&format!("{}<{}>{}</{}>\n", indent, name, html_escape(text), name));
+        }
+        other => {
+            xml.push_str(&format!("{}<{}>{}</{}>\n", indent, name, other, name));
+        }
+    }
+}
+
+/// Render a certificate as a PDF document. The desktop app and the server both use
+/// this, so a certificate looks the same wherever it is downloaded from. `notice` is
+/// printed in bold under the header, e.g. for a certificate the server has since revoked.
+pub fn render_certificate_pdf(certificate: &SanitizationCertificate, notice: Option<&str>) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+    use printpdf::{BuiltinFont, Mm, PdfDocument};
+    
+    let (doc, page, layer) = PdfDocument::new("Data Sanitization Certificate", Mm(210.0), Mm(297.0), "Layer 1");
+    let font = doc.add_builtin_font(BuiltinFont::Helvetica)?;
+    let font_bold = doc.add_builtin_font(BuiltinFont::HelveticaBold)?;
+    let mut current_layer = doc.get_page(page).get_layer(layer);
+    
+    current_layer.use_text("DATA SANITIZATION CERTIFICATE", 18.0, Mm(20.0), Mm(277.0), &font_bold);
+    current_layer.use_text(format!("Certificate ID: {}", certificate.id), 10.0, Mm(20.0), Mm(268.0), &font);
+    current_layer.use_text(format!("Certificate Hash: {}", certificate.certificate_hash), 8.0, Mm(20.0), Mm(262.0), &font);
+    
+    let mut y = 250.0;
+    if let Some(notice) = notice {
+        current_layer.use_text(notice, 11.0, Mm(20.0), Mm(255.0), &font_bold);
+        y -= 3.0;
+    }
+    for (heading, lines) in pdf_sections(certificate) {
+        if y < 40.0 {
+            let (page, layer) = doc.add_page(Mm(210.0), Mm(297.0), "Layer 1");
+            current_layer = doc.get_page(page).get_layer(layer);
+            y = 277.0;
+        }
+        current_layer.use_text(heading, 12.0, Mm(20.0), Mm(y), &font_bold);
+        y -= 7.0;
+        
+        for line in lines {
+            if y < 20.0 {
+                let (page, layer) = doc.add_page(Mm(210.0), Mm(297.0), "Layer 1");
+                current_layer = doc.get_page(page).get_layer(layer);
+                y = 277.0;
+            }
+            current_layer.use_text(line, 9.0, Mm(25.0), Mm(y), &font);
+            y -= 5.0;
+        }
+        y -= 4.0;
+    }
+    
+    let mut writer = std::io::BufWriter::new(Vec::new());
+    doc.save(&mut writer)?;
+    Ok(writer.into_inner()?)
+}
+
+/// The HTML and PDF certificates only count the samples; the full list of offsets and
+/// hashes is in the JSON and text certificates
+fn sampled_sectors_summary(samples: &[SectorSample]) -> String {
+    if samples.is_empty() {
+        "Not recorded".to_string()
+    } else {
+        format!("{} hashed after the final pass (offsets and SHA-256 listed in the JSON certificate)", samples.len())
+    }
+}
+
+/// Section headings and "label: value" lines for the PDF certificate
+fn pdf_sections(certificate: &SanitizationCertificate) -> Vec<(&'static str, Vec<String>)> {
+    let yes_no = |value: bool| if value { "Yes" } else { "No" };
+    let device = &certificate.device_info;
+    let sanitization = &certificate.sanitization_info;
+    let compliance = &certificate.compliance_info;
+    let verification = &certificate.verification_info;
+    let user = &certificate.user_info;
+    
+    let mut sanitization_lines = vec![
+        format!("Method: {}", sanitization.method),
+        format!("Algorithm: {}", sanitization.algorithm),
+        format!("Passes Completed: {}", sanitization.passes_completed),
+        format!("Total Bytes Processed: {}", sanitization.total_bytes_processed),
+        format!("Start Time: {}", sanitization.start_time.format("%Y-%m-%d %H:%M:%S UTC")),
+        format!("End Time: {}", sanitization.end_time.format("%Y-%m-%d %H:%M:%S UTC")),
+        format!("Duration: {} seconds", sanitization.duration_seconds),
+        format!("Average Speed: {:.2} MB/s", sanitization.average_speed_mbps),
+        format!("Success: {}", yes_no(sanitization.success)),
+        format!("Error Policy: {}", sanitization.error_policy.as_deref().unwrap_or("Not recorded")),
+        format!("Error Count: {}", sanitization.error_count),
+    ];
+    sanitization_lines.extend(sanitization.errors.iter().map(|e| format!("Error: {}", e)));
+    sanitization_lines.push(format!("Pre-Wipe SHA-256: {}", sanitization.pre_wipe_sha256.as_deref().unwrap_or("Not computed")));
+    sanitization_lines.push(format!("Post-Wipe SHA-256: {}", sanitization.post_wipe_sha256.as_deref().unwrap_or("Not computed")));
+    sanitization_lines.extend(sanitization.pass_timings.iter().map(|t| t.summary()));
+    
+    let mut sections = vec![
+        ("Device Information", vec![
+            format!("Device Path: {}", device.device_path),
+            format!("Device Name: {}", device.device_name),
+            format!("Device Type: {}", device.device_type),
+            format!("Manufacturer: {}", device.manufacturer),
+            format!("Model: {}", device.model),
+            format!("Serial Number: {}", device.serial_number),
+            format!("Capacity: {}", format_bytes(device.capacity)),
+            format!("Sector Size: {} bytes", device.sector_size),
+            format!("Interface: {}", device.interface),
+            format!("SMART Before Wipe: {}", smart_summary(&device.smart_before)),
+            format!("SMART After Wipe: {}", smart_summary(&device.smart_after)),
+            format!("Raw Identify Data: {}", identify_summary(&device.raw_identify)),
+        ]),
+        ("Sanitization Details", sanitization_lines),
+        ("Compliance", vec![
+            format!("Standards Met: {}", compliance.standards_met.join(", ")),
+            format!("NIST SP 800-88: {}", yes_no(compliance.nist_compliant)),
+            format!("DoD 5220.22-M: {}", yes_no(compliance.dod_compliant)),
+            format!("HIPAA: {}", yes_no(compliance.hipaa_compliant)),
+            format!("GDPR: {}", yes_no(compliance.gdpr_compliant)),
+            format!("Security Level: {}", compliance.security_level),
+        ]),
+        ("Verification", vec![
+            format!("Verification Performed: {}", yes_no(verification.verification_performed)),
+            format!("Method: {}", verification.verification_method),
+            format!("Passed: {}", yes_no(verification.verification_passed)),
+            format!("Residual Data Found: {}", yes_no(verification.residual_data_found)),
+            format!("Details: {}", verification.verification_details),
+            format!("Sampled Sectors: {}", sampled_sectors_summary(&verification.sampled_sectors)),
+        ]),
+        ("Operator", vec![
+            format!("Username: {}", user.username),
+            format!("Organization: {}", user.organization),
+            format!("Operator Signature: {}", user.operator_signature.as_deref().unwrap_or("Not provided")),
+            format!("Witness: {}", user.witness.as_deref().unwrap_or("Not provided")),
+            format!("Issued: {}", certificate.timestamp.format("%Y-%m-%d %H:%M:%S UTC")),
+        ]),
+    ];
+    if !certificate.wipe_log.is_empty() {
+        sections.push(("Appendix: Wipe Log", certificate.wipe_log.clone()));
+    }
+    sections
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn certificate_issued(timestamp: &str) -> SanitizationCertificate {
+        serde_json::from_value(serde_json::json!({
+            "id": "test",
+            "device_info": {
+                "device_path": "/dev/sdx", "device_name": "Test Drive", "device_type": "HDD",
+                "manufacturer": "", "model": "", "serial_number": "", "capacity": 0, "sector_size": 512,
+                "supports_secure_erase": false, "supports_crypto_erase": false, "encryption_status": "None"
+            },
+            "sanitization_info": {
+                "method": "Clear", "algorithm": "Zeros", "passes_completed": 1, "total_bytes_processed": 0,
+                "start_time": timestamp, "end_time": timestamp, "duration_seconds": 0,
+                "average_speed_mbps": 0.0, "success": true, "error_count": 0
+            },
+            "compliance_info": {
+                "standards_met": [], "nist_compliant": true, "dod_compliant": false,
+                "hipaa_compliant": false, "gdpr_compliant": false, "security_level": "Clear"
+            },
+            "verification_info": {
+                "verification_performed": false, "verification_method": "", "verification_passed": false,
+                "residual_data_found": false, "verification_details": ""
+            },
+            "timestamp": timestamp,
+            "user_info": { "username": "tester", "user_id": "1", "organization": "", "role": "operator" },
+            "certificate_hash": ""
+        })).unwrap()
+    }
+
+    #[test]
+    fn test_issued_between_is_inclusive_with_open_ends() {
+        let certificate = certificate_issued("2024-03-15T12:00:00Z");
+        let day = |d| NaiveDate::from_ymd_opt(2024, 3, d);
+
+        assert!(certificate.issued_between(None, None));
+        assert!(certificate.issued_between(day(15), day(15)));
+        assert!(certificate.issued_between(day(1), None));
+        assert!(certificate.issued_between(None, day(31)));
+        assert!(!certificate.issued_between(day(16), None));
+        assert!(!certificate.issued_between(None, day(14)));
+    }
+
+    #[test]
+    fn test_load_upgrades_old_certificate_in_place_with_backup() {
+        let dir = std::env::temp_dir().join(format!("shredx_certs_{}", std::process::id()));
+        let generator = CertificateGenerator::new(&dir);
+        let mut issued = certificate_issued("2024-03-15T12:00:00Z");
+        issued.certificate_hash = "issued-hash".to_string();
+        let mut original = serde_json::to_value(&issued).unwrap();
+        original.as_object_mut().unwrap().remove("schema_version");
+        let original = serde_json::to_string_pretty(&original).unwrap();
+        let path = dir.join("certificate_Test_Drive_20240315_120000.json");
+        fs::write(&path, &original).unwrap();
+
+        let loaded = generator.load_certificates().unwrap();
+        let resaved: SanitizationCertificate = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        let backup = fs::read_to_string(path.with_extension("json.v1")).unwrap();
+        let reloaded = generator.load_certificates().unwrap();
+        let _ = fs::remove_dir_all(&dir);
+
+        assert_eq!(loaded.len(), 1);
+        assert_eq!(resaved.schema_version, CERTIFICATE_SCHEMA_VERSION);
+        assert_eq!(resaved.certificate_hash, "issued-hash");
+        assert_eq!(backup, original);
+        assert_eq!(reloaded.len(), 1);
+    }
+}
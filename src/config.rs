@@ -1,6 +1,8 @@
 use serde::{Deserialize, Serialize};
 use std::fs;
 use std::env;
+use std::path::{Path, PathBuf};
+use crate::certificate::CertificateFormat;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AppConfig {
@@ -10,6 +12,59 @@ pub struct AppConfig {
     pub local_storage_only: bool,
     pub connection_timeout_seconds: u64,
     pub retry_attempts: u32,
+    /// Pause overwrites while the drive is hotter than `max_drive_temperature_c`
+    #[serde(default)]
+    pub thermal_throttling: bool,
+    #[serde(default = "default_max_drive_temperature")]
+    pub max_drive_temperature_c: u32,
+    /// Show a desktop notification when a batch of wipes finishes
+    #[serde(default = "default_completion_notifications")]
+    pub completion_notifications: bool,
+    /// Most drives wiped at once; the rest wait their turn. 0 wipes every selected drive at once.
+    #[serde(default)]
+    pub max_concurrent_wipes: usize,
+    /// Eraser method picked last session, by its canonical name
+    #[serde(default)]
+    pub last_algorithm: Option<String>,
+    /// Where certificates (in a `certificates` subdirectory) and reports are written
+    #[serde(default = "default_output_dir")]
+    pub output_dir: String,
+    /// Formats every certificate is written in when a wipe completes
+    #[serde(default = "default_certificate_formats")]
+    pub certificate_formats: Vec<CertificateFormat>,
+    /// Most verbose diagnostics shown: off, error, warn, info, debug or trace
+    #[serde(default = "default_log_level")]
+    pub log_level: String,
+    /// Also write diagnostics to a daily log file in the `logs` output subdirectory
+    #[serde(default)]
+    pub log_to_file: bool,
+    /// Attach each wipe's log to its certificate as an appendix
+    #[serde(default)]
+    pub attach_wipe_log: bool,
+    /// Read a sample back after every this many passes of a multi-pass overwrite; 0 only
+    /// verifies at the end
+    #[serde(default)]
+    pub verify_every_passes: u32,
+}
+
+fn default_log_level() -> String {
+    "info".to_string()
+}
+
+fn default_certificate_formats() -> Vec<CertificateFormat> {
+    vec![CertificateFormat::Json, CertificateFormat::Text]
+}
+
+fn default_output_dir() -> String {
+    ".".to_string()
+}
+
+fn default_max_drive_temperature() -> u32 {
+    60
+}
+
+fn default_completion_notifications() -> bool {
+    true
 }
 
 impl Default for AppConfig {
@@ -21,6 +76,17 @@ impl Default for AppConfig {
             local_storage_only: true,
             connection_timeout_seconds: 30,
             retry_attempts: 3,
+            thermal_throttling: false,
+            max_drive_temperature_c: default_max_drive_temperature(),
+            completion_notifications: default_completion_notifications(),
+            max_concurrent_wipes: 0,
+            last_algorithm: None,
+            output_dir: default_output_dir(),
+            certificate_formats: default_certificate_formats(),
+            log_level: default_log_level(),
+            log_to_file: false,
+            attach_wipe_log: false,
+            verify_every_passes: 0,
         }
     }
 }
@@ -67,6 +133,36 @@ impl AppConfig {
         format!("{}/api/{}", self.server_url.trim_end_matches('/'), endpoint.trim_start_matches('/'))
     }
     
+    /// Temperature limit for wipes, or `None` when throttling is off
+    pub fn thermal_limit(&self) -> Option<u32> {
+        self.thermal_throttling.then_some(self.max_drive_temperature_c)
+    }
+    
+    /// Passes between read-backs during a wipe, or `None` to verify only at the end
+    pub fn verify_interval(&self) -> Option<u32> {
+        (self.verify_every_passes > 0).then_some(self.verify_every_passes)
+    }
+    
+    /// Directory certificates are saved to and loaded from
+    pub fn certificates_dir(&self) -> PathBuf {
+        Path::new(&self.output_dir).join("certificates")
+    }
+    
+    /// Directory sanitization reports are saved to
+    pub fn reports_dir(&self) -> PathBuf {
+        PathBuf::from(&self.output_dir)
+    }
+    
+    /// Directory the rotating log files are written to
+    pub fn logs_dir(&self) -> PathBuf {
+        Path::new(&self.output_dir).join("logs")
+    }
+    
+    /// File the lifetime statistics are kept in
+    pub fn stats_file(&self) -> PathBuf {
+        Path::new(&self.output_dir).join("stats.json")
+    }
+    
     pub fn get_dashboard_url(&self) -> String {
         format!("{}/dashboard", self.server_url.trim_end_matches('/'))
     }
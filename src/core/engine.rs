@@ -18,10 +18,17 @@ pub struct WipeEngine {
 
 impl WipeEngine {
     pub fn new(config: WipeConfiguration) -> WipeResult2<Self> {
-        let ca = CertificateAuthority::new(
-            "SecureWipe Certificate Authority".to_string(),
-            "Data Security Solutions Inc.".to_string(),
-        )?;
+        let ca = match config.organization_signing {
+            Some(ref signing) => CertificateAuthority::from_organization_signing(
+                "SecureWipe Certificate Authority".to_string(),
+                "Data Security Solutions Inc.".to_string(),
+                signing,
+            )?,
+            None => CertificateAuthority::new(
+                "SecureWipe Certificate Authority".to_string(),
+                "Data Security Solutions Inc.".to_string(),
+            )?,
+        };
 
         // Save the CA to file for persistence
         ca.save_to_file("certificates/ca.json")?;
@@ -59,10 +66,10 @@ impl WipeEngine {
             timestamp: Utc::now(),
         };
 
-        println!("Starting secure wipe operation");
-        println!("Request ID: {}", request.id);
-        println!("Target: {}", request.target_path);
-        println!("Standard: {:?}", request.standard);
+        log::info!("Starting secure wipe operation");
+        log::info!("Request ID: {}", request.id);
+        log::info!("Target: {}", request.target_path);
+        log::info!("Standard: {:?}", request.standard);
 
         // Create progress channel
         let (progress_tx, progress_rx) = mpsc::channel::<WipeProgress>();
@@ -75,7 +82,7 @@ impl WipeEngine {
         let request_id = request.id;
         thread::spawn(move || {
             while let Ok(progress) = progress_rx.recv() {
-                println!(
+                log::info!(
                     "Progress: {:.1}% - Pass {}/{} - {} - {} sectors processed",
                     progress.percentage,
                     progress.current_pass,
@@ -115,8 +122,8 @@ impl WipeEngine {
             // Save updated CA (incremented counter)
             ca.save_to_file("certificates/ca.json")?;
 
-            println!("Certificate generated: {}", cert_filename);
-            println!("Audit report generated: {}", json_filename);
+            log::info!("Certificate generated: {}", cert_filename);
+            log::info!("Audit report generated: {}", json_filename);
 
             Some(cert_filename)
         } else {
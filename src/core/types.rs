@@ -51,6 +51,23 @@ pub struct WipeConfiguration {
     pub certificate_required: bool,
     pub buffer_size: usize,
     pub verification_sample_rate: f64,
+    /// Sign certificates under an organizational CA instead of a self-signed key
+    #[serde(default)]
+    pub organization_signing: Option<OrganizationSigning>,
+}
+
+/// PEM files that put issued certificates inside an organization's existing PKI.
+///
+/// This covers the certificates `WipeEngine` issues. The desktop app's certificates
+/// (`crate::certificate`) are protected by a content hash only and carry no chain.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrganizationSigning {
+    /// PKCS#8 RSA private key certificates are signed with
+    pub signing_key_path: String,
+    /// X.509 certificate of the signing key, followed by any intermediate CAs
+    pub certificate_chain_path: String,
+    /// Root CA the chain must end at; certificates are only valid if they chain to it
+    pub trust_anchor_path: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -0,0 +1,253 @@
+//! Crypto-erase for software-encrypted volumes
+//!
+//! Destroying the key material of an encrypted volume leaves only ciphertext behind,
+//! which NIST SP 800-88 accepts as a purge. It takes seconds instead of hours of
+//! overwriting, but only covers the encrypted volumes on the device. LUKS and BitLocker
+//! are supported.
+
+use std::fs::{File, OpenOptions};
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::sync::{Arc, Mutex};
+use rand::Rng;
+use crate::advanced_wiper::{DeviceInfo, EncryptionStatus, WipingProgress};
+use super::{find_encrypted_volumes, raw_volume_path};
+
+/// Header area assumed when a LUKS header can't be parsed; the cryptsetup default
+/// LUKS2 data offset, which also covers any LUKS1 layout
+const LUKS_DEFAULT_HEADER_AREA: u64 = 16 * 1024 * 1024;
+
+/// Overwritten at each BitLocker FVE metadata copy; the metadata block holding the
+/// encrypted VMK and FVEK is well under this
+const FVE_METADATA_AREA: u64 = 64 * 1024;
+
+/// Destroy the key material of every encrypted volume on the device
+pub fn crypto_erase(device_info: &DeviceInfo, progress_callback: Arc<Mutex<WipingProgress>>) -> io::Result<()> {
+    {
+        let mut progress = progress_callback.lock().unwrap();
+        progress.total_passes = 1;
+        progress.begin_pass(1);
+        progress.current_pattern = format!("Destroying {} key material", device_info.encryption_status.label());
+    }
+
+    // Key destruction says nothing about plaintext partitions next to the encrypted ones;
+    // look for them while the headers still exist
+    let plaintext_partitions = unencrypted_partitions(&device_info.device_path)?;
+
+    let erased = match device_info.encryption_status {
+        EncryptionStatus::Luks => luks_crypto_erase(&device_info.device_path)?,
+        EncryptionStatus::BitLocker => bitlocker_crypto_erase(&device_info.device_path)?,
+        status => {
+            return Err(io::Error::new(io::ErrorKind::Unsupported,
+                format!("Crypto-erase is not available for {} volumes", status.label())));
+        }
+    };
+
+    for offset in plaintext_partitions {
+        let message = format!("Partition at byte {} is not encrypted and was left readable by key destruction", offset);
+        log::warn!("⚠️  {}", message);
+        if let Ok(mut progress) = progress_callback.lock() {
+            progress.errors.push(message);
+        }
+    }
+
+    if let Ok(mut progress) = progress_callback.lock() {
+        progress.bytes_processed = progress.total_bytes;
+        progress.current_pattern = format!("Key material destroyed ({} volume(s))", erased);
+    }
+    Ok(())
+}
+
+/// Overwrite the LUKS header, key slots and (for LUKS2) the secondary header of every
+/// LUKS volume on the device with random data, then check the headers are gone.
+/// Returns the number of volumes erased.
+pub fn luks_crypto_erase(device_path: &str) -> io::Result<usize> {
+    let volumes: Vec<_> = find_encrypted_volumes(device_path)?
+        .into_iter()
+        .filter(|v| v.kind == EncryptionStatus::Luks)
+        .collect();
+    if volumes.is_empty() {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput,
+            format!("No LUKS header found on {}", device_path)));
+    }
+
+    let mut device = OpenOptions::new().read(true).write(true).open(device_path)?;
+    let device_size = device.seek(SeekFrom::End(0))?;
+    let mut rng = rand::thread_rng();
+    let mut buffer = vec![0u8; 1024 * 1024];
+
+    for volume in &volumes {
+        let area = luks_header_area(&mut device, volume.offset)?.min(device_size - volume.offset);
+        log::info!("🔑 Destroying LUKS header and key slots at byte {} ({} KB)", volume.offset, area / 1024);
+
+        device.seek(SeekFrom::Start(volume.offset))?;
+        let mut written = 0u64;
+        while written < area {
+            let len = ((area - written) as usize).min(buffer.len());
+            rng.fill(&mut buffer[..len]);
+            device.write_all(&buffer[..len])?;
+            written += len as u64;
+        }
+    }
+    device.sync_all()?;
+
+    if find_encrypted_volumes(device_path)?.iter().any(|v| v.kind == EncryptionStatus::Luks) {
+        return Err(io::Error::new(io::ErrorKind::Other,
+            format!("LUKS header still present on {} after crypto-erase", device_path)));
+    }
+
+    log::info!("✅ LUKS key material destroyed on {} ({} volume(s))", device_path, volumes.len());
+    Ok(volumes.len())
+}
+
+/// Destroy the three FVE metadata copies of every BitLocker volume on the device. They
+/// hold the volume master key and the FVEK wrapped by every protector, so once they are
+/// gone no recovery key or TPM can decrypt the volume. Returns the number of volumes erased.
+pub fn bitlocker_crypto_erase(device_path: &str) -> io::Result<usize> {
+    #[cfg(windows)]
+    lock_bitlocker_volume(device_path);
+
+    let volumes: Vec<_> = find_encrypted_volumes(device_path)?
+        .into_iter()
+        .filter(|v| v.kind == EncryptionStatus::BitLocker)
+        .collect();
+    if volumes.is_empty() {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput,
+            format!("No BitLocker volume found on {}", device_path)));
+    }
+
+    let mut device = OpenOptions::new().read(true).write(true).open(raw_volume_path(device_path))?;
+    let device_size = device.seek(SeekFrom::End(0))?;
+    let mut rng = rand::thread_rng();
+    let mut buffer = vec![0u8; FVE_METADATA_AREA as usize];
+    let mut destroyed = Vec::new();
+
+    for volume in &volumes {
+        let metadata_offsets = fve_metadata_offsets(&mut device, volume.offset)?;
+        if metadata_offsets.is_empty() {
+            return Err(io::Error::new(io::ErrorKind::InvalidData,
+                format!("BitLocker volume at byte {} lists no FVE metadata blocks", volume.offset)));
+        }
+
+        let before = destroyed.len();
+        for block in metadata_offsets {
+            let offset = match volume.offset.checked_add(block) {
+                Some(offset) if offset < device_size => offset,
+                _ => continue,
+            };
+            let len = FVE_METADATA_AREA.min(device_size - offset) as usize;
+            log::info!("🔑 Destroying BitLocker key metadata at byte {}", offset);
+            rng.fill(&mut buffer[..len]);
+            device.seek(SeekFrom::Start(offset))?;
+            device.write_all(&buffer[..len])?;
+            destroyed.push(offset);
+        }
+        // Offsets past the end of the device mean the boot sector can't be trusted;
+        // nothing was destroyed, so no crypto-erase can be claimed for this volume
+        if destroyed.len() == before {
+            return Err(io::Error::new(io::ErrorKind::InvalidData,
+                format!("BitLocker volume at byte {} has no FVE metadata within the device", volume.offset)));
+        }
+    }
+    device.sync_all()?;
+
+    // Every metadata copy must be gone, otherwise a protector could still unwrap the key
+    for &offset in &destroyed {
+        let mut signature = [0u8; 8];
+        device.seek(SeekFrom::Start(offset))?;
+        device.read_exact(&mut signature)?;
+        if &signature == b"-FVE-FS-" {
+            return Err(io::Error::new(io::ErrorKind::Other,
+                format!("BitLocker key metadata still present at byte {} after crypto-erase", offset)));
+        }
+    }
+
+    log::info!("✅ BitLocker key material destroyed on {} ({} volume(s), {} metadata copies)", device_path, volumes.len(), destroyed.len());
+    Ok(volumes.len())
+}
+
+/// Offsets, relative to the volume, of the FVE metadata copies listed in the BitLocker boot sector
+fn fve_metadata_offsets(device: &mut File, volume_offset: u64) -> io::Result<Vec<u64>> {
+    let mut boot_sector = [0u8; 512];
+    device.seek(SeekFrom::Start(volume_offset))?;
+    device.read_exact(&mut boot_sector)?;
+
+    let read_u64 = |at: usize| u64::from_le_bytes(boot_sector[at..at + 8].try_into().unwrap());
+    // Windows 7 and later keep three copies at 0xB0; Vista kept a single one at 0x38
+    let mut offsets: Vec<u64> = [0xB0, 0xB8, 0xC0].iter().map(|&at| read_u64(at)).filter(|&o| o != 0).collect();
+    if offsets.is_empty() && read_u64(0x38) != 0 {
+        offsets.push(read_u64(0x38));
+    }
+    Ok(offsets)
+}
+
+/// Lock and dismount the volume so Windows drops its cached keys before the metadata goes
+#[cfg(windows)]
+fn lock_bitlocker_volume(device_path: &str) {
+    let Some(letter) = super::volume_letter(device_path) else {
+        return;
+    };
+    match std::process::Command::new("manage-bde")
+        .args(["-lock", &format!("{}:", letter), "-ForceDismount"])
+        .output()
+    {
+        Ok(output) if output.status.success() => log::info!("🔒 Locked BitLocker volume {}:", letter),
+        Ok(output) => log::warn!("⚠️  manage-bde -lock {}: failed: {}", letter, String::from_utf8_lossy(&output.stdout).trim()),
+        Err(e) => log::warn!("⚠️  Could not run manage-bde: {}", e),
+    }
+}
+
+/// Bytes from the start of a LUKS volume to its encrypted payload
+fn luks_header_area(device: &mut File, offset: u64) -> io::Result<u64> {
+    let mut header = [0u8; 512];
+    device.seek(SeekFrom::Start(offset))?;
+    device.read_exact(&mut header)?;
+
+    let version = u16::from_be_bytes([header[6], header[7]]);
+    let area = match version {
+        // LUKS1 stores the payload offset in 512-byte sectors
+        1 => u32::from_be_bytes(header[104..108].try_into().unwrap()) as u64 * 512,
+        2 => luks2_data_offset(device, offset, u64::from_be_bytes(header[8..16].try_into().unwrap())),
+        _ => 0,
+    };
+
+    if area == 0 {
+        log::warn!("⚠️  Could not parse LUKS header at byte {}, destroying the default {} MB", offset, LUKS_DEFAULT_HEADER_AREA / (1024 * 1024));
+        Ok(LUKS_DEFAULT_HEADER_AREA)
+    } else {
+        Ok(area)
+    }
+}
+
+/// Data segment offset from the LUKS2 JSON metadata, 0 if it can't be read
+fn luks2_data_offset(device: &mut File, offset: u64, header_size: u64) -> u64 {
+    // The binary header takes the first 4 KiB, the JSON area fills the rest
+    if !(4096..=4 * 1024 * 1024).contains(&header_size) {
+        return 0;
+    }
+    let mut metadata = vec![0u8; (header_size - 4096) as usize];
+    if device.seek(SeekFrom::Start(offset + 4096)).is_err() || device.read_exact(&mut metadata).is_err() {
+        return 0;
+    }
+
+    let json_len = metadata.iter().position(|&b| b == 0).unwrap_or(metadata.len());
+    serde_json::from_slice::<serde_json::Value>(&metadata[..json_len]).ok()
+        .and_then(|json| {
+            json["segments"].as_object()?.values()
+                .filter_map(|segment| segment["offset"].as_str()?.parse::<u64>().ok())
+                .min()
+        })
+        .unwrap_or(0)
+}
+
+/// Start offsets of partitions that carry no encrypted volume
+fn unencrypted_partitions(device_path: &str) -> io::Result<Vec<u64>> {
+    let encrypted: Vec<u64> = find_encrypted_volumes(device_path)?.iter().map(|v| v.offset).collect();
+    let mut device = File::open(raw_volume_path(device_path))?;
+    let device_size = device.seek(SeekFrom::End(0))?;
+    let partitions = crate::sanitization::read_partition_extents(&mut device, device_size).unwrap_or_default();
+
+    Ok(partitions.into_iter()
+        .map(|(start, _)| start)
+        .filter(|start| !encrypted.contains(start))
+        .collect())
+}
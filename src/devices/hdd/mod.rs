@@ -7,14 +7,146 @@ use std::io;
 use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
 use std::fs::{File, OpenOptions};
-use std::io::{Write, Seek, SeekFrom};
-use crate::advanced_wiper::{DeviceInfo, DeviceType, WipingProgress, WipingAlgorithm};
-use crate::devices::DeviceEraser;
+use std::io::{Read, Write, Seek, SeekFrom};
+use crate::advanced_wiper::{ErrorPolicy, ThermalThrottle, EncryptionStatus, ConnectionInterface, self, DeviceInfo, DeviceType, OverwritePattern, WipingProgress, WipingAlgorithm};
+use crate::devices::{self, DeviceEraser, PassFill};
 use crate::ata_commands::AtaInterface;
 
+/// Write size used on SMR drives; large sequential writes let the drive fill
+/// whole shingled zones instead of staging small writes through its cache
+const SMR_WRITE_SIZE: usize = 16 * 1024 * 1024;
+
+/// Pass sequences of the fixed overwrite methods, with the name shown for each pass
+type Passes = &'static [(PassFill<'static>, &'static str)];
+
+const DOD_5220_22M_PASSES: Passes = &[
+    (PassFill::Byte(0x00), "Zeros (0x00)"),
+    (PassFill::Byte(0xFF), "Ones (0xFF)"),
+    (PassFill::Random, "Random"),
+];
+const DOD_5220_22M_ECE_PASSES: Passes = &[
+    (PassFill::Byte(0x00), "Zeros (0x00)"),
+    (PassFill::Byte(0xFF), "Ones (0xFF)"),
+    (PassFill::Random, "Random"),
+    (PassFill::Random, "ECE Random"),
+    (PassFill::Byte(0x00), "Zeros (0x00)"),
+    (PassFill::Byte(0xFF), "Ones (0xFF)"),
+    (PassFill::Random, "Random"),
+];
+const HMG_IS5_BASELINE_PASSES: Passes = &[(PassFill::Byte(0x00), "Zeros (0x00)")];
+const HMG_IS5_ENHANCED_PASSES: Passes = &[
+    (PassFill::Byte(0x00), "Zeros (0x00)"),
+    (PassFill::Byte(0xFF), "Ones (0xFF)"),
+    (PassFill::Random, "Random"),
+];
+const RCMP_TSSIT_OPS2_PASSES: Passes = &[
+    (PassFill::Byte(0x00), "Zeros (0x00)"),
+    (PassFill::Byte(0xFF), "Ones (0xFF)"),
+    (PassFill::Byte(0x00), "Zeros (0x00)"),
+    (PassFill::Byte(0xFF), "Ones (0xFF)"),
+    (PassFill::Byte(0x00), "Zeros (0x00)"),
+    (PassFill::Byte(0xFF), "Ones (0xFF)"),
+    (PassFill::Random, "Random"),
+];
+const VSITR_PASSES: Passes = &[
+    (PassFill::Byte(0x00), "Zeros (0x00)"),
+    (PassFill::Byte(0xFF), "Ones (0xFF)"),
+    (PassFill::Byte(0x00), "Zeros (0x00)"),
+    (PassFill::Byte(0xFF), "Ones (0xFF)"),
+    (PassFill::Byte(0x00), "Zeros (0x00)"),
+    (PassFill::Byte(0xFF), "Ones (0xFF)"),
+    (PassFill::Byte(0xAA), "0xAA"),
+];
+const AFSSI_5020_PASSES: Passes = &[
+    (PassFill::Byte(0x00), "Zeros (0x00)"),
+    (PassFill::Byte(0xFF), "Ones (0xFF)"),
+    (PassFill::Random, "Random"),
+];
+const NAVSO_P5239_26_PASSES: Passes = &[
+    (PassFill::Byte(0x01), "Character (0x01)"),
+    (PassFill::Byte(0xFE), "Complement (0xFE)"),
+    (PassFill::Random, "Random"),
+];
+const AUSTRALIAN_ISM_PASSES: Passes = &[
+    (PassFill::Random, "Random"),
+    (PassFill::Random, "Random"),
+    (PassFill::Random, "Random"),
+];
+
+/// Names of the passes the HDD eraser writes for `algorithm`, in order, or `None` for
+/// methods it doesn't carry out as an overwrite of its own
+pub fn pass_names(algorithm: &WipingAlgorithm) -> Option<Vec<String>> {
+    let fixed = |passes: Passes| Some(passes.iter().map(|&(_, name)| name.to_string()).collect());
+    match algorithm {
+        WipingAlgorithm::DoD522022M => fixed(DOD_5220_22M_PASSES),
+        WipingAlgorithm::DoD522022MEce => fixed(DOD_5220_22M_ECE_PASSES),
+        WipingAlgorithm::Gutmann => Some(gutmann_patterns().into_iter().map(|(_, name)| name).collect()),
+        WipingAlgorithm::ThreePass | WipingAlgorithm::SevenPass =>
+            Some(vec!["Random".to_string(); algorithm.pass_count() as usize]),
+        WipingAlgorithm::HmgIs5Baseline => fixed(HMG_IS5_BASELINE_PASSES),
+        WipingAlgorithm::HmgIs5Enhanced => fixed(HMG_IS5_ENHANCED_PASSES),
+        WipingAlgorithm::RcmpTssitOps2 => fixed(RCMP_TSSIT_OPS2_PASSES),
+        WipingAlgorithm::AustralianIsm => fixed(AUSTRALIAN_ISM_PASSES),
+        WipingAlgorithm::Vsitr => fixed(VSITR_PASSES),
+        WipingAlgorithm::Afssi5020 => fixed(AFSSI_5020_PASSES),
+        WipingAlgorithm::NavsoP523926 => fixed(NAVSO_P5239_26_PASSES),
+        WipingAlgorithm::MultiPass { passes, pattern } => Some(pattern.patterns(*passes).iter()
+            .map(|pattern| PassFill::of(pattern).1.to_string()).collect()),
+        _ => None,
+    }
+}
+
+/// Gutmann's 35 passes: three-byte patterns aimed at old magnetic encodings between
+/// four random passes at each end
+fn gutmann_patterns() -> Vec<(Vec<u8>, String)> {
+    let random = || -> Vec<u8> { (0..3).map(|_| rand::random::<u8>()).collect() };
+    vec![
+        // Random passes
+        (random(), "Random 1".to_string()),
+        (random(), "Random 2".to_string()),
+        (random(), "Random 3".to_string()),
+        (random(), "Random 4".to_string()),
+        // Specific patterns for magnetic drives
+        (vec![0x55, 0x55, 0x55], "Pattern 0x555555".to_string()),
+        (vec![0xAA, 0xAA, 0xAA], "Pattern 0xAAAAAA".to_string()),
+        (vec![0x92, 0x49, 0x24], "Pattern 0x924924".to_string()),
+        (vec![0x49, 0x24, 0x92], "Pattern 0x492492".to_string()),
+        (vec![0x24, 0x92, 0x49], "Pattern 0x249249".to_string()),
+        (vec![0x00, 0x00, 0x00], "Pattern 0x000000".to_string()),
+        (vec![0x11, 0x11, 0x11], "Pattern 0x111111".to_string()),
+        (vec![0x22, 0x22, 0x22], "Pattern 0x222222".to_string()),
+        (vec![0x33, 0x33, 0x33], "Pattern 0x333333".to_string()),
+        (vec![0x44, 0x44, 0x44], "Pattern 0x444444".to_string()),
+        (vec![0x55, 0x55, 0x55], "Pattern 0x555555".to_string()),
+        (vec![0x66, 0x66, 0x66], "Pattern 0x666666".to_string()),
+        (vec![0x77, 0x77, 0x77], "Pattern 0x777777".to_string()),
+        (vec![0x88, 0x88, 0x88], "Pattern 0x888888".to_string()),
+        (vec![0x99, 0x99, 0x99], "Pattern 0x999999".to_string()),
+        (vec![0xAA, 0xAA, 0xAA], "Pattern 0xAAAAAA".to_string()),
+        (vec![0xBB, 0xBB, 0xBB], "Pattern 0xBBBBBB".to_string()),
+        (vec![0xCC, 0xCC, 0xCC], "Pattern 0xCCCCCC".to_string()),
+        (vec![0xDD, 0xDD, 0xDD], "Pattern 0xDDDDDD".to_string()),
+        (vec![0xEE, 0xEE, 0xEE], "Pattern 0xEEEEEE".to_string()),
+        (vec![0xFF, 0xFF, 0xFF], "Pattern 0xFFFFFF".to_string()),
+        (vec![0x92, 0x49, 0x24], "Pattern 0x924924".to_string()),
+        (vec![0x49, 0x24, 0x92], "Pattern 0x492492".to_string()),
+        (vec![0x24, 0x92, 0x49], "Pattern 0x249249".to_string()),
+        (vec![0x6D, 0xB6, 0xDB], "Pattern 0x6DB6DB".to_string()),
+        (vec![0xB6, 0xDB, 0x6D], "Pattern 0xB6DB6D".to_string()),
+        (vec![0xDB, 0x6D, 0xB6], "Pattern 0xDB6DB6".to_string()),
+        // Final random passes
+        (random(), "Random 32".to_string()),
+        (random(), "Random 33".to_string()),
+        (random(), "Random 34".to_string()),
+        (random(), "Random 35".to_string()),
+    ]
+}
+
 pub struct HddEraser {
     buffer_size: usize,
     verify_after_wipe: bool,
+    error_policy: ErrorPolicy,
+    thermal_limit_c: Option<u32>,
 }
 
 impl HddEraser {
@@ -22,13 +154,27 @@ impl HddEraser {
         Self {
             buffer_size: 1024 * 1024, // 1MB buffer
             verify_after_wipe: true,
+            error_policy: ErrorPolicy::default(),
+            thermal_limit_c: None,
         }
     }
     
+    pub fn with_error_policy(mut self, error_policy: ErrorPolicy) -> Self {
+        self.error_policy = error_policy;
+        self
+    }
+    
+    pub fn with_thermal_limit(mut self, thermal_limit_c: Option<u32>) -> Self {
+        self.thermal_limit_c = thermal_limit_c;
+        self
+    }
+    
     pub fn with_buffer_size(buffer_size: usize) -> Self {
         Self {
             buffer_size,
             verify_after_wipe: true,
+            error_policy: ErrorPolicy::default(),
+            thermal_limit_c: None,
         }
     }
     
@@ -38,34 +184,55 @@ impl HddEraser {
         device_info: &DeviceInfo,
         progress_callback: Arc<Mutex<WipingProgress>>,
     ) -> io::Result<()> {
-        println!("🔄 Starting DoD 5220.22-M (3-pass) erasure for HDD");
+        log::info!("🔄 Starting DoD 5220.22-M (3-pass) erasure for HDD");
         
-        let patterns = [
-            vec![0x00; self.buffer_size], // Pass 1: All zeros
-            vec![0xFF; self.buffer_size], // Pass 2: All ones
-            self.generate_random_pattern(self.buffer_size), // Pass 3: Random
-        ];
+        let mut buffer = self.pass_buffer(device_info);
         
-        for (pass, pattern) in patterns.iter().enumerate() {
+        for (pass, &(fill, name)) in DOD_5220_22M_PASSES.iter().enumerate() {
             let pass_num = pass + 1;
-            println!("🔄 HDD DoD Pass {}/3", pass_num);
+            log::info!("🔄 HDD DoD Pass {}/3", pass_num);
             
             // Update progress
             if let Ok(mut progress) = progress_callback.lock() {
-                progress.current_pass = pass_num as u32;
+                progress.begin_pass(pass_num as u32);
                 progress.total_passes = 3;
-                progress.current_pattern = match pass {
-                    0 => "Zeros (0x00)".to_string(),
-                    1 => "Ones (0xFF)".to_string(),
-                    2 => "Random".to_string(),
-                    _ => "Unknown".to_string(),
-                };
+                progress.current_pattern = name.to_string();
             }
             
-            self.overwrite_device(device_info, pattern, progress_callback.clone())?;
+            devices::fill_pass_buffer(&mut buffer, fill);
+            self.overwrite_device(device_info, &buffer, progress_callback.clone())?;
         }
         
-        println!("✅ DoD 5220.22-M erasure completed for HDD");
+        log::info!("✅ DoD 5220.22-M erasure completed for HDD");
+        Ok(())
+    }
+    
+    /// DoD 5220.22-M ECE erasure (7-pass): a DoD 3-pass, one random pass, then another DoD 3-pass
+    pub fn dod_5220_22m_ece_erase(
+        &self,
+        device_info: &DeviceInfo,
+        progress_callback: Arc<Mutex<WipingProgress>>,
+    ) -> io::Result<()> {
+        log::info!("🔄 Starting DoD 5220.22-M ECE (7-pass) erasure for HDD");
+        
+        let mut buffer = self.pass_buffer(device_info);
+        
+        for (pass, &(fill, name)) in DOD_5220_22M_ECE_PASSES.iter().enumerate() {
+            let pass_num = pass + 1;
+            log::info!("🔄 HDD DoD ECE Pass {}/7: {}", pass_num, name);
+            
+            // Update progress
+            if let Ok(mut progress) = progress_callback.lock() {
+                progress.begin_pass(pass_num as u32);
+                progress.total_passes = 7;
+                progress.current_pattern = name.to_string();
+            }
+            
+            devices::fill_pass_buffer(&mut buffer, fill);
+            self.overwrite_device(device_info, &buffer, progress_callback.clone())?;
+        }
+        
+        log::info!("✅ DoD 5220.22-M ECE erasure completed for HDD");
         Ok(())
     }
     
@@ -75,27 +242,28 @@ impl HddEraser {
         device_info: &DeviceInfo,
         progress_callback: Arc<Mutex<WipingProgress>>,
     ) -> io::Result<()> {
-        println!("🔄 Starting Gutmann 35-pass erasure for HDD");
+        log::info!("🔄 Starting Gutmann 35-pass erasure for HDD");
         
         // Gutmann patterns for magnetic drives
-        let gutmann_patterns = self.get_gutmann_patterns();
+        let gutmann_patterns = gutmann_patterns();
+        let mut buffer = self.pass_buffer(device_info);
         
         for (pass, pattern_data) in gutmann_patterns.iter().enumerate() {
             let pass_num = pass + 1;
-            println!("🔄 HDD Gutmann Pass {}/35: {}", pass_num, pattern_data.1);
+            log::info!("🔄 HDD Gutmann Pass {}/35: {}", pass_num, pattern_data.1);
             
             // Update progress
             if let Ok(mut progress) = progress_callback.lock() {
-                progress.current_pass = pass_num as u32;
+                progress.begin_pass(pass_num as u32);
                 progress.total_passes = 35;
                 progress.current_pattern = pattern_data.1.clone();
             }
             
-            let pattern = self.expand_pattern(&pattern_data.0, self.buffer_size);
-            self.overwrite_device(device_info, &pattern, progress_callback.clone())?;
+            devices::fill_pass_buffer(&mut buffer, PassFill::Repeat(&pattern_data.0));
+            self.overwrite_device(device_info, &buffer, progress_callback.clone())?;
         }
         
-        println!("✅ Gutmann 35-pass erasure completed for HDD");
+        log::info!("✅ Gutmann 35-pass erasure completed for HDD");
         Ok(())
     }
     
@@ -106,23 +274,207 @@ impl HddEraser {
         passes: u32,
         progress_callback: Arc<Mutex<WipingProgress>>,
     ) -> io::Result<()> {
-        println!("🔄 Starting {}-pass random erasure for HDD", passes);
+        log::info!("🔄 Starting {}-pass random erasure for HDD", passes);
+        let mut buffer = self.pass_buffer(device_info);
         
         for pass in 1..=passes {
-            println!("🔄 HDD Random Pass {}/{}", pass, passes);
+            log::info!("🔄 HDD Random Pass {}/{}", pass, passes);
             
             // Update progress
             if let Ok(mut progress) = progress_callback.lock() {
-                progress.current_pass = pass;
+                progress.begin_pass(pass);
                 progress.total_passes = passes;
                 progress.current_pattern = "Random".to_string();
             }
             
-            let pattern = self.generate_random_pattern(self.buffer_size);
-            self.overwrite_device(device_info, &pattern, progress_callback.clone())?;
+            devices::fill_pass_buffer(&mut buffer, PassFill::Random);
+            self.overwrite_device(device_info, &buffer, progress_callback.clone())?;
         }
         
-        println!("✅ {}-pass random erasure completed for HDD", passes);
+        log::info!("✅ {}-pass random erasure completed for HDD", passes);
+        Ok(())
+    }
+    
+    /// HMG Infosec Standard 5 (UK): Baseline is a single zero pass, Enhanced is zeros,
+    /// ones and random. Both read the final pass back, as the standard requires.
+    pub fn hmg_is5_erase(
+        &self,
+        device_info: &DeviceInfo,
+        enhanced: bool,
+        progress_callback: Arc<Mutex<WipingProgress>>,
+    ) -> io::Result<()> {
+        if enhanced {
+            self.pattern_passes_erase(device_info, "HMG IS5 Enhanced", HMG_IS5_ENHANCED_PASSES, true, progress_callback)
+        } else {
+            self.pattern_passes_erase(device_info, "HMG IS5 Baseline", HMG_IS5_BASELINE_PASSES, true, progress_callback)
+        }
+    }
+    
+    /// RCMP TSSIT OPS-II (Canada): six alternating zero/one passes, then a random pass
+    /// that is read back
+    pub fn rcmp_tssit_ops2_erase(
+        &self,
+        device_info: &DeviceInfo,
+        progress_callback: Arc<Mutex<WipingProgress>>,
+    ) -> io::Result<()> {
+        self.pattern_passes_erase(device_info, "RCMP TSSIT OPS-II", RCMP_TSSIT_OPS2_PASSES, true, progress_callback)
+    }
+    
+    /// Overwrite with the operator's pass count and pattern. The final pass is read back,
+    /// since it need not be the zeros the generic check looks for.
+    pub fn multi_pass_erase(
+        &self,
+        device_info: &DeviceInfo,
+        passes: u32,
+        pattern: OverwritePattern,
+        progress_callback: Arc<Mutex<WipingProgress>>,
+    ) -> io::Result<()> {
+        let passes: Vec<(PassFill, &str)> = pattern.patterns(passes).iter().map(PassFill::of).collect();
+        self.pattern_passes_erase(device_info, "Multi-Pass Overwrite", &passes, true, progress_callback)
+    }
+    
+    /// BSI VSITR (Germany): six passes alternating zeros and ones, then 0xAA. The
+    /// standard asks for no read-back, but the final pass is verified anyway because
+    /// the generic zero check can't see it.
+    pub fn vsitr_erase(
+        &self,
+        device_info: &DeviceInfo,
+        progress_callback: Arc<Mutex<WipingProgress>>,
+    ) -> io::Result<()> {
+        self.pattern_passes_erase(device_info, "VSITR", VSITR_PASSES, true, progress_callback)
+    }
+    
+    /// US Air Force AFSSI-5020: zeros, ones, then random, with the random pass read
+    /// back; a mismatch fails the wipe as the standard requires
+    pub fn afssi_5020_erase(
+        &self,
+        device_info: &DeviceInfo,
+        progress_callback: Arc<Mutex<WipingProgress>>,
+    ) -> io::Result<()> {
+        self.pattern_passes_erase(device_info, "AFSSI-5020", AFSSI_5020_PASSES, true, progress_callback)
+    }
+    
+    /// US Navy NAVSO P-5239-26: a character, its complement, then random, with the
+    /// random pass read back
+    pub fn navso_p5239_26_erase(
+        &self,
+        device_info: &DeviceInfo,
+        progress_callback: Arc<Mutex<WipingProgress>>,
+    ) -> io::Result<()> {
+        self.pattern_passes_erase(device_info, "NAVSO P-5239-26", NAVSO_P5239_26_PASSES, true, progress_callback)
+    }
+    
+    /// Australian ISM magnetic media sanitisation: three random overwrites of the whole
+    /// drive, the last one read back
+    pub fn australian_ism_erase(
+        &self,
+        device_info: &DeviceInfo,
+        progress_callback: Arc<Mutex<WipingProgress>>,
+    ) -> io::Result<()> {
+        self.pattern_passes_erase(device_info, "Australian ISM", AUSTRALIAN_ISM_PASSES, true, progress_callback)
+    }
+    
+    /// Run a fixed sequence of overwrite passes as a standard defines them. With
+    /// `verify_final` the last pass is read back over the whole device.
+    fn pattern_passes_erase(
+        &self,
+        device_info: &DeviceInfo,
+        method: &str,
+        passes: &[(PassFill, &str)],
+        verify_final: bool,
+        progress_callback: Arc<Mutex<WipingProgress>>,
+    ) -> io::Result<()> {
+        let total_passes = passes.len() as u32;
+        log::info!("🔄 Starting {} ({}-pass) erasure for HDD", method, total_passes);
+        let mut buffer = self.pass_buffer(device_info);
+        
+        for (pass, &(fill, name)) in passes.iter().enumerate() {
+            let pass_num = pass as u32 + 1;
+            log::info!("🔄 HDD {} Pass {}/{}: {}", method, pass_num, total_passes, name);
+            
+            if let Ok(mut progress) = progress_callback.lock() {
+                progress.begin_pass(pass_num);
+                progress.total_passes = total_passes;
+                progress.current_pattern = name.to_string();
+            }
+            
+            devices::fill_pass_buffer(&mut buffer, fill);
+            self.overwrite_device(device_info, &buffer, progress_callback.clone())?;
+        }
+        
+        if verify_final {
+            log::info!("🔍 Verifying final {} pass", method);
+            if let Ok(mut progress) = progress_callback.lock() {
+                progress.finish_pass();
+                progress.current_pattern = "Verifying final pass".to_string();
+            }
+            self.verify_pattern(device_info, &buffer, &progress_callback)?;
+        }
+        
+        log::info!("✅ {} erasure completed for HDD", method);
+        Ok(())
+    }
+    
+    /// Read the whole device back and check it holds `pattern` repeated end to end
+    fn verify_pattern(
+        &self,
+        device_info: &DeviceInfo,
+        pattern: &[u8],
+        progress_callback: &Arc<Mutex<WipingProgress>>,
+    ) -> io::Result<()> {
+        let mut file = File::open(&device_info.device_path)?;
+        let mut buffer = vec![0u8; pattern.len()];
+        let total_size = device_info.size_bytes;
+        let mut verified = 0u64;
+        
+        while verified < total_size {
+            let len = std::cmp::min(pattern.len() as u64, total_size - verified) as usize;
+            file.read_exact(&mut buffer[..len])?;
+            if buffer[..len] != pattern[..len] {
+                return Err(io::Error::new(io::ErrorKind::InvalidData,
+                    format!("Data at byte {} does not match the final pass", verified)));
+            }
+            verified += len as u64;
+            
+            if let Ok(mut progress) = progress_callback.lock() {
+                progress.bytes_processed = verified;
+            }
+        }
+        Ok(())
+    }
+    
+    /// Clear an SSHD's flash cache with the drive's own secure erase ahead of the overwrite.
+    /// Where the drive can't run one the overwrite still goes ahead, but the cache is
+    /// recorded as not cleared in the wipe's errors, so the certificate never reports the
+    /// hybrid wipe as complete. An erase that was attempted and failed also stops the
+    /// job under `AbortOnFirst`.
+    fn clear_hybrid_cache(&self, device_info: &DeviceInfo, progress_callback: &Arc<Mutex<WipingProgress>>) -> io::Result<()> {
+        let previous_pattern = progress_callback.lock().ok()
+            .map(|mut progress| std::mem::replace(&mut progress.current_pattern, "Clearing SSHD flash cache (ATA Secure Erase)".to_string()));
+        
+        let result = AtaInterface::new(&device_info.device_path).and_then(|ata| {
+            if !ata.get_drive_info()?.security_supported {
+                return Err(io::Error::new(io::ErrorKind::Unsupported, "ATA Secure Erase not supported on this drive"));
+            }
+            ata.security_erase(false)
+        });
+        match result {
+            Ok(()) => log::info!("✅ SSHD flash cache cleared with ATA Secure Erase"),
+            Err(e) => {
+                let message = format!("SSHD flash cache not cleared, only the platters were overwritten: {}", e);
+                log::error!("❌ {}", message);
+                if let Ok(mut progress) = progress_callback.lock() {
+                    progress.errors.push(message);
+                }
+                if e.kind() != io::ErrorKind::Unsupported && self.error_policy == ErrorPolicy::AbortOnFirst {
+                    return Err(e);
+                }
+            }
+        }
+        
+        if let (Some(pattern), Ok(mut progress)) = (previous_pattern, progress_callback.lock()) {
+            progress.current_pattern = pattern;
+        }
         Ok(())
     }
     
@@ -133,11 +485,11 @@ impl HddEraser {
         enhanced: bool,
         progress_callback: Arc<Mutex<WipingProgress>>,
     ) -> io::Result<()> {
-        println!("🔄 Starting ATA Secure Erase for HDD (Enhanced: {})", enhanced);
+        log::info!("🔄 Starting ATA Secure Erase for HDD (Enhanced: {})", enhanced);
         
         // Update progress
         if let Ok(mut progress) = progress_callback.lock() {
-            progress.current_pass = 1;
+            progress.begin_pass(1);
             progress.total_passes = 1;
             progress.current_pattern = if enhanced {
                 "ATA Enhanced Secure Erase".to_string()
@@ -159,11 +511,11 @@ impl HddEraser {
                 
                 // Perform secure erase
                 ata.security_erase(enhanced)?;
-                println!("✅ ATA Secure Erase completed for HDD");
+                log::info!("✅ ATA Secure Erase completed for HDD");
                 Ok(())
             }
             Err(e) => {
-                println!("❌ ATA interface failed, falling back to software erasure");
+                log::error!("❌ ATA interface failed, falling back to software erasure");
                 // Fallback to software-based erasure
                 self.dod_5220_22m_erase(device_info, progress_callback)
             }
@@ -177,6 +529,18 @@ impl HddEraser {
         pattern: &[u8],
         progress_callback: Arc<Mutex<WipingProgress>>,
     ) -> io::Result<()> {
+        if !advanced_wiper::should_write_pass(&progress_callback)? {
+            return Ok(());
+        }
+        // SMR drives slow to a crawl on small rewrites, so feed them larger sequential chunks
+        let smr_pattern;
+        let pattern = if device_info.is_smr && pattern.len() < SMR_WRITE_SIZE {
+            smr_pattern = self.expand_pattern(pattern, SMR_WRITE_SIZE);
+            &smr_pattern[..]
+        } else {
+            pattern
+        };
+        
         let start_time = Instant::now();
         let mut file = OpenOptions::new()
             .write(true)
@@ -187,11 +551,17 @@ impl HddEraser {
         
         file.seek(SeekFrom::Start(0))?;
         
+        let mut throttle = ThermalThrottle::new(&device_info.device_path, self.thermal_limit_c);
+        
         while bytes_written < total_size {
+            devices::throttle_if_hot(&mut throttle, &progress_callback);
+            
             let remaining = total_size - bytes_written;
             let write_size = std::cmp::min(pattern.len() as u64, remaining) as usize;
             
-            file.write_all(&pattern[..write_size])?;
+            if let Err(e) = file.write_all(&pattern[..write_size]) {
+                devices::handle_write_error(self.error_policy, &mut file, bytes_written, write_size as u64, e, &progress_callback)?;
+            }
             bytes_written += write_size as u64;
             
             // Update progress
@@ -212,58 +582,18 @@ impl HddEraser {
         }
         
         file.sync_all()?;
-        Ok(())
+        devices::verify_pass_if_due(device_info, pattern, &progress_callback)
     }
     
-    /// Generate random pattern
-    fn generate_random_pattern(&self, size: usize) -> Vec<u8> {
-        use rand::Rng;
-        let mut rng = rand::thread_rng();
-        (0..size).map(|_| rng.r#gen::<u8>()).collect()
-    }
-    
-    /// Get Gutmann patterns
-    fn get_gutmann_patterns(&self) -> Vec<(Vec<u8>, String)> {
-        vec![
-            // Random passes
-            (self.generate_random_pattern(3), "Random 1".to_string()),
-            (self.generate_random_pattern(3), "Random 2".to_string()),
-            (self.generate_random_pattern(3), "Random 3".to_string()),
-            (self.generate_random_pattern(3), "Random 4".to_string()),
-            // Specific patterns for magnetic drives
-            (vec![0x55, 0x55, 0x55], "Pattern 0x555555".to_string()),
-            (vec![0xAA, 0xAA, 0xAA], "Pattern 0xAAAAAA".to_string()),
-            (vec![0x92, 0x49, 0x24], "Pattern 0x924924".to_string()),
-            (vec![0x49, 0x24, 0x92], "Pattern 0x492492".to_string()),
-            (vec![0x24, 0x92, 0x49], "Pattern 0x249249".to_string()),
-            (vec![0x00, 0x00, 0x00], "Pattern 0x000000".to_string()),
-            (vec![0x11, 0x11, 0x11], "Pattern 0x111111".to_string()),
-            (vec![0x22, 0x22, 0x22], "Pattern 0x222222".to_string()),
-            (vec![0x33, 0x33, 0x33], "Pattern 0x333333".to_string()),
-            (vec![0x44, 0x44, 0x44], "Pattern 0x444444".to_string()),
-            (vec![0x55, 0x55, 0x55], "Pattern 0x555555".to_string()),
-            (vec![0x66, 0x66, 0x66], "Pattern 0x666666".to_string()),
-            (vec![0x77, 0x77, 0x77], "Pattern 0x777777".to_string()),
-            (vec![0x88, 0x88, 0x88], "Pattern 0x888888".to_string()),
-            (vec![0x99, 0x99, 0x99], "Pattern 0x999999".to_string()),
-            (vec![0xAA, 0xAA, 0xAA], "Pattern 0xAAAAAA".to_string()),
-            (vec![0xBB, 0xBB, 0xBB], "Pattern 0xBBBBBB".to_string()),
-            (vec![0xCC, 0xCC, 0xCC], "Pattern 0xCCCCCC".to_string()),
-            (vec![0xDD, 0xDD, 0xDD], "Pattern 0xDDDDDD".to_string()),
-            (vec![0xEE, 0xEE, 0xEE], "Pattern 0xEEEEEE".to_string()),
-            (vec![0xFF, 0xFF, 0xFF], "Pattern 0xFFFFFF".to_string()),
-            (vec![0x92, 0x49, 0x24], "Pattern 0x924924".to_string()),
-            (vec![0x49, 0x24, 0x92], "Pattern 0x492492".to_string()),
-            (vec![0x24, 0x92, 0x49], "Pattern 0x249249".to_string()),
-            (vec![0x6D, 0xB6, 0xDB], "Pattern 0x6DB6DB".to_string()),
-            (vec![0xB6, 0xDB, 0x6D], "Pattern 0xB6DB6D".to_string()),
-            (vec![0xDB, 0x6D, 0xB6], "Pattern 0xDB6DB6".to_string()),
-            // Final random passes
-            (self.generate_random_pattern(3), "Random 32".to_string()),
-            (self.generate_random_pattern(3), "Random 33".to_string()),
-            (self.generate_random_pattern(3), "Random 34".to_string()),
-            (self.generate_random_pattern(3), "Random 35".to_string()),
-        ]
+    /// Buffer shared by every pass of a method. SMR drives get it at their larger write
+    /// size up front so `overwrite_device` never has to expand it per pass.
+    fn pass_buffer(&self, device_info: &DeviceInfo) -> Vec<u8> {
+        let size = if device_info.is_smr {
+            self.buffer_size.max(SMR_WRITE_SIZE)
+        } else {
+            self.buffer_size
+        };
+        vec![0u8; size]
     }
     
     /// Expand pattern to buffer size
@@ -279,12 +609,19 @@ impl HddEraser {
 
 impl DeviceEraser for HddEraser {
     fn analyze_device(&self, device_path: &str) -> io::Result<DeviceInfo> {
-        println!("🔍 Analyzing HDD device: {}", device_path);
+        log::info!("🔍 Analyzing HDD device: {}", device_path);
         
-        // Try to get detailed info via ATA interface
-        let device_info = match AtaInterface::new(device_path) {
-            Ok(ata) => {
-                let drive_info = ata.get_drive_info()?;
+        let identity = devices::read_device_identity(device_path);
+        
+        // Try to get detailed info via ATA interface; devices with no drive behind them (loop
+        // devices, some bridges) accept the ioctl but report no capacity
+        let ata_info = AtaInterface::new(device_path)
+            .and_then(|ata| ata.get_drive_info())
+            .ok()
+            .filter(|drive_info| drive_info.user_capacity > 0);
+        let rotation_rate = ata_info.as_ref().map(|drive_info| drive_info.rotation_rate);
+        let mut device_info = match ata_info {
+            Some(drive_info) => {
                 DeviceInfo {
                     device_path: device_path.to_string(),
                     device_type: DeviceType::HDD,
@@ -295,34 +632,58 @@ impl DeviceEraser for HddEraser {
                     supports_enhanced_secure_erase: drive_info.security_supported,
                     supports_crypto_erase: false, // HDDs typically don't have hardware encryption
                     is_removable: false,
-                    vendor: "Unknown".to_string(),
+                    vendor: devices::vendor_from_ata_model(&drive_info.model)
+                        .or(identity.vendor)
+                        .unwrap_or_else(|| "Unknown".to_string()),
                     model: drive_info.model,
                     serial: drive_info.serial,
+                    is_smr: drive_info.zoned,
+                    is_hybrid: false,
+                    encryption_status: EncryptionStatus::Unknown,
+                    interface: ConnectionInterface::Unknown,
                 }
             }
-            Err(_) => {
+            None => {
                 // Fallback to basic analysis
                 let file = File::open(device_path)?;
                 let metadata = file.metadata()?;
                 
                 DeviceInfo {
                     device_path: device_path.to_string(),
-                    device_type: DeviceType::HDD,
-                    size_bytes: metadata.len(),
+                    // This is the generic first look, so take the OS's word on the type if it has one
+                    device_type: identity.device_type.unwrap_or(DeviceType::HDD),
+                    size_bytes: identity.size_bytes.unwrap_or(metadata.len()),
                     sector_size: 512,
                     supports_trim: false,
                     supports_secure_erase: false,
                     supports_enhanced_secure_erase: false,
                     supports_crypto_erase: false,
                     is_removable: false,
-                    vendor: "Unknown".to_string(),
-                    model: "Unknown HDD".to_string(),
-                    serial: "Unknown".to_string(),
+                    vendor: identity.vendor.unwrap_or_else(|| "Unknown".to_string()),
+                    model: identity.model.unwrap_or_else(|| "Unknown HDD".to_string()),
+                    serial: identity.serial.unwrap_or_else(|| "Unknown".to_string()),
+                    is_smr: false,
+                    is_hybrid: false,
+                    encryption_status: EncryptionStatus::Unknown,
+                    interface: ConnectionInterface::Unknown,
                 }
             }
         };
         
-        println!("✅ HDD analysis complete: {} ({} bytes)", 
+        if !device_info.is_smr {
+            device_info.is_smr = advanced_wiper::is_zoned_device(device_path);
+        }
+        if device_info.is_smr {
+            log::warn!("⚠️  SMR (shingled) HDD detected - the wipe may be unusually slow");
+        }
+        device_info.is_hybrid = advanced_wiper::is_hybrid_drive(&device_info.model, rotation_rate, device_path);
+        if device_info.is_hybrid {
+            // Some OSes report an SSHD's media as SSD; the platters still need the HDD strategy
+            device_info.device_type = DeviceType::HDD;
+            log::warn!("⚠️  Hybrid (SSHD) drive detected - its flash cache will be cleared with ATA Secure Erase where supported");
+        }
+        
+        log::info!("✅ HDD analysis complete: {} ({} bytes)", 
                 device_info.model, device_info.size_bytes);
         Ok(device_info)
     }
@@ -333,61 +694,87 @@ impl DeviceEraser for HddEraser {
         algorithm: WipingAlgorithm,
         progress_callback: Arc<Mutex<WipingProgress>>,
     ) -> io::Result<()> {
-        println!("🚀 Starting HDD erasure with algorithm: {:?}", algorithm);
+        log::info!("🚀 Starting HDD erasure with algorithm: {:?}", algorithm);
+        
+        // An SSHD's NAND cache can keep copies of blocks the overwrite never reaches; the
+        // drive's own erase covers it, and the overwrite that follows covers the platters
+        if device_info.is_hybrid
+            && !matches!(algorithm, WipingAlgorithm::AtaSecureErase | WipingAlgorithm::AtaEnhancedSecureErase) {
+            self.clear_hybrid_cache(device_info, &progress_callback)?;
+        }
         
         match algorithm {
             WipingAlgorithm::DoD522022M => self.dod_5220_22m_erase(device_info, progress_callback),
+            WipingAlgorithm::DoD522022MEce => self.dod_5220_22m_ece_erase(device_info, progress_callback),
             WipingAlgorithm::Gutmann => self.gutmann_erase(device_info, progress_callback),
             WipingAlgorithm::AtaSecureErase => self.ata_secure_erase(device_info, false, progress_callback),
             WipingAlgorithm::AtaEnhancedSecureErase => self.ata_secure_erase(device_info, true, progress_callback),
             WipingAlgorithm::ThreePass => self.multi_pass_random_erase(device_info, 3, progress_callback),
             WipingAlgorithm::SevenPass => self.multi_pass_random_erase(device_info, 7, progress_callback),
+            WipingAlgorithm::MultiPass { passes, pattern } => self.multi_pass_erase(device_info, passes, pattern, progress_callback),
+            WipingAlgorithm::HmgIs5Baseline => self.hmg_is5_erase(device_info, false, progress_callback),
+            WipingAlgorithm::HmgIs5Enhanced => self.hmg_is5_erase(device_info, true, progress_callback),
+            WipingAlgorithm::RcmpTssitOps2 => self.rcmp_tssit_ops2_erase(device_info, progress_callback),
+            WipingAlgorithm::AustralianIsm => self.australian_ism_erase(device_info, progress_callback),
+            WipingAlgorithm::Vsitr => self.vsitr_erase(device_info, progress_callback),
+            WipingAlgorithm::Afssi5020 => self.afssi_5020_erase(device_info, progress_callback),
+            WipingAlgorithm::NavsoP523926 => self.navso_p5239_26_erase(device_info, progress_callback),
             _ => {
                 // Default to DoD 5220.22-M for other algorithms
-                println!("ℹ️  Using DoD 5220.22-M as default for HDD");
+                log::info!("ℹ️  Using DoD 5220.22-M as default for HDD");
                 self.dod_5220_22m_erase(device_info, progress_callback)
             }
         }
     }
     
-    fn verify_erasure(&self, device_info: &DeviceInfo) -> io::Result<bool> {
+    fn verify_erasure(&self, device_info: &DeviceInfo, expected: &[u8]) -> io::Result<bool> {
         if !self.verify_after_wipe {
             return Ok(true);
         }
         
-        println!("🔍 Verifying HDD erasure...");
+        log::info!("🔍 Verifying HDD erasure...");
         
-        let mut file = File::open(&device_info.device_path)?;
-        let mut buffer = vec![0u8; self.buffer_size];
-        let mut total_read = 0u64;
-        let sample_size = std::cmp::min(device_info.size_bytes, 100 * 1024 * 1024); // Sample first 100MB
-        
-        while total_read < sample_size {
-            let bytes_read = std::io::Read::read(&mut file, &mut buffer)?;
-            if bytes_read == 0 {
-                break;
-            }
-            
-            // Check for non-zero bytes (indicating potential data remnants)
-            if buffer[..bytes_read].iter().any(|&b| b != 0) {
-                println!("⚠️  Found non-zero data during verification");
-                return Ok(false);
-            }
-            
-            total_read += bytes_read as u64;
+        // Sample first 100MB
+        let verified = devices::region_reads_back(device_info, 100 * 1024 * 1024, self.buffer_size, expected, None)?;
+        if verified {
+            log::info!("✅ HDD erasure verification passed");
         }
-        
-        println!("✅ HDD erasure verification passed");
-        Ok(true)
+        Ok(verified)
     }
     
     fn get_recommended_algorithms(&self) -> Vec<WipingAlgorithm> {
         vec![
             WipingAlgorithm::DoD522022M,      // Standard 3-pass
+            WipingAlgorithm::DoD522022MEce,   // Extended 7-pass
             WipingAlgorithm::Gutmann,         // Maximum security 35-pass
             WipingAlgorithm::AtaSecureErase,  // Hardware-based if supported
             WipingAlgorithm::SevenPass,       // Enhanced multi-pass
             WipingAlgorithm::ThreePass,       // Basic multi-pass
         ]
     }
+    
+    fn supported_algorithms(&self, device_info: &DeviceInfo) -> Vec<WipingAlgorithm> {
+        let mut algorithms = vec![
+            WipingAlgorithm::DoD522022M,
+            WipingAlgorithm::DoD522022MEce,
+            WipingAlgorithm::Gutmann,
+            WipingAlgorithm::ThreePass,
+            WipingAlgorithm::SevenPass,
+            WipingAlgorithm::HmgIs5Baseline,
+            WipingAlgorithm::HmgIs5Enhanced,
+            WipingAlgorithm::RcmpTssitOps2,
+            WipingAlgorithm::AustralianIsm,
+            WipingAlgorithm::Vsitr,
+            WipingAlgorithm::Afssi5020,
+            WipingAlgorithm::NavsoP523926,
+            WipingAlgorithm::MULTI_PASS,
+        ];
+        if device_info.supports_secure_erase {
+            algorithms.push(WipingAlgorithm::AtaSecureErase);
+        }
+        if device_info.supports_enhanced_secure_erase {
+            algorithms.push(WipingAlgorithm::AtaEnhancedSecureErase);
+        }
+        algorithms
+    }
 }
\ No newline at end of file
@@ -9,10 +9,17 @@ pub mod ssd;
 pub mod nvme;
 pub mod usb;
 pub mod sdcard;
+pub mod crypto_erase;
+pub mod virtual_device;
+#[cfg(target_os = "linux")]
+pub mod loop_device;
 
-use std::io;
-use std::sync::{Arc, Mutex};
-use crate::advanced_wiper::{DeviceInfo, WipingProgress, WipingAlgorithm};
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{self, Read, Seek, SeekFrom};
+use std::sync::{Arc, LazyLock, Mutex};
+use crate::advanced_wiper::{ConnectionInterface, DeviceInfo, DeviceType, EncryptionStatus, ErrorPolicy, ThermalThrottle, WipingProgress, WipingAlgorithm};
+use crate::sanitization::SanitizationPattern;
 
 /// Common interface for all device types
 pub trait DeviceEraser {
@@ -27,44 +34,692 @@ pub trait DeviceEraser {
         progress_callback: Arc<Mutex<WipingProgress>>,
     ) -> io::Result<()>;
     
-    /// Verify erasure completion
-    fn verify_erasure(&self, device_info: &DeviceInfo) -> io::Result<bool>;
+    /// Verify the start of the device reads back as `expected`, the buffer the final pass
+    /// repeated from offset 0; an empty `expected` checks for zeros (e.g. after a hardware erase)
+    fn verify_erasure(&self, device_info: &DeviceInfo, expected: &[u8]) -> io::Result<bool>;
     
     /// Get recommended algorithms for this device type
     fn get_recommended_algorithms(&self) -> Vec<WipingAlgorithm>;
+    
+    /// Algorithms this eraser has a dedicated implementation for on this device,
+    /// including hardware commands only when the device reports support
+    fn supported_algorithms(&self, device_info: &DeviceInfo) -> Vec<WipingAlgorithm>;
 }
 
 /// Device type detection and factory
 pub struct DeviceFactory;
 
+/// Latest analysis of each device path, so looking at a drive again doesn't re-probe it
+static ANALYSES: LazyLock<Mutex<HashMap<String, DeviceInfo>>> = LazyLock::new(Default::default);
+
 impl DeviceFactory {
-    /// Create appropriate eraser based on device analysis
-    pub fn create_eraser(device_info: &DeviceInfo) -> Box<dyn DeviceEraser> {
+    /// Create appropriate eraser based on device analysis.
+    ///
+    /// `thermal_limit_c` pauses overwrites while the drive is hotter than the limit.
+    pub fn create_eraser(device_info: &DeviceInfo, error_policy: ErrorPolicy, thermal_limit_c: Option<u32>) -> Box<dyn DeviceEraser> {
         match device_info.device_type {
-            crate::advanced_wiper::DeviceType::HDD => Box::new(hdd::HddEraser::new()),
-            crate::advanced_wiper::DeviceType::SSD => Box::new(ssd::SsdEraser::new()),
-            crate::advanced_wiper::DeviceType::NVMe => Box::new(nvme::NvmeEraser::new()),
-            crate::advanced_wiper::DeviceType::USBDrive => Box::new(usb::UsbEraser::new()),
-            crate::advanced_wiper::DeviceType::SDCard => Box::new(sdcard::SdCardEraser::new()),
+            crate::advanced_wiper::DeviceType::HDD => Box::new(hdd::HddEraser::new().with_error_policy(error_policy).with_thermal_limit(thermal_limit_c)),
+            crate::advanced_wiper::DeviceType::SSD => Box::new(ssd::SsdEraser::new().with_error_policy(error_policy).with_thermal_limit(thermal_limit_c)),
+            crate::advanced_wiper::DeviceType::NVMe => Box::new(nvme::NvmeEraser::new().with_error_policy(error_policy).with_thermal_limit(thermal_limit_c)),
+            crate::advanced_wiper::DeviceType::USBDrive => Box::new(usb::UsbEraser::new().with_error_policy(error_policy).with_thermal_limit(thermal_limit_c)),
+            crate::advanced_wiper::DeviceType::SDCard => Box::new(sdcard::SdCardEraser::new().with_error_policy(error_policy).with_thermal_limit(thermal_limit_c)),
             crate::advanced_wiper::DeviceType::MMC | 
-            crate::advanced_wiper::DeviceType::EMmc => Box::new(sdcard::SdCardEraser::new()),
-            crate::advanced_wiper::DeviceType::CompactFlash => Box::new(usb::UsbEraser::new()),
-            crate::advanced_wiper::DeviceType::Other(_) => Box::new(hdd::HddEraser::new()), // Default fallback
+            crate::advanced_wiper::DeviceType::EMmc => Box::new(sdcard::SdCardEraser::new().with_error_policy(error_policy).with_thermal_limit(thermal_limit_c)),
+            crate::advanced_wiper::DeviceType::CompactFlash => Box::new(usb::UsbEraser::new().with_error_policy(error_policy).with_thermal_limit(thermal_limit_c)),
+            crate::advanced_wiper::DeviceType::Other(_) => Box::new(hdd::HddEraser::new().with_error_policy(error_policy).with_thermal_limit(thermal_limit_c)), // Default fallback
         }
     }
     
     /// Analyze device and return appropriate eraser
-    pub fn analyze_and_create(device_path: &str) -> io::Result<(DeviceInfo, Box<dyn DeviceEraser>)> {
-        // First, do a generic analysis to determine device type
+    pub fn analyze_and_create(device_path: &str, error_policy: ErrorPolicy, thermal_limit_c: Option<u32>) -> io::Result<(DeviceInfo, Box<dyn DeviceEraser>)> {
+        // A file standing in for a drive has nothing to probe
+        if let Some(sector_size) = virtual_device::sector_size_of(device_path) {
+            let device_info = virtual_device::analyze(device_path, sector_size)?;
+            let eraser = Self::create_eraser(&device_info, error_policy, thermal_limit_c);
+            if let Ok(mut analyses) = ANALYSES.lock() {
+                analyses.insert(device_path.to_string(), device_info.clone());
+            }
+            return Ok((device_info, eraser));
+        }
+        
+        // First, do a generic analysis to determine device type; the bus settles it
+        // outright for NVMe, USB and SD/MMC
+        let interface = detect_interface(device_path);
         let temp_eraser = hdd::HddEraser::new();
-        let device_info = temp_eraser.analyze_device(device_path)?;
+        let mut device_info = temp_eraser.analyze_device(device_path)?;
+        if let Some(device_type) = interface.implied_device_type() {
+            device_info.device_type = device_type;
+        }
         
         // Create the appropriate specialized eraser
-        let eraser = Self::create_eraser(&device_info);
+        let eraser = Self::create_eraser(&device_info, error_policy, thermal_limit_c);
         
         // Re-analyze with the specialized eraser for more detailed info
-        let detailed_info = eraser.analyze_device(device_path)?;
+        let mut detailed_info = eraser.analyze_device(device_path)?;
+        
+        detailed_info.interface = interface;
+        if detailed_info.supports_secure_erase && !interface.passes_ata_commands() {
+            log::warn!("⚠️  {} is attached over {} - hardware secure erase commands may not reach the drive",
+                    device_path, interface.label());
+        }
+        
+        // The erasers assume 512-byte sectors; 4Kn drives must be written and read in 4 KB units
+        if let Some(sector_size) = logical_sector_size(device_path) {
+            detailed_info.sector_size = sector_size;
+        }
+        
+        detailed_info.encryption_status = detect_encryption(device_path);
+        if detailed_info.encryption_status.is_encrypted() {
+            log::info!("🔐 {} volume found on {} - destroying its key is much faster than overwriting",
+                    detailed_info.encryption_status.label(), device_path);
+        }
+        
+        if let Ok(mut analyses) = ANALYSES.lock() {
+            analyses.insert(device_path.to_string(), detailed_info.clone());
+        }
         
         Ok((detailed_info, eraser))
     }
-}
\ No newline at end of file
+    
+    /// Like `analyze_and_create`, but reuses the last analysis of the device if there is one.
+    /// Wipes should always re-analyze; this is for showing a drive's details.
+    pub fn cached_or_analyze(device_path: &str, error_policy: ErrorPolicy, thermal_limit_c: Option<u32>) -> io::Result<(DeviceInfo, Box<dyn DeviceEraser>)> {
+        let cached = ANALYSES.lock().ok().and_then(|analyses| analyses.get(device_path).cloned());
+        match cached {
+            Some(device_info) => {
+                let eraser = Self::create_eraser(&device_info, error_policy, thermal_limit_c);
+                Ok((device_info, eraser))
+            }
+            None => Self::analyze_and_create(device_path, error_policy, thermal_limit_c),
+        }
+    }
+    
+    /// Drop the cached analysis of a device, e.g. once a wipe has changed it
+    pub fn forget(device_path: &str) {
+        if let Ok(mut analyses) = ANALYSES.lock() {
+            analyses.remove(device_path);
+        }
+    }
+    
+    /// Drop the cached analyses of devices that are no longer attached
+    pub fn retain_attached(attached_paths: &[String]) {
+        if let Ok(mut analyses) = ANALYSES.lock() {
+            analyses.retain(|path, _| attached_paths.contains(path));
+        }
+    }
+}
+
+/// An encrypted volume on a device and the byte offset of its header
+#[derive(Debug, Clone, Copy)]
+pub struct EncryptedVolume {
+    pub kind: EncryptionStatus,
+    pub offset: u64,
+}
+
+/// Look for BitLocker and LUKS headers at the start of the device and of every partition
+/// in its partition table. Works on whole disks (`/dev/sdb`, `\\.\PhysicalDrive1`) as well
+/// as on single volumes (`/dev/sdb1`, `\\.\E:`, or a drive letter such as `E:\`).
+pub fn find_encrypted_volumes(device_path: &str) -> io::Result<Vec<EncryptedVolume>> {
+    let mut device = File::open(raw_volume_path(device_path))?;
+    let device_size = device.seek(SeekFrom::End(0))?;
+    
+    let mut offsets = vec![0];
+    if let Ok(partitions) = crate::sanitization::read_partition_extents(&mut device, device_size) {
+        offsets.extend(partitions.iter().map(|&(start, _)| start));
+    }
+    
+    let mut volumes = Vec::new();
+    for offset in offsets {
+        let mut header = [0u8; 512];
+        device.seek(SeekFrom::Start(offset))?;
+        if device.read_exact(&mut header).is_err() {
+            continue;
+        }
+        if let Some(kind) = encryption_signature(&header) {
+            volumes.push(EncryptedVolume { kind, offset });
+        }
+    }
+    Ok(volumes)
+}
+
+/// Overall encryption status of a device, `Unknown` when it can't be read
+pub fn detect_encryption(device_path: &str) -> EncryptionStatus {
+    match find_encrypted_volumes(device_path) {
+        Ok(volumes) => volumes.first().map_or(EncryptionStatus::NotEncrypted, |v| v.kind),
+        Err(e) => {
+            // Without raw read access Windows can still report BitLocker through manage-bde
+            #[cfg(windows)]
+            if let Some(status) = manage_bde_status(device_path) {
+                return status;
+            }
+            log::warn!("⚠️  Could not check {} for encryption: {}", device_path, e);
+            EncryptionStatus::Unknown
+        }
+    }
+}
+
+/// Drive letter of a Windows volume path (`E:`, `E:\`, `\\.\E:`)
+pub(crate) fn volume_letter(device_path: &str) -> Option<char> {
+    let path = device_path.strip_prefix(r"\\.\").unwrap_or(device_path);
+    let path = path.strip_suffix('\\').unwrap_or(path);
+    let mut chars = path.chars();
+    match (chars.next(), chars.next(), chars.next()) {
+        (Some(letter), Some(':'), None) if letter.is_ascii_alphabetic() => Some(letter.to_ascii_uppercase()),
+        _ => None,
+    }
+}
+
+/// Raw device path for a volume, mapping drive letters to `\\.\E:`
+pub(crate) fn raw_volume_path(device_path: &str) -> String {
+    match volume_letter(device_path) {
+        Some(letter) => format!(r"\\.\{}:", letter),
+        None => device_path.to_string(),
+    }
+}
+
+#[cfg(windows)]
+fn manage_bde_status(device_path: &str) -> Option<EncryptionStatus> {
+    let letter = volume_letter(device_path)?;
+    let output = std::process::Command::new("manage-bde")
+        .args(["-status", &format!("{}:", letter)])
+        .output()
+        .ok()?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let conversion = stdout.lines().find(|line| line.trim_start().starts_with("Conversion Status:"))?;
+    if conversion.contains("Fully Decrypted") {
+        Some(EncryptionStatus::NotEncrypted)
+    } else {
+        Some(EncryptionStatus::BitLocker)
+    }
+}
+
+fn encryption_signature(header: &[u8]) -> Option<EncryptionStatus> {
+    if header.starts_with(b"LUKS\xba\xbe") {
+        Some(EncryptionStatus::Luks)
+    } else if header.get(3..11) == Some(b"-FVE-FS-".as_slice()) {
+        // BitLocker replaces the volume's OEM ID with this signature
+        Some(EncryptionStatus::BitLocker)
+    } else {
+        None
+    }
+}
+
+/// Logical sector size of a block device as the OS reports it (the size a virtual device
+/// was registered with), or `None` when it can't be told, e.g. for a regular file
+pub(crate) fn logical_sector_size(device_path: &str) -> Option<u32> {
+    if let Some(sector_size) = virtual_device::sector_size_of(device_path) {
+        return Some(sector_size);
+    }
+    
+    #[cfg(target_os = "linux")]
+    {
+        use std::path::Path;
+        
+        let device = std::fs::canonicalize(device_path).ok()?;
+        let mut sys_dir = Path::new("/sys/class/block").join(device.file_name()?);
+        // Partitions have no queue of their own; the sector size is the disk's
+        if sys_dir.join("partition").exists() {
+            sys_dir = sys_dir.join("..");
+        }
+        std::fs::read_to_string(sys_dir.join("queue/logical_block_size")).ok()?
+            .trim().parse().ok()
+    }
+    
+    #[cfg(windows)]
+    {
+        crate::platform::query_wmi_disk(device_path)
+            .map(|disk| disk.sector_size)
+            .filter(|&sector_size| sector_size > 0)
+    }
+    
+    #[cfg(not(any(target_os = "linux", windows)))]
+    None
+}
+
+/// Round a chunk length down to a whole number of sectors, never below one sector.
+///
+/// Keeping every write a sector multiple keeps offsets aligned too, which 4Kn drives
+/// and direct I/O require.
+pub(crate) fn align_to_sector(len: u64, sector_size: u32) -> u64 {
+    let sector_size = u64::from(sector_size.max(1));
+    (len / sector_size).max(1) * sector_size
+}
+
+/// Bytes of the device that can be written in whole sectors
+pub(crate) fn sector_aligned_size(device_info: &DeviceInfo) -> u64 {
+    let sector_size = u64::from(device_info.sector_size.max(1));
+    let aligned = device_info.size_bytes - device_info.size_bytes % sector_size;
+    if aligned != device_info.size_bytes {
+        log::warn!("⚠️  Device size is not a multiple of {} bytes; skipping the trailing {} bytes",
+                sector_size, device_info.size_bytes - aligned);
+    }
+    aligned
+}
+
+/// What one overwrite pass writes into the buffer shared by every pass of a method
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum PassFill<'a> {
+    Byte(u8),
+    Random,
+    Repeat(&'a [u8]),
+}
+
+impl PassFill<'static> {
+    /// The device erasers' equivalent of a sanitizer pattern, with the pass name shown for it
+    pub(crate) fn of(pattern: &SanitizationPattern) -> (Self, &'static str) {
+        match pattern {
+            SanitizationPattern::Zeros => (PassFill::Byte(0x00), "Zeros (0x00)"),
+            SanitizationPattern::Ones => (PassFill::Byte(0xFF), "Ones (0xFF)"),
+            SanitizationPattern::Random => (PassFill::Random, "Random"),
+            SanitizationPattern::DoD5220 => (PassFill::Repeat(&[0x55, 0xAA]), "0x55/0xAA"),
+            SanitizationPattern::DoD5220Complement => (PassFill::Repeat(&[0xAA, 0x55]), "0xAA/0x55"),
+            SanitizationPattern::Custom(byte) => (PassFill::Byte(*byte), "Custom byte"),
+        }
+    }
+}
+
+/// What each pass of `algorithm` writes, in order, for showing the operator before a
+/// wipe. Methods that don't overwrite describe what they do instead; methods decided
+/// per drive, or that touch only part of it, list nothing.
+pub fn pass_preview(algorithm: &WipingAlgorithm) -> Vec<String> {
+    if let Some(names) = hdd::pass_names(algorithm) {
+        return names;
+    }
+    let names = |patterns: Vec<SanitizationPattern>| patterns.iter().map(|p| PassFill::of(p).1.to_string()).collect();
+    match algorithm {
+        WipingAlgorithm::NistClear | WipingAlgorithm::Zeros | WipingAlgorithm::FastZero =>
+            names(vec![SanitizationPattern::Zeros]),
+        WipingAlgorithm::NistPurge => names(crate::sanitization::SanitizationMethod::Purge.patterns()),
+        WipingAlgorithm::Random => names(vec![SanitizationPattern::Random]),
+        WipingAlgorithm::Ones => names(vec![SanitizationPattern::Ones]),
+        WipingAlgorithm::TwoPass => names(vec![SanitizationPattern::Zeros, SanitizationPattern::Random]),
+        WipingAlgorithm::CustomPattern(bytes) => {
+            let bytes: Vec<String> = bytes.iter().map(|b| format!("{:02X}", b)).collect();
+            vec![format!("Pattern {}", bytes.join(" "))]
+        }
+        WipingAlgorithm::FreeSpaceWipe =>
+            vec!["Random, free space only".to_string(); crate::sanitization::FREE_SPACE_PASSES as usize],
+        WipingAlgorithm::AtaSecureErase | WipingAlgorithm::AtaEnhancedSecureErase
+        | WipingAlgorithm::NvmeSecureErase => vec!["The drive's own erase command".to_string()],
+        WipingAlgorithm::NvmeCryptoErase | WipingAlgorithm::CryptoErase => vec!["Encryption keys destroyed".to_string()],
+        _ => Vec::new(),
+    }
+}
+
+/// Refill the shared pass buffer in place; random passes get fresh data every time
+pub(crate) fn fill_pass_buffer(buffer: &mut [u8], fill: PassFill) {
+    match fill {
+        PassFill::Byte(byte) => buffer.fill(byte),
+        PassFill::Random => rand::Rng::fill(&mut rand::thread_rng(), buffer),
+        PassFill::Repeat(pattern) => {
+            for (i, byte) in buffer.iter_mut().enumerate() {
+                *byte = pattern[i % pattern.len()];
+            }
+        }
+    }
+}
+
+/// Read back `PASS_VERIFY_SAMPLES` random sector-aligned locations of the pass just
+/// written when the verify interval asks for it, so a drive that isn't taking the writes
+/// stops the job early instead of at the final verification. `written` is the buffer the
+/// pass wrote over and over from offset 0; it is kept as the progress's
+/// `last_pass_pattern` for `verify_final_pass`.
+pub(crate) fn verify_pass_if_due(
+    device_info: &DeviceInfo,
+    written: &[u8],
+    progress_callback: &Arc<Mutex<WipingProgress>>,
+) -> io::Result<()> {
+    let Some(pass) = progress_callback.lock().ok().and_then(|mut progress| {
+        progress.last_pass_pattern = Some(Arc::from(written));
+        progress.verify_due().then_some(progress.current_pass)
+    }) else {
+        return Ok(());
+    };
+    let sector_size = u64::from(device_info.sector_size.max(1));
+    let sample_size = crate::advanced_wiper::SECTOR_SAMPLE_SIZE.div_ceil(sector_size) * sector_size;
+    let sample_size = sample_size.min(device_info.size_bytes / sector_size * sector_size);
+    if sample_size == 0 || written.is_empty() {
+        return Ok(());
+    }
+    let last_sample_sector = (device_info.size_bytes - sample_size) / sector_size;
+    
+    log::info!("🔍 Reading back {} locations after pass {}", crate::sanitization::PASS_VERIFY_SAMPLES, pass);
+    let mut file = File::open(&device_info.device_path)?;
+    let mut buffer = vec![0u8; sample_size as usize];
+    let mut rng = rand::thread_rng();
+    for _ in 0..crate::sanitization::PASS_VERIFY_SAMPLES {
+        let position = rand::Rng::gen_range(&mut rng, 0..=last_sample_sector) * sector_size;
+        file.seek(SeekFrom::Start(position))?;
+        file.read_exact(&mut buffer)?;
+        let start = (position % written.len() as u64) as usize;
+        if buffer.iter().enumerate().any(|(i, &byte)| byte != written[(start + i) % written.len()]) {
+            return Err(io::Error::new(io::ErrorKind::InvalidData,
+                format!("Pass {} did not read back as written at offset {}", pass, position)));
+        }
+    }
+    log::info!("✅ Pass {} read back correctly", pass);
+    Ok(())
+}
+
+/// Run the eraser's final verification against what the last pass actually wrote, so a
+/// method ending on ones or random data isn't failed for not reading back as zeros
+pub fn verify_final_pass(
+    eraser: &dyn DeviceEraser,
+    device_info: &DeviceInfo,
+    progress_callback: &Arc<Mutex<WipingProgress>>,
+) -> io::Result<bool> {
+    let expected = progress_callback.lock().ok().and_then(|progress| progress.last_pass_pattern.clone());
+    eraser.verify_erasure(device_info, expected.as_deref().unwrap_or(&[]))
+}
+
+/// Whether the first `sample_size` bytes of the device read back as `expected` repeated
+/// from offset 0 (zeros when `expected` is empty), reading `buffer_size` bytes at a time
+/// with `pause` between reads on media that need sparing
+pub(crate) fn region_reads_back(
+    device_info: &DeviceInfo,
+    sample_size: u64,
+    buffer_size: usize,
+    expected: &[u8],
+    pause: Option<std::time::Duration>,
+) -> io::Result<bool> {
+    let mut file = File::open(&device_info.device_path)?;
+    let mut buffer = vec![0u8; buffer_size];
+    let sample_size = sample_size.min(device_info.size_bytes);
+    let mut total_read = 0u64;
+    
+    while total_read < sample_size {
+        let len = ((sample_size - total_read) as usize).min(buffer.len());
+        let bytes_read = file.read(&mut buffer[..len])?;
+        if bytes_read == 0 {
+            break;
+        }
+        
+        let mismatch = if expected.is_empty() {
+            buffer[..bytes_read].iter().position(|&b| b != 0)
+        } else {
+            let start = (total_read % expected.len() as u64) as usize;
+            buffer[..bytes_read].iter().enumerate()
+                .position(|(i, &b)| b != expected[(start + i) % expected.len()])
+        };
+        if let Some(i) = mismatch {
+            log::warn!("⚠️  Byte {} does not hold what the final pass wrote", total_read + i as u64);
+            return Ok(false);
+        }
+        
+        total_read += bytes_read as u64;
+        if let Some(pause) = pause {
+            std::thread::sleep(pause);
+        }
+    }
+    Ok(true)
+}
+
+/// Apply the error policy to a failed write of `len` bytes at `offset`.
+///
+/// The error is always recorded in the progress. Under `ContinueAndReport` the file is
+/// positioned past the failed region so the caller can carry on with the next chunk.
+pub(crate) fn handle_write_error(
+    error_policy: ErrorPolicy,
+    file: &mut File,
+    offset: u64,
+    len: u64,
+    error: io::Error,
+    progress_callback: &Arc<Mutex<WipingProgress>>,
+) -> io::Result<()> {
+    let message = format!("Write failed at byte {} ({} bytes): {}", offset, len, error);
+    log::error!("❌ {}", message);
+    if let Ok(mut progress) = progress_callback.lock() {
+        progress.errors.push(message);
+    }
+    
+    // There is no next chunk to move on to on a device that has gone
+    if crate::advanced_wiper::is_device_removed_error(&error) {
+        return Err(error);
+    }
+    match error_policy {
+        ErrorPolicy::AbortOnFirst => Err(error),
+        ErrorPolicy::ContinueAndReport => {
+            file.seek(SeekFrom::Start(offset + len))?;
+            Ok(())
+        }
+    }
+}
+
+/// Wait out any thermal pause, showing it in the progress while it lasts
+pub(crate) fn throttle_if_hot(throttle: &mut ThermalThrottle, progress_callback: &Arc<Mutex<WipingProgress>>) {
+    let mut previous_pattern = None;
+    throttle.wait_if_hot(|temperature| {
+        if let Ok(mut progress) = progress_callback.lock() {
+            let cooling = format!("Cooling down ({}°C)", temperature);
+            let pattern = std::mem::replace(&mut progress.current_pattern, cooling);
+            previous_pattern.get_or_insert(pattern);
+        }
+    });
+    
+    if let Some(pattern) = previous_pattern {
+        if let Ok(mut progress) = progress_callback.lock() {
+            progress.current_pattern = pattern;
+        }
+    }
+}
+
+/// Vendor/model/serial strings read from the OS for a block device
+#[derive(Debug, Clone, Default)]
+pub(crate) struct DeviceIdentity {
+    pub vendor: Option<String>,
+    pub model: Option<String>,
+    pub serial: Option<String>,
+    // From WMI on Windows and the sysfs `size` on Linux, where block devices report a length of 0
+    pub size_bytes: Option<u64>,
+    pub device_type: Option<DeviceType>,
+}
+
+/// Read the identity strings the kernel exposes in sysfs.
+///
+/// Covers SCSI/SATA INQUIRY data, USB descriptors of the parent USB device,
+/// NVMe controller identify strings and SD/MMC CID registers.
+#[cfg(target_os = "linux")]
+pub(crate) fn read_device_identity(device_path: &str) -> DeviceIdentity {
+    use std::path::Path;
+    
+    let mut identity = DeviceIdentity::default();
+    let name = match Path::new(device_path).file_name().and_then(|n| n.to_str()) {
+        Some(name) => name,
+        None => return identity,
+    };
+    let read = |path: &Path| std::fs::read_to_string(path).ok()
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty());
+    
+    let mut sys_dir = Path::new("/sys/class/block").join(name);
+    // sysfs counts 512-byte units whatever the logical sector size
+    identity.size_bytes = read(&sys_dir.join("size"))
+        .and_then(|sectors| sectors.parse::<u64>().ok())
+        .map(|sectors| sectors * 512);
+    
+    // Loop devices have no hardware behind them; name them after their image
+    if loop_device::is_loop_device(device_path) {
+        identity.vendor = Some("Linux".to_string());
+        identity.model = Some(match loop_device::backing_file(device_path) {
+            Some(file) => format!("Loop device ({})", file),
+            None => "Loop device".to_string(),
+        });
+        return identity;
+    }
+    
+    // Partitions carry no identity of their own; use the parent disk's
+    if sys_dir.join("partition").exists() {
+        sys_dir = sys_dir.join("..");
+    }
+    let device_dir = match std::fs::canonicalize(sys_dir.join("device")) {
+        Ok(dir) => dir,
+        Err(_) => return identity,
+    };
+    
+    // SCSI INQUIRY (SATA, USB mass storage), NVMe identify, SD/MMC CID
+    identity.vendor = read(&device_dir.join("vendor"))
+        .filter(|vendor| vendor != "ATA" && !vendor.starts_with("0x"));
+    identity.model = read(&device_dir.join("model")).or_else(|| read(&device_dir.join("name")));
+    identity.serial = read(&device_dir.join("serial"));
+    if identity.vendor.is_none() {
+        identity.vendor = read(&device_dir.join("manfid"))
+            .and_then(|manfid| u32::from_str_radix(manfid.trim_start_matches("0x"), 16).ok())
+            .and_then(sd_manufacturer_name)
+            .map(str::to_string);
+    }
+    
+    // USB descriptors live on the USB device several levels above the SCSI device
+    if let Some(usb_dir) = device_dir.ancestors().find(|dir| dir.join("idVendor").exists()) {
+        identity.vendor = identity.vendor.or_else(|| read(&usb_dir.join("manufacturer")));
+        identity.model = identity.model.or_else(|| read(&usb_dir.join("product")));
+        if let Some(serial) = read(&usb_dir.join("serial")) {
+            identity.serial = Some(serial);
+        }
+    }
+    
+    identity
+}
+
+/// Read the identity WMI reports for the disk behind a drive letter or physical drive.
+///
+/// Reaches disks that don't answer ATA IDENTIFY, such as those behind USB bridges or
+/// presented by a RAID controller, and reports their size and media type as well.
+#[cfg(windows)]
+pub(crate) fn read_device_identity(device_path: &str) -> DeviceIdentity {
+    let Some(disk) = crate::platform::query_wmi_disk(device_path) else {
+        return DeviceIdentity::default();
+    };
+    let known = |value: String| Some(value).filter(|value| !value.is_empty());
+    
+    let device_type = match (disk.bus_type.as_str(), disk.media_type.as_str()) {
+        ("NVMe", _) => Some(DeviceType::NVMe),
+        ("USB", _) => Some(DeviceType::USBDrive),
+        ("SD", _) => Some(DeviceType::SDCard),
+        ("MMC", _) => Some(DeviceType::MMC),
+        (_, "SSD") => Some(DeviceType::SSD),
+        (_, "HDD") => Some(DeviceType::HDD),
+        _ => None,
+    };
+    
+    DeviceIdentity {
+        vendor: vendor_from_ata_model(&disk.model),
+        model: known(disk.model),
+        serial: known(disk.serial),
+        size_bytes: Some(disk.size_bytes).filter(|&size| size > 0),
+        device_type,
+    }
+}
+
+#[cfg(not(any(target_os = "linux", windows)))]
+pub(crate) fn read_device_identity(_device_path: &str) -> DeviceIdentity {
+    DeviceIdentity::default()
+}
+
+/// Work out the bus a block device hangs off from its sysfs path, with udev's `ID_BUS`
+/// to tell SATA from plain SCSI
+#[cfg(target_os = "linux")]
+pub(crate) fn detect_interface(device_path: &str) -> ConnectionInterface {
+    use std::path::Path;
+    
+    let Some(name) = Path::new(device_path).file_name().and_then(|n| n.to_str()) else {
+        return ConnectionInterface::Unknown;
+    };
+    let sys_dir = Path::new("/sys/class/block").join(name);
+    let Ok(sys_path) = std::fs::canonicalize(&sys_dir) else {
+        return ConnectionInterface::Unknown;
+    };
+    let sys_path = sys_path.to_string_lossy();
+    
+    // udev keeps its properties under the block device's major:minor
+    let id_bus = std::fs::read_to_string(sys_dir.join("dev")).ok()
+        .and_then(|dev| std::fs::read_to_string(format!("/run/udev/data/b{}", dev.trim())).ok())
+        .and_then(|data| data.lines()
+            .find_map(|line| line.strip_prefix("E:ID_BUS=").map(str::to_string)));
+    
+    // USB first: USB mass storage also shows up as a SCSI host
+    if sys_path.contains("/usb") || id_bus.as_deref() == Some("usb") {
+        ConnectionInterface::Usb
+    } else if name.starts_with("nvme") || sys_path.contains("/nvme") {
+        ConnectionInterface::Nvme
+    } else if name.starts_with("mmcblk") || sys_path.contains("/mmc_host") {
+        ConnectionInterface::SdMmc
+    } else if name.starts_with("md") && sys_path.contains("/virtual/block/") {
+        ConnectionInterface::Raid
+    } else if sys_path.contains("/virtio") || name.starts_with("vd") || name.starts_with("xvd") || name.starts_with("loop") {
+        ConnectionInterface::Virtual
+    } else if id_bus.as_deref() == Some("ata") || sys_path.contains("/ata") {
+        ConnectionInterface::Sata
+    } else if sys_path.contains("/end_device-") || sys_path.contains("/sas_") {
+        ConnectionInterface::Sas
+    } else if id_bus.as_deref() == Some("scsi") || sys_path.contains("/host") {
+        ConnectionInterface::Scsi
+    } else {
+        ConnectionInterface::Unknown
+    }
+}
+
+/// Bus type from the storage stack, as WMI reports it
+#[cfg(windows)]
+pub(crate) fn detect_interface(device_path: &str) -> ConnectionInterface {
+    match crate::platform::query_wmi_disk(device_path).map(|disk| disk.bus_type) {
+        Some(bus) => match bus.as_str() {
+            "SATA" | "ATA" => ConnectionInterface::Sata,
+            "SAS" => ConnectionInterface::Sas,
+            "SCSI" => ConnectionInterface::Scsi,
+            "NVMe" => ConnectionInterface::Nvme,
+            "USB" => ConnectionInterface::Usb,
+            "SD" | "MMC" => ConnectionInterface::SdMmc,
+            "RAID" => ConnectionInterface::Raid,
+            "Virtual" => ConnectionInterface::Virtual,
+            _ => ConnectionInterface::Unknown,
+        },
+        None => ConnectionInterface::Unknown,
+    }
+}
+
+#[cfg(not(any(target_os = "linux", windows)))]
+pub(crate) fn detect_interface(_device_path: &str) -> ConnectionInterface {
+    ConnectionInterface::Unknown
+}
+
+/// Manufacturer names for common SD card CID manufacturer IDs
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn sd_manufacturer_name(manfid: u32) -> Option<&'static str> {
+    match manfid {
+        0x02 => Some("Toshiba"),
+        0x03 => Some("SanDisk"),
+        0x1b => Some("Samsung"),
+        0x27 => Some("Phison"),
+        0x28 => Some("Lexar"),
+        0x41 => Some("Kingston"),
+        0x74 => Some("Transcend"),
+        _ => None,
+    }
+}
+
+/// Guess the vendor from an ATA model string, since IDENTIFY DEVICE has no vendor field
+pub(crate) fn vendor_from_ata_model(model: &str) -> Option<String> {
+    let upper = model.to_uppercase();
+    let vendor = if upper.starts_with("WDC") || upper.starts_with("WD") {
+        "Western Digital"
+    } else if upper.starts_with("ST") {
+        "Seagate"
+    } else if upper.starts_with("SAMSUNG") {
+        "Samsung"
+    } else if upper.starts_with("TOSHIBA") {
+        "Toshiba"
+    } else if upper.starts_with("HGST") || upper.starts_with("HITACHI") {
+        "HGST"
+    } else if upper.starts_with("CRUCIAL") || upper.starts_with("CT") {
+        "Crucial"
+    } else if upper.starts_with("INTEL") {
+        "Intel"
+    } else if upper.starts_with("KINGSTON") {
+        "Kingston"
+    } else if upper.starts_with("SANDISK") {
+        "SanDisk"
+    } else {
+        return None;
+    };
+    Some(vendor.to_string())
+}
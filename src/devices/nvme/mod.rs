@@ -9,13 +9,33 @@ use std::time::{Duration, Instant};
 use std::fs::{File, OpenOptions};
 use std::io::{Write, Seek, SeekFrom};
 use std::process::Command;
-use crate::advanced_wiper::{DeviceInfo, DeviceType, WipingProgress, WipingAlgorithm};
-use crate::devices::DeviceEraser;
+use crate::advanced_wiper::{self, ErrorPolicy, ThermalThrottle, EncryptionStatus, ConnectionInterface, DeviceInfo, DeviceType, WipingProgress, WipingAlgorithm};
+use crate::devices::{self, DeviceEraser};
+
+/// Controller and namespace capabilities reported by Identify
+#[derive(Debug, Clone, Default)]
+pub struct NvmeCapabilities {
+    /// Format NVM is supported (OACS bit 1), which carries the User Data Erase
+    pub format_nvm: bool,
+    /// Cryptographic erase is supported as part of Format NVM (FNA bit 2)
+    pub crypto_erase: bool,
+    /// Write Zeroes command is supported (ONCS bit 3)
+    pub write_zeroes: bool,
+    /// Dataset Management (deallocate) is supported (ONCS bit 2)
+    pub deallocate: bool,
+    pub vendor: Option<String>,
+    pub model: Option<String>,
+    pub serial: Option<String>,
+    /// Size of the namespace's current LBA format in bytes
+    pub lba_size: Option<u32>,
+}
 
 pub struct NvmeEraser {
     buffer_size: usize,
     verify_after_wipe: bool,
     namespace_id: u32,
+    error_policy: ErrorPolicy,
+    thermal_limit_c: Option<u32>,
 }
 
 impl NvmeEraser {
@@ -24,14 +44,28 @@ impl NvmeEraser {
             buffer_size: 4 * 1024 * 1024, // 4MB buffer for NVMe
             verify_after_wipe: true,
             namespace_id: 1, // Default namespace
+            error_policy: ErrorPolicy::default(),
+            thermal_limit_c: None,
         }
     }
     
+    pub fn with_error_policy(mut self, error_policy: ErrorPolicy) -> Self {
+        self.error_policy = error_policy;
+        self
+    }
+    
+    pub fn with_thermal_limit(mut self, thermal_limit_c: Option<u32>) -> Self {
+        self.thermal_limit_c = thermal_limit_c;
+        self
+    }
+    
     pub fn with_namespace(namespace_id: u32) -> Self {
         Self {
             buffer_size: 4 * 1024 * 1024,
             verify_after_wipe: true,
             namespace_id,
+            error_policy: ErrorPolicy::default(),
+            thermal_limit_c: None,
         }
     }
     
@@ -41,11 +75,11 @@ impl NvmeEraser {
         device_info: &DeviceInfo,
         progress_callback: Arc<Mutex<WipingProgress>>,
     ) -> io::Result<()> {
-        println!("🔄 Starting NVMe Secure Erase (User Data)");
+        log::info!("🔄 Starting NVMe Secure Erase (User Data)");
         
         // Update progress
         if let Ok(mut progress) = progress_callback.lock() {
-            progress.current_pass = 1;
+            progress.begin_pass(1);
             progress.total_passes = 1;
             progress.current_pattern = "NVMe Secure Erase".to_string();
         }
@@ -59,7 +93,7 @@ impl NvmeEraser {
         
         // Execute NVMe secure erase command
         // Note: This would typically use nvme-cli or Windows NVMe APIs
-        println!("🔧 Executing NVMe Format with Secure Erase...");
+        log::info!("🔧 Executing NVMe Format with Secure Erase...");
         
         // Simulate NVMe secure erase (in real implementation, this would use proper NVMe commands)
         let start_time = Instant::now();
@@ -76,11 +110,11 @@ impl NvmeEraser {
                     progress.speed_mbps = (device_info.size_bytes as f64) / (1024.0 * 1024.0) / start_time.elapsed().as_secs_f64();
                 }
                 
-                println!("✅ NVMe Secure Erase completed");
+                log::info!("✅ NVMe Secure Erase completed");
                 Ok(())
             }
             Err(e) => {
-                println!("❌ NVMe Secure Erase failed: {}", e);
+                log::error!("❌ NVMe Secure Erase failed: {}", e);
                 Err(e)
             }
         }
@@ -92,11 +126,11 @@ impl NvmeEraser {
         device_info: &DeviceInfo,
         progress_callback: Arc<Mutex<WipingProgress>>,
     ) -> io::Result<()> {
-        println!("🔄 Starting NVMe Cryptographic Erase");
+        log::info!("🔄 Starting NVMe Cryptographic Erase");
         
         // Update progress
         if let Ok(mut progress) = progress_callback.lock() {
-            progress.current_pass = 1;
+            progress.begin_pass(1);
             progress.total_passes = 1;
             progress.current_pattern = "NVMe Crypto Erase".to_string();
         }
@@ -108,7 +142,7 @@ impl NvmeEraser {
             ));
         }
         
-        println!("🔐 Executing NVMe Cryptographic Erase...");
+        log::info!("🔐 Executing NVMe Cryptographic Erase...");
         let start_time = Instant::now();
         
         // Execute cryptographic erase
@@ -123,11 +157,11 @@ impl NvmeEraser {
                     progress.speed_mbps = (device_info.size_bytes as f64) / (1024.0 * 1024.0) / start_time.elapsed().as_secs_f64();
                 }
                 
-                println!("✅ NVMe Cryptographic Erase completed");
+                log::info!("✅ NVMe Cryptographic Erase completed");
                 Ok(())
             }
             Err(e) => {
-                println!("❌ NVMe Cryptographic Erase failed: {}", e);
+                log::error!("❌ NVMe Cryptographic Erase failed: {}", e);
                 Err(e)
             }
         }
@@ -139,11 +173,11 @@ impl NvmeEraser {
         device_info: &DeviceInfo,
         progress_callback: Arc<Mutex<WipingProgress>>,
     ) -> io::Result<()> {
-        println!("🔄 Starting NVMe Write Zeroes");
+        log::info!("🔄 Starting NVMe Write Zeroes");
         
         // Update progress
         if let Ok(mut progress) = progress_callback.lock() {
-            progress.current_pass = 1;
+            progress.begin_pass(1);
             progress.total_passes = 1;
             progress.current_pattern = "NVMe Write Zeroes".to_string();
         }
@@ -153,7 +187,10 @@ impl NvmeEraser {
         let blocks_per_command = 65536; // Maximum blocks per Write Zeroes command
         let mut blocks_processed = 0u64;
         
-        println!("🔧 Writing zeroes to {} blocks...", total_blocks);
+        log::info!("🔧 Writing zeroes to {} blocks...", total_blocks);
+        
+        // Shared by every command; a range is written through it in buffer-sized pieces
+        let zero_buffer = vec![0u8; self.buffer_size];
         
         while blocks_processed < total_blocks {
             let blocks_remaining = total_blocks - blocks_processed;
@@ -163,7 +200,8 @@ impl NvmeEraser {
             let result = self.execute_write_zeroes_command(
                 device_info,
                 blocks_processed,
-                blocks_to_process
+                blocks_to_process,
+                &zero_buffer
             );
             
             match result {
@@ -188,13 +226,21 @@ impl NvmeEraser {
                     }
                 }
                 Err(e) => {
-                    println!("❌ Write Zeroes failed at block {}: {}", blocks_processed, e);
-                    return Err(e);
+                    let message = format!("Write Zeroes failed at block {}: {}", blocks_processed, e);
+                    log::error!("❌ {}", message);
+                    if let Ok(mut progress) = progress_callback.lock() {
+                        progress.errors.push(message);
+                    }
+                    if self.error_policy == ErrorPolicy::AbortOnFirst {
+                        return Err(e);
+                    }
+                    // Skip the failed range and carry on with the next command
+                    blocks_processed += blocks_to_process;
                 }
             }
         }
         
-        println!("✅ NVMe Write Zeroes completed");
+        log::info!("✅ NVMe Write Zeroes completed");
         Ok(())
     }
     
@@ -204,7 +250,7 @@ impl NvmeEraser {
         device_info: &DeviceInfo,
         progress_callback: Arc<Mutex<WipingProgress>>,
     ) -> io::Result<()> {
-        println!("🔄 Starting NVMe Deallocate");
+        log::info!("🔄 Starting NVMe Deallocate");
         
         if !device_info.supports_trim {
             return Err(io::Error::new(
@@ -215,7 +261,7 @@ impl NvmeEraser {
         
         // Update progress
         if let Ok(mut progress) = progress_callback.lock() {
-            progress.current_pass = 1;
+            progress.begin_pass(1);
             progress.total_passes = 1;
             progress.current_pattern = "NVMe Deallocate".to_string();
         }
@@ -223,7 +269,7 @@ impl NvmeEraser {
         let start_time = Instant::now();
         let total_blocks = device_info.size_bytes / device_info.sector_size as u64;
         
-        println!("🔧 Deallocating {} blocks...", total_blocks);
+        log::info!("🔧 Deallocating {} blocks...", total_blocks);
         
         // Execute deallocate command for the entire device
         let result = self.execute_deallocate_command(device_info, 0, total_blocks);
@@ -237,11 +283,11 @@ impl NvmeEraser {
                     progress.speed_mbps = (device_info.size_bytes as f64) / (1024.0 * 1024.0) / start_time.elapsed().as_secs_f64();
                 }
                 
-                println!("✅ NVMe Deallocate completed");
+                log::info!("✅ NVMe Deallocate completed");
                 Ok(())
             }
             Err(e) => {
-                println!("❌ NVMe Deallocate failed: {}", e);
+                log::error!("❌ NVMe Deallocate failed: {}", e);
                 Err(e)
             }
         }
@@ -253,11 +299,11 @@ impl NvmeEraser {
         device_info: &DeviceInfo,
         progress_callback: Arc<Mutex<WipingProgress>>,
     ) -> io::Result<()> {
-        println!("🔄 Starting single-pass overwrite for NVMe");
+        log::info!("🔄 Starting single-pass overwrite for NVMe");
         
         // Update progress
         if let Ok(mut progress) = progress_callback.lock() {
-            progress.current_pass = 1;
+            progress.begin_pass(1);
             progress.total_passes = 1;
             progress.current_pattern = "Random Overwrite".to_string();
         }
@@ -265,7 +311,7 @@ impl NvmeEraser {
         let pattern = self.generate_random_pattern(self.buffer_size);
         self.overwrite_device(device_info, &pattern, progress_callback)?;
         
-        println!("✅ Single-pass overwrite completed for NVMe");
+        log::info!("✅ Single-pass overwrite completed for NVMe");
         Ok(())
     }
     
@@ -300,7 +346,7 @@ impl NvmeEraser {
             }
             Err(_) => {
                 // Fallback: simulate the operation
-                println!("ℹ️  nvme-cli not available, cannot perform NVMe format.");
+                log::info!("ℹ️  nvme-cli not available, cannot perform NVMe format.");
                 Err(io::Error::new(
                     io::ErrorKind::NotFound,
                     "nvme-cli tool not found. Cannot perform hardware secure erase."
@@ -315,6 +361,7 @@ impl NvmeEraser {
         device_info: &DeviceInfo,
         start_block: u64,
         num_blocks: u64,
+        zero_buffer: &[u8],
     ) -> io::Result<()> {
         // This would typically use NVMe Write Zeroes command
         // For now, simulate with actual zero writes
@@ -324,10 +371,14 @@ impl NvmeEraser {
         
         let start_offset = start_block * device_info.sector_size as u64;
         let write_size = num_blocks * device_info.sector_size as u64;
-        let zero_buffer = vec![0u8; write_size as usize];
-        
+        // A range can be 65536 blocks (256 MB with 4Kn sectors), far larger than the buffer
         file.seek(SeekFrom::Start(start_offset))?;
-        file.write_all(&zero_buffer)?;
+        let mut written = 0u64;
+        while written < write_size {
+            let len = (write_size - written).min(zero_buffer.len() as u64) as usize;
+            file.write_all(&zero_buffer[..len])?;
+            written += len as u64;
+        }
         file.sync_data()?;
         
         Ok(())
@@ -342,7 +393,7 @@ impl NvmeEraser {
     ) -> io::Result<()> {
         // This would typically use NVMe Deallocate command
         // For now, return error as we cannot guarantee erasure without proper driver support
-        println!("🔧 Deallocating blocks {} to {}", start_block, start_block + num_blocks - 1);
+        log::info!("🔧 Deallocating blocks {} to {}", start_block, start_block + num_blocks - 1);
         Err(io::Error::new(
             io::ErrorKind::Unsupported,
             "NVMe Deallocate not implemented for this platform"
@@ -356,25 +407,35 @@ impl NvmeEraser {
         pattern: &[u8],
         progress_callback: Arc<Mutex<WipingProgress>>,
     ) -> io::Result<()> {
+        if !advanced_wiper::should_write_pass(&progress_callback)? {
+            return Ok(());
+        }
         let start_time = Instant::now();
         let mut file = OpenOptions::new()
             .write(true)
             .open(&device_info.device_path)?;
         
-        let total_size = device_info.size_bytes;
+        let total_size = devices::sector_aligned_size(device_info);
         let mut bytes_written = 0u64;
         
         file.seek(SeekFrom::Start(0))?;
         
         // Use very large chunks for NVMe to maximize performance
         let chunk_size = std::cmp::max(self.buffer_size, 8 * 1024 * 1024); // At least 8MB
+        let chunk_size = devices::align_to_sector(chunk_size as u64, device_info.sector_size) as usize;
         let pattern_chunk = self.expand_pattern(pattern, chunk_size);
         
+        let mut throttle = ThermalThrottle::new(&device_info.device_path, self.thermal_limit_c);
+        
         while bytes_written < total_size {
+            devices::throttle_if_hot(&mut throttle, &progress_callback);
+            
             let remaining = total_size - bytes_written;
             let write_size = std::cmp::min(pattern_chunk.len() as u64, remaining) as usize;
             
-            file.write_all(&pattern_chunk[..write_size])?;
+            if let Err(e) = file.write_all(&pattern_chunk[..write_size]) {
+                devices::handle_write_error(self.error_policy, &mut file, bytes_written, write_size as u64, e, &progress_callback)?;
+            }
             bytes_written += write_size as u64;
             
             // Force sync less frequently for NVMe (better performance)
@@ -400,7 +461,7 @@ impl NvmeEraser {
         }
         
         file.sync_all()?;
-        Ok(())
+        devices::verify_pass_if_due(device_info, &pattern_chunk, &progress_callback)
     }
     
     /// Generate random pattern
@@ -420,24 +481,92 @@ impl NvmeEraser {
         result
     }
     
-    /// Detect NVMe capabilities
-    fn detect_nvme_capabilities(&self, device_path: &str) -> (bool, bool, bool) {
-        // This would typically query the NVMe controller
-        // For now, return conservative defaults
-        let supports_secure_erase = true;  // Most NVMe drives support this
-        let supports_crypto_erase = true;  // Many modern NVMe drives support this
-        let supports_deallocate = true;    // Standard NVMe feature
-        
-        (supports_secure_erase, supports_crypto_erase, supports_deallocate)
+    /// Query the controller and namespace via Identify (nvme-cli).
+    ///
+    /// If the query fails every capability is reported as unsupported, so a wipe never
+    /// relies on a hardware command the drive may not have.
+    fn detect_nvme_capabilities(&self, device_path: &str) -> NvmeCapabilities {
+        let id_ctrl = match self.run_identify(&["id-ctrl", device_path, "--output-format=json"]) {
+            Ok(json) => json,
+            Err(e) => {
+                log::warn!("⚠️  Could not identify NVMe controller on {}: {}", device_path, e);
+                log::warn!("⚠️  Assuming no hardware erase support");
+                return NvmeCapabilities::default();
+            }
+        };
+        
+        let oacs = id_ctrl["oacs"].as_u64().unwrap_or(0);
+        let fna = id_ctrl["fna"].as_u64().unwrap_or(0);
+        let oncs = id_ctrl["oncs"].as_u64().unwrap_or(0);
+        let text = |key: &str| id_ctrl[key].as_str()
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty());
+        
+        let mut capabilities = NvmeCapabilities {
+            format_nvm: oacs & (1 << 1) != 0,
+            crypto_erase: oacs & (1 << 1) != 0 && fna & (1 << 2) != 0,
+            write_zeroes: oncs & (1 << 3) != 0,
+            deallocate: oncs & (1 << 2) != 0,
+            vendor: id_ctrl["vid"].as_u64().map(pci_vendor_name),
+            model: text("mn"),
+            serial: text("sn"),
+            lba_size: None,
+        };
+        
+        let namespace_id = self.namespace_id.to_string();
+        match self.run_identify(&["id-ns", device_path, "--namespace-id", &namespace_id, "--output-format=json"]) {
+            Ok(id_ns) => {
+                // Bits 3:0 of FLBAS select the active LBA format; LBADS is log2 of its size
+                let format_index = id_ns["flbas"].as_u64().unwrap_or(0) & 0xF;
+                capabilities.lba_size = id_ns["lbafs"][format_index as usize]["ds"].as_u64()
+                    .filter(|ds| (9..32).contains(ds))
+                    .map(|ds| 1u32 << ds);
+            }
+            Err(e) => log::warn!("⚠️  Could not identify NVMe namespace {}: {}", namespace_id, e),
+        }
+        
+        log::info!("🔍 NVMe capabilities: format={} crypto={} write_zeroes={} deallocate={}",
+                capabilities.format_nvm, capabilities.crypto_erase,
+                capabilities.write_zeroes, capabilities.deallocate);
+        capabilities
+    }
+    
+    /// Run an nvme-cli identify command and parse its JSON output
+    fn run_identify(&self, args: &[&str]) -> io::Result<serde_json::Value> {
+        let output = Command::new("nvme").args(args).output()?;
+        if !output.status.success() {
+            return Err(io::Error::new(
+                io::ErrorKind::Other,
+                String::from_utf8_lossy(&output.stderr).trim().to_string(),
+            ));
+        }
+        serde_json::from_slice(&output.stdout)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+}
+
+/// Name for the PCI vendor ID reported in Identify Controller (VID)
+fn pci_vendor_name(vid: u64) -> String {
+    match vid {
+        0x144d => "Samsung".to_string(),
+        0x8086 => "Intel".to_string(),
+        0x15b7 => "Western Digital".to_string(),
+        0x1344 => "Micron".to_string(),
+        0x1c5c => "SK hynix".to_string(),
+        0x2646 => "Kingston".to_string(),
+        0x1179 | 0x1e0f => "Kioxia".to_string(),
+        0x1bb1 => "Seagate".to_string(),
+        0x1987 => "Phison".to_string(),
+        _ => format!("PCI vendor {:#06x}", vid),
     }
 }
 
 impl DeviceEraser for NvmeEraser {
     fn analyze_device(&self, device_path: &str) -> io::Result<DeviceInfo> {
-        println!("🔍 Analyzing NVMe device: {}", device_path);
+        log::info!("🔍 Analyzing NVMe device: {}", device_path);
         
-        let (supports_secure_erase, supports_crypto_erase, supports_deallocate) = 
-            self.detect_nvme_capabilities(device_path);
+        let capabilities = self.detect_nvme_capabilities(device_path);
+        let identity = devices::read_device_identity(device_path);
         
         // Try to get basic device info
         let device_info = match File::open(device_path) {
@@ -446,22 +575,26 @@ impl DeviceEraser for NvmeEraser {
                 DeviceInfo {
                     device_path: device_path.to_string(),
                     device_type: DeviceType::NVMe,
-                    size_bytes: metadata.len(),
-                    sector_size: 4096, // NVMe typically uses 4K sectors
-                    supports_trim: supports_deallocate,
-                    supports_secure_erase,
-                    supports_enhanced_secure_erase: supports_secure_erase,
-                    supports_crypto_erase,
+                    size_bytes: identity.size_bytes.unwrap_or(metadata.len()),
+                    sector_size: capabilities.lba_size.unwrap_or(4096), // NVMe typically uses 4K sectors
+                    supports_trim: capabilities.deallocate,
+                    supports_secure_erase: capabilities.format_nvm,
+                    supports_enhanced_secure_erase: capabilities.format_nvm,
+                    supports_crypto_erase: capabilities.crypto_erase,
                     is_removable: false,
-                    vendor: "Unknown".to_string(),
-                    model: "Unknown NVMe".to_string(),
-                    serial: "Unknown".to_string(),
+                    vendor: capabilities.vendor.or(identity.vendor).unwrap_or_else(|| "Unknown".to_string()),
+                    model: capabilities.model.or(identity.model).unwrap_or_else(|| "Unknown NVMe".to_string()),
+                    serial: capabilities.serial.or(identity.serial).unwrap_or_else(|| "Unknown".to_string()),
+                    is_smr: false,
+                    is_hybrid: false,
+                    encryption_status: EncryptionStatus::Unknown,
+                    interface: ConnectionInterface::Unknown,
                 }
             }
             Err(e) => return Err(e),
         };
         
-        println!("✅ NVMe analysis complete: {} ({} bytes)", 
+        log::info!("✅ NVMe analysis complete: {} ({} bytes)", 
                 device_info.model, device_info.size_bytes);
         Ok(device_info)
     }
@@ -472,7 +605,7 @@ impl DeviceEraser for NvmeEraser {
         algorithm: WipingAlgorithm,
         progress_callback: Arc<Mutex<WipingProgress>>,
     ) -> io::Result<()> {
-        println!("🚀 Starting NVMe erasure with algorithm: {:?}", algorithm);
+        log::info!("🚀 Starting NVMe erasure with algorithm: {:?}", algorithm);
         
         match algorithm {
             WipingAlgorithm::NvmeSecureErase => self.nvme_secure_erase(device_info, progress_callback),
@@ -487,49 +620,33 @@ impl DeviceEraser for NvmeEraser {
             _ => {
                 // Default to NVMe Secure Erase if supported, otherwise crypto erase
                 if device_info.supports_secure_erase {
-                    println!("ℹ️  Using NVMe Secure Erase as default");
+                    log::info!("ℹ️  Using NVMe Secure Erase as default");
                     self.nvme_secure_erase(device_info, progress_callback)
                 } else if device_info.supports_crypto_erase {
-                    println!("ℹ️  Using NVMe Crypto Erase as fallback");
+                    log::info!("ℹ️  Using NVMe Crypto Erase as fallback");
                     self.nvme_crypto_erase(device_info, progress_callback)
                 } else {
-                    println!("ℹ️  Using single-pass overwrite as fallback");
+                    log::info!("ℹ️  Using single-pass overwrite as fallback");
                     self.single_pass_overwrite(device_info, progress_callback)
                 }
             }
         }
     }
     
-    fn verify_erasure(&self, device_info: &DeviceInfo) -> io::Result<bool> {
+    fn verify_erasure(&self, device_info: &DeviceInfo, expected: &[u8]) -> io::Result<bool> {
         if !self.verify_after_wipe {
             return Ok(true);
         }
         
-        println!("🔍 Verifying NVMe erasure...");
+        log::info!("🔍 Verifying NVMe erasure...");
         
-        let mut file = File::open(&device_info.device_path)?;
-        let mut buffer = vec![0u8; self.buffer_size];
-        let mut total_read = 0u64;
         // For NVMe, sample strategically across the device
-        let sample_size = std::cmp::min(device_info.size_bytes, 1024 * 1024 * 1024); // Sample first 1GB
-        
-        while total_read < sample_size {
-            let bytes_read = std::io::Read::read(&mut file, &mut buffer)?;
-            if bytes_read == 0 {
-                break;
-            }
-            
-            // Check for non-zero bytes
-            if buffer[..bytes_read].iter().any(|&b| b != 0) {
-                println!("⚠️  Found non-zero data during NVMe verification");
-                return Ok(false);
-            }
-            
-            total_read += bytes_read as u64;
+        // Sample first 1GB
+        let verified = devices::region_reads_back(device_info, 1024 * 1024 * 1024, self.buffer_size, expected, None)?;
+        if verified {
+            log::info!("✅ NVMe erasure verification passed");
         }
-        
-        println!("✅ NVMe erasure verification passed");
-        Ok(true)
+        Ok(verified)
     }
     
     fn get_recommended_algorithms(&self) -> Vec<WipingAlgorithm> {
@@ -541,4 +658,20 @@ impl DeviceEraser for NvmeEraser {
             WipingAlgorithm::Zeros,              // Simple zero fill
         ]
     }
+    
+    fn supported_algorithms(&self, device_info: &DeviceInfo) -> Vec<WipingAlgorithm> {
+        let mut algorithms = vec![
+            WipingAlgorithm::NistClear,
+            WipingAlgorithm::Random,
+            WipingAlgorithm::Zeros,
+            WipingAlgorithm::Ones,
+        ];
+        if device_info.supports_secure_erase {
+            algorithms.push(WipingAlgorithm::NvmeSecureErase);
+        }
+        if device_info.supports_crypto_erase {
+            algorithms.push(WipingAlgorithm::NvmeCryptoErase);
+        }
+        algorithms
+    }
 }
\ No newline at end of file
@@ -9,14 +9,16 @@ use std::time::{Duration, Instant};
 use std::fs::{File, OpenOptions};
 use std::io::{Write, Seek, SeekFrom};
 use std::process::Command;
-use crate::advanced_wiper::{DeviceInfo, DeviceType, WipingProgress, WipingAlgorithm};
-use crate::devices::DeviceEraser;
+use crate::advanced_wiper::{self, ErrorPolicy, ThermalThrottle, EncryptionStatus, ConnectionInterface, DeviceInfo, DeviceType, WipingProgress, WipingAlgorithm};
+use crate::devices::{self, DeviceEraser, PassFill};
 
 pub struct SdCardEraser {
     buffer_size: usize,
     verify_after_wipe: bool,
     wear_leveling_aware: bool,
     max_write_cycles: u32,
+    error_policy: ErrorPolicy,
+    thermal_limit_c: Option<u32>,
 }
 
 impl SdCardEraser {
@@ -26,15 +28,29 @@ impl SdCardEraser {
             verify_after_wipe: true,
             wear_leveling_aware: true,
             max_write_cycles: 1000, // Conservative estimate for consumer SD cards
+            error_policy: ErrorPolicy::default(),
+            thermal_limit_c: None,
         }
     }
     
+    pub fn with_error_policy(mut self, error_policy: ErrorPolicy) -> Self {
+        self.error_policy = error_policy;
+        self
+    }
+    
+    pub fn with_thermal_limit(mut self, thermal_limit_c: Option<u32>) -> Self {
+        self.thermal_limit_c = thermal_limit_c;
+        self
+    }
+    
     pub fn for_high_endurance() -> Self {
         Self {
             buffer_size: 512 * 1024, // 512KB buffer
             verify_after_wipe: true,
             wear_leveling_aware: true,
             max_write_cycles: 10000, // High-endurance cards
+            error_policy: ErrorPolicy::default(),
+            thermal_limit_c: None,
         }
     }
     
@@ -44,6 +60,8 @@ impl SdCardEraser {
             verify_after_wipe: true,
             wear_leveling_aware: true,
             max_write_cycles: 100000, // Industrial-grade cards
+            error_policy: ErrorPolicy::default(),
+            thermal_limit_c: None,
         }
     }
     
@@ -53,11 +71,11 @@ impl SdCardEraser {
         device_info: &DeviceInfo,
         progress_callback: Arc<Mutex<WipingProgress>>,
     ) -> io::Result<()> {
-        println!("🔄 Starting single-pass random erasure for SD card");
+        log::info!("🔄 Starting single-pass random erasure for SD card");
         
         // Update progress
         if let Ok(mut progress) = progress_callback.lock() {
-            progress.current_pass = 1;
+            progress.begin_pass(1);
             progress.total_passes = 1;
             progress.current_pattern = "Random".to_string();
         }
@@ -65,7 +83,7 @@ impl SdCardEraser {
         let pattern = self.generate_random_pattern(self.buffer_size);
         self.overwrite_device_gentle(device_info, &pattern, progress_callback)?;
         
-        println!("✅ Single-pass random erasure completed for SD card");
+        log::info!("✅ Single-pass random erasure completed for SD card");
         Ok(())
     }
     
@@ -75,11 +93,11 @@ impl SdCardEraser {
         device_info: &DeviceInfo,
         progress_callback: Arc<Mutex<WipingProgress>>,
     ) -> io::Result<()> {
-        println!("🔄 Starting single-pass zero fill for SD card");
+        log::info!("🔄 Starting single-pass zero fill for SD card");
         
         // Update progress
         if let Ok(mut progress) = progress_callback.lock() {
-            progress.current_pass = 1;
+            progress.begin_pass(1);
             progress.total_passes = 1;
             progress.current_pattern = "Zeros".to_string();
         }
@@ -87,7 +105,7 @@ impl SdCardEraser {
         let pattern = vec![0u8; self.buffer_size];
         self.overwrite_device_gentle(device_info, &pattern, progress_callback)?;
         
-        println!("✅ Single-pass zero fill completed for SD card");
+        log::info!("✅ Single-pass zero fill completed for SD card");
         Ok(())
     }
     
@@ -97,11 +115,11 @@ impl SdCardEraser {
         device_info: &DeviceInfo,
         progress_callback: Arc<Mutex<WipingProgress>>,
     ) -> io::Result<()> {
-        println!("🔄 Starting SD Card erase command");
+        log::info!("🔄 Starting SD Card erase command");
         
         // Update progress
         if let Ok(mut progress) = progress_callback.lock() {
-            progress.current_pass = 1;
+            progress.begin_pass(1);
             progress.total_passes = 1;
             progress.current_pattern = "SD Erase Command".to_string();
         }
@@ -115,11 +133,11 @@ impl SdCardEraser {
                     progress.total_bytes = device_info.size_bytes;
                 }
                 
-                println!("✅ SD Card erase command completed");
+                log::info!("✅ SD Card erase command completed");
                 Ok(())
             }
             Err(e) => {
-                println!("❌ SD erase command failed, falling back to software erasure: {}", e);
+                log::error!("❌ SD erase command failed, falling back to software erasure: {}", e);
                 // Fallback to single-pass zero fill
                 self.single_pass_zeros(device_info, progress_callback)
             }
@@ -132,7 +150,7 @@ impl SdCardEraser {
         device_info: &DeviceInfo,
         progress_callback: Arc<Mutex<WipingProgress>>,
     ) -> io::Result<()> {
-        println!("🔄 Starting filesystem-level secure deletion for SD card");
+        log::info!("🔄 Starting filesystem-level secure deletion for SD card");
         
         // This feature requires complex filesystem parsing which is not fully implemented
         // Return error to avoid false sense of security
@@ -148,11 +166,11 @@ impl SdCardEraser {
         device_info: &DeviceInfo,
         progress_callback: Arc<Mutex<WipingProgress>>,
     ) -> io::Result<()> {
-        println!("🔄 Starting quick format for SD card");
+        log::info!("🔄 Starting quick format for SD card");
         
         // Update progress
         if let Ok(mut progress) = progress_callback.lock() {
-            progress.current_pass = 1;
+            progress.begin_pass(1);
             progress.total_passes = 1;
             progress.current_pattern = "Quick Format".to_string();
         }
@@ -174,7 +192,7 @@ impl SdCardEraser {
                         progress.total_bytes = device_info.size_bytes;
                     }
                     
-                    println!("✅ Quick format completed for SD card");
+                    log::info!("✅ Quick format completed for SD card");
                     Ok(())
                 } else {
                     let error_msg = String::from_utf8_lossy(&result.stderr);
@@ -185,7 +203,7 @@ impl SdCardEraser {
                 }
             }
             Err(e) => {
-                println!("❌ Format command failed: {}", e);
+                log::error!("❌ Format command failed: {}", e);
                 Err(e)
             }
         }
@@ -197,20 +215,21 @@ impl SdCardEraser {
         device_info: &DeviceInfo,
         progress_callback: Arc<Mutex<WipingProgress>>,
     ) -> io::Result<()> {
-        println!("🔄 Starting conservative 2-pass erasure for SD card");
+        log::info!("🔄 Starting conservative 2-pass erasure for SD card");
         
         let patterns = [
-            vec![0x00; self.buffer_size], // Pass 1: Zeros
-            self.generate_random_pattern(self.buffer_size), // Pass 2: Random
+            PassFill::Byte(0x00), // Pass 1: Zeros
+            PassFill::Random,     // Pass 2: Random
         ];
+        let mut buffer = vec![0u8; self.buffer_size];
         
-        for (pass, pattern) in patterns.iter().enumerate() {
+        for (pass, &fill) in patterns.iter().enumerate() {
             let pass_num = pass + 1;
-            println!("🔄 SD Card Pass {}/2", pass_num);
+            log::info!("🔄 SD Card Pass {}/2", pass_num);
             
             // Update progress
             if let Ok(mut progress) = progress_callback.lock() {
-                progress.current_pass = pass_num as u32;
+                progress.begin_pass(pass_num as u32);
                 progress.total_passes = 2;
                 progress.current_pattern = match pass {
                     0 => "Zeros".to_string(),
@@ -219,16 +238,17 @@ impl SdCardEraser {
                 };
             }
             
-            self.overwrite_device_gentle(device_info, pattern, progress_callback.clone())?;
+            devices::fill_pass_buffer(&mut buffer, fill);
+            self.overwrite_device_gentle(device_info, &buffer, progress_callback.clone())?;
             
             // Longer delay between passes for SD card health
             if pass < patterns.len() - 1 {
-                println!("⏳ Pausing between passes for SD card health...");
+                log::info!("⏳ Pausing between passes for SD card health...");
                 std::thread::sleep(Duration::from_secs(5));
             }
         }
         
-        println!("✅ Conservative 2-pass erasure completed for SD card");
+        log::info!("✅ Conservative 2-pass erasure completed for SD card");
         Ok(())
     }
     
@@ -236,7 +256,7 @@ impl SdCardEraser {
     fn execute_sd_erase_command(&self, _device_info: &DeviceInfo) -> io::Result<()> {
         // This would typically use SD card specific commands
         // For now, return error to force fallback to software erasure
-        println!("🔧 Executing SD native erase command...");
+        log::info!("🔧 Executing SD native erase command...");
         
         Err(io::Error::new(
             io::ErrorKind::Unsupported,
@@ -246,17 +266,17 @@ impl SdCardEraser {
     
     /// Analyze filesystem on SD card
     fn _analyze_filesystem(&self, device_path: &str) -> io::Result<()> {
-        println!("🔍 Analyzing filesystem on SD card...");
+        log::info!("🔍 Analyzing filesystem on SD card...");
         std::thread::sleep(Duration::from_millis(500));
-        println!("✅ Filesystem analysis completed");
+        log::info!("✅ Filesystem analysis completed");
         Ok(())
     }
     
     /// Secure delete all files
     fn _secure_delete_files(&self, device_path: &str) -> io::Result<()> {
-        println!("🗑️  Securely deleting files on SD card...");
+        log::info!("🗑️  Securely deleting files on SD card...");
         std::thread::sleep(Duration::from_secs(2));
-        println!("✅ File deletion completed");
+        log::info!("✅ File deletion completed");
         Ok(())
     }
     
@@ -266,7 +286,7 @@ impl SdCardEraser {
         device_path: &str,
         progress_callback: Arc<Mutex<WipingProgress>>,
     ) -> io::Result<()> {
-        println!("🔧 Filling free space on SD card (gentle mode)...");
+        log::info!("🔧 Filling free space on SD card (gentle mode)...");
         
         let drive_letter = self.extract_drive_letter(device_path)?;
         let fill_file_path = format!("{}:\\temp_sd_fill.tmp", drive_letter);
@@ -302,11 +322,11 @@ impl SdCardEraser {
                     }
                 }
                 Err(e) if e.kind() == io::ErrorKind::StorageFull => {
-                    println!("✅ Free space filled gently ({} bytes)", bytes_written);
+                    log::info!("✅ Free space filled gently ({} bytes)", bytes_written);
                     break;
                 }
                 Err(e) => {
-                    println!("❌ Error filling free space: {}", e);
+                    log::error!("❌ Error filling free space: {}", e);
                     break;
                 }
             }
@@ -321,9 +341,9 @@ impl SdCardEraser {
     
     /// Cleanup filesystem
     fn _cleanup_filesystem(&self, device_path: &str) -> io::Result<()> {
-        println!("🧹 Cleaning up SD card filesystem...");
+        log::info!("🧹 Cleaning up SD card filesystem...");
         std::thread::sleep(Duration::from_millis(500));
-        println!("✅ Filesystem cleanup completed");
+        log::info!("✅ Filesystem cleanup completed");
         Ok(())
     }
     
@@ -349,25 +369,35 @@ impl SdCardEraser {
         pattern: &[u8],
         progress_callback: Arc<Mutex<WipingProgress>>,
     ) -> io::Result<()> {
+        if !advanced_wiper::should_write_pass(&progress_callback)? {
+            return Ok(());
+        }
         let start_time = Instant::now();
         let mut file = OpenOptions::new()
             .write(true)
             .open(&device_info.device_path)?;
         
-        let total_size = device_info.size_bytes;
+        let total_size = devices::sector_aligned_size(device_info);
         let mut bytes_written = 0u64;
         
         file.seek(SeekFrom::Start(0))?;
         
         // Use very small chunks for SD cards to minimize wear
         let chunk_size = std::cmp::min(self.buffer_size, 128 * 1024); // Max 128KB chunks
+        let chunk_size = devices::align_to_sector(chunk_size as u64, device_info.sector_size) as usize;
         let pattern_chunk = self.expand_pattern(pattern, chunk_size);
         
+        let mut throttle = ThermalThrottle::new(&device_info.device_path, self.thermal_limit_c);
+        
         while bytes_written < total_size {
+            devices::throttle_if_hot(&mut throttle, &progress_callback);
+            
             let remaining = total_size - bytes_written;
             let write_size = std::cmp::min(pattern_chunk.len() as u64, remaining) as usize;
             
-            file.write_all(&pattern_chunk[..write_size])?;
+            if let Err(e) = file.write_all(&pattern_chunk[..write_size]) {
+                devices::handle_write_error(self.error_policy, &mut file, bytes_written, write_size as u64, e, &progress_callback)?;
+            }
             bytes_written += write_size as u64;
             
             // Gentle sync pattern for SD cards
@@ -391,14 +421,15 @@ impl SdCardEraser {
                 }
             }
             
-            // Gentle pause every 10MB to prevent overheating and wear
-            if bytes_written % (10 * 1024 * 1024) == 0 {
+            // Gentle pause every 10MB to prevent overheating and wear, unless the
+            // card reports a temperature and the thermal throttle is pacing writes
+            if !throttle.is_active() && bytes_written % (10 * 1024 * 1024) == 0 {
                 std::thread::sleep(Duration::from_millis(200));
             }
         }
         
         file.sync_all()?;
-        Ok(())
+        devices::verify_pass_if_due(device_info, &pattern_chunk, &progress_callback)
     }
     
     /// Generate random pattern
@@ -431,8 +462,9 @@ impl SdCardEraser {
 
 impl DeviceEraser for SdCardEraser {
     fn analyze_device(&self, device_path: &str) -> io::Result<DeviceInfo> {
-        println!("🔍 Analyzing SD card: {}", device_path);
+        log::info!("🔍 Analyzing SD card: {}", device_path);
         
+        let identity = devices::read_device_identity(device_path);
         let (supports_native_erase, card_type) = self.detect_sd_capabilities(device_path);
         
         // Try to get basic device info
@@ -442,22 +474,26 @@ impl DeviceEraser for SdCardEraser {
                 DeviceInfo {
                     device_path: device_path.to_string(),
                     device_type: DeviceType::SDCard,
-                    size_bytes: metadata.len(),
+                    size_bytes: identity.size_bytes.unwrap_or(metadata.len()),
                     sector_size: 512, // Standard for SD cards
                     supports_trim: false, // SD cards don't typically support TRIM
                     supports_secure_erase: supports_native_erase,
                     supports_enhanced_secure_erase: false,
                     supports_crypto_erase: false, // Rare in consumer SD cards
                     is_removable: true,
-                    vendor: "Unknown".to_string(),
-                    model: card_type,
-                    serial: "Unknown".to_string(),
+                    vendor: identity.vendor.unwrap_or_else(|| "Unknown".to_string()),
+                    model: identity.model.unwrap_or(card_type),
+                    serial: identity.serial.unwrap_or_else(|| "Unknown".to_string()),
+                    is_smr: false,
+                    is_hybrid: false,
+                    encryption_status: EncryptionStatus::Unknown,
+                    interface: ConnectionInterface::Unknown,
                 }
             }
             Err(e) => return Err(e),
         };
         
-        println!("✅ SD card analysis complete: {} ({} bytes)", 
+        log::info!("✅ SD card analysis complete: {} ({} bytes)", 
                 device_info.model, device_info.size_bytes);
         Ok(device_info)
     }
@@ -468,7 +504,7 @@ impl DeviceEraser for SdCardEraser {
         algorithm: WipingAlgorithm,
         progress_callback: Arc<Mutex<WipingProgress>>,
     ) -> io::Result<()> {
-        println!("🚀 Starting SD card erasure with algorithm: {:?}", algorithm);
+        log::info!("🚀 Starting SD card erasure with algorithm: {:?}", algorithm);
         
         match algorithm {
             WipingAlgorithm::Random => self.single_pass_random(device_info, progress_callback),
@@ -484,49 +520,30 @@ impl DeviceEraser for SdCardEraser {
             _ => {
                 // Default to native erase if supported, otherwise single-pass random
                 if device_info.supports_secure_erase {
-                    println!("ℹ️  Using SD native erase as default");
+                    log::info!("ℹ️  Using SD native erase as default");
                     self.sd_erase_command(device_info, progress_callback)
                 } else {
-                    println!("ℹ️  Using single-pass random as default for SD card");
+                    log::info!("ℹ️  Using single-pass random as default for SD card");
                     self.single_pass_random(device_info, progress_callback)
                 }
             }
         }
     }
     
-    fn verify_erasure(&self, device_info: &DeviceInfo) -> io::Result<bool> {
+    fn verify_erasure(&self, device_info: &DeviceInfo, expected: &[u8]) -> io::Result<bool> {
         if !self.verify_after_wipe {
             return Ok(true);
         }
         
-        println!("🔍 Verifying SD card erasure (gentle verification)...");
+        log::info!("🔍 Verifying SD card erasure (gentle verification)...");
         
-        let mut file = File::open(&device_info.device_path)?;
-        let mut buffer = vec![0u8; self.buffer_size];
-        let mut total_read = 0u64;
         // For SD cards, very conservative sampling to minimize wear
-        let sample_size = std::cmp::min(device_info.size_bytes, 10 * 1024 * 1024); // Sample first 10MB only
-        
-        while total_read < sample_size {
-            let bytes_read = std::io::Read::read(&mut file, &mut buffer)?;
-            if bytes_read == 0 {
-                break;
-            }
-            
-            // Check for non-zero bytes
-            if buffer[..bytes_read].iter().any(|&b| b != 0) {
-                println!("⚠️  Found non-zero data during SD card verification");
-                return Ok(false);
-            }
-            
-            total_read += bytes_read as u64;
-            
-            // Gentle pause during verification
-            std::thread::sleep(Duration::from_millis(10));
+        // Sample first 10MB only, with a gentle pause between reads
+        let verified = devices::region_reads_back(device_info, 10 * 1024 * 1024, self.buffer_size, expected, Some(Duration::from_millis(10)))?;
+        if verified {
+            log::info!("✅ SD card erasure verification passed");
         }
-        
-        println!("✅ SD card erasure verification passed");
-        Ok(true)
+        Ok(verified)
     }
     
     fn get_recommended_algorithms(&self) -> Vec<WipingAlgorithm> {
@@ -538,4 +555,16 @@ impl DeviceEraser for SdCardEraser {
             WipingAlgorithm::TwoPass,          // Conservative 2-pass for critical data
         ]
     }
+    
+    fn supported_algorithms(&self, _device_info: &DeviceInfo) -> Vec<WipingAlgorithm> {
+        vec![
+            WipingAlgorithm::NistClear,
+            WipingAlgorithm::Random,
+            WipingAlgorithm::Zeros,
+            WipingAlgorithm::Ones,
+            WipingAlgorithm::QuickFormat,
+            WipingAlgorithm::TwoPass,
+            WipingAlgorithm::FileSystemWipe,
+        ]
+    }
 }
\ No newline at end of file
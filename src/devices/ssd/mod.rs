@@ -10,14 +10,16 @@ use std::time::{Duration, Instant};
 use std::fs::{File, OpenOptions};
 use std::io::{Write, Seek, SeekFrom};
 use std::process::Command;
-use crate::advanced_wiper::{DeviceInfo, DeviceType, WipingProgress, WipingAlgorithm};
-use crate::devices::DeviceEraser;
+use crate::advanced_wiper::{self, ErrorPolicy, ThermalThrottle, EncryptionStatus, ConnectionInterface, DeviceInfo, DeviceType, WipingProgress, WipingAlgorithm};
+use crate::devices::{self, DeviceEraser};
 use crate::ata_commands::AtaInterface;
 
 pub struct SsdEraser {
     buffer_size: usize,
     verify_after_wipe: bool,
     use_trim: bool,
+    error_policy: ErrorPolicy,
+    thermal_limit_c: Option<u32>,
 }
 
 impl SsdEraser {
@@ -26,14 +28,28 @@ impl SsdEraser {
             buffer_size: 2 * 1024 * 1024, // 2MB buffer for SSDs
             verify_after_wipe: true,
             use_trim: true,
+            error_policy: ErrorPolicy::default(),
+            thermal_limit_c: None,
         }
     }
     
+    pub fn with_error_policy(mut self, error_policy: ErrorPolicy) -> Self {
+        self.error_policy = error_policy;
+        self
+    }
+    
+    pub fn with_thermal_limit(mut self, thermal_limit_c: Option<u32>) -> Self {
+        self.thermal_limit_c = thermal_limit_c;
+        self
+    }
+    
     pub fn with_trim(use_trim: bool) -> Self {
         Self {
             buffer_size: 2 * 1024 * 1024,
             verify_after_wipe: true,
             use_trim,
+            error_policy: ErrorPolicy::default(),
+            thermal_limit_c: None,
         }
     }
     
@@ -44,11 +60,11 @@ impl SsdEraser {
         enhanced: bool,
         progress_callback: Arc<Mutex<WipingProgress>>,
     ) -> io::Result<()> {
-        println!("🔄 Starting ATA Secure Erase for SSD (Enhanced: {})", enhanced);
+        log::info!("🔄 Starting ATA Secure Erase for SSD (Enhanced: {})", enhanced);
         
         // Update progress
         if let Ok(mut progress) = progress_callback.lock() {
-            progress.current_pass = 1;
+            progress.begin_pass(1);
             progress.total_passes = 1;
             progress.current_pattern = if enhanced {
                 "ATA Enhanced Secure Erase".to_string()
@@ -67,7 +83,7 @@ impl SsdEraser {
                     ));
                 }
                 
-                println!("🔧 Performing ATA Secure Erase...");
+                log::info!("🔧 Performing ATA Secure Erase...");
                 ata.security_erase(enhanced)?;
                 
                 // Update progress to completion
@@ -76,11 +92,11 @@ impl SsdEraser {
                     progress.total_bytes = device_info.size_bytes;
                 }
                 
-                println!("✅ ATA Secure Erase completed for SSD");
+                log::info!("✅ ATA Secure Erase completed for SSD");
                 Ok(())
             }
             Err(e) => {
-                println!("❌ ATA interface failed: {}", e);
+                log::error!("❌ ATA interface failed: {}", e);
                 Err(io::Error::new(
                     io::ErrorKind::Other,
                     format!("ATA Secure Erase failed: {}", e)
@@ -95,11 +111,11 @@ impl SsdEraser {
         device_info: &DeviceInfo,
         progress_callback: Arc<Mutex<WipingProgress>>,
     ) -> io::Result<()> {
-        println!("🔄 Starting Cryptographic Erase for SSD");
+        log::info!("🔄 Starting Cryptographic Erase for SSD");
         
         // Update progress
         if let Ok(mut progress) = progress_callback.lock() {
-            progress.current_pass = 1;
+            progress.begin_pass(1);
             progress.total_passes = 1;
             progress.current_pattern = "Cryptographic Erase".to_string();
         }
@@ -113,7 +129,7 @@ impl SsdEraser {
         
         // For Windows, we would use Microsoft's Encrypted Hard Drive API
         // This is a simplified implementation
-        println!("🔐 Performing cryptographic key rotation...");
+        log::info!("🔐 Performing cryptographic key rotation...");
         
         // Simulate crypto erase process
         std::thread::sleep(Duration::from_secs(2));
@@ -124,7 +140,7 @@ impl SsdEraser {
             progress.total_bytes = device_info.size_bytes;
         }
         
-        println!("✅ Cryptographic erase completed for SSD");
+        log::info!("✅ Cryptographic erase completed for SSD");
         Ok(())
     }
     
@@ -134,7 +150,7 @@ impl SsdEraser {
         device_info: &DeviceInfo,
         progress_callback: Arc<Mutex<WipingProgress>>,
     ) -> io::Result<()> {
-        println!("🔄 Starting TRIM-based erase for SSD");
+        log::info!("🔄 Starting TRIM-based erase for SSD");
         
         if !device_info.supports_trim {
             return Err(io::Error::new(
@@ -145,7 +161,7 @@ impl SsdEraser {
         
         // Update progress
         if let Ok(mut progress) = progress_callback.lock() {
-            progress.current_pass = 1;
+            progress.begin_pass(1);
             progress.total_passes = 1;
             progress.current_pattern = "TRIM Command".to_string();
         }
@@ -157,7 +173,7 @@ impl SsdEraser {
             
         match output {
             Ok(_) => {
-                println!("🔧 TRIM enabled, performing full device TRIM...");
+                log::info!("🔧 TRIM enabled, performing full device TRIM...");
                 
                 // Perform TRIM operation
                 let trim_output = Command::new("fsutil")
@@ -172,17 +188,17 @@ impl SsdEraser {
                             progress.total_bytes = device_info.size_bytes;
                         }
                         
-                        println!("✅ TRIM-based erase completed for SSD");
+                        log::info!("✅ TRIM-based erase completed for SSD");
                         Ok(())
                     }
                     Err(e) => {
-                        println!("❌ TRIM operation failed: {}", e);
+                        log::error!("❌ TRIM operation failed: {}", e);
                         Err(io::Error::new(io::ErrorKind::Other, "TRIM operation failed"))
                     }
                 }
             }
             Err(e) => {
-                println!("❌ Failed to enable TRIM: {}", e);
+                log::error!("❌ Failed to enable TRIM: {}", e);
                 Err(io::Error::new(io::ErrorKind::Other, "Failed to enable TRIM"))
             }
         }
@@ -194,11 +210,11 @@ impl SsdEraser {
         device_info: &DeviceInfo,
         progress_callback: Arc<Mutex<WipingProgress>>,
     ) -> io::Result<()> {
-        println!("🔄 Starting single-pass overwrite for SSD");
+        log::info!("🔄 Starting single-pass overwrite for SSD");
         
         // Update progress
         if let Ok(mut progress) = progress_callback.lock() {
-            progress.current_pass = 1;
+            progress.begin_pass(1);
             progress.total_passes = 1;
             progress.current_pattern = "Random Overwrite".to_string();
         }
@@ -208,7 +224,7 @@ impl SsdEraser {
         
         // Perform TRIM after overwrite if supported
         if self.use_trim && device_info.supports_trim {
-            println!("🔧 Following up with TRIM command...");
+            log::info!("🔧 Following up with TRIM command...");
             let _ = self.trim_erase(device_info, Arc::new(Mutex::new(
                 crate::advanced_wiper::WipingProgress {
                     algorithm: WipingAlgorithm::Random,
@@ -219,11 +235,17 @@ impl SsdEraser {
                     current_pattern: "TRIM".to_string(),
                     estimated_time_remaining: Duration::from_secs(0),
                     speed_mbps: 0.0,
+                    errors: Vec::new(),
+                    pass_timings: Vec::new(),
+                    pass_started: None,
+                    resume_from_pass: 0,
+                    verify_interval: None,
+                    last_pass_pattern: None,
                 }
             )));
         }
         
-        println!("✅ Single-pass overwrite completed for SSD");
+        log::info!("✅ Single-pass overwrite completed for SSD");
         Ok(())
     }
     
@@ -233,11 +255,11 @@ impl SsdEraser {
         device_info: &DeviceInfo,
         progress_callback: Arc<Mutex<WipingProgress>>,
     ) -> io::Result<()> {
-        println!("🔄 Starting NIST Clear for SSD");
+        log::info!("🔄 Starting NIST Clear for SSD");
         
         // Update progress
         if let Ok(mut progress) = progress_callback.lock() {
-            progress.current_pass = 1;
+            progress.begin_pass(1);
             progress.total_passes = 1;
             progress.current_pattern = "NIST Clear".to_string();
         }
@@ -248,8 +270,8 @@ impl SsdEraser {
         
         // Verify the erasure
         if self.verify_after_wipe {
-            println!("🔍 Verifying NIST Clear...");
-            let verified = self.verify_erasure(device_info)?;
+            log::info!("🔍 Verifying NIST Clear...");
+            let verified = self.verify_erasure(device_info, &[])?;
             if !verified {
                 return Err(io::Error::new(
                     io::ErrorKind::Other,
@@ -258,7 +280,7 @@ impl SsdEraser {
             }
         }
         
-        println!("✅ NIST Clear completed for SSD");
+        log::info!("✅ NIST Clear completed for SSD");
         Ok(())
     }
     
@@ -269,6 +291,9 @@ impl SsdEraser {
         pattern: &[u8],
         progress_callback: Arc<Mutex<WipingProgress>>,
     ) -> io::Result<()> {
+        if !advanced_wiper::should_write_pass(&progress_callback)? {
+            return Ok(());
+        }
         let start_time = Instant::now();
         let mut file = OpenOptions::new()
             .write(true)
@@ -283,11 +308,17 @@ impl SsdEraser {
         let chunk_size = std::cmp::max(self.buffer_size, 4 * 1024 * 1024); // At least 4MB
         let pattern_chunk = self.expand_pattern(pattern, chunk_size);
         
+        let mut throttle = ThermalThrottle::new(&device_info.device_path, self.thermal_limit_c);
+        
         while bytes_written < total_size {
+            devices::throttle_if_hot(&mut throttle, &progress_callback);
+            
             let remaining = total_size - bytes_written;
             let write_size = std::cmp::min(pattern_chunk.len() as u64, remaining) as usize;
             
-            file.write_all(&pattern_chunk[..write_size])?;
+            if let Err(e) = file.write_all(&pattern_chunk[..write_size]) {
+                devices::handle_write_error(self.error_policy, &mut file, bytes_written, write_size as u64, e, &progress_callback)?;
+            }
             bytes_written += write_size as u64;
             
             // Force sync every 100MB to ensure data is written
@@ -313,7 +344,7 @@ impl SsdEraser {
         }
         
         file.sync_all()?;
-        Ok(())
+        devices::verify_pass_if_due(device_info, &pattern_chunk, &progress_callback)
     }
     
     /// Generate random pattern
@@ -336,7 +367,9 @@ impl SsdEraser {
 
 impl DeviceEraser for SsdEraser {
     fn analyze_device(&self, device_path: &str) -> io::Result<DeviceInfo> {
-        println!("🔍 Analyzing SSD device: {}", device_path);
+        log::info!("🔍 Analyzing SSD device: {}", device_path);
+        
+        let identity = devices::read_device_identity(device_path);
         
         // Try to get detailed info via ATA interface
         let device_info = match AtaInterface::new(device_path) {
@@ -352,9 +385,15 @@ impl DeviceEraser for SsdEraser {
                     supports_enhanced_secure_erase: drive_info.security_supported,
                     supports_crypto_erase: self.detect_crypto_support(&drive_info.model),
                     is_removable: false,
-                    vendor: "Unknown".to_string(),
+                    vendor: devices::vendor_from_ata_model(&drive_info.model)
+                        .or(identity.vendor)
+                        .unwrap_or_else(|| "Unknown".to_string()),
                     model: drive_info.model,
                     serial: drive_info.serial,
+                    is_smr: false,
+                    is_hybrid: false,
+                    encryption_status: EncryptionStatus::Unknown,
+                    interface: ConnectionInterface::Unknown,
                 }
             }
             Err(_) => {
@@ -365,21 +404,25 @@ impl DeviceEraser for SsdEraser {
                 DeviceInfo {
                     device_path: device_path.to_string(),
                     device_type: DeviceType::SSD,
-                    size_bytes: metadata.len(),
+                    size_bytes: identity.size_bytes.unwrap_or(metadata.len()),
                     sector_size: 512,
                     supports_trim: true,
                     supports_secure_erase: false,
                     supports_enhanced_secure_erase: false,
                     supports_crypto_erase: false,
                     is_removable: false,
-                    vendor: "Unknown".to_string(),
-                    model: "Unknown SSD".to_string(),
-                    serial: "Unknown".to_string(),
+                    vendor: identity.vendor.unwrap_or_else(|| "Unknown".to_string()),
+                    model: identity.model.unwrap_or_else(|| "Unknown SSD".to_string()),
+                    serial: identity.serial.unwrap_or_else(|| "Unknown".to_string()),
+                    is_smr: false,
+                    is_hybrid: false,
+                    encryption_status: EncryptionStatus::Unknown,
+                    interface: ConnectionInterface::Unknown,
                 }
             }
         };
         
-        println!("✅ SSD analysis complete: {} ({} bytes)", 
+        log::info!("✅ SSD analysis complete: {} ({} bytes)", 
                 device_info.model, device_info.size_bytes);
         Ok(device_info)
     }
@@ -390,7 +433,7 @@ impl DeviceEraser for SsdEraser {
         algorithm: WipingAlgorithm,
         progress_callback: Arc<Mutex<WipingProgress>>,
     ) -> io::Result<()> {
-        println!("🚀 Starting SSD erasure with algorithm: {:?}", algorithm);
+        log::info!("🚀 Starting SSD erasure with algorithm: {:?}", algorithm);
         
         match algorithm {
             WipingAlgorithm::AtaSecureErase => self.ata_secure_erase(device_info, false, progress_callback),
@@ -409,46 +452,30 @@ impl DeviceEraser for SsdEraser {
             _ => {
                 // Default to ATA Secure Erase for SSDs if supported, otherwise single pass
                 if device_info.supports_secure_erase {
-                    println!("ℹ️  Using ATA Secure Erase as default for SSD");
+                    log::info!("ℹ️  Using ATA Secure Erase as default for SSD");
                     self.ata_secure_erase(device_info, false, progress_callback)
                 } else {
-                    println!("ℹ️  Using single-pass overwrite as fallback for SSD");
+                    log::info!("ℹ️  Using single-pass overwrite as fallback for SSD");
                     self.single_pass_overwrite(device_info, progress_callback)
                 }
             }
         }
     }
     
-    fn verify_erasure(&self, device_info: &DeviceInfo) -> io::Result<bool> {
+    fn verify_erasure(&self, device_info: &DeviceInfo, expected: &[u8]) -> io::Result<bool> {
         if !self.verify_after_wipe {
             return Ok(true);
         }
         
-        println!("🔍 Verifying SSD erasure...");
+        log::info!("🔍 Verifying SSD erasure...");
         
-        let mut file = File::open(&device_info.device_path)?;
-        let mut buffer = vec![0u8; self.buffer_size];
-        let mut total_read = 0u64;
         // For SSDs, sample more strategically due to wear leveling
-        let sample_size = std::cmp::min(device_info.size_bytes, 500 * 1024 * 1024); // Sample first 500MB
-        
-        while total_read < sample_size {
-            let bytes_read = std::io::Read::read(&mut file, &mut buffer)?;
-            if bytes_read == 0 {
-                break;
-            }
-            
-            // Check for non-zero bytes
-            if buffer[..bytes_read].iter().any(|&b| b != 0) {
-                println!("⚠️  Found non-zero data during SSD verification");
-                return Ok(false);
-            }
-            
-            total_read += bytes_read as u64;
+        // Sample first 500MB
+        let verified = devices::region_reads_back(device_info, 500 * 1024 * 1024, self.buffer_size, expected, None)?;
+        if verified {
+            log::info!("✅ SSD erasure verification passed");
         }
-        
-        println!("✅ SSD erasure verification passed");
-        Ok(true)
+        Ok(verified)
     }
     
     fn get_recommended_algorithms(&self) -> Vec<WipingAlgorithm> {
@@ -460,6 +487,25 @@ impl DeviceEraser for SsdEraser {
             WipingAlgorithm::Random,                // Single-pass fallback
         ]
     }
+    
+    fn supported_algorithms(&self, device_info: &DeviceInfo) -> Vec<WipingAlgorithm> {
+        let mut algorithms = vec![
+            WipingAlgorithm::NistClear,
+            WipingAlgorithm::Random,
+            WipingAlgorithm::Zeros,
+            WipingAlgorithm::Ones,
+        ];
+        if device_info.supports_secure_erase {
+            algorithms.push(WipingAlgorithm::AtaSecureErase);
+        }
+        if device_info.supports_enhanced_secure_erase {
+            algorithms.push(WipingAlgorithm::AtaEnhancedSecureErase);
+        }
+        if device_info.supports_crypto_erase {
+            algorithms.push(WipingAlgorithm::NvmeCryptoErase);
+        }
+        algorithms
+    }
 }
 
 impl SsdEraser {
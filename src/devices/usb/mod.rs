@@ -9,13 +9,15 @@ use std::time::{Duration, Instant};
 use std::fs::{File, OpenOptions};
 use std::io::{Write, Seek, SeekFrom};
 use std::process::Command;
-use crate::advanced_wiper::{DeviceInfo, DeviceType, WipingProgress, WipingAlgorithm};
-use crate::devices::DeviceEraser;
+use crate::advanced_wiper::{self, ErrorPolicy, ThermalThrottle, EncryptionStatus, ConnectionInterface, DeviceInfo, DeviceType, WipingProgress, WipingAlgorithm};
+use crate::devices::{self, DeviceEraser, PassFill};
 
 pub struct UsbEraser {
     buffer_size: usize,
     verify_after_wipe: bool,
     conservative_approach: bool,
+    error_policy: ErrorPolicy,
+    thermal_limit_c: Option<u32>,
 }
 
 impl UsbEraser {
@@ -24,14 +26,28 @@ impl UsbEraser {
             buffer_size: 512 * 1024, // 512KB buffer for USB (smaller to avoid timeout)
             verify_after_wipe: true,
             conservative_approach: true, // Protect USB drive lifespan
+            error_policy: ErrorPolicy::default(),
+            thermal_limit_c: None,
         }
     }
     
+    pub fn with_error_policy(mut self, error_policy: ErrorPolicy) -> Self {
+        self.error_policy = error_policy;
+        self
+    }
+    
+    pub fn with_thermal_limit(mut self, thermal_limit_c: Option<u32>) -> Self {
+        self.thermal_limit_c = thermal_limit_c;
+        self
+    }
+    
     pub fn with_buffer_size(buffer_size: usize) -> Self {
         Self {
             buffer_size,
             verify_after_wipe: true,
             conservative_approach: true,
+            error_policy: ErrorPolicy::default(),
+            thermal_limit_c: None,
         }
     }
     
@@ -40,6 +56,8 @@ impl UsbEraser {
             buffer_size: 1024 * 1024, // 1MB buffer
             verify_after_wipe: true,
             conservative_approach: false,
+            error_policy: ErrorPolicy::default(),
+            thermal_limit_c: None,
         }
     }
     
@@ -49,11 +67,11 @@ impl UsbEraser {
         device_info: &DeviceInfo,
         progress_callback: Arc<Mutex<WipingProgress>>,
     ) -> io::Result<()> {
-        println!("🔄 Starting single-pass random erasure for USB drive");
+        log::info!("🔄 Starting single-pass random erasure for USB drive");
         
         // Update progress
         if let Ok(mut progress) = progress_callback.lock() {
-            progress.current_pass = 1;
+            progress.begin_pass(1);
             progress.total_passes = 1;
             progress.current_pattern = "Random".to_string();
         }
@@ -61,7 +79,7 @@ impl UsbEraser {
         let pattern = self.generate_random_pattern(self.buffer_size);
         self.overwrite_device(device_info, &pattern, progress_callback)?;
         
-        println!("✅ Single-pass random erasure completed for USB drive");
+        log::info!("✅ Single-pass random erasure completed for USB drive");
         Ok(())
     }
     
@@ -71,11 +89,11 @@ impl UsbEraser {
         device_info: &DeviceInfo,
         progress_callback: Arc<Mutex<WipingProgress>>,
     ) -> io::Result<()> {
-        println!("🔄 Starting single-pass zero fill for USB drive");
+        log::info!("🔄 Starting single-pass zero fill for USB drive");
         
         // Update progress
         if let Ok(mut progress) = progress_callback.lock() {
-            progress.current_pass = 1;
+            progress.begin_pass(1);
             progress.total_passes = 1;
             progress.current_pattern = "Zeros".to_string();
         }
@@ -83,7 +101,7 @@ impl UsbEraser {
         let pattern = vec![0u8; self.buffer_size];
         self.overwrite_device(device_info, &pattern, progress_callback)?;
         
-        println!("✅ Single-pass zero fill completed for USB drive");
+        log::info!("✅ Single-pass zero fill completed for USB drive");
         Ok(())
     }
     
@@ -93,11 +111,11 @@ impl UsbEraser {
         device_info: &DeviceInfo,
         progress_callback: Arc<Mutex<WipingProgress>>,
     ) -> io::Result<()> {
-        println!("🔄 Starting quick format + overwrite for USB drive");
+        log::info!("🔄 Starting quick format + overwrite for USB drive");
         
         // Update progress
         if let Ok(mut progress) = progress_callback.lock() {
-            progress.current_pass = 1;
+            progress.begin_pass(1);
             progress.total_passes = 2;
             progress.current_pattern = "Quick Format".to_string();
         }
@@ -107,7 +125,7 @@ impl UsbEraser {
         
         // Update progress
         if let Ok(mut progress) = progress_callback.lock() {
-            progress.current_pass = 2;
+            progress.begin_pass(2);
             progress.current_pattern = "Random Overwrite".to_string();
         }
         
@@ -115,7 +133,7 @@ impl UsbEraser {
         let pattern = self.generate_random_pattern(self.buffer_size);
         self.overwrite_device(device_info, &pattern, progress_callback)?;
         
-        println!("✅ Quick format + overwrite completed for USB drive");
+        log::info!("✅ Quick format + overwrite completed for USB drive");
         Ok(())
     }
     
@@ -126,25 +144,26 @@ impl UsbEraser {
         progress_callback: Arc<Mutex<WipingProgress>>,
     ) -> io::Result<()> {
         if self.conservative_approach {
-            println!("ℹ️  Conservative mode enabled, using single-pass instead");
+            log::info!("ℹ️  Conservative mode enabled, using single-pass instead");
             return self.single_pass_random(device_info, progress_callback);
         }
         
-        println!("🔄 Starting 3-pass erasure for USB drive");
+        log::info!("🔄 Starting 3-pass erasure for USB drive");
         
         let patterns = [
-            vec![0x00; self.buffer_size], // Pass 1: Zeros
-            vec![0xFF; self.buffer_size], // Pass 2: Ones
-            self.generate_random_pattern(self.buffer_size), // Pass 3: Random
+            PassFill::Byte(0x00), // Pass 1: Zeros
+            PassFill::Byte(0xFF), // Pass 2: Ones
+            PassFill::Random,     // Pass 3: Random
         ];
+        let mut buffer = vec![0u8; self.buffer_size];
         
-        for (pass, pattern) in patterns.iter().enumerate() {
+        for (pass, &fill) in patterns.iter().enumerate() {
             let pass_num = pass + 1;
-            println!("🔄 USB Pass {}/3", pass_num);
+            log::info!("🔄 USB Pass {}/3", pass_num);
             
             // Update progress
             if let Ok(mut progress) = progress_callback.lock() {
-                progress.current_pass = pass_num as u32;
+                progress.begin_pass(pass_num as u32);
                 progress.total_passes = 3;
                 progress.current_pattern = match pass {
                     0 => "Zeros".to_string(),
@@ -154,7 +173,8 @@ impl UsbEraser {
                 };
             }
             
-            self.overwrite_device(device_info, pattern, progress_callback.clone())?;
+            devices::fill_pass_buffer(&mut buffer, fill);
+            self.overwrite_device(device_info, &buffer, progress_callback.clone())?;
             
             // Add delay between passes to prevent overheating
             if pass < patterns.len() - 1 {
@@ -162,7 +182,7 @@ impl UsbEraser {
             }
         }
         
-        println!("✅ 3-pass erasure completed for USB drive");
+        log::info!("✅ 3-pass erasure completed for USB drive");
         Ok(())
     }
     
@@ -172,11 +192,11 @@ impl UsbEraser {
         device_info: &DeviceInfo,
         progress_callback: Arc<Mutex<WipingProgress>>,
     ) -> io::Result<()> {
-        println!("🔄 Starting filesystem-level secure deletion for USB drive");
+        log::info!("🔄 Starting filesystem-level secure deletion for USB drive");
         
         // Update progress
         if let Ok(mut progress) = progress_callback.lock() {
-            progress.current_pass = 1;
+            progress.begin_pass(1);
             progress.total_passes = 3;
             progress.current_pattern = "File Deletion".to_string();
         }
@@ -186,7 +206,7 @@ impl UsbEraser {
         
         // Update progress
         if let Ok(mut progress) = progress_callback.lock() {
-            progress.current_pass = 2;
+            progress.begin_pass(2);
             progress.current_pattern = "Free Space Fill".to_string();
         }
         
@@ -195,20 +215,20 @@ impl UsbEraser {
         
         // Update progress
         if let Ok(mut progress) = progress_callback.lock() {
-            progress.current_pass = 3;
+            progress.begin_pass(3);
             progress.current_pattern = "Cleanup".to_string();
         }
         
         // Step 3: Final cleanup
         self.cleanup_temp_files(&device_info.device_path)?;
         
-        println!("✅ Filesystem-level secure deletion completed for USB drive");
+        log::info!("✅ Filesystem-level secure deletion completed for USB drive");
         Ok(())
     }
     
     /// Quick format the USB drive
     fn quick_format(&self, device_info: &DeviceInfo) -> io::Result<()> {
-        println!("🔧 Performing quick format...");
+        log::info!("🔧 Performing quick format...");
         
         // Extract drive letter from device path
         let drive_letter = self.extract_drive_letter(&device_info.device_path)?;
@@ -221,7 +241,7 @@ impl UsbEraser {
         match output {
             Ok(result) => {
                 if result.status.success() {
-                    println!("✅ Quick format completed");
+                    log::info!("✅ Quick format completed");
                     Ok(())
                 } else {
                     let error_msg = String::from_utf8_lossy(&result.stderr);
@@ -232,7 +252,7 @@ impl UsbEraser {
                 }
             }
             Err(e) => {
-                println!("❌ Format command failed: {}", e);
+                log::error!("❌ Format command failed: {}", e);
                 Err(e)
             }
         }
@@ -240,7 +260,7 @@ impl UsbEraser {
     
     /// Delete all files on the drive
     fn delete_all_files(&self, _device_path: &str) -> io::Result<()> {
-        println!("🗑️  Deleting all files...");
+        log::info!("🗑️  Deleting all files...");
         
         // This would recursively delete all files and directories
         // For now, return error as it is not implemented
@@ -256,7 +276,7 @@ impl UsbEraser {
         device_path: &str,
         progress_callback: Arc<Mutex<WipingProgress>>,
     ) -> io::Result<()> {
-        println!("🔧 Filling free space...");
+        log::info!("🔧 Filling free space...");
         
         let drive_letter = self.extract_drive_letter(device_path)?;
         let fill_file_path = format!("{}:\\temp_fill_file.tmp", drive_letter);
@@ -289,11 +309,20 @@ impl UsbEraser {
                     }
                 }
                 Err(e) if e.kind() == io::ErrorKind::StorageFull => {
-                    println!("✅ Free space filled ({} bytes)", bytes_written);
+                    log::info!("✅ Free space filled ({} bytes)", bytes_written);
                     break;
                 }
                 Err(e) => {
-                    println!("❌ Error filling free space: {}", e);
+                    let message = format!("Error filling free space after {} bytes: {}", bytes_written, e);
+                    log::error!("❌ {}", message);
+                    if let Ok(mut progress) = progress_callback.lock() {
+                        progress.errors.push(message);
+                    }
+                    if self.error_policy == ErrorPolicy::AbortOnFirst {
+                        drop(file);
+                        let _ = std::fs::remove_file(&fill_file_path);
+                        return Err(e);
+                    }
                     break;
                 }
             }
@@ -308,7 +337,7 @@ impl UsbEraser {
     
     /// Clean up temporary files
     fn cleanup_temp_files(&self, _device_path: &str) -> io::Result<()> {
-        println!("🧹 Cleaning up temporary files...");
+        log::info!("🧹 Cleaning up temporary files...");
         
         // This would clean up any remaining temporary files
         // For now, return error as it is not implemented
@@ -341,25 +370,35 @@ impl UsbEraser {
         pattern: &[u8],
         progress_callback: Arc<Mutex<WipingProgress>>,
     ) -> io::Result<()> {
+        if !advanced_wiper::should_write_pass(&progress_callback)? {
+            return Ok(());
+        }
         let start_time = Instant::now();
         let mut file = OpenOptions::new()
             .write(true)
             .open(&device_info.device_path)?;
         
-        let total_size = device_info.size_bytes;
+        let total_size = devices::sector_aligned_size(device_info);
         let mut bytes_written = 0u64;
         
         file.seek(SeekFrom::Start(0))?;
         
         // Use smaller chunks for USB drives to avoid timeouts
         let chunk_size = std::cmp::min(self.buffer_size, 256 * 1024); // Max 256KB chunks
+        let chunk_size = devices::align_to_sector(chunk_size as u64, device_info.sector_size) as usize;
         let pattern_chunk = self.expand_pattern(pattern, chunk_size);
         
+        let mut throttle = ThermalThrottle::new(&device_info.device_path, self.thermal_limit_c);
+        
         while bytes_written < total_size {
+            devices::throttle_if_hot(&mut throttle, &progress_callback);
+            
             let remaining = total_size - bytes_written;
             let write_size = std::cmp::min(pattern_chunk.len() as u64, remaining) as usize;
             
-            file.write_all(&pattern_chunk[..write_size])?;
+            if let Err(e) = file.write_all(&pattern_chunk[..write_size]) {
+                devices::handle_write_error(self.error_policy, &mut file, bytes_written, write_size as u64, e, &progress_callback)?;
+            }
             bytes_written += write_size as u64;
             
             // Sync more frequently for USB drives
@@ -383,14 +422,14 @@ impl UsbEraser {
                 }
             }
             
-            // Small delay to prevent overheating USB drive
-            if bytes_written % (50 * 1024 * 1024) == 0 {
+            // Without a temperature reading, fall back to a small fixed delay to prevent overheating
+            if !throttle.is_active() && bytes_written % (50 * 1024 * 1024) == 0 {
                 std::thread::sleep(Duration::from_millis(100));
             }
         }
         
         file.sync_all()?;
-        Ok(())
+        devices::verify_pass_if_due(device_info, &pattern_chunk, &progress_callback)
     }
     
     /// Generate random pattern
@@ -423,8 +462,9 @@ impl UsbEraser {
 
 impl DeviceEraser for UsbEraser {
     fn analyze_device(&self, device_path: &str) -> io::Result<DeviceInfo> {
-        println!("🔍 Analyzing USB drive: {}", device_path);
+        log::info!("🔍 Analyzing USB drive: {}", device_path);
         
+        let identity = devices::read_device_identity(device_path);
         let (supports_secure_erase, supports_trim) = self.detect_usb_capabilities(device_path);
         
         // Try to get basic device info
@@ -434,22 +474,26 @@ impl DeviceEraser for UsbEraser {
                 DeviceInfo {
                     device_path: device_path.to_string(),
                     device_type: DeviceType::USBDrive,
-                    size_bytes: metadata.len(),
+                    size_bytes: identity.size_bytes.unwrap_or(metadata.len()),
                     sector_size: 512, // Standard for most USB drives
                     supports_trim,
                     supports_secure_erase,
                     supports_enhanced_secure_erase: false,
                     supports_crypto_erase: false, // Rare in USB drives
                     is_removable: true,
-                    vendor: "Unknown".to_string(),
-                    model: "Unknown USB Drive".to_string(),
-                    serial: "Unknown".to_string(),
+                    vendor: identity.vendor.unwrap_or_else(|| "Unknown".to_string()),
+                    model: identity.model.unwrap_or_else(|| "Unknown USB Drive".to_string()),
+                    serial: identity.serial.unwrap_or_else(|| "Unknown".to_string()),
+                    is_smr: false,
+                    is_hybrid: false,
+                    encryption_status: EncryptionStatus::Unknown,
+                    interface: ConnectionInterface::Unknown,
                 }
             }
             Err(e) => return Err(e),
         };
         
-        println!("✅ USB drive analysis complete: {} ({} bytes)", 
+        log::info!("✅ USB drive analysis complete: {} ({} bytes)", 
                 device_info.model, device_info.size_bytes);
         Ok(device_info)
     }
@@ -460,7 +504,7 @@ impl DeviceEraser for UsbEraser {
         algorithm: WipingAlgorithm,
         progress_callback: Arc<Mutex<WipingProgress>>,
     ) -> io::Result<()> {
-        println!("🚀 Starting USB drive erasure with algorithm: {:?}", algorithm);
+        log::info!("🚀 Starting USB drive erasure with algorithm: {:?}", algorithm);
         
         match algorithm {
             WipingAlgorithm::Random => self.single_pass_random(device_info, progress_callback),
@@ -475,42 +519,26 @@ impl DeviceEraser for UsbEraser {
             },
             _ => {
                 // Default to single-pass random for USB drives (preserves lifespan)
-                println!("ℹ️  Using single-pass random as default for USB drive");
+                log::info!("ℹ️  Using single-pass random as default for USB drive");
                 self.single_pass_random(device_info, progress_callback)
             }
         }
     }
     
-    fn verify_erasure(&self, device_info: &DeviceInfo) -> io::Result<bool> {
+    fn verify_erasure(&self, device_info: &DeviceInfo, expected: &[u8]) -> io::Result<bool> {
         if !self.verify_after_wipe {
             return Ok(true);
         }
         
-        println!("🔍 Verifying USB drive erasure...");
+        log::info!("🔍 Verifying USB drive erasure...");
         
-        let mut file = File::open(&device_info.device_path)?;
-        let mut buffer = vec![0u8; self.buffer_size];
-        let mut total_read = 0u64;
         // For USB drives, sample conservatively to avoid wear
-        let sample_size = std::cmp::min(device_info.size_bytes, 50 * 1024 * 1024); // Sample first 50MB
-        
-        while total_read < sample_size {
-            let bytes_read = std::io::Read::read(&mut file, &mut buffer)?;
-            if bytes_read == 0 {
-                break;
-            }
-            
-            // Check for non-zero bytes
-            if buffer[..bytes_read].iter().any(|&b| b != 0) {
-                println!("⚠️  Found non-zero data during USB drive verification");
-                return Ok(false);
-            }
-            
-            total_read += bytes_read as u64;
+        // Sample first 50MB
+        let verified = devices::region_reads_back(device_info, 50 * 1024 * 1024, self.buffer_size, expected, None)?;
+        if verified {
+            log::info!("✅ USB drive erasure verification passed");
         }
-        
-        println!("✅ USB drive erasure verification passed");
-        Ok(true)
+        Ok(verified)
     }
     
     fn get_recommended_algorithms(&self) -> Vec<WipingAlgorithm> {
@@ -532,4 +560,16 @@ impl DeviceEraser for UsbEraser {
             ]
         }
     }
+    
+    fn supported_algorithms(&self, _device_info: &DeviceInfo) -> Vec<WipingAlgorithm> {
+        vec![
+            WipingAlgorithm::NistClear,
+            WipingAlgorithm::Random,
+            WipingAlgorithm::Zeros,
+            WipingAlgorithm::Ones,
+            WipingAlgorithm::QuickFormat,
+            WipingAlgorithm::ThreePass,
+            WipingAlgorithm::FileSystemWipe,
+        ]
+    }
 }
\ No newline at end of file
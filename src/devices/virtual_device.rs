@@ -0,0 +1,149 @@
+//! File-backed virtual devices for testing and demos
+//!
+//! A regular file registered here (`--target-file <path>`) is analyzed as a hard drive
+//! the length of the file, so the full wipe, verify and certificate pipeline can run
+//! without a real disk anywhere near it.
+
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::sync::{LazyLock, Mutex};
+use sha2::{Digest, Sha256};
+use crate::advanced_wiper::{ConnectionInterface, DeviceInfo, DeviceType};
+
+/// Sector size a virtual device reports unless another one is given
+pub const DEFAULT_SECTOR_SIZE: u32 = 512;
+
+/// Registered virtual devices by path, with the sector size each one reports
+static VIRTUAL_DEVICES: LazyLock<Mutex<HashMap<String, u32>>> = LazyLock::new(Default::default);
+
+/// Treat the file at `path` as a block device from now on. Only regular files are
+/// accepted, so a typo can't turn a real device node into a "virtual" one.
+pub fn register(path: &str, sector_size: u32) -> io::Result<()> {
+    let metadata = fs::metadata(path)?;
+    if !metadata.is_file() {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput,
+            format!("{} is not a regular file", path)));
+    }
+    if !sector_size.is_power_of_two() || sector_size < 512 {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput,
+            format!("Sector size {} is not a power of two of at least 512", sector_size)));
+    }
+
+    log::info!("🧪 Using {} as a virtual device ({} bytes, {}-byte sectors)", path, metadata.len(), sector_size);
+    if let Ok(mut devices) = VIRTUAL_DEVICES.lock() {
+        devices.insert(path.to_string(), sector_size);
+    }
+    Ok(())
+}
+
+/// Paths of the registered virtual devices
+pub fn registered() -> Vec<String> {
+    VIRTUAL_DEVICES.lock().map(|devices| devices.keys().cloned().collect()).unwrap_or_default()
+}
+
+/// Sector size of the path if it is a registered virtual device
+pub fn sector_size_of(path: &str) -> Option<u32> {
+    VIRTUAL_DEVICES.lock().ok().and_then(|devices| devices.get(path).copied())
+}
+
+/// Describe a virtual device the way a drive analysis would. The serial is derived from
+/// the file's path, so certificates and selection files can still tell devices apart.
+pub fn analyze(path: &str, sector_size: u32) -> io::Result<DeviceInfo> {
+    let size_bytes = fs::metadata(path)?.len();
+    let canonical = fs::canonicalize(path).unwrap_or_else(|_| path.into());
+    let digest = Sha256::digest(canonical.to_string_lossy().as_bytes());
+
+    Ok(DeviceInfo {
+        device_path: path.to_string(),
+        device_type: DeviceType::HDD,
+        size_bytes,
+        sector_size,
+        supports_trim: false,
+        supports_secure_erase: false,
+        supports_enhanced_secure_erase: false,
+        supports_crypto_erase: false,
+        is_removable: false,
+        vendor: "Virtual".to_string(),
+        model: "File-backed virtual disk".to_string(),
+        serial: format!("VIRTUAL-{}", hex::encode_upper(&digest[..6])),
+        is_smr: false,
+        is_hybrid: false,
+        encryption_status: super::detect_encryption(path),
+        interface: ConnectionInterface::Virtual,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use crate::advanced_wiper::{ErrorPolicy, OverwritePattern, WipingAlgorithm, WipingProgress};
+    use crate::devices::DeviceFactory;
+
+    fn new_progress(algorithm: WipingAlgorithm, verify_interval: Option<u32>) -> Arc<Mutex<WipingProgress>> {
+        Arc::new(Mutex::new(WipingProgress {
+            algorithm,
+            current_pass: 0,
+            total_passes: 1,
+            bytes_processed: 0,
+            total_bytes: 0,
+            current_pattern: String::new(),
+            estimated_time_remaining: std::time::Duration::ZERO,
+            speed_mbps: 0.0,
+            errors: Vec::new(),
+            pass_timings: Vec::new(),
+            pass_started: None,
+            resume_from_pass: 0,
+            verify_interval,
+            last_pass_pattern: None,
+        }))
+    }
+
+    #[test]
+    fn test_virtual_device_wipe_and_verify() {
+        let path = std::env::temp_dir().join(format!("shredx_virtual_{}.img", std::process::id()));
+        fs::write(&path, vec![0xA5u8; 3 * 1024 * 1024]).unwrap();
+        let path = path.to_string_lossy().to_string();
+        register(&path, 4096).unwrap();
+
+        let (device_info, eraser) = DeviceFactory::analyze_and_create(&path, ErrorPolicy::default(), None).unwrap();
+        assert_eq!(device_info.size_bytes, 3 * 1024 * 1024);
+        assert_eq!(device_info.sector_size, 4096);
+        assert_eq!(device_info.interface, ConnectionInterface::Virtual);
+        assert!(device_info.serial.starts_with("VIRTUAL-"));
+
+        let progress = new_progress(WipingAlgorithm::HmgIs5Baseline, None);
+        eraser.erase_device(&device_info, WipingAlgorithm::HmgIs5Baseline, progress.clone()).unwrap();
+        assert!(crate::devices::verify_final_pass(eraser.as_ref(), &device_info, &progress).unwrap());
+        assert!(fs::read(&path).unwrap().iter().all(|&b| b == 0));
+        assert_eq!(progress.lock().unwrap().bytes_processed, device_info.size_bytes);
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_verify_interval_reads_passes_back() {
+        let path = std::env::temp_dir().join(format!("shredx_verify_{}.img", std::process::id()));
+        fs::write(&path, vec![0xA5u8; 1024 * 1024]).unwrap();
+        let path = path.to_string_lossy().to_string();
+        register(&path, 512).unwrap();
+        let (device_info, eraser) = DeviceFactory::analyze_and_create(&path, ErrorPolicy::default(), None).unwrap();
+
+        // Every pass of a multi-pass overwrite is read back and holds what was written
+        let algorithm = WipingAlgorithm::MultiPass { passes: 3, pattern: OverwritePattern::Alternating };
+        let progress = new_progress(algorithm.clone(), Some(1));
+        eraser.erase_device(&device_info, algorithm, progress.clone()).unwrap();
+        assert_eq!(progress.lock().unwrap().current_pass, 3);
+
+        // A pass that didn't stick fails as soon as it is read back
+        let progress = new_progress(WipingAlgorithm::ThreePass, Some(2));
+        progress.lock().unwrap().current_pass = 1;
+        crate::devices::verify_pass_if_due(&device_info, &[0x5A; 4096], &progress).unwrap();
+        progress.lock().unwrap().current_pass = 2;
+        let error = crate::devices::verify_pass_if_due(&device_info, &[0x5A; 4096], &progress).unwrap_err();
+        assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -70,14 +70,14 @@ impl SecureSanitizer {
             if drive_geometry.has_hpa {
                 match drive.unlock_hpa() {
                     Ok(true) => {
-                        println!("HPA unlocked successfully");
+                        log::info!("HPA unlocked successfully");
                         // Re-read geometry after HPA unlock
                         let new_identify = drive.identify_device()?;
                         actual_geometry = drive.parse_drive_geometry(&new_identify);
                     }
-                    Ok(false) => println!("No HPA detected or already unlocked"),
+                    Ok(false) => log::info!("No HPA detected or already unlocked"),
                     Err(e) => {
-                        println!("Warning: Failed to unlock HPA: {}", e);
+                        log::warn!("Warning: Failed to unlock HPA: {}", e);
                         return Err(WipeError {
                             code: WipeErrorCode::HPAUnlockFailed,
                             message: "Failed to unlock HPA for hidden area wipe".to_string(),
@@ -120,7 +120,7 @@ impl SecureSanitizer {
                 &request.id,
             )?;
 
-            println!("Pass {} completed in {:.2} seconds", 
+            log::info!("Pass {} completed in {:.2} seconds", 
                      pass_num + 1, 
                      pass_start_time.elapsed().as_secs_f64());
         }
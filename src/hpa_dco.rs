@@ -112,7 +112,7 @@ impl HpaDcoDetector {
         
         Ok(SimpleDriveInfo {
             user_capacity: basic_info.user_capacity / 512, // Convert to sectors
-            native_capacity: hpa_info.native_max_lba,
+            native_capacity: hpa_info.native_max_lba + 1, // Max LBA to sectors
             hpa_detected: hpa_info.present,
             dco_detected,
         })
@@ -120,20 +120,12 @@ impl HpaDcoDetector {
 
     /// Detect Host Protected Area (HPA)
     fn detect_hpa(&self, ata: &AtaInterface, identify_data: &crate::ata_commands::IdentifyDeviceData) -> io::Result<HpaInfo> {
-        let words = &identify_data.data;
-        
-        // Get user-addressable capacity from IDENTIFY DEVICE
-        let user_max_lba = if words[83] & 0x0400 != 0 {
-            // 48-bit addressing
-            ((words[103] as u64) << 48) | ((words[102] as u64) << 32) | 
-            ((words[101] as u64) << 16) | (words[100] as u64)
-        } else {
-            // 28-bit addressing
-            ((words[61] as u64) << 16) | (words[60] as u64)
-        };
+        // Highest user-addressable LBA from IDENTIFY DEVICE; READ NATIVE MAX reports an LBA
+        // too, not a sector count
+        let user_max_lba = identify_data.user_sectors().saturating_sub(1);
 
         // Get native capacity using READ NATIVE MAX ADDRESS
-        let native_max_lba = match ata.read_native_max_address(words[83] & 0x0400 != 0) {
+        let native_max_lba = match ata.read_native_max_address(identify_data.use_ext_commands()) {
             Ok(lba) => lba,
             Err(_) => {
                 // If command fails, assume no HPA
@@ -309,8 +301,7 @@ impl HpaDcoDetector {
         
         // Get current information
         let identify_data = ata.identify_device()?;
-        let words = &identify_data.data;
-        let use_ext = words[83] & 0x0400 != 0;
+        let use_ext = identify_data.use_ext_commands();
         
         // Get native max address
         let native_max_lba = ata.read_native_max_address(use_ext)?;
@@ -318,7 +309,7 @@ impl HpaDcoDetector {
         // Set max address to native capacity
         ata.set_max_address(native_max_lba, use_ext)?;
         
-        println!("✅ HPA removed. Drive capacity restored to {} sectors", native_max_lba);
+        log::info!("✅ HPA removed. Drive capacity restored to {} sectors", native_max_lba);
         Ok(())
     }
 
@@ -343,19 +334,19 @@ impl HpaDcoDetector {
         // 3. SECURITY ERASE UNIT
         // 4. Proper error handling and timeout management
         
-        println!("⚠️ SECURE ERASE NOT FULLY IMPLEMENTED");
-        println!("Real implementation would:");
-        println!("1. Set user password");
-        println!("2. Issue SECURITY ERASE PREPARE (if enhanced)");
-        println!("3. Issue SECURITY ERASE UNIT command");
-        println!("4. Monitor completion status");
+        log::warn!("⚠️ SECURE ERASE NOT FULLY IMPLEMENTED");
+        log::info!("Real implementation would:");
+        log::info!("1. Set user password");
+        log::info!("2. Issue SECURITY ERASE PREPARE (if enhanced)");
+        log::info!("3. Issue SECURITY ERASE UNIT command");
+        log::info!("4. Monitor completion status");
         
         Ok(())
     }
 
     /// Attempt to sanitize DCO areas (limited success - requires manufacturer tools)
     pub fn attempt_dco_sanitization(&self, drive_path: &str) -> io::Result<()> {
-        println!("🔍 Attempting DCO area sanitization...");
+        log::info!("🔍 Attempting DCO area sanitization...");
         
         // DCO removal requires manufacturer-specific tools in most cases
         // However, we can try some standard approaches
@@ -367,27 +358,27 @@ impl HpaDcoDetector {
         let features_word = identify_data.data[83];
         
         if features_word & 0x0800 != 0 {
-            println!("📋 Drive supports DCO feature set");
+            log::info!("📋 Drive supports DCO feature set");
             
             // Try to access DCO features (this may not work on modern drives)
-            println!("⚠️ DCO sanitization has limited effectiveness:");
-            println!("  • DCO removal typically requires manufacturer tools");
-            println!("  • Some enterprise drives may support DCO restore");
-            println!("  • Consumer drives rarely allow DCO modification");
-            println!("  • Physical destruction may be required for highest security");
+            log::warn!("⚠️ DCO sanitization has limited effectiveness:");
+            log::info!("  • DCO removal typically requires manufacturer tools");
+            log::info!("  • Some enterprise drives may support DCO restore");
+            log::info!("  • Consumer drives rarely allow DCO modification");
+            log::info!("  • Physical destruction may be required for highest security");
             
             // Attempt to get DCO information (may fail)
-            match ata.read_native_max_address(true) {
+            match ata.read_native_max_address(identify_data.use_ext_commands()) {
                 Ok(native_max) => {
-                    println!("📏 Native max address: {} sectors", native_max);
-                    println!("💡 Recommendation: Use hardware-level secure erase if supported");
+                    log::info!("📏 Native max address: {} sectors", native_max);
+                    log::info!("💡 Recommendation: Use hardware-level secure erase if supported");
                 }
                 Err(_) => {
-                    println!("❌ Unable to access native capacity information");
+                    log::error!("❌ Unable to access native capacity information");
                 }
             }
         } else {
-            println!("ℹ️ Drive does not support DCO feature set");
+            log::info!("ℹ️ Drive does not support DCO feature set");
         }
         
         Ok(())
@@ -395,29 +386,23 @@ impl HpaDcoDetector {
 
     /// Enhanced HPA removal with verification
     pub fn remove_hpa_thoroughly(&self, drive_path: &str) -> io::Result<bool> {
-        println!("🔧 Performing thorough HPA removal...");
+        log::info!("🔧 Performing thorough HPA removal...");
         
         let ata = AtaInterface::new(drive_path)?;
         
         // Get initial state
         let identify_data = ata.identify_device()?;
-        let words = &identify_data.data;
-        let use_ext = words[83] & 0x0400 != 0;
+        let use_ext = identify_data.use_ext_commands();
         
-        // Get current and native capacities
-        let current_max_lba = if use_ext {
-            ((words[103] as u64) << 48) | ((words[102] as u64) << 32) | 
-            ((words[101] as u64) << 16) | (words[100] as u64)
-        } else {
-            ((words[61] as u64) << 16) | (words[60] as u64)
-        };
+        // Get current and native max LBAs
+        let current_max_lba = identify_data.user_sectors().saturating_sub(1);
         
         let native_max_lba = ata.read_native_max_address(use_ext)?;
         
         if native_max_lba > current_max_lba {
-            println!("🚨 HPA detected: Current={} sectors, Native={} sectors", 
+            log::info!("🚨 HPA detected: Current={} sectors, Native={} sectors", 
                     current_max_lba, native_max_lba);
-            println!("📏 Hidden capacity: {:.2} MB", 
+            log::info!("📏 Hidden capacity: {:.2} MB", 
                     (native_max_lba - current_max_lba) as f64 * 512.0 / (1024.0 * 1024.0));
             
             // Set max address to native capacity
@@ -425,85 +410,79 @@ impl HpaDcoDetector {
             
             // Verify the change
             let verify_data = ata.identify_device()?;
-            let verify_words = &verify_data.data;
-            let new_current_max = if use_ext {
-                ((verify_words[103] as u64) << 48) | ((verify_words[102] as u64) << 32) | 
-                ((verify_words[101] as u64) << 16) | (verify_words[100] as u64)
-            } else {
-                ((verify_words[61] as u64) << 16) | (verify_words[60] as u64)
-            };
+            let new_current_max = verify_data.user_sectors().saturating_sub(1);
             
             if new_current_max == native_max_lba {
-                println!("✅ HPA successfully removed. Full capacity restored: {} sectors", native_max_lba);
+                log::info!("✅ HPA successfully removed. Full capacity restored: {} sectors", native_max_lba);
                 return Ok(true);
             } else {
-                println!("⚠️ HPA removal incomplete. Current: {}, Expected: {}", 
+                log::warn!("⚠️ HPA removal incomplete. Current: {}, Expected: {}", 
                         new_current_max, native_max_lba);
                 return Ok(false);
             }
         } else {
-            println!("ℹ️ No HPA detected or already removed");
+            log::info!("ℹ️ No HPA detected or already removed");
             return Ok(true);
         }
     }
 
     /// Perform comprehensive sanitization including HPA/DCO areas
     pub fn comprehensive_clean(&self, device_path: &str, method: &SanitizationMethod) -> io::Result<()> {
-        println!("🚀 Starting comprehensive drive sanitization...");
+        log::info!("🚀 Starting comprehensive drive sanitization...");
         
         // 1. Detect HPA/DCO before sanitization
         let drive_info = self.get_comprehensive_drive_info(device_path)?;
         
         // 2. Remove HPA thoroughly to access hidden areas
         if drive_info.hpa_detected {
-            println!("🔧 Performing thorough HPA removal...");
+            log::info!("🔧 Performing thorough HPA removal...");
             match self.remove_hpa_thoroughly(device_path) {
-                Ok(true) => println!("✅ HPA removal completed successfully"),
-                Ok(false) => println!("⚠️ HPA removal incomplete - some areas may remain hidden"),
+                Ok(true) => log::info!("✅ HPA removal completed successfully"),
+                Ok(false) => log::warn!("⚠️ HPA removal incomplete - some areas may remain hidden"),
                 Err(e) => {
-                    println!("❌ HPA removal failed: {}", e);
-                    println!("⚠️ Continuing with sanitization of accessible areas only");
+                    log::error!("❌ HPA removal failed: {}", e);
+                    log::warn!("⚠️ Continuing with sanitization of accessible areas only");
                 }
             }
         }
         
         // 3. Sanitize the entire drive using native capacity
-        println!("🔄 Sanitizing drive with full native capacity...");
+        log::info!("🔄 Sanitizing drive with full native capacity...");
         if let Err(e) = crate::sanitization::sanitize_device_with_size(
             device_path, method, drive_info.native_capacity
         ) {
-            eprintln!("❌ Sanitization failed: {}", e);
+            log::error!("❌ Sanitization failed: {}", e);
             return Err(e);
         }
         
         // 4. Attempt to handle DCO areas
         if drive_info.dco_detected {
-            println!("🔍 Attempting DCO area sanitization...");
+            log::info!("🔍 Attempting DCO area sanitization...");
             if let Err(e) = self.attempt_dco_sanitization(device_path) {
-                println!("⚠️ DCO sanitization attempt failed: {}", e);
+                log::warn!("⚠️ DCO sanitization attempt failed: {}", e);
             }
         }
         
         // 5. Final verification
-        println!("🔍 Performing final verification...");
+        log::info!("🔍 Performing final verification...");
         let final_info = self.get_comprehensive_drive_info(device_path)?;
         if final_info.hpa_detected {
-            println!("⚠️ Warning: HPA still detected after removal attempt");
-            println!("💡 Consider using manufacturer tools or physical destruction for complete security");
+            log::warn!("⚠️ Warning: HPA still detected after removal attempt");
+            log::info!("💡 Consider using manufacturer tools or physical destruction for complete security");
         } else {
-            println!("✅ HPA verification passed - no hidden areas detected");
+            log::info!("✅ HPA verification passed - no hidden areas detected");
         }
         
         if final_info.dco_detected {
-            println!("⚠️ Warning: DCO still detected - limited sanitization effectiveness");
-            println!("💡 DCO removal requires manufacturer-specific tools or hardware destruction");
+            log::warn!("⚠️ Warning: DCO still detected - limited sanitization effectiveness");
+            log::info!("💡 DCO removal requires manufacturer-specific tools or hardware destruction");
         }
         
-        println!("🎉 Comprehensive sanitization completed");
-        println!("📊 Summary:");
-        println!("  • Sanitized capacity: {:.2} GB", drive_info.native_capacity as f64 * 512.0 / (1024.0 * 1024.0 * 1024.0));
-        println!("  • HPA handled: {}", if !final_info.hpa_detected { "✅ Yes" } else { "⚠️ Partial" });
-        println!("  • DCO handled: {}", if !final_info.dco_detected { "✅ Yes" } else { "⚠️ Limited" });
+        log::info!("🎉 Comprehensive sanitization completed");
+        log::info!("📊 Summary:");
+        log::info!("  • Sanitized capacity: {:.2} GB", drive_info.native_capacity as f64 * 512.0 / (1024.0 * 1024.0 * 1024.0));
+        log::info!("  • HPA handled: {}", if !final_info.hpa_detected { "✅ Yes" } else { "⚠️ Partial" });
+        log::info!("  • DCO handled: {}", if !final_info.dco_detected { "✅ Yes" } else { "⚠️ Limited" });
         
         Ok(())
     }
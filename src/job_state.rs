@@ -0,0 +1,62 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io;
+
+const JOB_STATE_FILE: &str = "job_state.json";
+
+/// How far one drive's wipe had got, saved while it runs so that a wipe cut short by a
+/// crash or a closed window can be offered for resuming on the next launch
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JobState {
+    pub drive_name: String,
+    pub drive_path: String,
+    /// Identifies the drive on the next launch, since paths can change between boots
+    #[serde(default)]
+    pub serial: Option<String>,
+    /// Method that was running, by its canonical name
+    pub algorithm: String,
+    /// Passes written in full; a resumed wipe starts again with the pass after these
+    pub completed_passes: u32,
+    pub total_passes: u32,
+    pub started_at: chrono::DateTime<chrono::Utc>,
+    /// Last time the state was saved, i.e. roughly when the wipe stopped
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl JobState {
+    /// Whether this job was running on the attached drive with the given serial. Paths
+    /// are reassigned when drives are swapped, so a job is only resumed on the drive
+    /// whose serial it recorded; with either serial unknown the wipe starts from pass 1.
+    pub fn matches(&self, serial: Option<&str>) -> bool {
+        match (self.serial.as_deref(), serial) {
+            (Some(expected), Some(serial)) => expected.eq_ignore_ascii_case(serial),
+            _ => false,
+        }
+    }
+
+    /// Where the wipe stopped, for the resume prompt
+    pub fn describe(&self) -> String {
+        format!("{} ({}) - {}: {} of {} passes done, last seen {}", self.drive_name, self.drive_path,
+            self.algorithm, self.completed_passes, self.total_passes,
+            self.updated_at.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M"))
+    }
+}
+
+/// Jobs left unfinished by the last session
+pub fn load() -> Vec<JobState> {
+    fs::read_to_string(JOB_STATE_FILE).ok()
+        .and_then(|state| serde_json::from_str(&state).ok())
+        .unwrap_or_default()
+}
+
+/// Replace the saved jobs; with none left the file is removed
+pub fn save(jobs: &[JobState]) -> io::Result<()> {
+    if jobs.is_empty() {
+        return match fs::remove_file(JOB_STATE_FILE) {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e),
+            _ => Ok(()),
+        };
+    }
+    let state = serde_json::to_string_pretty(jobs).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    fs::write(JOB_STATE_FILE, state)
+}
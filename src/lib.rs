@@ -1,4 +1,5 @@
 pub mod sanitization;
+pub mod certificate;
 pub mod advanced_wiper;
 pub mod ata_commands;
 pub mod hpa_dco;
@@ -0,0 +1,95 @@
+use std::cell::RefCell;
+use std::fmt::Write as _;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use tracing::field::{Field, Visit};
+use tracing::{Event, Subscriber};
+use tracing_appender::non_blocking::WorkerGuard;
+use tracing_subscriber::filter::LevelFilter;
+use tracing_subscriber::fmt;
+use tracing_subscriber::layer::{Context, Layer};
+use tracing_subscriber::prelude::*;
+
+/// Levels accepted by `--log-level` and the Settings tab, least verbose first
+pub const LOG_LEVELS: [&str; 6] = ["off", "error", "warn", "info", "debug", "trace"];
+
+/// Most lines kept per job, so a wipe that logs in a loop can't exhaust memory
+const MAX_JOB_LOG_LINES: usize = 20_000;
+
+/// Messages logged while one drive was being wiped, one line each
+pub type JobLog = Arc<Mutex<Vec<String>>>;
+
+thread_local! {
+    static CURRENT_JOB_LOG: RefCell<Option<JobLog>> = const { RefCell::new(None) };
+}
+
+/// Collect everything this thread logs at info level or above into `job_log`, whatever
+/// the console level is. Work the thread hands to other threads is not captured.
+pub fn capture_on_this_thread(job_log: JobLog) {
+    CURRENT_JOB_LOG.with(|current| *current.borrow_mut() = Some(job_log));
+}
+
+/// Appends events to the job log of the thread they happen on, if it has one
+struct JobLogLayer;
+
+impl<S: Subscriber> Layer<S> for JobLogLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        CURRENT_JOB_LOG.with(|current| {
+            let Some(job_log) = current.borrow().clone() else {
+                return;
+            };
+            let mut message = MessageVisitor(String::new());
+            event.record(&mut message);
+            if let Ok(mut lines) = job_log.lock() {
+                if lines.len() < MAX_JOB_LOG_LINES {
+                    lines.push(format!("{} {:>5} {}", chrono::Utc::now().format("%H:%M:%S"),
+                        event.metadata().level(), message.0.trim_end()));
+                } else if lines.len() == MAX_JOB_LOG_LINES {
+                    lines.push(format!("... log truncated after {} lines", MAX_JOB_LOG_LINES));
+                }
+            }
+        });
+    }
+}
+
+/// Pulls the formatted message out of an event
+struct MessageVisitor(String);
+
+impl Visit for MessageVisitor {
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        if field.name() == "message" {
+            let _ = write!(self.0, "{:?}", value);
+        }
+    }
+}
+
+/// Parse a level name, e.g. from `--log-level`
+pub fn parse_level(level: &str) -> Option<LevelFilter> {
+    level.trim().parse().ok()
+}
+
+/// Send `log` output to stderr and, when `log_dir` is given, to a file there that rotates
+/// daily. The returned guard flushes the file when dropped, so keep it until exit.
+pub fn init(level: LevelFilter, log_dir: Option<&Path>) -> Option<WorkerGuard> {
+    let (file_writer, guard) = match log_dir.map(std::fs::create_dir_all) {
+        Some(Ok(())) => {
+            let appender = tracing_appender::rolling::daily(log_dir?, "shredx.log");
+            let (writer, guard) = tracing_appender::non_blocking(appender);
+            (Some(writer), Some(guard))
+        }
+        Some(Err(e)) => {
+            eprintln!("⚠️  Cannot create log directory, logging to the console only: {}", e);
+            (None, None)
+        }
+        None => (None, None),
+    };
+
+    // Installing the subscriber also forwards the `log` macros used throughout the crate.
+    // The level only applies to the console and file; job logs always get info and above.
+    tracing_subscriber::registry()
+        .with(fmt::layer().with_writer(std::io::stderr).with_filter(level))
+        .with(file_writer.map(|writer| fmt::layer().with_ansi(false).with_writer(writer).with_filter(level)))
+        .with(JobLogLayer.with_filter(LevelFilter::INFO))
+        .init();
+    guard
+}
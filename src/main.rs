@@ -1,6 +1,8 @@
 use eframe::egui;
-use std::time::Duration;
+use std::collections::{HashMap, VecDeque};
+use std::time::{Duration, Instant};
 use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
 use chrono;
 
 // Platform-specific imports (currently unused)
@@ -23,19 +25,30 @@ mod config;
 mod app_config;
 mod server_client;
 mod certificate;
+mod report;
+mod notification;
+mod batch;
+mod stats;
+mod logging;
+mod job_state;
 
 #[cfg(feature = "server")]
 mod server;
 
-use sanitization::{DataSanitizer, SanitizationProgress};
-use advanced_wiper::{AdvancedWiper, WipingAlgorithm, WipingProgress, DeviceInfo};
-use ui::{SecureTheme, TabWidget, DriveTableWidget, DriveInfo, AdvancedOptionsWidget, show_logo, auth::AuthWidget};
+use sanitization::{DataSanitizer, SanitizationMethod, SanitizationProgress, FREE_SPACE_PASSES};
+use advanced_wiper::{AdvancedWiper, WipingAlgorithm, WipingProgress, DeviceInfo, DeviceType, DeviceHashes, EncryptionStatus, SmartSnapshots, ThroughputBenchmark, DEVICE_REMOVED, WIPE_STOPPED, MAX_OVERWRITE_PASSES};
+use ui::{SecureTheme, TabWidget, DriveTableWidget, DriveRowAction, DriveInfo, AdvancedOptionsWidget, format_bytes, show_logo, auth::AuthWidget};
 use platform::{get_system_drives, get_device_path_for_sanitization};
 use auth::{AuthSystem, AuthUI, AuthPage};
 use config::AppConfig;
-use app_config::AppConfig as ServerConfig;
-use server_client::ServerClient;
-use certificate::{CertificateGenerator, SanitizationCertificate, DeviceCertificateInfo, SanitizationInfo, UserInfo};
+use stats::LifetimeStats;
+use app_config::{AppConfig as ServerConfig, SyncConflictPolicy};
+use server_client::{LoginRequest, ServerClient, ProxyError};
+use certificate::{CertificateFormat, CertificateGenerator, SanitizationCertificate, DeviceCertificateInfo, SanitizationInfo, UserInfo};
+use report::{ReportFormat, SanitizationReport};
+use batch::SelectedDrive;
+use job_state::JobState;
+use base64::{Engine as _, engine::general_purpose};
 
 #[derive(Debug, Clone)]
 struct DiskInfo {
@@ -48,8 +61,19 @@ struct DiskInfo {
     used_space: u64,
     label: String,
     selected: bool,
+    /// Set for network shares and virtual disks, which are only wiped with the override on
+    wipe_guard: Option<String>,
 }
 
+/// Smallest benchmark worth reporting; anything less mostly measures the drive's cache
+const MIN_BENCHMARK_BYTES: u64 = 64 * 1024 * 1024;
+
+/// How often wipe progress is recomputed and the UI repainted while drives are wiping
+const PROGRESS_REFRESH_INTERVAL: Duration = Duration::from_millis(250);
+
+/// How often running wipes' job state is saved for resuming after a crash
+const JOB_STATE_SAVE_INTERVAL: Duration = Duration::from_secs(5);
+
 struct HDDApp {
     disks: Vec<DiskInfo>,
     sanitizer: DataSanitizer,
@@ -61,7 +85,36 @@ struct HDDApp {
     advanced_wiper: AdvancedWiper,
     selected_algorithm: WipingAlgorithm,
     device_analysis: Option<DeviceInfo>,
-    wipe_progress: Arc<Mutex<WipingProgress>>,
+    // Progress reported by each drive's wipe thread, keyed by drive table index
+    drive_progress: HashMap<usize, Arc<Mutex<WipingProgress>>>,
+    // Optional before/after device hashes, keyed the same way
+    drive_hashes: HashMap<usize, Arc<Mutex<DeviceHashes>>>,
+    drive_smart: HashMap<usize, Arc<Mutex<SmartSnapshots>>>,
+    // Raw IDENTIFY data each wipe thread read from its drive, for the certificate
+    drive_identify: HashMap<usize, Arc<Mutex<Option<Vec<u8>>>>>,
+    // What each drive's wipe thread logged, for the certificate appendix
+    drive_logs: HashMap<usize, logging::JobLog>,
+    // Benchmarks started from the row menu; `None` until the benchmark thread finishes
+    drive_benchmarks: HashMap<usize, Arc<Mutex<Option<Result<ThroughputBenchmark, String>>>>>,
+    // Hardware details found by each wipe thread's device analysis
+    drive_device_info: HashMap<usize, Arc<Mutex<Option<DeviceInfo>>>>,
+    // Running wipe threads; each returns whether its wipe actually succeeded
+    drive_threads: HashMap<usize, JoinHandle<Result<(), String>>>,
+    // Outcome of each finished wipe thread, which is what marks a drive complete
+    drive_results: HashMap<usize, Result<(), String>>,
+    // (path, name, index) of selected drives waiting for a free slot under `max_concurrent_wipes`
+    wipe_queue: VecDeque<(String, String, usize)>,
+    // When wipe progress was last recomputed; throttled to PROGRESS_REFRESH_INTERVAL
+    last_progress_refresh: Option<Instant>,
+    // How far each wipe has got, kept on disk so a crash leaves something to resume;
+    // wipes stopped at shutdown or by a removed device stay until the next launch
+    job_states: HashMap<usize, JobState>,
+    last_job_state_save: Option<Instant>,
+    // Jobs the last session left unfinished, until they are resumed or discarded
+    interrupted_jobs: Vec<JobState>,
+    resume_prompt: bool,
+    // (method, first pass to write) of each drive being resumed, taken when its wipe starts
+    drive_resume: HashMap<usize, (WipingAlgorithm, u32)>,
     
     // New UI Components
     tab_widget: TabWidget,
@@ -78,33 +131,55 @@ struct HDDApp {
     config: AppConfig,
     server_config: ServerConfig,
     server_client: Option<ServerClient>,
+    lifetime_stats: LifetimeStats,
     
     // Certificate Management
     certificate_generator: CertificateGenerator,
     certificates: Vec<SanitizationCertificate>,
+    // Certificates the server reports as revoked, by certificate id, with the reason;
+    // filled by the sync at login
+    revoked_certificates: Arc<Mutex<HashMap<String, String>>>,
+    certificate_search: String,
+    certificate_from: String,     // YYYY-MM-DD, empty for no lower bound
+    certificate_to: String,       // YYYY-MM-DD, empty for no upper bound
     current_sanitization_start: Option<chrono::DateTime<chrono::Utc>>,
+    operator_signature: String,
+    witness: String,
+    // Who runs the wipe when nobody is signed in; required before a wipe can start
+    operator_name: String,
+    operator_id: String,
+    // (username, user id) of the operator captured when the running wipe started
+    wipe_operator: Option<(String, String)>,
+    
+    // Reporting
+    report_format: ReportFormat,
+    // Where the drive selection is exported to and imported from (.json or .csv)
+    selection_file: String,
+    // Batch job to load (.json or .csv) and the per-drive methods it set, keyed by drive
+    // table index; drives without an entry run the selected method
+    job_file: String,
+    drive_algorithms: HashMap<usize, WipingAlgorithm>,
+    // Serials of drives that haven't been analyzed, read in the background for matching
+    // selections and jobs; `None` until the probe finishes, then `Some(None)` if unreadable
+    drive_serials: HashMap<usize, Arc<Mutex<Option<Option<String>>>>>,
+    // Lets network shares, virtual disks and other guarded targets be wiped; deliberately
+    // not saved, so the override never outlives the session it was turned on in
+    allow_guarded_targets: bool,
+    // The window was asked to close while wipes were running and is waiting for an answer
+    close_prompt: bool,
+    // Wipes were told to stop after their current pass; the window closes once they have
+    closing_after_pass: bool,
 }
 
 impl HDDApp {
     fn new() -> Self {
-        let initial_progress = WipingProgress {
-            algorithm: WipingAlgorithm::NistClear,
-            current_pass: 0,
-            total_passes: 1,
-            bytes_processed: 0,
-            total_bytes: 0,
-            current_pattern: "Ready".to_string(),
-            estimated_time_remaining: Duration::from_secs(0),
-            speed_mbps: 0.0,
-        };
-        
         let config = AppConfig::load();
         let server_config = ServerConfig::load();
-        let certificate_generator = CertificateGenerator::new();
+        let certificate_generator = CertificateGenerator::new(config.certificates_dir());
         
         // Load existing certificates
         let certificates = certificate_generator.load_certificates().unwrap_or_else(|e| {
-            eprintln!("Warning: Could not load certificates: {}", e);
+            log::warn!("Warning: Could not load certificates: {}", e);
             Vec::new()
         });
         
@@ -118,7 +193,22 @@ impl HDDApp {
             advanced_wiper: AdvancedWiper::new(),
             selected_algorithm: WipingAlgorithm::NistClear,
             device_analysis: None,
-            wipe_progress: Arc::new(Mutex::new(initial_progress)),
+            drive_progress: HashMap::new(),
+            drive_hashes: HashMap::new(),
+            drive_logs: HashMap::new(),
+            drive_benchmarks: HashMap::new(),
+            drive_smart: HashMap::new(),
+            drive_identify: HashMap::new(),
+            drive_device_info: HashMap::new(),
+            drive_threads: HashMap::new(),
+            drive_results: HashMap::new(),
+            wipe_queue: VecDeque::new(),
+            last_progress_refresh: None,
+            job_states: HashMap::new(),
+            last_job_state_save: None,
+            interrupted_jobs: job_state::load(),
+            resume_prompt: false,
+            drive_resume: HashMap::new(),
             
             tab_widget: TabWidget::new(),
             drive_table: DriveTableWidget::new(),
@@ -130,18 +220,47 @@ impl HDDApp {
             is_authenticated: false,
             
             config: config.clone(),
+            lifetime_stats: LifetimeStats::load(&config.stats_file()),
             server_config: server_config.clone(),
             server_client: if server_config.is_server_enabled() {
-                Some(ServerClient::new(server_config.server_url.clone()))
+                match ServerClient::from_config(&server_config) {
+                    Ok(client) => Some(client),
+                    Err(e) => {
+                        // Never fall back to an unpinned client, that would silently drop the MITM protection
+                        log::error!("Error: Invalid proxy or TLS pinning configuration, server sync disabled: {}", e);
+                        None
+                    }
+                }
             } else {
                 None
             },
             
             certificate_generator,
             certificates,
+            revoked_certificates: Arc::new(Mutex::new(HashMap::new())),
+            certificate_search: String::new(),
+            certificate_from: String::new(),
+            certificate_to: String::new(),
             current_sanitization_start: None,
+            operator_signature: String::new(),
+            witness: String::new(),
+            operator_name: String::new(),
+            operator_id: String::new(),
+            wipe_operator: None,
+            
+            report_format: ReportFormat::Text,
+            selection_file: "drive_selection.json".to_string(),
+            job_file: "batch_job.json".to_string(),
+            drive_algorithms: HashMap::new(),
+            drive_serials: HashMap::new(),
+            allow_guarded_targets: false,
+            close_prompt: false,
+            closing_after_pass: false,
         };
         
+        app.restore_selections();
+        app.resume_prompt = !app.interrupted_jobs.is_empty();
+        
         // Initialize authentication widget
         app.auth_widget.initialize(app.server_config.is_server_enabled(), &app.server_config.server_url);
         
@@ -149,9 +268,228 @@ impl HDDApp {
         app
     }
 
+    /// Bring back the eraser method from the last session
+    fn restore_selections(&mut self) {
+        if let Some(ref name) = self.config.last_algorithm {
+            match name.parse::<WipingAlgorithm>() {
+                Ok(algorithm) => {
+                    self.advanced_options.algorithm = algorithm.clone();
+                    self.selected_algorithm = algorithm;
+                }
+                Err(e) => log::warn!("⚠️  Ignoring the remembered eraser method: {}", e),
+            }
+        }
+    }
+    
+    /// Save the eraser method whenever the operator changes it
+    fn remember_selections(&mut self) {
+        let algorithm = self.advanced_options.eraser_method();
+        if self.config.last_algorithm.as_ref() == Some(&algorithm) {
+            return;
+        }
+        self.config.last_algorithm = Some(algorithm);
+        if let Err(e) = self.config.save() {
+            log::warn!("⚠️  Could not remember the selected method: {}", e);
+        }
+    }
+    
+    /// Certificate formats Settings writes on completion, for reports and the summary
+    fn certificate_formats_label(&self) -> String {
+        let formats: Vec<&str> = self.config.certificate_formats.iter().map(|format| format.label()).collect();
+        if formats.is_empty() { "None".to_string() } else { formats.join(", ") }
+    }
+    
+    /// Media type found by the drive's hardware analysis, if it ran
+    fn drive_media(&self, drive_index: usize) -> Option<DeviceType> {
+        self.drive_device_info.get(&drive_index)
+            .and_then(|info| info.lock().ok().and_then(|info| info.as_ref().map(|info| info.device_type.clone())))
+    }
+
+    /// Serial number of a drive as far as it's known without touching the hardware: from its
+    /// analysis or a finished background probe. The outer `None` means it isn't known yet.
+    fn known_serial(&self, drive_index: usize) -> Option<Option<String>> {
+        let analyzed = self.drive_device_info.get(&drive_index)
+            .and_then(|info| info.lock().ok().and_then(|info| info.clone()));
+        if let Some(device_info) = analyzed {
+            return Some(Some(device_info.serial).filter(|serial| !serial.is_empty() && serial != "Unknown"));
+        }
+        self.drive_serials.get(&drive_index).and_then(|probe| probe.lock().ok().and_then(|serial| serial.clone()))
+    }
+    
+    /// Serials of every attached drive, or `None` while some are still being read; drives
+    /// are probed on a background thread so the UI never waits on the hardware
+    fn drive_serials(&mut self) -> Option<Vec<Option<String>>> {
+        let serials: Vec<Option<Option<String>>> = (0..self.drive_table.drives.len())
+            .map(|index| self.known_serial(index))
+            .collect();
+        if serials.iter().all(Option::is_some) {
+            return Some(serials.into_iter().flatten().collect());
+        }
+        
+        for index in (0..serials.len()).filter(|index| serials[*index].is_none()) {
+            if self.drive_serials.contains_key(&index) {
+                continue;
+            }
+            let device_path = Self::device_path(&self.drive_table.drives[index].path);
+            let error_policy = self.advanced_options.error_policy;
+            let thermal_limit = self.config.thermal_limit();
+            let probe = Arc::new(Mutex::new(None));
+            self.drive_serials.insert(index, Arc::clone(&probe));
+            std::thread::spawn(move || {
+                let serial = devices::DeviceFactory::cached_or_analyze(&device_path, error_policy, thermal_limit).ok()
+                    .map(|(device_info, _)| device_info.serial)
+                    .filter(|serial| !serial.is_empty() && serial != "Unknown");
+                if let Ok(mut probe) = probe.lock() {
+                    *probe = Some(serial);
+                }
+            });
+        }
+        self.last_error_message = Some("⏳ Reading drive serial numbers - try again in a moment".to_string());
+        None
+    }
+    
+    fn export_selection(&mut self) {
+        let Some(serials) = self.drive_serials() else {
+            return;
+        };
+        let selected: Vec<SelectedDrive> = self.drive_table.drives.iter().zip(serials)
+            .filter(|(drive, _)| drive.selected)
+            .map(|(drive, serial)| SelectedDrive {
+                name: drive.name.clone(),
+                path: drive.path.clone(),
+                size_bytes: drive.size_bytes,
+                serial,
+            })
+            .collect();
+        if selected.is_empty() {
+            self.last_error_message = Some("❌ No drives selected to export".to_string());
+            return;
+        }
+        
+        self.last_error_message = Some(match batch::save_selection(std::path::Path::new(&self.selection_file), &selected) {
+            Ok(()) => format!("✅ Exported {} drive(s) to {}", selected.len(), self.selection_file),
+            Err(e) => format!("❌ Failed to export the selection: {}", e),
+        });
+    }
+    
+    /// Select the attached drives listed in the selection file, matching by serial
+    fn import_selection(&mut self) {
+        let listed = match batch::load_selection(std::path::Path::new(&self.selection_file)) {
+            Ok(listed) => listed,
+            Err(e) => {
+                self.last_error_message = Some(format!("❌ Failed to import {}: {}", self.selection_file, e));
+                return;
+            }
+        };
+        
+        let serials: Vec<Option<String>> = if listed.iter().any(|entry| entry.serial.is_some()) {
+            let Some(serials) = self.drive_serials() else {
+                return;
+            };
+            serials
+        } else {
+            vec![None; self.drive_table.drives.len()]
+        };
+        let mut found = 0;
+        for (drive, serial) in self.drive_table.drives.iter_mut().zip(&serials) {
+            drive.selected = listed.iter().any(|entry| entry.matches(&drive.path, serial.as_deref()));
+            if drive.selected {
+                found += 1;
+            }
+        }
+        self.drive_table.select_all = false;
+        
+        self.last_error_message = Some(if found < listed.len() {
+            format!("⚠️  Selected {} of the {} drive(s) in {}; the rest are not attached", found, listed.len(), self.selection_file)
+        } else {
+            format!("✅ Selected {} drive(s) from {}", found, self.selection_file)
+        });
+    }
+    
+    /// Method to run on a drive: its batch job entry if it has one, otherwise the selected method
+    fn drive_algorithm(&self, drive_index: usize) -> WipingAlgorithm {
+        self.drive_algorithms.get(&drive_index).cloned().unwrap_or_else(|| self.selected_algorithm.clone())
+    }
+    
+    /// Load a batch job, selecting its drives and setting each one's method. Nothing is
+    /// applied unless every method is known and every listed drive is attached.
+    fn import_job(&mut self) {
+        let entries = match batch::load_job(std::path::Path::new(&self.job_file)) {
+            Ok(entries) => entries,
+            Err(e) => {
+                self.last_error_message = Some(format!("❌ Failed to load {}: {}", self.job_file, e));
+                return;
+            }
+        };
+        if entries.is_empty() {
+            self.last_error_message = Some(format!("❌ {} lists no drives", self.job_file));
+            return;
+        }
+        
+        let serials: Vec<Option<String>> = if entries.iter().any(|entry| entry.serial.is_some()) {
+            let Some(serials) = self.drive_serials() else {
+                return;
+            };
+            serials
+        } else {
+            vec![None; self.drive_table.drives.len()]
+        };
+        
+        let mut problems = Vec::new();
+        let mut assignments = HashMap::new();
+        for entry in &entries {
+            let algorithm = match entry.algorithm.parse::<WipingAlgorithm>() {
+                Ok(algorithm) => algorithm,
+                Err(e) => {
+                    problems.push(format!("{}: {}", entry.describe(), e));
+                    continue;
+                }
+            };
+            let attached = self.drive_table.drives.iter().zip(&serials)
+                .position(|(drive, serial)| entry.matches(&drive.path, serial.as_deref()));
+            match attached {
+                Some(index) if assignments.contains_key(&index) => {
+                    problems.push(format!("{} is listed more than once", entry.describe()));
+                }
+                Some(index) => {
+                    assignments.insert(index, algorithm);
+                }
+                None => problems.push(format!("{} is not attached", entry.describe())),
+            }
+        }
+        if !problems.is_empty() {
+            self.last_error_message = Some(format!("❌ Batch job not loaded: {}", problems.join("; ")));
+            return;
+        }
+        
+        for (index, drive) in self.drive_table.drives.iter_mut().enumerate() {
+            drive.selected = assignments.contains_key(&index);
+            if let Some(algorithm) = assignments.get(&index) {
+                drive.status = format!("Job: {}", algorithm);
+            }
+        }
+        self.drive_table.select_all = false;
+        log::info!("📋 Loaded batch job {} for {} drive(s)", self.job_file, assignments.len());
+        self.last_error_message = Some(format!("✅ Batch job loaded: {} drive(s) selected with their methods", assignments.len()));
+        self.drive_algorithms = assignments;
+    }
+    
+    /// Go back to running the selected method on every drive
+    fn clear_job(&mut self) {
+        for index in self.drive_algorithms.keys() {
+            if let Some(drive) = self.drive_table.drives.get_mut(*index) {
+                drive.status = "Ready".to_string();
+            }
+        }
+        self.drive_algorithms.clear();
+    }
+    
     fn refresh_disks(&mut self) {
         self.disks.clear();
         self.drive_table.drives.clear();
+        // Job methods are keyed by table index, which a refresh can reorder
+        self.drive_algorithms.clear();
+        self.drive_serials.clear();
         
         // Use cross-platform drive detection
         match get_system_drives() {
@@ -168,84 +506,99 @@ impl HDDApp {
                         used_space: platform_drive.total_space.saturating_sub(platform_drive.free_space),
                         label: platform_drive.label.clone(),
                         selected: false,
+                        wipe_guard: platform_drive.wipe_guard.clone(),
                     };
                     
                     // Add to internal list
                     self.disks.push(disk_info.clone());
                     
                     // Add to drive table widget
-                    let drive_ui_info = DriveInfo::new(
+                    let mut drive_ui_info = DriveInfo::new(
                         platform_drive.label,
                         platform_drive.path,
-                        Self::format_bytes(platform_drive.total_space),
-                        Self::format_bytes(platform_drive.total_space.saturating_sub(platform_drive.free_space)),
+                        platform_drive.total_space,
+                        platform_drive.total_space.saturating_sub(platform_drive.free_space),
                     );
+                    if let Some(reason) = &platform_drive.wipe_guard {
+                        drive_ui_info.status = format!("⚠️ Not wiped: {}", reason);
+                    }
                     self.drive_table.add_drive(drive_ui_info);
                 }
             }
             Err(e) => {
-                println!("Error getting system drives: {}", e);
+                log::error!("Error getting system drives: {}", e);
             }
         }
-    }
-
-    // Cross-platform disk info is now handled by the platform module
-
-    fn get_detailed_drive_info(&self, drive_letter: &str) -> (String, bool) {
-        use ata_commands::AtaInterface;
-        
-        let drive_num = (drive_letter.chars().next().unwrap() as u8).saturating_sub(b'A');
-        let physical_drive_path = format!(r"\\.\PhysicalDrive{}", drive_num);
         
-        match AtaInterface::new(&physical_drive_path) {
-            Ok(ata) => {
-                match ata.identify_device() {
-                    Ok(identify_data) => {
-                        let drive_info = ata.parse_identify_data(&identify_data);
-                        
-                        let model_lower = drive_info.model.to_lowercase();
-                        let drive_type = if model_lower.contains("ssd") || 
-                                          model_lower.contains("solid state") ||
-                                          model_lower.contains("nvme") ||
-                                          model_lower.contains("m.2") {
-                            "SSD (Solid State Drive)"
-                        } else if model_lower.contains("hdd") || 
-                                  model_lower.contains("hard disk") ||
-                                  !model_lower.is_empty() {
-                            "HDD (Hard Disk Drive)"
-                        } else {
-                            "Fixed Drive (Unknown Type)"
-                        };
-                        
-                        let secure_erase_available = drive_info.security_supported && 
-                                                   !drive_info.security_frozen;
-                        
-                        (drive_type.to_string(), secure_erase_available)
-                    },
-                    Err(_) => ("Fixed Drive (ATA Detection Failed)".to_string(), false),
-                }
-            },
-            Err(_) => ("Fixed Drive (No ATA Access)".to_string(), false),
+        // Files given with --target-file stand in for drives
+        for path in devices::virtual_device::registered() {
+            let size = std::fs::metadata(&path).map(|m| m.len()).unwrap_or(0);
+            self.disks.push(DiskInfo {
+                drive_letter: path.clone(),
+                drive_type: "Virtual (file)".to_string(),
+                detailed_type: "Virtual (file)".to_string(),
+                file_system: "None".to_string(),
+                total_space: size,
+                free_space: 0,
+                used_space: size,
+                label: "Virtual Disk".to_string(),
+                selected: false,
+                // Registered on purpose with --target-file
+                wipe_guard: None,
+            });
+            self.drive_table.add_drive(DriveInfo::new(
+                "Virtual Disk".to_string(),
+                path,
+                size,
+                size,
+            ));
         }
+        
+        let attached: Vec<String> = self.drive_table.drives.iter().map(|d| Self::device_path(&d.path)).collect();
+        devices::DeviceFactory::retain_attached(&attached);
     }
 
-    fn format_bytes(bytes: u64) -> String {
-        const UNITS: &[&str] = &["B", "KB", "MB", "GB", "TB"];
-        let mut size = bytes as f64;
-        let mut unit_index = 0;
+    // Cross-platform disk info is now handled by the platform module
 
-        while size >= 1024.0 && unit_index < UNITS.len() - 1 {
-            size /= 1024.0;
-            unit_index += 1;
+    /// Convert a drive path to the device path format the erasers expect
+    fn device_path(drive_path: &str) -> String {
+        if drive_path.ends_with(':') {
+            format!("{}\\", drive_path)
+        } else {
+            drive_path.to_string()
         }
-
-        format!("{:.2} {}", size, UNITS[unit_index])
+    }
+    
+    /// Analyze one drive in the background without wiping it; the Details tab
+    /// fills in once the analysis finishes
+    fn analyze_drive(&mut self, drive_index: usize) {
+        let Some(drive) = self.drive_table.drives.get(drive_index) else {
+            return;
+        };
+        let device_path = Self::device_path(&drive.path);
+        let error_policy = self.advanced_options.error_policy;
+        let thermal_limit = self.config.thermal_limit();
+        let analyzed_info = Arc::new(Mutex::new(None));
+        self.drive_device_info.insert(drive_index, Arc::clone(&analyzed_info));
+        
+        log::info!("🔍 Analyzing {} ({})", drive.name, device_path);
+        std::thread::spawn(move || {
+            match devices::DeviceFactory::cached_or_analyze(&device_path, error_policy, thermal_limit) {
+                Ok((device_info, _)) => {
+                    log::info!("✅ Analysis of {} complete: {} {}", device_path, device_info.device_type, device_info.model);
+                    if let Ok(mut analyzed) = analyzed_info.lock() {
+                        *analyzed = Some(device_info);
+                    }
+                }
+                Err(e) => log::error!("❌ Analysis of {} failed: {}", device_path, e),
+            }
+        });
     }
     
     fn handle_erase_request(&mut self) {
-        println!("🚨 HANDLE_ERASE_REQUEST CALLED!");
-        println!("🔐 Auth status: {}", self.is_authenticated);
-        println!("✅ Confirm erase: {}", self.advanced_options.confirm_erase);
+        log::debug!("🚨 HANDLE_ERASE_REQUEST CALLED!");
+        log::debug!("🔐 Auth status: {}", self.is_authenticated);
+        log::debug!("✅ Confirm erase: {}", self.advanced_options.confirm_erase);
         
         // Check if user is authenticated (no role restrictions)
         /* Authentication check disabled for ease of use
@@ -261,6 +614,12 @@ impl HDDApp {
             return;
         }
         
+        // Every certificate must name an accountable operator
+        if self.current_operator().is_none() {
+            self.last_error_message = Some("❌ Sign in or enter the operator name before starting - every certificate must name the person responsible".to_string());
+            return;
+        }
+        
         // Get selected drives
         let selected_drives: Vec<usize> = self.drive_table.drives
             .iter()
@@ -270,11 +629,11 @@ impl HDDApp {
             .collect();
             
         // Debug information
-        println!("🔧 DEBUG: Total drives: {}", self.drive_table.drives.len());
+        log::debug!("🔧 DEBUG: Total drives: {}", self.drive_table.drives.len());
         for (i, drive) in self.drive_table.drives.iter().enumerate() {
-            println!("🔧 DEBUG: Drive {}: {} - Selected: {}", i, drive.name, drive.selected);
+            log::debug!("🔧 DEBUG: Drive {}: {} - Selected: {}", i, drive.name, drive.selected);
         }
-        println!("🔧 DEBUG: Selected drives: {:?}", selected_drives);
+        log::debug!("🔧 DEBUG: Selected drives: {:?}", selected_drives);
             
         if selected_drives.is_empty() {
             self.last_error_message = Some("❌ No drives selected for sanitization. Please use the checkboxes to select drives first.".to_string());
@@ -291,21 +650,49 @@ impl HDDApp {
             }
         }
         
+        // A benchmark still running would report over the wipe's status when it finishes
+        let benchmarking = selected_drives.iter()
+            .find(|drive_idx| self.drive_benchmarks.contains_key(drive_idx))
+            .and_then(|&drive_idx| self.drive_table.drives.get(drive_idx));
+        if let Some(drive) = benchmarking {
+            self.last_error_message = Some(format!("⚠️ {} is still being benchmarked - start the wipe once the benchmark finishes", drive.name));
+            return;
+        }
+        
+        // Network shares and virtual disks are never wiped by accident; the file-level
+        // fallback on a share would be deleting files on the server
+        if !self.allow_guarded_targets {
+            let guarded = selected_drives.iter()
+                .filter_map(|&drive_idx| self.disks.get(drive_idx))
+                .find_map(|disk_info| disk_info.wipe_guard.as_ref().map(|reason| (&disk_info.drive_letter, reason)));
+            if let Some((drive, reason)) = guarded {
+                self.last_error_message = Some(format!("❌ {} ({}) will not be wiped - turn on the override in Settings → Advanced only if you are sure",
+                    drive, reason));
+                return;
+            }
+        }
+        
         // Start real sanitization for selected drives
         self.sanitization_in_progress = true;
-        self.last_error_message = Some(format!("� REAL SANITIZATION STARTED: {} erasure for {} drive(s) - ALL FILES AND FOLDERS WILL BE PERMANENTLY DESTROYED!", 
-            self.advanced_options.eraser_method, selected_drives.len()));
+        if self.selected_algorithm == WipingAlgorithm::FreeSpaceWipe {
+            self.last_error_message = Some(format!("� FREE SPACE WIPE STARTED for {} drive(s) - existing files are kept, unallocated space will be overwritten",
+                selected_drives.len()));
+        } else {
+            self.last_error_message = Some(format!("� REAL SANITIZATION STARTED: {} erasure for {} drive(s) - ALL FILES AND FOLDERS WILL BE PERMANENTLY DESTROYED!", 
+                self.advanced_options.eraser_method(), selected_drives.len()));
+        }
         
         // Start actual sanitization process
         self.start_real_sanitization();
     }
     
     fn start_real_sanitization(&mut self) {
-        // Record sanitization start time for certificate generation
+        // Record sanitization start time and operator for certificate generation
         self.current_sanitization_start = Some(chrono::Utc::now());
+        self.wipe_operator = self.current_operator();
         
         // Collect drives to sanitize
-        let drives_to_process: Vec<(String, String, usize)> = self.drive_table.drives
+        let drives_to_process: VecDeque<(String, String, usize)> = self.drive_table.drives
             .iter()
             .enumerate()
             .filter(|(_, drive)| drive.selected)
@@ -316,19 +703,45 @@ impl HDDApp {
             return;
         }
         
-        // Start the sanitization process for each selected drive
-        for (drive_path, drive_name, drive_index) in drives_to_process {
+        // Drop progress left over from a previous run
+        self.drive_progress.clear();
+        self.drive_hashes.clear();
+        self.drive_logs.clear();
+        self.drive_smart.clear();
+        self.drive_identify.clear();
+        self.drive_device_info.clear();
+        self.drive_threads.clear();
+        self.drive_results.clear();
+        self.drive_resume.retain(|index, _| drives_to_process.iter().any(|(_, _, i)| i == index));
+        
+        for (_, _, drive_index) in &drives_to_process {
+            if let Some(drive) = self.drive_table.drives.get_mut(*drive_index) {
+                drive.status = "Queued".to_string();
+            }
+        }
+        self.wipe_queue = drives_to_process;
+        self.start_queued_wipes();
+        
+        // Begin progress tracking
+        self.refresh_sanitization_progress();
+    }
+    
+    /// Start queued drives until `max_concurrent_wipes` are running
+    fn start_queued_wipes(&mut self) {
+        let limit = self.config.max_concurrent_wipes;
+        while limit == 0 || self.drive_threads.len() < limit {
+            let Some((drive_path, drive_name, drive_index)) = self.wipe_queue.pop_front() else {
+                break;
+            };
             // Use device-specific sanitization by default, with fallback to traditional method
             self.start_device_specific_sanitization(&drive_path, &drive_name, drive_index);
         }
-        
-        // Begin progress simulation/tracking
-        self.simulate_sanitization_progress();
     }
     
     /// Enhanced sanitization using device-specific erasers
     fn start_device_specific_sanitization(&mut self, drive_path: &str, drive_name: &str, drive_index: usize) {
         // Get the actual device path for sanitization (platform-specific)
+        let free_space = self.disks.get(drive_index).map(|d| d.free_space).unwrap_or(0);
         let sanitization_path = if let Some(disk_info) = self.disks.get(drive_index) {
             get_device_path_for_sanitization(&platform::DriveInfo {
                 path: disk_info.drive_letter.clone(),
@@ -336,49 +749,189 @@ impl HDDApp {
                 drive_type: disk_info.drive_type.clone(),
                 total_space: disk_info.total_space,
                 free_space: disk_info.free_space,
+                wipe_guard: disk_info.wipe_guard.clone(),
             })
         } else {
             drive_path.to_string()
         };
-        println!("🔍 Starting device-specific analysis and sanitization for drive {} ({})", drive_name, drive_path);
+        log::info!("🔍 Starting device-specific analysis and sanitization for drive {} ({})", drive_name, drive_path);
         
-        // Convert drive path to device path format
-        let device_path = if drive_path.ends_with(':') {
-            format!("{}\\", drive_path)
-        } else {
-            drive_path.to_string()
-        };
+        let device_path = Self::device_path(drive_path);
         
         // Clone necessary data for the thread
         let device_path_clone = device_path.clone();
         let sanitization_path_clone = sanitization_path.clone();
         let drive_name_clone = drive_name.to_string();
-        let selected_algorithm = self.selected_algorithm.clone();
-        let wipe_progress = Arc::clone(&self.wipe_progress);
+        let selected_algorithm = self.drive_algorithm(drive_index);
+        let wipe_progress = self.open_progress_channel(drive_index, selected_algorithm.clone());
+        let job_log = self.open_job_log(drive_index);
+        let resume = self.drive_resume.remove(&drive_index);
+        // This wipe replaces whatever an earlier session left unfinished on the drive
+        let serial = self.known_serial(drive_index).flatten();
+        // A job with no serial can't be resumed anywhere; drop it once its path is wiped afresh
+        self.interrupted_jobs.retain(|job| !job.matches(serial.as_deref())
+            && !(job.serial.is_none() && job.drive_path == drive_path));
+        let now = chrono::Utc::now();
+        self.job_states.insert(drive_index, JobState {
+            drive_name: drive_name.to_string(),
+            drive_path: drive_path.to_string(),
+            serial,
+            algorithm: selected_algorithm.to_string(),
+            completed_passes: resume.as_ref().map_or(0, |(_, pass)| pass.saturating_sub(1)),
+            total_passes: selected_algorithm.pass_count(),
+            started_at: now,
+            updated_at: now,
+        });
+        self.save_job_states();
+        let hash_device = self.advanced_options.hash_device;
+        let error_policy = self.advanced_options.error_policy;
+        let sensitivity = self.advanced_options.sensitivity;
+        let thermal_limit = self.config.thermal_limit();
+        let verify_interval = self.config.verify_interval();
+        let device_hashes = Arc::new(Mutex::new(DeviceHashes {
+            in_progress: hash_device,
+            ..Default::default()
+        }));
+        self.drive_hashes.insert(drive_index, Arc::clone(&device_hashes));
+        let analyzed_info = Arc::new(Mutex::new(None));
+        self.drive_device_info.insert(drive_index, Arc::clone(&analyzed_info));
+        let smart_snapshots = Arc::new(Mutex::new(SmartSnapshots::default()));
+        self.drive_smart.insert(drive_index, Arc::clone(&smart_snapshots));
+        let raw_identify = Arc::new(Mutex::new(None));
+        self.drive_identify.insert(drive_index, Arc::clone(&raw_identify));
         
-        // Start analysis and sanitization in a separate thread
-        std::thread::spawn(move || {
-            match devices::DeviceFactory::analyze_and_create(&device_path_clone) {
+        // Start analysis and sanitization in a separate thread; its result decides
+        // whether the drive is reported as wiped
+        let handle = std::thread::spawn(move || -> Result<(), String> {
+            logging::capture_on_this_thread(job_log);
+            
+            // Free-space mode works on the mounted filesystem and keeps its files,
+            // so it never touches the raw device
+            if selected_algorithm == WipingAlgorithm::FreeSpaceWipe {
+                let sanitizer = DataSanitizer::new().with_error_policy(error_policy);
+                let callback = forward_progress(&wipe_progress);
+
+                let outcome = match sanitizer.wipe_free_space(&device_path_clone, FREE_SPACE_PASSES, free_space, Some(callback)) {
+                    Ok(_) => {
+                        log::info!("✅ Free space wipe completed for {}", drive_name_clone);
+                        Ok(())
+                    }
+                    Err(e) => {
+                        log::error!("❌ Free space wipe failed for {}: {}", drive_name_clone, e);
+                        Err(wipe_failure(&device_path_clone, "Free space wipe failed", &e))
+                    }
+                };
+                if let Ok(mut wp) = wipe_progress.lock() {
+                    wp.errors.extend(sanitizer.errors());
+                    wp.finish_pass();
+                }
+                if let Ok(mut hashes) = device_hashes.lock() {
+                    hashes.in_progress = false;
+                }
+                return outcome;
+            }
+            
+            // Quick sanitize only destroys partition tables and metadata, which the
+            // device erasers have no notion of, so it goes straight to the sanitizer
+            if selected_algorithm == WipingAlgorithm::QuickSanitize {
+                let sanitizer = DataSanitizer::new().with_error_policy(error_policy);
+                let callback = forward_progress(&wipe_progress);
+
+                let outcome = match sanitizer.quick_sanitize(&sanitization_path_clone, Some(callback)) {
+                    Ok(_) => {
+                        log::info!("⚡ Quick sanitize completed for {}", drive_name_clone);
+                        Ok(())
+                    }
+                    Err(e) => {
+                        log::error!("❌ Quick sanitize failed for {}: {}", drive_name_clone, e);
+                        Err(wipe_failure(&sanitization_path_clone, "Quick sanitize failed", &e))
+                    }
+                };
+                if let Ok(mut wp) = wipe_progress.lock() {
+                    wp.errors.extend(sanitizer.errors());
+                    wp.finish_pass();
+                }
+                if let Ok(mut hashes) = device_hashes.lock() {
+                    hashes.in_progress = false;
+                }
+                return outcome;
+            }
+            
+            let outcome = match devices::DeviceFactory::analyze_and_create(&device_path_clone, error_policy, thermal_limit) {
                 Ok((device_info, eraser)) => {
-                    println!("✅ Device analysis complete:");
-                    println!("   Device Type: {:?}", device_info.device_type);
-                    println!("   Model: {}", device_info.model);
-                    println!("   Size: {} bytes", device_info.size_bytes);
-                    println!("   Supports Secure Erase: {}", device_info.supports_secure_erase);
-                    println!("   Supports TRIM: {}", device_info.supports_trim);
+                    log::info!("✅ Device analysis complete:");
+                    log::info!("   Device Type: {}", device_info.device_type);
+                    log::info!("   Model: {}", device_info.model);
+                    log::info!("   Size: {} bytes", device_info.size_bytes);
+                    log::info!("   Vendor: {} / Serial: {}", device_info.vendor, device_info.serial);
+                    log::info!("   Supports Secure Erase: {}", device_info.supports_secure_erase);
+                    log::info!("   Supports TRIM: {}", device_info.supports_trim);
+                    if let Ok(mut analyzed) = analyzed_info.lock() {
+                        *analyzed = Some(device_info.clone());
+                    }
+                    if device_info.is_smr {
+                        log::warn!("⚠️  {} is an SMR (shingled) drive - expect the wipe to be unusually slow", drive_name_clone);
+                    }
+                    if let Ok(mut snapshots) = smart_snapshots.lock() {
+                        snapshots.before = advanced_wiper::read_smart_snapshot(&device_info.device_path);
+                    }
+                    if let Ok(mut raw_identify) = raw_identify.lock() {
+                        *raw_identify = advanced_wiper::read_raw_identify(&device_info);
+                    }
                     
                     // Get recommended algorithms for this device type
                     let recommended_algorithms = eraser.get_recommended_algorithms();
-                    println!("🔧 Recommended algorithms: {:?}", recommended_algorithms);
+                    log::info!("🔧 Recommended algorithms: {:?}", recommended_algorithms);
                     
-                    // Use selected algorithm, or fall back to first recommended
-                    let algorithm_to_use = if recommended_algorithms.contains(&selected_algorithm) {
+                    // Run the operator's choice whenever this device can perform it, even if it
+                    // isn't among the recommended ones; only substitute when it can't, and say so
+                    // Crypto-erase depends on the volume encryption rather than the device type
+                    let mut supported_algorithms = eraser.supported_algorithms(&device_info);
+                    if device_info.encryption_status.is_encrypted() {
+                        supported_algorithms.push(WipingAlgorithm::CryptoErase);
+                    }
+                    let selected_algorithm = if selected_algorithm == WipingAlgorithm::NistAuto {
+                        let chosen = advanced_wiper::nist_auto_algorithm(&device_info, sensitivity, &supported_algorithms);
+                        log::info!("🧭 NIST 800-88 Auto chose {} for {} ({}, {} sensitivity)",
+                                chosen, drive_name_clone, device_info.device_type, sensitivity.label());
+                        chosen
+                    } else if selected_algorithm == WipingAlgorithm::AustralianIsm {
+                        let chosen = advanced_wiper::ism_algorithm(&device_info, &supported_algorithms);
+                        log::info!("🧭 Australian ISM chose {} for {} ({})", chosen, drive_name_clone, device_info.device_type);
+                        chosen
+                    } else {
                         selected_algorithm
+                    };
+                    let algorithm_to_use = if supported_algorithms.iter().any(|a| a.same_method(&selected_algorithm)) {
+                        if !recommended_algorithms.iter().any(|a| a.same_method(&selected_algorithm)) {
+                            log::info!("ℹ️  {:?} is not a recommended algorithm for {} - running it as selected", selected_algorithm, drive_name_clone);
+                        }
+                        selected_algorithm.clone()
                     } else {
-                        recommended_algorithms.first().cloned().unwrap_or(WipingAlgorithm::Random)
+                        let fallback = recommended_algorithms.first().cloned().unwrap_or(WipingAlgorithm::Random);
+                        log::warn!("⚠️  {:?} cannot be performed on {} - substituting {:?}; the certificate will record the substitution",
+                                selected_algorithm, drive_name_clone, fallback);
+                        fallback
                     };
                     
-                    println!("🚀 Using algorithm: {:?}", algorithm_to_use);
+                    log::info!("🚀 Using algorithm: {:?}", algorithm_to_use);
+                    
+                    // A resumed wipe skips the passes an earlier session wrote, but only when the
+                    // same method runs again; its last pass is always rewritten, so there is
+                    // something to verify
+                    let resume_from_pass = match resume {
+                        Some((algorithm, pass)) if algorithm == algorithm_to_use => pass.min(algorithm_to_use.pass_count()),
+                        Some((algorithm, _)) => {
+                            log::warn!("⚠️  {} was interrupted while running {}, not {} - wiping it again from the first pass",
+                                drive_name_clone, algorithm, algorithm_to_use);
+                            0
+                        }
+                        None => 0,
+                    };
+                    if resume_from_pass > 1 {
+                        log::info!("⏯️  Resuming {} at pass {} of {}; the passes before it were written in an earlier session",
+                            drive_name_clone, resume_from_pass, algorithm_to_use.pass_count());
+                    }
                     
                     // Initialize progress
                     if let Ok(mut progress) = wipe_progress.lock() {
@@ -386,91 +939,213 @@ impl HDDApp {
                         progress.bytes_processed = 0;
                         progress.total_bytes = device_info.size_bytes;
                         progress.current_pass = 0;
-                        progress.total_passes = match algorithm_to_use {
-                            WipingAlgorithm::DoD522022M => 3,
-                            WipingAlgorithm::Gutmann => 35,
-                            WipingAlgorithm::SevenPass => 7,
-                            WipingAlgorithm::ThreePass => 3,
-                            WipingAlgorithm::TwoPass => 2,
-                            _ => 1,
-                        };
+                        progress.total_passes = algorithm_to_use.pass_count();
+                        progress.resume_from_pass = resume_from_pass;
+                    }
+                    
+                    if hash_device {
+                        log::info!("🔐 Hashing {} before wipe...", drive_name_clone);
+                        match advanced_wiper::hash_device(&device_info.device_path, device_info.size_bytes, &wipe_progress) {
+                            Ok(hash) => {
+                                log::info!("🔐 Pre-wipe SHA-256 for {}: {}", drive_name_clone, hash);
+                                if let Ok(mut hashes) = device_hashes.lock() {
+                                    hashes.pre_wipe = Some(hash);
+                                }
+                            }
+                            Err(e) => log::warn!("⚠️  Pre-wipe hash failed for {}: {}", drive_name_clone, e),
+                        }
                     }
                     
                     // Perform device-specific erasure
-                    match eraser.erase_device(&device_info, algorithm_to_use, wipe_progress.clone()) {
+                    let is_crypto_erase = algorithm_to_use == WipingAlgorithm::CryptoErase;
+                    // The national standard methods read their final pass back themselves
+                    let self_verified = matches!(algorithm_to_use,
+                        WipingAlgorithm::HmgIs5Baseline | WipingAlgorithm::HmgIs5Enhanced
+                        | WipingAlgorithm::RcmpTssitOps2 | WipingAlgorithm::AustralianIsm
+                        | WipingAlgorithm::Vsitr | WipingAlgorithm::Afssi5020 | WipingAlgorithm::NavsoP523926
+                        | WipingAlgorithm::MultiPass { .. });
+                    let erase_result = if is_crypto_erase {
+                        devices::crypto_erase::crypto_erase(&device_info, wipe_progress.clone())
+                    } else {
+                        eraser.erase_device(&device_info, algorithm_to_use.clone(), wipe_progress.clone())
+                    };
+                    if let Ok(mut progress) = wipe_progress.lock() {
+                        progress.finish_pass();
+                    }
+                    match erase_result {
                         Ok(_) => {
-                            println!("✅ Device-specific erasure completed for {}", drive_name_clone);
+                            log::info!("✅ Device-specific erasure completed for {}", drive_name_clone);
                             
-                            // Verify erasure if supported
-                            match eraser.verify_erasure(&device_info) {
-                                Ok(true) => println!("✅ Erasure verification passed for {}", drive_name_clone),
-                                Ok(false) => println!("⚠️  Erasure verification failed for {}", drive_name_clone),
-                                Err(e) => println!("❌ Erasure verification error for {}: {}", drive_name_clone, e),
+                            // Verify erasure if supported; crypto-erase leaves ciphertext in place
+                            // and checks the key material itself
+                            let verified = if is_crypto_erase || self_verified {
+                                Ok(())
+                            } else {
+                                match devices::verify_final_pass(eraser.as_ref(), &device_info, &wipe_progress) {
+                                    Ok(true) => {
+                                        log::info!("✅ Erasure verification passed for {}", drive_name_clone);
+                                        Ok(())
+                                    }
+                                    Ok(false) => {
+                                        log::warn!("⚠️  Erasure verification failed for {}", drive_name_clone);
+                                        Err("Erasure verification failed".to_string())
+                                    }
+                                    Err(e) => {
+                                        log::error!("❌ Erasure verification error for {}: {}", drive_name_clone, e);
+                                        Err(wipe_failure(&device_info.device_path, "Erasure verification error", &e))
+                                    }
+                                }
+                            };
+                            
+                            if hash_device {
+                                log::info!("🔐 Hashing {} after wipe...", drive_name_clone);
+                                match advanced_wiper::hash_device(&device_info.device_path, device_info.size_bytes, &wipe_progress) {
+                                    Ok(hash) => {
+                                        log::info!("🔐 Post-wipe SHA-256 for {}: {}", drive_name_clone, hash);
+                                        if let Ok(mut hashes) = device_hashes.lock() {
+                                            hashes.post_wipe = Some(hash);
+                                        }
+                                    }
+                                    Err(e) => log::warn!("⚠️  Post-wipe hash failed for {}: {}", drive_name_clone, e),
+                                }
                             }
+                            
+                            match advanced_wiper::sample_sector_hashes(&device_info.device_path, device_info.size_bytes,
+                                                                      device_info.sector_size, advanced_wiper::SECTOR_SAMPLE_COUNT) {
+                                Ok(samples) => {
+                                    if let Ok(mut hashes) = device_hashes.lock() {
+                                        hashes.sampled_sectors = samples;
+                                    }
+                                }
+                                Err(e) => log::warn!("⚠️  Sector sampling failed for {}: {}", drive_name_clone, e),
+                            }
+                            verified
                         }
-                        Err(e) => {
-                            println!("❌ Device-specific erasure failed for {}: {}", drive_name_clone, e);
-                            println!("🔄 Falling back to traditional file-level sanitization...");
+                        // Closing ShredX stopped the wipe between passes; a fallback purge
+                        // would keep it running for hours after the operator asked to quit
+                        Err(erase_error) if advanced_wiper::is_wipe_stopped_error(&erase_error) => {
+                            log::warn!("⏹️  Wipe of {} stopped after its current pass because ShredX is closing", drive_name_clone);
+                            Err(WIPE_STOPPED.to_string())
+                        }
+                        // Nothing can be purged on a device that has been unplugged
+                        Err(erase_error) if advanced_wiper::device_removed(&device_info.device_path, &erase_error) => {
+                            log::error!("❌ Device-specific erasure failed for {}: {}", drive_name_clone, erase_error);
+                            Err(wipe_failure(&device_info.device_path, "Device erasure failed", &erase_error))
+                        }
+                        // A failed read-back is the standard's verdict on the wipe; purging
+                        // over it would hide that from the certificate
+                        Err(erase_error) if self_verified && erase_error.kind() == std::io::ErrorKind::InvalidData => {
+                            log::error!("❌ {:?} verification failed for {}: {}", algorithm_to_use, drive_name_clone, erase_error);
+                            Err(format!("Erasure verification failed: {}", erase_error))
+                        }
+                        Err(erase_error) => {
+                            log::error!("❌ Device-specific erasure failed for {}: {}", drive_name_clone, erase_error);
+                            log::info!("🔄 Falling back to traditional file-level sanitization...");
                             
                             // Fallback to NIST SP 800-88 disk purge
-                            let sanitizer = DataSanitizer::new();
-                            let wp_clone = wipe_progress.clone();
-                            let callback = Box::new(move |p: SanitizationProgress| {
-                                if let Ok(mut wp) = wp_clone.lock() {
-                                    wp.bytes_processed = p.bytes_processed;
-                                    wp.total_bytes = p.total_bytes;
-                                    wp.current_pass = p.current_pass;
-                                    wp.total_passes = p.total_passes;
-                                    wp.estimated_time_remaining = p.estimated_time_remaining;
-                                    wp.current_pattern = p.current_operation;
-                                }
-                            });
+                            // The certificate reports the purge that ran, not the method that failed
+                            if let Ok(mut wp) = wipe_progress.lock() {
+                                wp.algorithm = WipingAlgorithm::from(SanitizationMethod::Purge);
+                                wp.resume_from_pass = 0;
+                                wp.total_passes = wp.algorithm.pass_count();
+                            }
+                            let sanitizer = DataSanitizer::new().with_error_policy(error_policy).with_thermal_limit(thermal_limit)
+                                .with_verify_interval(verify_interval);
+                            let callback = forward_progress(&wipe_progress);
 
-                            match sanitizer.nist_purge_entire_disk(&device_path_clone, Some(callback)) {
-                                Ok(_) => println!("✅ NIST SP 800-88 Purge completed for {}", drive_name_clone),
-                                Err(e) => println!("❌ NIST SP 800-88 Purge also failed for {}: {}", drive_name_clone, e),
+                            let purged = match sanitizer.nist_purge_entire_disk(&device_path_clone, Some(callback)) {
+                                Ok(_) => {
+                                    log::info!("✅ NIST SP 800-88 Purge completed for {}", drive_name_clone);
+                                    Ok(())
+                                }
+                                Err(e) => {
+                                    log::error!("❌ NIST SP 800-88 Purge also failed for {}: {}", drive_name_clone, e);
+                                    Err(wipe_failure(&device_path_clone,
+                                        &format!("Device erasure failed ({}) and NIST purge fallback failed", erase_error), &e))
+                                }
+                            };
+                            if let Ok(mut wp) = wipe_progress.lock() {
+                                wp.errors.extend(sanitizer.errors());
+                                if sanitizer.fell_back_to_files() {
+                                    // Only files and free space were overwritten; no disk purge to certify
+                                    wp.algorithm = WipingAlgorithm::FileSystemWipe;
+                                }
                             }
+                            if let Ok(mut hashes) = device_hashes.lock() {
+                                hashes.sampled_sectors = sanitizer.sampled_sectors();
+                            }
+                            purged
                         }
                     }
                 }
                 Err(e) => {
-                    println!("❌ Device analysis failed for {}: {}", drive_name_clone, e);
-                    println!("🔄 Falling back to traditional file-level sanitization...");
+                    log::error!("❌ Device analysis failed for {}: {}", drive_name_clone, e);
+                    log::info!("🔄 Falling back to traditional file-level sanitization...");
                     
                     // Fallback to NIST SP 800-88 disk purge
-                    let sanitizer = DataSanitizer::new();
-                    let wp_clone = wipe_progress.clone();
-                    let callback = Box::new(move |p: SanitizationProgress| {
-                        if let Ok(mut wp) = wp_clone.lock() {
-                            wp.bytes_processed = p.bytes_processed;
-                            wp.total_bytes = p.total_bytes;
-                            wp.current_pass = p.current_pass;
-                            wp.total_passes = p.total_passes;
-                            wp.estimated_time_remaining = p.estimated_time_remaining;
-                            wp.current_pattern = p.current_operation;
-                        }
-                    });
+                    // The certificate reports the purge that ran, not the method that failed
+                    if let Ok(mut wp) = wipe_progress.lock() {
+                        wp.algorithm = WipingAlgorithm::from(SanitizationMethod::Purge);
+                        wp.resume_from_pass = 0;
+                        wp.total_passes = wp.algorithm.pass_count();
+                    }
+                    let sanitizer = DataSanitizer::new().with_error_policy(error_policy).with_thermal_limit(thermal_limit)
+                        .with_verify_interval(verify_interval);
+                    let callback = forward_progress(&wipe_progress);
 
-                    match sanitizer.nist_purge_entire_disk(&sanitization_path_clone, Some(callback)) {
-                        Ok(_) => println!("✅ NIST SP 800-88 Purge completed for {}", drive_name_clone),
-                        Err(e) => println!("❌ NIST SP 800-88 Purge also failed for {}: {}", drive_name_clone, e),
+                    let purged = match sanitizer.nist_purge_entire_disk(&sanitization_path_clone, Some(callback)) {
+                        Ok(_) => {
+                            log::info!("✅ NIST SP 800-88 Purge completed for {}", drive_name_clone);
+                            Ok(())
+                        }
+                        Err(e) => {
+                            log::error!("❌ NIST SP 800-88 Purge also failed for {}: {}", drive_name_clone, e);
+                            Err(wipe_failure(&device_path_clone, "NIST SP 800-88 Purge failed", &e))
+                        }
+                    };
+                    if let Ok(mut wp) = wipe_progress.lock() {
+                        wp.errors.extend(sanitizer.errors());
+                        if sanitizer.fell_back_to_files() {
+                            // Only files and free space were overwritten; no disk purge to certify
+                            wp.algorithm = WipingAlgorithm::FileSystemWipe;
+                        }
+                    }
+                    if let Ok(mut hashes) = device_hashes.lock() {
+                        hashes.sampled_sectors = sanitizer.sampled_sectors();
                     }
+                    purged
                 }
+            };
+            
+            // Closes the last pass of the fallback purge
+            if let Ok(mut progress) = wipe_progress.lock() {
+                progress.finish_pass();
+            }
+            if let Ok(mut snapshots) = smart_snapshots.lock() {
+                // Only drives that were read before the wipe get an after snapshot to compare
+                if snapshots.before.is_some() {
+                    snapshots.after = advanced_wiper::read_smart_snapshot(&device_path_clone);
+                }
+            }
+            // The wipe changed the device; the next look at it has to probe again
+            devices::DeviceFactory::forget(&device_path_clone);
+            if let Ok(mut hashes) = device_hashes.lock() {
+                hashes.in_progress = false;
             }
+            outcome
         });
+        self.drive_threads.insert(drive_index, handle);
         
         // Initialize progress tracking for this drive
-        let total_bytes = if let Some(drive) = self.drive_table.drives.get(drive_index) {
-            self.parse_size_to_bytes(&drive.size)
-        } else {
-            1_000_000_000 // Default 1GB if drive not found
-        };
+        let total_bytes = self.drive_size_bytes(drive_index);
         
+        let selected_algorithm = self.drive_algorithm(drive_index);
         if let Some(drive) = self.drive_table.drives.get_mut(drive_index) {
             drive.start_processing(total_bytes);
             drive.status = format!("Device-specific {} erasure", 
-                match self.selected_algorithm {
+                match selected_algorithm {
                     WipingAlgorithm::DoD522022M => "DoD 5220.22-M",
+                    WipingAlgorithm::DoD522022MEce => "DoD 5220.22-M ECE",
                     WipingAlgorithm::Gutmann => "Gutmann 35-pass",
                     WipingAlgorithm::AtaSecureErase => "ATA Secure Erase",
                     WipingAlgorithm::NvmeSecureErase => "NVMe Secure Erase",
@@ -480,6 +1155,127 @@ impl HDDApp {
         }
     }
 
+    /// (username, user id) of the person running wipes: the signed-in user, otherwise
+    /// the operator entered on the Drives tab
+    fn current_operator(&self) -> Option<(String, String)> {
+        if let Some(user) = self.auth_system.current_user() {
+            return Some((user.username.clone(), user.id.clone()));
+        }
+        if let Some(username) = self.auth_widget.get_current_user() {
+            return Some((username.to_string(), username.to_string()));
+        }
+        let name = self.operator_name.trim();
+        if name.is_empty() {
+            return None;
+        }
+        let id = self.operator_id.trim();
+        Some((name.to_string(), if id.is_empty() { "N/A".to_string() } else { id.to_string() }))
+    }
+
+    /// Give a drive its own progress channel; only that drive's wipe thread writes to it
+    /// and only that drive's row reads from it
+    fn open_progress_channel(&mut self, drive_index: usize, algorithm: WipingAlgorithm) -> Arc<Mutex<WipingProgress>> {
+        let wipe_progress = Arc::new(Mutex::new(WipingProgress {
+            algorithm,
+            current_pass: 0,
+            total_passes: 1,
+            bytes_processed: 0,
+            total_bytes: 0,
+            current_pattern: "Ready".to_string(),
+            estimated_time_remaining: Duration::from_secs(0),
+            speed_mbps: 0.0,
+            errors: Vec::new(),
+            pass_timings: Vec::new(),
+            pass_started: None,
+            resume_from_pass: 0,
+            verify_interval: self.config.verify_interval(),
+            last_pass_pattern: None,
+        }));
+        self.drive_progress.insert(drive_index, Arc::clone(&wipe_progress));
+        wipe_progress
+    }
+
+    /// Measure a drive's write throughput in the background; a mounted drive gets a scratch
+    /// file that is deleted afterwards, an unmounted one has a region rewritten unchanged
+    fn benchmark_drive(&mut self, drive_index: usize) {
+        let (Some(drive), Some(disk_info)) = (self.drive_table.drives.get_mut(drive_index), self.disks.get(drive_index)) else {
+            return;
+        };
+        if drive.is_active() || self.drive_benchmarks.contains_key(&drive_index) {
+            self.last_error_message = Some(format!("⚠️ {} is busy; benchmark it once it is idle", drive.name));
+            return;
+        }
+        
+        // Leave at least half the free space alone on a mounted drive
+        let target = Self::device_path(&drive.path);
+        let max_bytes = if std::path::Path::new(&target).is_dir() {
+            (disk_info.free_space / 2).min(advanced_wiper::BENCHMARK_BYTES)
+        } else {
+            advanced_wiper::BENCHMARK_BYTES
+        };
+        if max_bytes < MIN_BENCHMARK_BYTES {
+            self.last_error_message = Some(format!("❌ Not enough free space on {} for a meaningful benchmark", drive.name));
+            return;
+        }
+        drive.status = "⏱ Benchmarking...".to_string();
+        
+        let outcome = Arc::new(Mutex::new(None));
+        self.drive_benchmarks.insert(drive_index, Arc::clone(&outcome));
+        log::info!("⏱ Benchmarking {} ({})", drive.name, target);
+        std::thread::spawn(move || {
+            let result = advanced_wiper::benchmark_throughput(&target, max_bytes).map_err(|e| e.to_string());
+            if let Ok(mut outcome) = outcome.lock() {
+                *outcome = Some(result);
+            }
+        });
+    }
+    
+    /// Report finished benchmarks and keep their speed as the drive's ETA seed
+    fn collect_benchmarks(&mut self) {
+        let finished: Vec<(usize, Result<ThroughputBenchmark, String>)> = self.drive_benchmarks.iter()
+            .filter_map(|(&i, outcome)| outcome.lock().ok()?.take().map(|result| (i, result)))
+            .collect();
+        for (drive_index, result) in finished {
+            self.drive_benchmarks.remove(&drive_index);
+            let passes = self.drive_algorithm(drive_index).pass_count();
+            let Some(drive) = self.drive_table.drives.get_mut(drive_index) else {
+                continue;
+            };
+            match result {
+                // A read speed says little about how fast the wipe will write, so it is
+                // reported but never seeds the ETA
+                Ok(benchmark) if benchmark.read_only => {
+                    let message = format!("{:.0} MB/s read - the drive could not be opened for writing, so no wipe time is estimated",
+                        benchmark.mbps());
+                    log::info!("⏱ Benchmark of {}: {}", drive.name, message);
+                    drive.status = format!("⏱ {:.0} MB/s read", benchmark.mbps());
+                    self.last_error_message = Some(format!("⚠️ {}: {}", drive.name, message));
+                }
+                Ok(benchmark) => {
+                    let estimate = benchmark.estimate(drive.size_bytes, passes).as_secs();
+                    let message = format!("{:.0} MB/s write - about {}h {:02}m for {} pass(es) over {}",
+                        benchmark.mbps(), estimate / 3600, estimate % 3600 / 60, passes, format_bytes(drive.size_bytes));
+                    log::info!("⏱ Benchmark of {}: {}", drive.name, message);
+                    drive.benchmark_speed = Some(benchmark.bytes_per_second());
+                    drive.status = format!("⏱ {:.0} MB/s", benchmark.mbps());
+                    self.last_error_message = Some(format!("✅ {}: {}", drive.name, message));
+                }
+                Err(e) => {
+                    log::error!("❌ Benchmark of {} failed: {}", drive.name, e);
+                    drive.status = "Ready".to_string();
+                    self.last_error_message = Some(format!("❌ Benchmark of {} failed: {}", drive.name, e));
+                }
+            }
+        }
+    }
+
+    /// Give a drive an empty log for its wipe thread to capture into
+    fn open_job_log(&mut self, drive_index: usize) -> logging::JobLog {
+        let job_log = logging::JobLog::default();
+        self.drive_logs.insert(drive_index, Arc::clone(&job_log));
+        job_log
+    }
+
     fn start_drive_sanitization(&mut self, drive_path: &str, drive_name: &str, drive_index: usize) {
         let sanitizer = DataSanitizer::new();
         let passes = 3; // NIST SP 800-88 and DoD 5220.22-M typically use 3 passes
@@ -491,27 +1287,34 @@ impl HDDApp {
             drive_path.to_string()
         };
         
-        println!("🔥 Starting real sanitization of drive {} ({})", drive_name, full_drive_path);
+        log::info!("🔥 Starting real sanitization of drive {} ({})", drive_name, full_drive_path);
         
         // Start sanitization in a separate thread to avoid blocking UI
         let drive_path_clone = full_drive_path.clone();
-        std::thread::spawn(move || {
-            match sanitizer.sanitize_files_and_free_space(&drive_path_clone, passes, None) {
+        let wipe_progress = self.open_progress_channel(drive_index, WipingAlgorithm::FileSystemWipe);
+        let job_log = self.open_job_log(drive_index);
+        let handle = std::thread::spawn(move || -> Result<(), String> {
+            logging::capture_on_this_thread(job_log);
+            let callback = forward_progress(&wipe_progress);
+            match sanitizer.sanitize_files_and_free_space(&drive_path_clone, passes, Some(callback)) {
                 Ok(_) => {
-                    println!("✅ Successfully sanitized drive: {}", drive_path_clone);
+                    log::info!("✅ Successfully sanitized drive: {}", drive_path_clone);
+                    Ok(())
+                }
+                Err(e) if advanced_wiper::is_wipe_stopped_error(&e) => {
+                    log::warn!("⏹️  Sanitization of {} stopped after its current pass because ShredX is closing", drive_path_clone);
+                    Err(WIPE_STOPPED.to_string())
                 }
                 Err(e) => {
-                    println!("❌ Failed to sanitize drive {}: {}", drive_path_clone, e);
+                    log::error!("❌ Failed to sanitize drive {}: {}", drive_path_clone, e);
+                    Err(format!("Sanitization failed: {}", e))
                 }
             }
         });
+        self.drive_threads.insert(drive_index, handle);
         
         // Initialize progress tracking for this drive
-        let total_bytes = if let Some(drive) = self.drive_table.drives.get(drive_index) {
-            self.parse_size_to_bytes(&drive.size)
-        } else {
-            1_000_000_000 // Default 1GB if drive not found
-        };
+        let total_bytes = self.drive_size_bytes(drive_index);
         
         if let Some(drive) = self.drive_table.drives.get_mut(drive_index) {
             drive.start_processing(total_bytes);
@@ -519,80 +1322,126 @@ impl HDDApp {
         }
     }
     
-    fn simulate_sanitization_progress(&mut self) {
-        // Collect drive data first to avoid borrowing conflicts
-        let mut drive_updates = Vec::new();
-        let mut total_bytes_all_drives = 0u64;
-        let mut total_processed_all_drives = 0u64;
-        
-        // Check actual progress from the background thread
-        let (real_bytes_processed, real_total_bytes, real_pass, real_total_passes) = 
-            if let Ok(progress) = self.wipe_progress.lock() {
-                (progress.bytes_processed, progress.total_bytes, progress.current_pass, progress.total_passes)
-            } else {
-                (0, 0, 0, 0)
-            };
-
-        // Start processing for selected drives
-        for (i, drive) in self.drive_table.drives.iter().enumerate() {
-            if drive.selected && drive.progress == 0.0 {
-                // Simulate total bytes based on drive size
-                // Parse size string (e.g., "100 GB" -> bytes)
-                let total_bytes = self.parse_size_to_bytes(&drive.size);
-                drive_updates.push((i, total_bytes, true)); // true = start processing
+    fn refresh_sanitization_progress(&mut self) {
+        // Collect the outcome of every wipe thread that has finished
+        let finished: Vec<usize> = self.drive_threads.iter()
+            .filter(|(_, handle)| handle.is_finished())
+            .map(|(&i, _)| i)
+            .collect();
+        for i in finished {
+            if let Some(handle) = self.drive_threads.remove(&i) {
+                let result = handle.join()
+                    .unwrap_or_else(|_| Err("Wipe thread panicked".to_string()));
+                // Only a wipe that was cut short can be picked up again
+                if !matches!(&result, Err(e) if e == WIPE_STOPPED || e == DEVICE_REMOVED) {
+                    self.job_states.remove(&i);
+                }
+                self.last_job_state_save = None;
+                self.drive_results.insert(i, result);
             }
         }
+        // Finished drives free their slots for the next ones in the queue
+        self.start_queued_wipes();
         
-        // Apply start processing updates
-        for (i, total_bytes, start) in drive_updates {
-            if start {
-                if let Some(drive) = self.drive_table.drives.get_mut(i) {
-                    drive.start_processing(total_bytes);
+        let mut total_bytes_all_drives = 0u64;
+        let mut total_processed_all_drives = 0u64;
+        
+        // Snapshot the progress reported by each drive's own wipe thread
+        let mut real_progress: HashMap<usize, (u64, u64, u32, u32, String)> = HashMap::new();
+        for (&i, wipe_progress) in &self.drive_progress {
+            if let Ok(progress) = wipe_progress.lock() {
+                real_progress.insert(i, (progress.bytes_processed, progress.total_bytes, progress.current_pass,
+                    progress.total_passes, progress.current_pattern.clone()));
+                if let (true, Some(job)) = (self.drive_threads.contains_key(&i), self.job_states.get_mut(&i)) {
+                    let timed = progress.pass_timings.iter().map(|timing| timing.pass).max().unwrap_or(0);
+                    job.completed_passes = timed.max(progress.resume_from_pass.saturating_sub(1));
+                    job.total_passes = progress.total_passes;
+                    job.algorithm = progress.algorithm.to_string();
+                    job.updated_at = chrono::Utc::now();
+                    if job.serial.is_none() {
+                        job.serial = self.drive_device_info.get(&i)
+                            .and_then(|info| info.lock().ok().and_then(|info| info.as_ref().map(|info| info.serial.clone())))
+                            .filter(|serial| !serial.is_empty() && serial != "Unknown");
+                    }
                 }
             }
         }
+        if self.last_job_state_save.is_none_or(|last| last.elapsed() >= JOB_STATE_SAVE_INTERVAL) {
+            self.save_job_states();
+        }
         
         // Update progress for processing drives and calculate overall progress
         let mut any_in_progress = false;
         let mut all_completed = true;
         
-        for drive in &mut self.drive_table.drives {
-            if drive.selected {
-                total_bytes_all_drives += drive.bytes_total;
-                
-                if drive.start_time.is_some() && drive.progress < 1.0 {
-                    // Use real progress if available and non-zero, otherwise fallback to simulation
-                    let new_bytes_processed = if real_total_bytes > 0 {
-                        // Map the single thread progress to this drive (assuming single drive wipe for now)
-                        // If multiple drives, this logic needs to be smarter or we need per-drive progress tracking
-                        if real_total_bytes >= drive.bytes_total {
-                             // If reported total is larger or equal, use ratio
-                             let ratio = real_bytes_processed as f64 / real_total_bytes as f64;
-                             (ratio * drive.bytes_total as f64) as u64
-                        } else {
-                             real_bytes_processed
+        let mut real_pass = 0;
+        let mut real_total_passes = 0;
+        
+        for (i, drive) in self.drive_table.drives.iter_mut().enumerate() {
+            let running = self.drive_threads.contains_key(&i);
+            let result = self.drive_results.get(&i);
+            if !running && result.is_none() {
+                continue;
+            }
+            
+            let cut_short = match result {
+                Some(Err(e)) if e == DEVICE_REMOVED || e == WIPE_STOPPED => Some(e.as_str()),
+                _ => None,
+            };
+            if let (true, Some(status)) = (drive.is_active(), cut_short) {
+                // Only what was written before the device went away, or the wipe was
+                // stopped, counts; the bar stays there
+                if let Some(&(bytes_processed, total_bytes, ..)) = real_progress.get(&i) {
+                    drive.reconcile_total(total_bytes);
+                    drive.update_progress(bytes_processed);
+                }
+                drive.interrupt(status);
+                any_in_progress = true;
+            } else if drive.is_active() {
+                let new_bytes_processed = if result.is_some() {
+                    // The thread has returned, so the drive is done either way
+                    drive.bytes_total
+                } else {
+                    match real_progress.get(&i) {
+                        Some(&(bytes_processed, total_bytes, pass, total_passes, ref pattern)) if total_bytes > 0 => {
+                            drive.status = format!("Pass {}/{}: {}", pass.max(1), total_passes, pattern);
+                            // Report the least advanced drive's pass in the overall progress
+                            if real_total_passes == 0 || pass < real_pass {
+                                real_pass = pass;
+                                real_total_passes = total_passes;
+                            }
+                            // The drive started out with the size parsed from the table, which is
+                            // only an estimate; the wipe thread reports the device's real size
+                            drive.reconcile_total(total_bytes);
+                            // Only the thread finishing (verification and post-wipe hash included)
+                            // completes the drive
+                            bytes_processed.min(total_bytes.saturating_sub(1))
                         }
-                    } else {
-                        // Fallback simulation: 2MB per update cycle
-                        let increment = 1024 * 1024 * 2; 
-                        (drive.bytes_processed + increment).min(drive.bytes_total)
-                    };
-
-                    drive.update_progress(new_bytes_processed);
-                    any_in_progress = true;
-                    
-                    if drive.progress < 1.0 {
-                        all_completed = false;
+                        _ => drive.bytes_processed,
                     }
+                };
+
+                drive.update_progress(new_bytes_processed);
+                match result {
+                    Some(Ok(())) => drive.status = "Complete".to_string(),
+                    Some(Err(e)) => drive.status = format!("Failed: {}", e),
+                    None => {}
                 }
+                any_in_progress = true;
                 
-                total_processed_all_drives += drive.bytes_processed;
+                if drive.progress < 1.0 {
+                    all_completed = false;
+                }
             }
+            
+            total_bytes_all_drives += drive.bytes_total;
+            total_processed_all_drives += drive.bytes_processed.min(drive.bytes_total);
         }
         
         // Update overall sanitization progress
         if total_bytes_all_drives > 0 {
-            let overall_percentage = (total_processed_all_drives as f64 / total_bytes_all_drives as f64) * 100.0;
+            // Never report more than done, whatever the drives' totals say
+            let overall_percentage = (total_processed_all_drives as f64 / total_bytes_all_drives as f64 * 100.0).min(100.0);
             
             let progress = SanitizationProgress {
                 current_pass: if real_total_passes > 0 { real_pass } else { if overall_percentage < 33.0 { 1 } else if overall_percentage < 66.0 { 2 } else { 3 } },
@@ -607,69 +1456,229 @@ impl HDDApp {
         }
         
         // Check if sanitization is complete
-        if all_completed && any_in_progress {
+        if all_completed && any_in_progress && self.wipe_queue.is_empty() {
             self.sanitization_in_progress = false;
-            self.last_error_message = Some("✅ Sanitization completed successfully!".to_string());
+            let failed = self.drive_results.values().filter(|result| result.is_err()).count();
+            let removed = self.drive_results.values()
+                .filter(|result| result.as_ref().is_err_and(|e| e == DEVICE_REMOVED))
+                .count();
+            self.last_error_message = Some(if failed == 0 {
+                "✅ Sanitization completed successfully!".to_string()
+            } else if removed > 0 {
+                format!("❌ Sanitization failed on {} of {} drive(s); {} removed before its wipe finished and is NOT sanitized",
+                    failed, self.drive_results.len(), removed)
+            } else {
+                format!("❌ Sanitization failed on {} of {} drive(s) - see the drive status and certificates",
+                    failed, self.drive_results.len())
+            });
             
             // Generate certificates for completed sanitization
             self.generate_completion_certificates();
         }
     }
     
-    fn parse_size_to_bytes(&self, size_str: &str) -> u64 {
-        // Parse size string like "100 GB", "50.5 MB" etc.
-        let parts: Vec<&str> = size_str.split_whitespace().collect();
-        if parts.len() != 2 {
-            return 1_000_000_000; // Default 1GB if parsing fails
-        }
-        
-        let number: f64 = parts[0].parse().unwrap_or(1.0);
-        let unit = parts[1].to_uppercase();
-        
-        let multiplier: u64 = match unit.as_str() {
-            "B" => 1,
-            "KB" => 1_000,
-            "MB" => 1_000_000,
-            "GB" => 1_000_000_000,
-            "TB" => 1_000_000_000_000,
-            _ => 1_000_000_000, // Default to GB
-        };
-        
-        (number * multiplier as f64) as u64
+    /// Closing the window mid-wipe would kill the wipe threads partway through a pass and
+    /// leave the drives without certificates, so a close while wiping asks first. Stopping
+    /// lets every wipe finish its current pass and closes once their certificates are out.
+    fn handle_close_request(&mut self, ctx: &egui::Context) {
+        let wiping = !self.drive_threads.is_empty();
+        if ctx.input(|i| i.viewport().close_requested()) && wiping {
+            ctx.send_viewport_cmd(egui::ViewportCommand::CancelClose);
+            self.close_prompt = !self.closing_after_pass;
+        }
+        if self.closing_after_pass && !wiping && !self.sanitization_in_progress {
+            ctx.send_viewport_cmd(egui::ViewportCommand::Close);
+            return;
+        }
+        
+        if self.close_prompt {
+            egui::Window::new("Wipe in progress")
+                .collapsible(false)
+                .resizable(false)
+                .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+                .show(ctx, |ui| {
+                    ui.label(format!("{} drive(s) are still being wiped. Closing now would leave them half-written with no certificate.",
+                        self.drive_threads.len()));
+                    ui.add_space(10.0);
+                    ui.horizontal(|ui| {
+                        if ui.button("⏹️ Finish current pass and close").clicked() {
+                            log::warn!("⏹️  Closing: wipes stop after their current pass and are certified as incomplete");
+                            advanced_wiper::request_stop_after_pass();
+                            self.wipe_queue.clear();
+                            self.closing_after_pass = true;
+                            self.close_prompt = false;
+                        }
+                        if ui.button("Keep wiping").clicked() {
+                            self.close_prompt = false;
+                        }
+                    });
+                });
+        }
+        if self.closing_after_pass {
+            egui::Window::new("Closing")
+                .collapsible(false)
+                .resizable(false)
+                .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+                .show(ctx, |ui| {
+                    ui.label("Waiting for the current pass on each drive to finish; ShredX closes once their certificates are written.");
+                });
+            ctx.request_repaint_after(PROGRESS_REFRESH_INTERVAL);
+        }
+    }
+    
+    /// Write the running and still-unresumed jobs to disk
+    fn save_job_states(&mut self) {
+        let jobs: Vec<JobState> = self.job_states.values().chain(&self.interrupted_jobs).cloned().collect();
+        if let Err(e) = job_state::save(&jobs) {
+            log::warn!("⚠️  Could not save the state of running wipes: {}", e);
+        }
+        self.last_job_state_save = Some(Instant::now());
+    }
+    
+    /// Select the attached drives of the interrupted jobs with the method each was running,
+    /// so the next erase starts them again after their last completed pass
+    fn resume_interrupted_jobs(&mut self) {
+        let serials: Vec<Option<String>> = if self.interrupted_jobs.iter().any(|job| job.serial.is_some()) {
+            let Some(serials) = self.drive_serials() else {
+                return;
+            };
+            serials
+        } else {
+            vec![None; self.drive_table.drives.len()]
+        };
+        
+        let mut problems = Vec::new();
+        let mut resumes = HashMap::new();
+        for job in &self.interrupted_jobs {
+            let algorithm = match job.algorithm.parse::<WipingAlgorithm>() {
+                Ok(algorithm) => algorithm,
+                Err(e) => {
+                    problems.push(format!("{}: {}", job.describe(), e));
+                    continue;
+                }
+            };
+            if job.serial.is_none() {
+                problems.push(format!("{} has no recorded serial number, so it can't be told apart from another drive - erase it again from pass 1", job.describe()));
+                continue;
+            }
+            let attached = serials.iter().position(|serial| job.matches(serial.as_deref()));
+            match attached {
+                Some(index) => {
+                    resumes.insert(index, (algorithm, job.completed_passes + 1));
+                }
+                None => problems.push(format!("{} is not attached", job.describe())),
+            }
+        }
+        if resumes.is_empty() {
+            self.last_error_message = Some(format!("❌ Nothing to resume: {}", problems.join("; ")));
+            return;
+        }
+        
+        for (index, drive) in self.drive_table.drives.iter_mut().enumerate() {
+            drive.selected = resumes.contains_key(&index);
+            if let Some((algorithm, pass)) = resumes.get(&index) {
+                drive.status = format!("Resume: pass {} of {}", pass, algorithm.pass_count());
+                self.drive_algorithms.insert(index, algorithm.clone());
+            }
+        }
+        self.drive_table.select_all = false;
+        log::info!("⏯️  Selected {} interrupted job(s) for resuming", resumes.len());
+        self.last_error_message = Some(if problems.is_empty() {
+            format!("✅ {} interrupted drive(s) selected - confirm and erase to resume them", resumes.len())
+        } else {
+            format!("⚠️ {} interrupted drive(s) selected - confirm and erase to resume them; skipped {}", resumes.len(), problems.join("; "))
+        });
+        self.drive_resume = resumes;
+    }
+    
+    /// On launch, offer to pick up the wipes the last session left unfinished
+    fn show_resume_prompt(&mut self, ctx: &egui::Context) {
+        if !self.resume_prompt || self.interrupted_jobs.is_empty() {
+            return;
+        }
+        egui::Window::new("Resume incomplete jobs?")
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ctx, |ui| {
+                ui.label("These wipes were stopped before they finished:");
+                for job in &self.interrupted_jobs {
+                    ui.label(format!("• {}", job.describe()));
+                }
+                ui.label(egui::RichText::new("Resuming skips the passes already written; the last pass is always written again so it can be verified.")
+                    .small().weak());
+                ui.add_space(10.0);
+                ui.horizontal(|ui| {
+                    if ui.button("⏯️ Resume").clicked() {
+                        self.resume_interrupted_jobs();
+                        self.resume_prompt = false;
+                    }
+                    if ui.button("Later").clicked() {
+                        self.resume_prompt = false;
+                    }
+                    if ui.button("🗑️ Discard").clicked() {
+                        log::info!("🗑️  Discarded {} interrupted job(s)", self.interrupted_jobs.len());
+                        self.interrupted_jobs.clear();
+                        self.save_job_states();
+                        self.resume_prompt = false;
+                    }
+                });
+            });
+    }
+    
+    /// Size of a drive in bytes as the OS reported it, for progress until the wipe
+    /// reports the device's own size
+    fn drive_size_bytes(&self, drive_index: usize) -> u64 {
+        self.drive_table.drives.get(drive_index).map_or(0, |drive| drive.size_bytes)
     }
     
     fn generate_sanitization_report(&mut self) {
         let timestamp = chrono::Local::now().format("%Y%m%d_%H%M%S").to_string();
-        let filename = format!("sanitization_report_{}.txt", timestamp);
-        
-        let mut report = String::new();
-        report.push_str("SHREDX - Sanitization Report\n");
-        report.push_str(&format!("Generated: {}\n", chrono::Local::now().format("%Y-%m-%d %H:%M:%S")));
-        report.push_str(&format!("Erasure Method: {}\n", self.advanced_options.eraser_method));
-        report.push_str(&format!("Verification: {}\n", self.advanced_options.verification));
-        report.push_str("\n=== SANITIZED DRIVES ===\n");
-        
-        for drive in &self.drive_table.drives {
-            if drive.selected && drive.progress >= 1.0 {
-                report.push_str(&format!("✅ {} ({}): Complete\n", drive.name, drive.path));
-                report.push_str(&format!("   Size: {}\n", drive.size));
-                report.push_str(&format!("   Status: {}\n", drive.status));
+        let filename = format!("sanitization_report_{}.{}", timestamp, self.report_format.extension());
+        
+        // Only claim what every selected drive's wipe met; a substituted method can meet fewer
+        let mut standards: Option<Vec<String>> = None;
+        let mut methods: Vec<String> = Vec::new();
+        for (index, _) in self.drive_table.drives.iter().enumerate().filter(|(_, drive)| drive.selected) {
+            let selected = self.drive_algorithm(index);
+            if !methods.contains(&selected.to_string()) {
+                methods.push(selected.to_string());
             }
+            // A drive that never ran met nothing
+            let met = match self.drive_progress.get(&index).and_then(|progress| progress.lock().ok().map(|p| p.algorithm.clone())) {
+                Some(algorithm_used) => advanced_wiper::standards_met(&algorithm_used, &selected, self.drive_media(index).as_ref()),
+                None => Vec::new(),
+            };
+            standards = Some(match standards {
+                Some(common) => common.into_iter().filter(|standard| met.contains(standard)).collect(),
+                None => met,
+            });
         }
         
-        report.push_str("\n=== COMPLIANCE ===\n");
-        report.push_str("This sanitization process complies with:\n");
-        if self.advanced_options.eraser_method.contains("NIST") {
-            report.push_str("- NIST SP 800-88 Guidelines\n");
-        }
-        if self.advanced_options.eraser_method.contains("DoD") {
-            report.push_str("- DoD 5220.22-M Standards\n");
+        if methods.is_empty() {
+            methods.push(self.advanced_options.eraser_method());
         }
+        let report = SanitizationReport::new(
+            &methods.join(", "),
+            &self.certificate_formats_label(),
+            standards.unwrap_or_default(),
+            &self.drive_table.drives,
+        );
+        
+        let content = match report.render(self.report_format) {
+            Ok(content) => content,
+            Err(e) => {
+                self.last_error_message = Some(format!("❌ Failed to build report: {}", e));
+                return;
+            }
+        };
         
         // Try to save the report
-        match std::fs::write(&filename, report) {
+        let reports_dir = self.config.reports_dir();
+        let path = reports_dir.join(&filename);
+        match std::fs::create_dir_all(&reports_dir).and_then(|_| std::fs::write(&path, content)) {
             Ok(_) => {
-                self.last_error_message = Some(format!("✅ Report saved as: {}", filename));
+                self.last_error_message = Some(format!("✅ Report saved as: {}", path.display()));
             }
             Err(e) => {
                 self.last_error_message = Some(format!("❌ Failed to save report: {}", e));
@@ -678,6 +1687,37 @@ impl HDDApp {
     }
 }
 
+/// The wipe thread's error for a failed step: `DEVICE_REMOVED` when the device went
+/// away and `WIPE_STOPPED` when closing ShredX stopped it, so the drive ends up
+/// incomplete rather than as an ordinary failure
+fn wipe_failure(device_path: &str, context: &str, error: &std::io::Error) -> String {
+    if advanced_wiper::is_wipe_stopped_error(error) {
+        WIPE_STOPPED.to_string()
+    } else if advanced_wiper::device_removed(device_path, error) {
+        log::warn!("🔌 {} was removed during the wipe: {}", device_path, error);
+        DEVICE_REMOVED.to_string()
+    } else {
+        format!("{}: {}", context, error)
+    }
+}
+
+/// Progress callback for the sanitizer that reports into one drive's own channel
+fn forward_progress(wipe_progress: &Arc<Mutex<WipingProgress>>) -> Box<dyn Fn(SanitizationProgress)> {
+    let wipe_progress = Arc::clone(wipe_progress);
+    Box::new(move |p: SanitizationProgress| {
+        if let Ok(mut wp) = wipe_progress.lock() {
+            wp.bytes_processed = p.bytes_processed;
+            wp.total_bytes = p.total_bytes;
+            if p.current_pass > 0 {
+                wp.begin_pass(p.current_pass);
+            }
+            wp.total_passes = p.total_passes;
+            wp.estimated_time_remaining = p.estimated_time_remaining;
+            wp.current_pattern = p.current_operation;
+        }
+    })
+}
+
 impl eframe::App for HDDApp {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
         // Apply SHREDX theme
@@ -689,6 +1729,9 @@ impl eframe::App for HDDApp {
         // Set window title
         ctx.send_viewport_cmd(egui::ViewportCommand::Title("SHREDX - HDD Secure Wipe Tool".to_string()));
         
+        self.handle_close_request(ctx);
+        self.show_resume_prompt(ctx);
+        
         egui::CentralPanel::default().show(ctx, |ui| {
             // Show server authentication UI if server is enabled and not authenticated
             if self.server_config.is_server_enabled() && !self.auth_widget.is_authenticated() {
@@ -699,6 +1742,7 @@ impl eframe::App for HDDApp {
                     // Authentication state changed, check if now authenticated
                     if self.auth_widget.is_authenticated() {
                         self.refresh_disks();
+                        self.sync_certificates_on_login();
                     }
                 }
                 return; // Don't show main UI until server authenticated
@@ -725,11 +1769,25 @@ impl eframe::App for HDDApp {
             
             // Continuous progress updates for active sanitization processes
             let has_active_process = self.drive_table.drives.iter()
-                .any(|drive| drive.start_time.is_some() && drive.progress < 1.0);
+                .any(|drive| drive.is_active());
                 
             if has_active_process {
-                self.simulate_sanitization_progress();
-                ctx.request_repaint(); // Ensure UI updates continuously
+                // Wipe threads report through shared state, so a few refreshes a second
+                // is enough; repainting every frame would pin a core the wipe could use
+                let due = self.last_progress_refresh
+                    .is_none_or(|last| last.elapsed() >= PROGRESS_REFRESH_INTERVAL);
+                if due {
+                    self.refresh_sanitization_progress();
+                    self.last_progress_refresh = Some(Instant::now());
+                }
+                ctx.request_repaint_after(PROGRESS_REFRESH_INTERVAL);
+            } else {
+                self.last_progress_refresh = None;
+            }
+            
+            if !self.drive_benchmarks.is_empty() {
+                self.collect_benchmarks();
+                ctx.request_repaint_after(PROGRESS_REFRESH_INTERVAL);
             }
         
             // Main UI - only shown when authenticated
@@ -752,6 +1810,9 @@ impl HDDApp {
                     if self.server_config.is_server_enabled() {
                         // Server-based authentication
                         self.auth_widget.logout();
+                        if let Some(ref mut server_client) = self.server_client {
+                            server_client.logout();
+                        }
                     } else {
                         // Local authentication
                         self.auth_system.logout();
@@ -792,7 +1853,55 @@ impl HDDApp {
             match active_tab {
                 0 => {
                     // Drives tab
-                    self.drive_table.show(ui);
+                    match self.drive_table.show(ui) {
+                        Some(DriveRowAction::Analyze(index)) => {
+                            self.drive_table.select_only(index);
+                            self.analyze_drive(index);
+                            self.tab_widget.active_tab = 1;
+                        }
+                        Some(DriveRowAction::Benchmark(index)) => {
+                            self.benchmark_drive(index);
+                        }
+                        Some(DriveRowAction::ViewDetails(index)) => {
+                            self.drive_table.select_only(index);
+                            self.tab_widget.active_tab = 1;
+                        }
+                        Some(DriveRowAction::Wipe(index)) => {
+                            self.drive_table.select_only(index);
+                            self.handle_erase_request();
+                        }
+                        None => {}
+                    }
+                    
+                    ui.add_space(10.0);
+                    
+                    // Save the selection to document a batch, or load it to repeat one
+                    ui.horizontal(|ui| {
+                        ui.label("Selection file :");
+                        ui.add(egui::TextEdit::singleline(&mut self.selection_file)
+                            .hint_text("drive_selection.json or .csv")
+                            .desired_width(250.0));
+                        if ui.button("📤 Export Selection").clicked() {
+                            self.export_selection();
+                        }
+                        if ui.button("📥 Import Selection").clicked() {
+                            self.import_selection();
+                        }
+                    });
+                    
+                    // A job file planned in advance selects the drives and sets each one's method
+                    ui.horizontal(|ui| {
+                        ui.label("Batch job file :");
+                        ui.add(egui::TextEdit::singleline(&mut self.job_file)
+                            .hint_text("batch_job.json or .csv")
+                            .desired_width(250.0));
+                        if ui.button("📋 Load Batch Job").clicked() {
+                            self.import_job();
+                        }
+                        if !self.drive_algorithms.is_empty() && ui.button("✖ Clear Job").clicked() {
+                            self.clear_job();
+                        }
+                    });
                     
                     ui.add_space(30.0);
                     
@@ -804,7 +1913,55 @@ impl HDDApp {
                         (true, "Unauthenticated") // Allow unauthenticated users to sanitize
                     };
                     
-                    if self.advanced_options.show_with_permissions(ui, can_sanitize, user_role) {
+                    // Operator named on the completion certificates
+                    let signed_in = self.auth_system.current_user().map(|user| user.username.clone())
+                        .or_else(|| self.auth_widget.get_current_user().map(|user| user.to_string()));
+                    ui.horizontal(|ui| {
+                        ui.label("Operator :");
+                        if let Some(username) = signed_in {
+                            ui.label(format!("{} (signed in)", username));
+                        } else {
+                            ui.add(egui::TextEdit::singleline(&mut self.operator_name)
+                                .hint_text("Required")
+                                .desired_width(200.0));
+                            
+                            ui.add_space(20.0);
+                            
+                            ui.label("Operator ID :");
+                            ui.add(egui::TextEdit::singleline(&mut self.operator_id)
+                                .hint_text("Badge / employee ID")
+                                .desired_width(150.0));
+                        }
+                    });
+                    
+                    // Operator sign-off recorded on the completion certificates
+                    ui.horizontal(|ui| {
+                        ui.label("Operator signature :");
+                        ui.add(egui::TextEdit::singleline(&mut self.operator_signature)
+                            .hint_text("Technician name / sign-off")
+                            .desired_width(200.0));
+                        
+                        ui.add_space(20.0);
+                        
+                        ui.label("Witness :");
+                        ui.add(egui::TextEdit::singleline(&mut self.witness)
+                            .hint_text("Optional")
+                            .desired_width(200.0));
+                    });
+                    
+                    ui.add_space(10.0);
+                    
+                    let erase_requested = self.advanced_options.show_with_permissions(ui, can_sanitize, user_role);
+                    
+                    // Keep the algorithm in step with the eraser method dropdown
+                    let algorithm = self.advanced_options.algorithm();
+                    if algorithm != self.selected_algorithm {
+                        log::info!("🔧 Eraser method set to {}", algorithm);
+                        self.selected_algorithm = algorithm;
+                    }
+                    self.remember_selections();
+                    
+                    if erase_requested {
                         self.handle_erase_request();
                     }
                     
@@ -842,13 +1999,37 @@ impl HDDApp {
                                     ui.group(|ui| {
                                         ui.heading(&drive.name);
                                         ui.label(format!("Path: {}", disk_info.drive_letter));
-                                        ui.label(format!("Type: {}", disk_info.detailed_type));
+                                        let analyzed = self.drive_device_info.get(&i)
+                                            .and_then(|info| info.lock().ok().and_then(|info| info.clone()));
+                                        match analyzed {
+                                            Some(ref analyzed) => ui.label(format!("Type: {}", analyzed.device_type)),
+                                            None => ui.label(format!("Type: {}", disk_info.detailed_type)),
+                                        };
                                         ui.label(format!("File System: {}", disk_info.file_system));
                                         ui.label(format!("Total Space: {}", drive.size));
-                                        ui.label(format!("Used Space: {}", drive.used));
-                                        ui.label(format!("Free Space: {}", Self::format_bytes(disk_info.free_space)));
-                                        ui.label("Secure Erase: ❓ Detection needed");
-                                        ui.label("Encrypted: ❓ Detection needed");
+                                        if drive.size_bytes > 0 {
+                                            ui.label(format!("Used Space: {} ({:.0}%)", drive.used,
+                                                drive.used_bytes as f64 / drive.size_bytes as f64 * 100.0));
+                                        } else {
+                                            ui.label(format!("Used Space: {}", drive.used));
+                                        }
+                                        ui.label(format!("Free Space: {}", format_bytes(drive.size_bytes.saturating_sub(drive.used_bytes))));
+                                        if let Some(analyzed) = analyzed {
+                                            ui.label(format!("Vendor: {}", analyzed.vendor));
+                                            ui.label(format!("Model: {}", analyzed.model));
+                                            ui.label(format!("Serial: {}", analyzed.serial));
+                                            ui.label(format!("Interface: {}", analyzed.interface.label()));
+                                            ui.label(format!("Secure Erase: {}", if analyzed.supports_secure_erase { "✅ Supported" } else { "❌ Not supported" }));
+                                            ui.label(format!("Encrypted: {}", match analyzed.encryption_status {
+                                                EncryptionStatus::Unknown => "❓ Unknown",
+                                                EncryptionStatus::NotEncrypted => "🔓 Not encrypted",
+                                                EncryptionStatus::BitLocker => "🔐 BitLocker",
+                                                EncryptionStatus::Luks => "🔐 LUKS",
+                                            }));
+                                        } else {
+                                            ui.label("Secure Erase: ❓ Detection needed");
+                                            ui.label("Encrypted: ❓ Detection needed");
+                                        }
                                     });
                                 }
                             }
@@ -890,15 +2071,26 @@ impl HDDApp {
                                         }
                                         
                                         ui.add_space(10.0);
-                                        ui.label(format!("Method: {}", self.advanced_options.eraser_method));
-                                        ui.label(format!("Verification: {}", self.advanced_options.verification));
+                                        ui.label(format!("Method: {}", self.advanced_options.eraser_method()));
+                                        ui.label(format!("Certificates: {}", self.certificate_formats_label()));
                                         ui.label(format!("Completion Time: {}", 
                                             chrono::Local::now().format("%Y-%m-%d %H:%M:%S")));
                                         
                                         ui.add_space(10.0);
-                                        if ui.button("💾 Save Report").clicked() {
-                                            self.generate_sanitization_report();
-                                        }
+                                        ui.horizontal(|ui| {
+                                            ui.label("Format:");
+                                            egui::ComboBox::from_id_salt("report_format")
+                                                .selected_text(self.report_format.label())
+                                                .show_ui(ui, |ui| {
+                                                    for format in ReportFormat::all() {
+                                                        ui.selectable_value(&mut self.report_format, *format, format.label());
+                                                    }
+                                                });
+                                            
+                                            if ui.button("💾 Save Report").clicked() {
+                                                self.generate_sanitization_report();
+                                            }
+                                        });
                                     });
                                 }
                             } else {
@@ -922,15 +2114,15 @@ impl HDDApp {
                                     
                                     ui.label(format!(
                                         "Processed: {} / {}",
-                                        Self::format_bytes(progress.bytes_processed),
-                                        Self::format_bytes(progress.total_bytes)
+                                        format_bytes(progress.bytes_processed),
+                                        format_bytes(progress.total_bytes)
                                     ));
                                 }
                                 
                                 ui.add_space(10.0);
                                 ui.horizontal(|ui| {
                                     ui.label("🔧 Method:");
-                                    ui.label(&self.advanced_options.eraser_method);
+                                    ui.label(self.advanced_options.eraser_method());
                                 });
                                 
                                 // Show individual drive progress
@@ -940,6 +2132,7 @@ impl HDDApp {
                                     if drive.selected && drive.start_time.is_some() {
                                         ui.horizontal(|ui| {
                                             let status_icon = if drive.progress >= 1.0 { "✅" } 
+                                                           else if drive.interrupted { "⚠" }
                                                            else if drive.progress > 0.0 { "🔄" } 
                                                            else { "⏸" };
                                             ui.label(status_icon);
@@ -992,7 +2185,7 @@ impl HDDApp {
             ui.horizontal(|ui| {
                 if ui.button("🔄 Refresh").clicked() {
                     self.certificates = self.certificate_generator.load_certificates().unwrap_or_else(|e| {
-                        eprintln!("Warning: Could not load certificates: {}", e);
+                        log::warn!("Warning: Could not load certificates: {}", e);
                         Vec::new()
                     });
                 }
@@ -1001,6 +2194,49 @@ impl HDDApp {
                 ui.label(format!("Total certificates: {}", self.certificates.len()));
             });
             
+            ui.add_space(10.0);
+            
+            // Search and date range, applied to the locally stored certificates
+            let parse_day = |value: &str| chrono::NaiveDate::parse_from_str(value.trim(), "%Y-%m-%d").ok();
+            ui.horizontal(|ui| {
+                ui.label("🔎 Search:");
+                ui.add(egui::TextEdit::singleline(&mut self.certificate_search)
+                    .hint_text("Device name, path, serial or ID")
+                    .desired_width(220.0));
+                
+                ui.add_space(10.0);
+                ui.label("From:");
+                ui.add(egui::TextEdit::singleline(&mut self.certificate_from)
+                    .hint_text("YYYY-MM-DD")
+                    .desired_width(90.0));
+                ui.label("To:");
+                ui.add(egui::TextEdit::singleline(&mut self.certificate_to)
+                    .hint_text("YYYY-MM-DD")
+                    .desired_width(90.0));
+                
+                if ui.button("Clear").clicked() {
+                    self.certificate_search.clear();
+                    self.certificate_from.clear();
+                    self.certificate_to.clear();
+                }
+            });
+            
+            let from = parse_day(&self.certificate_from);
+            let to = parse_day(&self.certificate_to);
+            if (!self.certificate_from.trim().is_empty() && from.is_none())
+                || (!self.certificate_to.trim().is_empty() && to.is_none()) {
+                ui.colored_label(SecureTheme::DANGER_RED, "Dates must be in YYYY-MM-DD format; invalid bounds are ignored");
+            }
+            
+            let certificates_to_show: Vec<SanitizationCertificate> = self.certificates.iter()
+                .filter(|c| c.matches_search(&self.certificate_search) && c.issued_between(from, to))
+                .cloned()
+                .collect();
+            let revoked = self.revoked_certificates.lock().map(|revoked| revoked.clone()).unwrap_or_default();
+            if certificates_to_show.len() != self.certificates.len() {
+                ui.label(format!("Showing {} of {} certificates", certificates_to_show.len(), self.certificates.len()));
+            }
+            
             ui.add_space(20.0);
             
             if self.certificates.is_empty() {
@@ -1019,7 +2255,6 @@ impl HDDApp {
                 egui::ScrollArea::vertical()
                     .max_height(400.0)
                     .show(ui, |ui| {
-                        let certificates_to_show: Vec<SanitizationCertificate> = self.certificates.clone();
                         for (index, certificate) in certificates_to_show.iter().enumerate() {
                             ui.group(|ui| {
                                 ui.set_min_width(800.0);
@@ -1037,11 +2272,22 @@ impl HDDApp {
                                     
                                     ui.vertical(|ui| {
                                         ui.heading(&certificate.device_info.device_name);
-                                        ui.label(format!("Certificate ID: {}", &certificate.id[..8]));
+                                        ui.horizontal(|ui| {
+                                            let short_id = certificate.id.get(..8).unwrap_or(&certificate.id);
+                                            ui.label(format!("Certificate ID: {}", short_id))
+                                                .on_hover_text(&certificate.id);
+                                            if ui.small_button("📋 Copy ID").on_hover_text("Copy the full certificate ID").clicked() {
+                                                ui.output_mut(|o| o.copied_text = certificate.id.clone());
+                                            }
+                                        });
                                     });
                                     
                                     ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
                                         ui.label(certificate.timestamp.format("%Y-%m-%d %H:%M:%S").to_string());
+                                        if let Some(reason) = revoked.get(&certificate.id) {
+                                            ui.colored_label(SecureTheme::DANGER_RED, "🚫 Revoked")
+                                                .on_hover_text(format!("Revoked on the server: {}", reason));
+                                        }
                                     });
                                 });
                                 
@@ -1053,7 +2299,7 @@ impl HDDApp {
                                         ui.strong("Device Information:");
                                         ui.label(format!("Path: {}", certificate.device_info.device_path));
                                         ui.label(format!("Type: {}", certificate.device_info.device_type));
-                                        ui.label(format!("Capacity: {}", Self::format_bytes(certificate.device_info.capacity)));
+                                        ui.label(format!("Capacity: {}", format_bytes(certificate.device_info.capacity)));
                                     });
                                     
                                     ui.add_space(30.0);
@@ -1098,6 +2344,17 @@ impl HDDApp {
                                         }
                                     }
                                     
+                                    if ui.button("🌐 Save HTML").clicked() {
+                                        match self.certificate_generator.save_certificate_html(certificate) {
+                                            Ok(filepath) => {
+                                                self.last_error_message = Some(format!("✅ HTML report saved: {}", filepath));
+                                            }
+                                            Err(e) => {
+                                                self.last_error_message = Some(format!("❌ Failed to save HTML report: {}", e));
+                                            }
+                                        }
+                                    }
+                                    
                                     if self.server_config.is_server_enabled() && self.auth_widget.is_authenticated() {
                                         if ui.button("☁️ Upload to Server").clicked() {
                                             self.upload_certificate_to_server(certificate.clone());
@@ -1107,7 +2364,7 @@ impl HDDApp {
                                 });
                             });
                             
-                            if index < self.certificates.len() - 1 {
+                            if index + 1 < certificates_to_show.len() {
                                 ui.add_space(10.0);
                             }
                         }
@@ -1164,7 +2421,7 @@ impl HDDApp {
                     
                     if ui.button("🌐 Open Web Dashboard").clicked() {
                         if let Err(e) = webbrowser::open(&self.config.get_dashboard_url()) {
-                            eprintln!("Failed to open browser: {}", e);
+                            log::error!("Failed to open browser: {}", e);
                         }
                     }
                 } else {
@@ -1180,7 +2437,7 @@ impl HDDApp {
                 ui.horizontal(|ui| {
                     if ui.button("💾 Save Configuration").clicked() {
                         if let Err(e) = self.config.save() {
-                            eprintln!("Failed to save configuration: {}", e);
+                            log::error!("Failed to save configuration: {}", e);
                         } else {
                             // Update server client if configuration changed
                             #[cfg(feature = "server")]
@@ -1208,6 +2465,121 @@ impl HDDApp {
             
             ui.add_space(20.0);
             
+            // Thermal protection for long overwrites
+            ui.group(|ui| {
+                ui.heading("Thermal Protection");
+                ui.add_space(10.0);
+                
+                ui.checkbox(&mut self.config.thermal_throttling, "🌡️ Pause wiping when the drive gets too hot");
+                ui.add_enabled_ui(self.config.thermal_throttling, |ui| {
+                    ui.horizontal(|ui| {
+                        ui.label("Maximum drive temperature (°C):");
+                        ui.add(egui::DragValue::new(&mut self.config.max_drive_temperature_c).range(35..=85));
+                    });
+                });
+                ui.label("Uses the drive's SMART temperature; drives that report none are wiped without pauses.");
+                
+                ui.add_space(10.0);
+                ui.heading("Concurrency");
+                ui.add_space(10.0);
+                ui.horizontal(|ui| {
+                    ui.label("Maximum drives wiped at once:");
+                    ui.add(egui::DragValue::new(&mut self.config.max_concurrent_wipes).range(0..=32));
+                });
+                ui.label("0 wipes every selected drive at once. A low limit is gentler on shared USB hubs and controllers.");
+                
+                ui.add_space(10.0);
+                ui.heading("Verification");
+                ui.add_space(10.0);
+                ui.horizontal(|ui| {
+                    ui.label("Read back a sample after every");
+                    ui.add(egui::DragValue::new(&mut self.config.verify_every_passes).range(0..=MAX_OVERWRITE_PASSES));
+                    ui.label("passes");
+                });
+                ui.label("0 verifies only once the wipe is done. Reading back along the way stops a failing drive early on long multi-pass purges.");
+                
+                ui.add_space(10.0);
+                ui.heading("Certificate Formats");
+                ui.add_space(10.0);
+                ui.label("Write each certificate as:");
+                ui.horizontal(|ui| {
+                    for &format in CertificateFormat::all() {
+                        let mut enabled = self.config.certificate_formats.contains(&format);
+                        if ui.checkbox(&mut enabled, format.label()).changed() {
+                            if enabled {
+                                self.config.certificate_formats.push(format);
+                            } else {
+                                self.config.certificate_formats.retain(|&f| f != format);
+                            }
+                        }
+                    }
+                });
+                if self.config.certificate_formats.is_empty() {
+                    ui.colored_label(SecureTheme::WARNING_ORANGE, "⚠️ No certificate files will be written");
+                } else if !self.config.certificate_formats.contains(&CertificateFormat::Json) {
+                    ui.label("Without JSON, new certificates won't be listed in the Certificates tab after a restart.");
+                }
+                ui.checkbox(&mut self.config.attach_wipe_log, "📝 Attach the wipe log to each certificate as an appendix");
+                
+                ui.add_space(10.0);
+                ui.heading("Notifications");
+                ui.add_space(10.0);
+                ui.checkbox(&mut self.config.completion_notifications, "🔔 Desktop notification when wiping finishes");
+                
+                ui.add_space(10.0);
+                ui.heading("Diagnostics");
+                ui.add_space(10.0);
+                ui.horizontal(|ui| {
+                    ui.label("Log level:");
+                    egui::ComboBox::from_id_salt("log_level")
+                        .selected_text(self.config.log_level.as_str())
+                        .show_ui(ui, |ui| {
+                            for level in logging::LOG_LEVELS {
+                                ui.selectable_value(&mut self.config.log_level, level.to_string(), level);
+                            }
+                        });
+                });
+                let logs_dir = self.config.logs_dir();
+                ui.checkbox(&mut self.config.log_to_file,
+                    format!("📝 Also write a daily log file to {}", logs_dir.display()));
+                ui.label("Log settings take effect the next time the tool starts; --log-level overrides the level.");
+                
+                ui.add_space(10.0);
+                
+                if ui.button("💾 Save Configuration").clicked() {
+                    if let Err(e) = self.config.save() {
+                        log::error!("Failed to save configuration: {}", e);
+                    }
+                }
+            });
+            
+            ui.add_space(20.0);
+            
+            // Totals across every session, for reporting
+            ui.group(|ui| {
+                ui.heading("Lifetime Statistics");
+                ui.add_space(10.0);
+                
+                let stats = &self.lifetime_stats;
+                ui.label(format!("Data erased: {}", format_bytes(stats.bytes_erased)));
+                ui.label(format!("Drives erased: {} of {} processed", stats.drives_erased, stats.drives_processed));
+                ui.label(match stats.since {
+                    Some(since) => format!("Counting since: {}", since.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M")),
+                    None => "No drives processed yet".to_string(),
+                });
+                
+                ui.add_space(10.0);
+                
+                if ui.button("🔄 Reset Statistics").on_hover_text("Start counting from zero, e.g. for a new reporting period").clicked() {
+                    self.lifetime_stats.reset();
+                    if let Err(e) = self.lifetime_stats.save(&self.config.stats_file()) {
+                        log::error!("Failed to save lifetime statistics: {}", e);
+                    }
+                }
+            });
+            
+            ui.add_space(20.0);
+            
             // Application settings
             ui.group(|ui| {
                 ui.heading("Application Settings");
@@ -1251,59 +2623,122 @@ impl HDDApp {
                 
                 if ui.button("📁 Open Data Directory").clicked() {
                     if let Err(e) = webbrowser::open("file://.") {
-                        eprintln!("Failed to open directory: {}", e);
+                        log::error!("Failed to open directory: {}", e);
                     }
                 }
                 
                 ui.add_space(10.0);
                 
+                ui.checkbox(&mut self.allow_guarded_targets,
+                    "⚠️ Allow wiping network shares and virtual disks (this session only)");
+                
+                ui.add_space(10.0);
+                
+                // Kiosks may run from a read-only or shared working directory
+                ui.horizontal(|ui| {
+                    ui.label("Output directory:");
+                    ui.text_edit_singleline(&mut self.config.output_dir);
+                    if ui.button("💾 Apply").on_hover_text("Save certificates and reports here from now on").clicked() {
+                        self.apply_output_dir();
+                    }
+                });
+                
+                ui.add_space(10.0);
+                
                 ui.label("Configuration file location: ./config.json");
+                ui.label(format!("Statistics location: {}", self.config.stats_file().display()));
                 ui.label("User data location: ./users.json");
-                ui.label("Certificates location: ./reports/");
+                ui.label(format!("Certificates location: {}", self.config.certificates_dir().display()));
+                ui.label(format!("Reports location: {}", self.config.reports_dir().display()));
             });
         });
     }
     
+    /// Switch certificates, reports and statistics to the output directory entered in
+    /// Settings and load the certificates and statistics already there
+    fn apply_output_dir(&mut self) {
+        let certificates_dir = self.config.certificates_dir();
+        if let Err(e) = std::fs::create_dir_all(&certificates_dir) {
+            self.last_error_message = Some(format!("❌ Cannot use {} as the output directory: {}", self.config.output_dir, e));
+            return;
+        }
+        
+        self.certificate_generator = CertificateGenerator::new(&certificates_dir);
+        self.certificates = self.certificate_generator.load_certificates().unwrap_or_else(|e| {
+            log::warn!("Warning: Could not load certificates: {}", e);
+            Vec::new()
+        });
+        self.lifetime_stats = LifetimeStats::load(&self.config.stats_file());
+        self.last_error_message = Some(match self.config.save() {
+            Ok(()) => format!("✅ Certificates and reports will be saved to {}", self.config.output_dir),
+            Err(e) => format!("⚠️ Output directory changed for this session, but the configuration could not be saved: {}", e),
+        });
+    }
+    
     fn generate_completion_certificates(&mut self) {
         let end_time = chrono::Utc::now();
         let start_time = self.current_sanitization_start.unwrap_or(end_time);
         
-        // Get current user information
-        let user_info = if let Some(user) = self.auth_system.current_user() {
-            UserInfo {
-                username: user.username.clone(),
-                user_id: user.id.clone(),
-                organization: "HDD Tool User".to_string(),
-                role: "User".to_string(), // All users have the same role now
-            }
-        } else {
-            UserInfo {
-                username: "Unknown".to_string(),
-                user_id: "unknown".to_string(),
-                organization: "HDD Tool User".to_string(),
-                role: "User".to_string(),
-            }
+        // Operator sign-off captured on the Drives tab
+        let operator_signature = Some(self.operator_signature.trim().to_string()).filter(|s| !s.is_empty());
+        let witness = Some(self.witness.trim().to_string()).filter(|s| !s.is_empty());
+        
+        // The operator captured when the wipe started; a certificate without one is not issued
+        let Some((username, user_id)) = self.wipe_operator.clone().or_else(|| self.current_operator()) else {
+            self.last_error_message = Some("❌ No operator recorded for this wipe - certificates were not generated".to_string());
+            return;
+        };
+        let user_info = UserInfo {
+            username,
+            user_id,
+            organization: "HDD Tool User".to_string(),
+            role: "User".to_string(), // All users have the same role now
+            operator_signature,
+            witness,
         };
 
         // Generate certificates for each completed drive
+        let mut outcomes = Vec::new();
         for (drive_index, drive) in self.drive_table.drives.iter().enumerate() {
-            if drive.selected && drive.progress >= 1.0 {
+            if let Some(result) = self.drive_results.get(&drive_index) {
                 if let Some(disk_info) = self.disks.get(drive_index) {
-                    // Create device certificate info
+                    // Create device certificate info, using the hardware analysis when it ran
+                    let analyzed = self.drive_device_info.get(&drive_index)
+                        .and_then(|info| info.lock().ok().and_then(|info| info.clone()));
+                    let smart = self.drive_smart.get(&drive_index)
+                        .and_then(|smart| smart.lock().ok().map(|s| s.clone()));
                     let device_info = DeviceCertificateInfo {
                         device_path: disk_info.drive_letter.clone(),
                         device_name: drive.name.clone(),
-                        device_type: disk_info.drive_type.clone(),
-                        manufacturer: "Unknown".to_string(), // Would be detected from actual hardware
-                        model: "Unknown".to_string(),
-                        serial_number: "N/A".to_string(),
+                        device_type: analyzed.as_ref().map_or_else(|| disk_info.drive_type.clone(), |a| a.device_type.to_string()),
+                        manufacturer: analyzed.as_ref().map_or_else(|| "Unknown".to_string(), |a| a.vendor.clone()),
+                        model: analyzed.as_ref().map_or_else(|| "Unknown".to_string(), |a| a.model.clone()),
+                        serial_number: analyzed.as_ref().map_or_else(|| "N/A".to_string(), |a| a.serial.clone()),
                         capacity: disk_info.total_space,
-                        sector_size: 512, // Standard sector size
-                        supports_secure_erase: false, // Would be detected
-                        supports_crypto_erase: false,
-                        encryption_status: "Unknown".to_string(),
+                        sector_size: analyzed.as_ref().map_or(512, |a| a.sector_size),
+                        supports_secure_erase: analyzed.as_ref().is_some_and(|a| a.supports_secure_erase),
+                        supports_crypto_erase: analyzed.as_ref().is_some_and(|a| a.supports_crypto_erase),
+                        encryption_status: analyzed.as_ref().map_or("Unknown", |a| a.encryption_status.label()).to_string(),
+                        interface: analyzed.as_ref().map_or("Unknown", |a| a.interface.label()).to_string(),
+                        smart_before: smart.as_ref().and_then(|s| s.before.clone()),
+                        smart_after: smart.as_ref().and_then(|s| s.after.clone()),
+                        profile: analyzed.clone(),
+                        raw_identify: self.drive_identify.get(&drive_index)
+                            .and_then(|raw| raw.lock().ok().and_then(|raw| raw.as_ref().map(|raw| general_purpose::STANDARD.encode(raw)))),
                     };
 
+                    let device_hashes = self.drive_hashes.get(&drive_index)
+                        .and_then(|hashes| hashes.lock().ok().map(|h| h.clone()));
+                    let mut wipe_errors = self.drive_progress.get(&drive_index)
+                        .and_then(|progress| progress.lock().ok().map(|p| p.errors.clone()))
+                        .unwrap_or_default();
+                    // A wipe that failed outright must never certify as successful
+                    if let Err(e) = result {
+                        if !wipe_errors.contains(e) {
+                            wipe_errors.push(e.clone());
+                        }
+                    }
+                    
                     // Create sanitization info
                     let duration = end_time.signed_duration_since(start_time).num_seconds() as u64;
                     let speed = if duration > 0 {
@@ -1312,41 +2747,101 @@ impl HDDApp {
                         0.0
                     };
 
+                    // The wipe thread records the algorithm it actually ran, which differs from the
+                    // selection when the device couldn't perform it
+                    let selected = self.drive_algorithm(drive_index);
+                    let algorithm_used = self.drive_progress.get(&drive_index)
+                        .and_then(|progress| progress.lock().ok().map(|p| p.algorithm.clone()))
+                        .unwrap_or_else(|| selected.clone());
+                    let algorithm = if algorithm_used == WipingAlgorithm::QuickSanitize {
+                        format!("{} - NOT a full overwrite: only partition tables, the first/last {} MB and filesystem metadata were destroyed",
+                            algorithm_used, sanitization::QUICK_EDGE_SIZE / (1024 * 1024))
+                    } else if algorithm_used == WipingAlgorithm::CryptoErase {
+                        format!("{} - key destruction: the {} key material was destroyed, encrypted data was not overwritten",
+                            algorithm_used, analyzed.as_ref().map_or("volume", |a| a.encryption_status.label()))
+                    } else if selected == WipingAlgorithm::NistAuto {
+                        format!("{} (chosen by NIST SP 800-88 Auto for {} sensitivity data on {})", algorithm_used,
+                            self.advanced_options.sensitivity.label(), analyzed.as_ref().map_or("this device".to_string(), |a| a.device_type.to_string()))
+                    } else if selected == WipingAlgorithm::AustralianIsm && algorithm_used != selected {
+                        format!("{} (chosen by the Australian ISM for {} media)", algorithm_used,
+                            analyzed.as_ref().map_or("this device".to_string(), |a| a.device_type.to_string()))
+                    } else if algorithm_used == selected {
+                        algorithm_used.to_string()
+                    } else {
+                        format!("{} (substituted for {}, which the device could not run)", algorithm_used, selected)
+                    };
+                    let resumed_at = self.drive_progress.get(&drive_index)
+                        .and_then(|progress| progress.lock().ok().map(|p| p.resume_from_pass))
+                        .unwrap_or(0);
+                    let algorithm = if resumed_at > 1 {
+                        format!("{} (resumed at pass {} after an interruption)", algorithm, resumed_at)
+                    } else {
+                        algorithm
+                    };
+
+                    let stopped = matches!(result, Err(e) if e == WIPE_STOPPED);
                     let sanitization_info = SanitizationInfo {
-                        method: self.advanced_options.eraser_method.clone(),
-                        algorithm: format!("{:?}", self.selected_algorithm),
-                        passes_completed: match self.selected_algorithm {
-                            WipingAlgorithm::DoD522022M => 3,
-                            WipingAlgorithm::Gutmann => 35,  
-                            WipingAlgorithm::SevenPass => 7,
-                            WipingAlgorithm::ThreePass => 3,
-                            WipingAlgorithm::TwoPass => 2,
-                            _ => 1,
+                        method: selected.to_string(),
+                        algorithm,
+                        // A wipe stopped at shutdown ran only the passes it timed, after any an
+                        // earlier session wrote before it was resumed
+                        passes_completed: if stopped {
+                            self.drive_progress.get(&drive_index)
+                                .and_then(|progress| progress.lock().ok().map(|p| p.pass_timings.len() as u32))
+                                .unwrap_or(0) + resumed_at.saturating_sub(1)
+                        } else {
+                            algorithm_used.pass_count()
+                        },
+                        // A removed or stopped device only got as far as the wipe had written
+                        total_bytes_processed: if algorithm_used == WipingAlgorithm::QuickSanitize
+                            || matches!(result, Err(e) if e == DEVICE_REMOVED) || stopped {
+                            self.drive_progress.get(&drive_index)
+                                .and_then(|progress| progress.lock().ok().map(|p| p.bytes_processed))
+                                .unwrap_or(0)
+                        } else {
+                            disk_info.total_space
                         },
-                        total_bytes_processed: disk_info.total_space,
                         start_time,
                         end_time,
                         duration_seconds: duration,
                         average_speed_mbps: speed,
-                        success: true,
-                        error_count: 0,
+                        success: wipe_errors.is_empty(),
+                        error_count: wipe_errors.len() as u32,
+                        pre_wipe_sha256: device_hashes.as_ref().and_then(|h| h.pre_wipe.clone()),
+                        post_wipe_sha256: device_hashes.as_ref().and_then(|h| h.post_wipe.clone()),
+                        error_policy: Some(self.advanced_options.error_policy.label().to_string()),
+                        errors: wipe_errors,
+                        pass_timings: self.drive_progress.get(&drive_index)
+                            .and_then(|progress| progress.lock().ok().map(|p| p.pass_timings.clone()))
+                            .unwrap_or_default(),
                     };
 
                     // Generate certificate
                     match self.certificate_generator.generate_certificate(
                         device_info,
                         sanitization_info,
+                        advanced_wiper::standards_met(&algorithm_used, &selected, analyzed.as_ref().map(|a| &a.device_type)),
+                        device_hashes.map(|h| h.sampled_sectors).unwrap_or_default(),
                         user_info.clone(),
+                        if self.config.attach_wipe_log {
+                            self.drive_logs.get(&drive_index)
+                                .and_then(|job_log| job_log.lock().ok().map(|lines| lines.clone()))
+                                .unwrap_or_default()
+                        } else {
+                            Vec::new()
+                        },
                     ) {
                         Ok(certificate) => {
-                            // Save certificate locally
-                            if let Err(e) = self.certificate_generator.save_certificate_local(&certificate) {
-                                eprintln!("Warning: Could not save certificate locally: {}", e);
-                            }
-
-                            // Save human-readable report
-                            if let Err(e) = self.certificate_generator.save_certificate_report(&certificate) {
-                                eprintln!("Warning: Could not save certificate report: {}", e);
+                            outcomes.push((drive.name.clone(), certificate.sanitization_info.success));
+                            self.lifetime_stats.record(certificate.sanitization_info.success,
+                                certificate.sanitization_info.total_bytes_processed);
+                            
+                            // Save in the formats chosen in Settings; only the JSON copy is
+                            // reloaded into the Certificates tab
+                            for &format in &self.config.certificate_formats {
+                                if let Err(e) = self.certificate_generator.save_certificate_as(&certificate, format) {
+                                    log::warn!("Warning: Could not save {} certificate: {}", format.extension(), e);
+                                }
                             }
 
                             // Add to local certificates list
@@ -1358,38 +2853,42 @@ impl HDDApp {
                                     self.upload_certificate_to_server(certificate);
                                 } else if self.auth_system.is_authenticated() {
                                     // Could upload via local auth too if we had server integration
-                                    println!("Certificate ready for server upload when server connection is available");
+                                    log::info!("Certificate ready for server upload when server connection is available");
                                 }
                             }
                         }
                         Err(e) => {
-                            eprintln!("Error generating certificate for {}: {}", drive.name, e);
+                            log::error!("Error generating certificate for {}: {}", drive.name, e);
+                            outcomes.push((drive.name.clone(), false));
+                            // Without a certificate the wipe can't be vouched for, so nothing counts as erased
+                            self.lifetime_stats.record(false, 0);
                         }
                     }
                 }
             }
         }
 
+        if let Err(e) = self.lifetime_stats.save(&self.config.stats_file()) {
+            log::warn!("Warning: Could not save lifetime statistics: {}", e);
+        }
+
+        if self.config.completion_notifications {
+            notification::notify_wipe_complete(&outcomes);
+        }
+
         self.current_sanitization_start = None; // Reset for next sanitization
     }
 
     fn upload_certificate_to_server(&self, certificate: SanitizationCertificate) {
         if let Some(ref server_client) = self.server_client {
-            let certificate_data = match serde_json::to_string(&certificate) {
-                Ok(data) => data,
+            let (certificate_data, device_info, method) = match upload_fields(&certificate) {
+                Ok(fields) => fields,
                 Err(e) => {
-                    eprintln!("Error serializing certificate: {}", e);
+                    log::error!("Error serializing certificate: {}", e);
                     return;
                 }
             };
 
-            let device_info = format!("{} - {} ({})", 
-                certificate.device_info.device_name,
-                certificate.device_info.device_type,
-                certificate.device_info.device_path);
-
-            let method = certificate.sanitization_info.method.clone();
-
             // Clone server_client for async operation
             let server_client_clone = server_client.clone();
             
@@ -1398,18 +2897,165 @@ impl HDDApp {
                 match server_client_clone.upload_certificate(certificate_data, device_info, method).await {
                     Ok(response) => {
                         if response.success {
-                            println!("✅ Certificate uploaded to server successfully!");
+                            log::info!("✅ Certificate uploaded to server successfully!");
                         } else {
-                            println!("❌ Server rejected certificate: {}", response.message);
+                            log::error!("❌ Server rejected certificate: {}", response.message);
                         }
                     }
                     Err(e) => {
-                        println!("❌ Failed to upload certificate to server: {}", e);
+                        if e.downcast_ref::<ProxyError>().is_some() {
+                            log::info!("🌐 Proxy error while uploading certificate - check the proxy settings: {}", e);
+                        } else {
+                            log::error!("❌ Failed to upload certificate to server: {}", e);
+                        }
                     }
                 }
             });
         }
     }
+    
+    /// Sign in to the server with the credentials just used to log in, then upload every
+    /// local certificate the server doesn't have yet, e.g. ones made while auto-upload was off.
+    /// Certificates that differ between the two sides are settled by the configured conflict policy.
+    fn sync_certificates_on_login(&mut self) {
+        let Some((username, password)) = self.auth_widget.take_server_credentials() else {
+            return;
+        };
+        let Some(ref server_client) = self.server_client else {
+            return;
+        };
+        let mut server_client = server_client.clone();
+        let local_certificates = self.certificates.clone();
+        let certificate_generator = self.certificate_generator.clone();
+        let policy = self.server_config.sync_conflict_policy;
+        let revoked_certificates = Arc::clone(&self.revoked_certificates);
+        let request = LoginRequest { username, password };
+        
+        tokio::spawn(async move {
+            let login = server_client.login(request).await.map_err(|e| e.to_string());
+            match login {
+                Ok(response) if response.success => {}
+                Ok(response) => {
+                    log::error!("❌ Server login failed, certificates not synced: {}", response.message);
+                    return;
+                }
+                Err(e) => {
+                    log::error!("❌ Could not reach the server, certificates not synced: {}", e);
+                    return;
+                }
+            }
+            
+            let listed = server_client.get_user_certificates().await.map_err(|e| e.to_string());
+            let stored = match listed {
+                Ok(response) if response.success => response.data.unwrap_or_default(),
+                Ok(response) => {
+                    log::error!("❌ Could not list the server's certificates, not syncing: {}", response.message);
+                    return;
+                }
+                Err(e) => {
+                    log::error!("❌ Could not list the server's certificates, not syncing: {}", e);
+                    return;
+                }
+            };
+            
+            // Newest server copy of each certificate id; a local-wins upload leaves the older one
+            // behind. Copies in a schema this build can't read are known by id only.
+            let mut on_server: HashMap<String, (String, Option<SanitizationCertificate>)> = HashMap::new();
+            for record in stored {
+                let Ok(data) = serde_json::from_str::<serde_json::Value>(&record.certificate_data) else {
+                    continue;
+                };
+                let Some(id) = data["id"].as_str() else {
+                    continue;
+                };
+                if record.revoked {
+                    let reason = record.revocation_reason.clone().unwrap_or_else(|| "no reason given".to_string());
+                    if let Ok(mut revoked) = revoked_certificates.lock() {
+                        revoked.insert(id.to_string(), reason);
+                    }
+                }
+                if on_server.get(id).is_none_or(|(created_at, _)| *created_at < record.created_at) {
+                    let certificate = serde_json::from_value::<SanitizationCertificate>(data.clone()).ok();
+                    on_server.insert(id.to_string(), (record.created_at, certificate));
+                }
+            }
+            
+            let mut to_upload: Vec<&SanitizationCertificate> = Vec::new();
+            let mut conflicts = 0;
+            for local in &local_certificates {
+                match on_server.get(&local.id) {
+                    // A server copy kept by an earlier keep-both resolution
+                    None if local.id.strip_suffix("-server").is_some_and(|id| on_server.contains_key(id)) => {}
+                    None => to_upload.push(local),
+                    Some((_, Some(server))) if server.certificate_hash != local.certificate_hash => {
+                        conflicts += 1;
+                        if resolve_sync_conflict(policy, &certificate_generator, local, server) {
+                            to_upload.push(local);
+                        }
+                    }
+                    Some(_) => {}
+                }
+            }
+            if to_upload.is_empty() {
+                log::info!("☁️  All {} local certificate(s) are already on the server ({} conflict(s))", local_certificates.len(), conflicts);
+                return;
+            }
+            
+            log::info!("☁️  Uploading {} local certificate(s) to the server", to_upload.len());
+            let mut uploaded = 0;
+            for certificate in to_upload {
+                let Ok((certificate_data, device_info, method)) = upload_fields(certificate) else {
+                    continue;
+                };
+                let result = server_client.upload_certificate(certificate_data, device_info, method).await
+                    .map_err(|e| e.to_string());
+                match result {
+                    Ok(response) if response.success => uploaded += 1,
+                    Ok(response) => log::error!("❌ Server rejected certificate {}: {}", certificate.id, response.message),
+                    Err(e) => log::error!("❌ Failed to upload certificate {}: {}", certificate.id, e),
+                }
+            }
+            log::info!("✅ Synced {} certificate(s) to the server ({} conflict(s))", uploaded, conflicts);
+            if conflicts > 0 && policy != SyncConflictPolicy::LocalWins {
+                log::info!("📜 Local certificates changed; refresh the Certificates tab to see them");
+            }
+        });
+    }
+}
+
+/// Log a certificate that differs between this machine and the server and apply the policy
+/// to the local side. Returns whether the local copy should be uploaded.
+fn resolve_sync_conflict(policy: SyncConflictPolicy, generator: &CertificateGenerator,
+                         local: &SanitizationCertificate, server: &SanitizationCertificate) -> bool {
+    log::warn!("⚠️  Certificate {} differs from the server's copy (local hash {}, server hash {}); resolving as {:?}",
+        local.id, local.certificate_hash, server.certificate_hash, policy);
+    let saved = match policy {
+        SyncConflictPolicy::LocalWins => return true,
+        SyncConflictPolicy::ServerWins => generator.replace_certificate_local(server),
+        SyncConflictPolicy::KeepBoth => {
+            // The hash covers the id, so the renamed copy needs its own
+            let mut copy = server.clone();
+            copy.id = format!("{}-server", server.id);
+            generator.calculate_certificate_hash(&copy)
+                .and_then(|hash| {
+                    copy.certificate_hash = hash;
+                    generator.save_certificate_local(&copy)
+                })
+        }
+    };
+    if let Err(e) = saved {
+        log::error!("❌ Could not save the server's copy of certificate {}: {}", local.id, e);
+    }
+    false
+}
+
+/// (certificate JSON, device description, method) as the server's upload endpoint takes them
+fn upload_fields(certificate: &SanitizationCertificate) -> serde_json::Result<(String, String, String)> {
+    let device_info = format!("{} - {} ({})",
+        certificate.device_info.device_name,
+        certificate.device_info.device_type,
+        certificate.device_info.device_path);
+    Ok((serde_json::to_string(certificate)?, device_info, certificate.sanitization_info.method.clone()))
 }
 
 fn main() -> eframe::Result<()> {
@@ -1421,6 +3067,52 @@ fn main() -> eframe::Result<()> {
 
     // Enter the runtime context to allow tokio::spawn to work
     let _enter = rt.enter();
+    
+    let args: Vec<String> = std::env::args().collect();
+    let arg_value = |flag: &str| args.iter().position(|arg| arg == flag).and_then(|i| args.get(i + 1));
+    
+    // --log-level <level> overrides the level saved in Settings for this run
+    let config = AppConfig::load();
+    let level_name = arg_value("--log-level").unwrap_or(&config.log_level);
+    let Some(log_level) = logging::parse_level(level_name) else {
+        eprintln!("❌ Unknown log level {}; use one of {}", level_name, logging::LOG_LEVELS.join(", "));
+        std::process::exit(2);
+    };
+    let logs_dir = config.logs_dir();
+    let _log_guard = logging::init(log_level, config.log_to_file.then_some(logs_dir.as_path()));
+    
+    // --target-file <path> [--sector-size <bytes>] wipes a regular file instead of a drive
+    if let Some(target_file) = arg_value("--target-file") {
+        let sector_size = match arg_value("--sector-size").map(|size| size.parse::<u32>()) {
+            Some(Ok(size)) => size,
+            Some(Err(_)) => {
+                log::error!("❌ --sector-size must be a number of bytes");
+                std::process::exit(2);
+            }
+            None => devices::virtual_device::DEFAULT_SECTOR_SIZE,
+        };
+        if let Err(e) = devices::virtual_device::register(target_file, sector_size) {
+            log::error!("❌ Cannot use {} as a virtual device: {}", target_file, e);
+            std::process::exit(2);
+        }
+    }
+    
+    // --attach-image <path> [--sector-size <bytes>] puts a disk image on a loop device for
+    // the session, so it is wiped through the real block-device path
+    #[cfg(target_os = "linux")]
+    let attached_image = match arg_value("--attach-image") {
+        Some(image) => {
+            let sector_size = arg_value("--sector-size").and_then(|size| size.parse::<u32>().ok());
+            match devices::loop_device::attach(image, sector_size) {
+                Ok(loop_path) => Some(loop_path),
+                Err(e) => {
+                    log::error!("❌ Cannot attach {}: {}", image, e);
+                    std::process::exit(2);
+                }
+            }
+        }
+        None => None,
+    };
 
     let native_options = eframe::NativeOptions {
         viewport: egui::ViewportBuilder::default()
@@ -1429,9 +3121,18 @@ fn main() -> eframe::Result<()> {
         ..Default::default()
     };
     
-    eframe::run_native(
+    let result = eframe::run_native(
         "SHREDX - HDD Secure Wipe Tool",
         native_options,
         Box::new(|_cc| Ok(Box::new(HDDApp::new()))),
-    )
+    );
+    
+    #[cfg(target_os = "linux")]
+    if let Some(loop_path) = attached_image {
+        if let Err(e) = devices::loop_device::detach(&loop_path) {
+            log::warn!("⚠️  {}", e);
+        }
+    }
+    
+    result
 }
\ No newline at end of file
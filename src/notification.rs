@@ -0,0 +1,78 @@
+//! Desktop notifications for unattended wipes
+//!
+//! Uses the notifier that ships with each platform (notify-send, osascript, or a
+//! PowerShell toast) rather than a notification crate. If the notifier is missing
+//! the failure is logged and the wipe result is unaffected.
+
+use std::io;
+use std::process::Command;
+
+/// Show one notification summarizing the outcome of every drive in the batch
+pub fn notify_wipe_complete(outcomes: &[(String, bool)]) {
+    if outcomes.is_empty() {
+        return;
+    }
+
+    let failed = outcomes.iter().filter(|(_, success)| !success).count();
+    let title = if failed == 0 {
+        format!("Wipe complete: {} drive(s) sanitized", outcomes.len())
+    } else {
+        format!("Wipe finished: {} of {} drive(s) failed", failed, outcomes.len())
+    };
+    let body = outcomes.iter()
+        .map(|(name, success)| format!("{} {}", if *success { "✅" } else { "❌" }, name))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    // The notifier can take a moment to start; keep it off the UI thread
+    std::thread::spawn(move || {
+        if let Err(e) = show(&title, &body) {
+            log::warn!("⚠️  Could not show desktop notification: {}", e);
+        }
+    });
+}
+
+#[cfg(target_os = "linux")]
+fn show(title: &str, body: &str) -> io::Result<()> {
+    run(Command::new("notify-send").args(["--app-name=SHREDX", title, body]))
+}
+
+#[cfg(target_os = "macos")]
+fn show(title: &str, body: &str) -> io::Result<()> {
+    let quote = |s: &str| format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""));
+    let script = format!("display notification {} with title {}", quote(body), quote(title));
+    run(Command::new("osascript").args(["-e", &script]))
+}
+
+#[cfg(windows)]
+fn show(title: &str, body: &str) -> io::Result<()> {
+    // Text goes through the environment so it never needs PowerShell escaping. Toasts
+    // need a registered app ID; PowerShell's own is always present.
+    const SCRIPT: &str = r#"
+[Windows.UI.Notifications.ToastNotificationManager, Windows.UI.Notifications, ContentType = WindowsRuntime] > $null
+$template = [Windows.UI.Notifications.ToastNotificationManager]::GetTemplateContent([Windows.UI.Notifications.ToastTemplateType]::ToastText02)
+$text = $template.GetElementsByTagName('text')
+$text.Item(0).AppendChild($template.CreateTextNode($env:SHREDX_NOTIFY_TITLE)) > $null
+$text.Item(1).AppendChild($template.CreateTextNode($env:SHREDX_NOTIFY_BODY)) > $null
+$toast = [Windows.UI.Notifications.ToastNotification]::new($template)
+[Windows.UI.Notifications.ToastNotificationManager]::CreateToastNotifier('{1AC14E77-02E7-4E5D-B744-2EB1AE5198B7}\WindowsPowerShell\v1.0\powershell.exe').Show($toast)
+"#;
+    run(Command::new("powershell")
+        .args(["-NoProfile", "-NonInteractive", "-Command", SCRIPT])
+        .env("SHREDX_NOTIFY_TITLE", title)
+        .env("SHREDX_NOTIFY_BODY", body))
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", windows)))]
+fn show(_title: &str, _body: &str) -> io::Result<()> {
+    Err(io::Error::new(io::ErrorKind::Unsupported, "desktop notifications are not supported on this platform"))
+}
+
+fn run(command: &mut Command) -> io::Result<()> {
+    let output = command.output()?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(io::Error::new(io::ErrorKind::Other, String::from_utf8_lossy(&output.stderr).trim().to_string()))
+    }
+}
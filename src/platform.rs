@@ -11,6 +11,22 @@ pub struct DriveInfo {
     pub drive_type: String,
     pub total_space: u64,
     pub free_space: u64,
+    /// Why the drive must not be wiped without an explicit override (a network share,
+    /// RAM disk or virtual disk); `None` for local media
+    pub wipe_guard: Option<String>,
+}
+
+/// Disk details reported by WMI
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(not(windows), allow(dead_code))]
+pub struct WmiDiskInfo {
+    pub disk_number: u32,
+    pub model: String,
+    pub serial: String,
+    pub size_bytes: u64,
+    pub media_type: String, // "HDD", "SSD", "SCM" or "Unspecified"
+    pub bus_type: String,   // "SATA", "NVMe", "USB", "RAID", "Virtual", ...
+    pub sector_size: u32,   // Logical sector size, 0 if not reported
 }
 
 #[cfg(windows)]
@@ -48,16 +64,30 @@ pub mod windows_impl {
                     let drive_path_wide: Vec<u16> = drive_path.encode_utf16().chain(std::iter::once(0)).collect();
                     let drive_path_pwstr = PWSTR::from_raw(drive_path_wide.as_ptr() as *mut u16);
                     
-                    // Get drive type
-                    let drive_type = match GetDriveTypeW(drive_path_pwstr) {
-                        DRIVE_FIXED => "Fixed Drive (HDD/SSD)",
-                        DRIVE_REMOVABLE => "Removable Drive (USB/Floppy)",
-                        DRIVE_CDROM => "CD-ROM Drive",
-                        DRIVE_REMOTE => "Network Drive",
-                        DRIVE_RAMDISK => "RAM Disk",
-                        DRIVE_UNKNOWN => "Unknown",
-                        _ => "Other",
-                    }.to_string();
+                    // Get drive type; WMI tells a fixed drive's media and bus apart
+                    let raw_drive_type = GetDriveTypeW(drive_path_pwstr);
+                    let wmi_disk = if raw_drive_type == DRIVE_FIXED { query_wmi_disk(&drive_path) } else { None };
+                    let drive_type = match raw_drive_type {
+                        DRIVE_FIXED => wmi_disk.as_ref().and_then(fixed_drive_type)
+                            .unwrap_or_else(|| "Fixed Drive (HDD/SSD)".to_string()),
+                        DRIVE_REMOVABLE => "Removable Drive (USB/Floppy)".to_string(),
+                        DRIVE_CDROM => "CD-ROM Drive".to_string(),
+                        DRIVE_REMOTE => "Network Drive".to_string(),
+                        DRIVE_RAMDISK => "RAM Disk".to_string(),
+                        DRIVE_UNKNOWN => "Unknown".to_string(),
+                        _ => "Other".to_string(),
+                    };
+                    
+                    // A mapped share lives on a server, and a mounted VHD is a file on another disk
+                    let wipe_guard = match raw_drive_type {
+                        DRIVE_REMOTE => Some("network share"),
+                        DRIVE_RAMDISK => Some("RAM disk"),
+                        DRIVE_CDROM => Some("optical drive"),
+                        DRIVE_FIXED => wmi_disk.as_ref()
+                            .filter(|disk| disk.bus_type == "Virtual")
+                            .map(|_| "virtual disk (VHD)"),
+                        _ => None,
+                    }.map(str::to_string);
                     
                     // Get volume information
                     let mut volume_name_buffer = [0u16; 256];
@@ -107,6 +137,7 @@ pub mod windows_impl {
                         drive_type,
                         total_space,
                         free_space,
+                        wipe_guard,
                     });
                 }
             }
@@ -114,6 +145,76 @@ pub mod windows_impl {
         
         Ok(drives)
     }
+    
+    /// What a fixed drive is, from its WMI bus and media type; `None` when WMI can't tell
+    fn fixed_drive_type(disk: &WmiDiskInfo) -> Option<String> {
+        if crate::advanced_wiper::is_hybrid_model(&disk.model) {
+            return Some(format!("SSHD (Hybrid Drive) ({})", disk.model));
+        }
+        match (disk.bus_type.as_str(), disk.media_type.as_str()) {
+            ("NVMe", _) => Some(format!("NVMe SSD ({})", disk.model)),
+            ("USB", _) => Some(format!("USB Drive ({})", disk.model)),
+            (_, "SSD") => Some(format!("SSD (Solid State Drive) ({})", disk.model)),
+            (_, "HDD") => Some(format!("HDD (Hard Disk Drive) ({})", disk.model)),
+            _ => None,
+        }
+    }
+    
+    /// Look a disk up in WMI (`Win32_DiskDrive` for model/serial/size, `MSFT_PhysicalDisk`
+    /// for media and bus type). `drive` is a drive letter ("E:", "E:\\") or a
+    /// `\\.\PhysicalDriveN` path.
+    pub fn query_wmi_disk(drive: &str) -> Option<WmiDiskInfo> {
+        let disk_number = match drive.strip_prefix(r"\\.\PhysicalDrive") {
+            Some(number) => number.parse::<u32>().ok()?.to_string(),
+            None => {
+                let letter = drive.trim_start_matches(r"\\.\").chars().next().filter(|c| c.is_ascii_alphabetic())?;
+                format!("(Get-Partition -DriveLetter {} -ErrorAction Stop).DiskNumber", letter)
+            }
+        };
+        let script = format!(
+            "$n = {}; \
+             $d = Get-CimInstance Win32_DiskDrive -Filter \"Index=$n\"; \
+             $p = Get-CimInstance -Namespace root/Microsoft/Windows/Storage MSFT_PhysicalDisk -Filter \"DeviceId='$n'\"; \
+             [pscustomobject]@{{ DiskNumber = [int]$n; Model = $d.Model; SerialNumber = $d.SerialNumber; \
+             Size = [uint64]$d.Size; MediaType = [int]$p.MediaType; BusType = [int]$p.BusType; \
+             LogicalSectorSize = [int]$p.LogicalSectorSize }} | ConvertTo-Json -Compress",
+            disk_number);
+        
+        let output = std::process::Command::new("powershell")
+            .args(["-NoProfile", "-NonInteractive", "-Command", &script])
+            .output()
+            .ok()
+            .filter(|output| output.status.success())?;
+        let disk: serde_json::Value = serde_json::from_slice(&output.stdout).ok()?;
+        let text = |key: &str| disk[key].as_str().map(|value| value.trim().to_string()).unwrap_or_default();
+        
+        Some(WmiDiskInfo {
+            disk_number: disk["DiskNumber"].as_u64()? as u32,
+            model: text("Model"),
+            serial: text("SerialNumber"),
+            size_bytes: disk["Size"].as_u64().unwrap_or(0),
+            sector_size: disk["LogicalSectorSize"].as_u64().unwrap_or(0) as u32,
+            media_type: match disk["MediaType"].as_u64() {
+                Some(3) => "HDD",
+                Some(4) => "SSD",
+                Some(5) => "SCM",
+                _ => "Unspecified",
+            }.to_string(),
+            bus_type: match disk["BusType"].as_u64() {
+                Some(1) => "SCSI",
+                Some(3) => "ATA",
+                Some(7) => "USB",
+                Some(8) => "RAID",
+                Some(10) => "SAS",
+                Some(11) => "SATA",
+                Some(12) => "SD",
+                Some(13) => "MMC",
+                Some(14) | Some(15) => "Virtual",
+                Some(17) => "NVMe",
+                _ => "Unknown",
+            }.to_string(),
+        })
+    }
 }
 
 #[cfg(unix)]
@@ -150,6 +251,7 @@ pub mod unix_impl {
                 drive_type,
                 total_space: disk.total_space(),
                 free_space: disk.available_space(),
+                wipe_guard: wipe_guard_for_file_system(&file_system),
             });
         }
         
@@ -172,14 +274,53 @@ pub mod unix_impl {
                         drive_type: determine_drive_type_from_path(dev_path),
                         total_space: get_device_size(dev_path).unwrap_or(0),
                         free_space: 0, // Unmounted, so no free space info
+                        wipe_guard: None,
                     });
                 }
             }
         }
         
+        // Disk images attached with losetup
+        #[cfg(target_os = "linux")]
+        for loop_device in crate::devices::loop_device::attached() {
+            drives.push(DriveInfo {
+                total_space: get_device_size(&loop_device.path).unwrap_or(0),
+                label: format!("Loop Device ({})", loop_device.backing_file),
+                // Snap packages and other read-only images are mounted through loop devices too
+                wipe_guard: is_read_only(&loop_device.path).then(|| "read-only loop device".to_string()),
+                path: loop_device.path,
+                drive_type: "Loop Device (disk image)".to_string(),
+                free_space: 0,
+            });
+        }
+        
         Ok(drives)
     }
     
+    /// File systems that are not local media: network shares, whose file-level wipe
+    /// would reach the server, and in-memory or image-backed mounts
+    fn wipe_guard_for_file_system(file_system: &str) -> Option<String> {
+        const NETWORK: &[&str] = &["nfs", "nfs4", "cifs", "smb3", "smbfs", "9p", "ceph", "glusterfs",
+            "fuse.sshfs", "sshfs", "davfs", "fuse.davfs2", "afs"];
+        const VIRTUAL: &[&str] = &["tmpfs", "ramfs", "overlay", "squashfs", "devtmpfs"];
+        
+        let file_system = file_system.to_lowercase();
+        if NETWORK.contains(&file_system.as_str()) {
+            Some("network share".to_string())
+        } else if VIRTUAL.contains(&file_system.as_str()) {
+            Some("virtual file system".to_string())
+        } else {
+            None
+        }
+    }
+    
+    #[cfg(target_os = "linux")]
+    fn is_read_only(device_path: &str) -> bool {
+        Path::new(device_path).file_name()
+            .and_then(|name| fs::read_to_string(Path::new("/sys/block").join(name).join("ro")).ok())
+            .is_some_and(|ro| ro.trim() == "1")
+    }
+    
     fn determine_drive_type(mount_point: &str, file_system: &str) -> String {
         if mount_point.starts_with("/media/") || mount_point.starts_with("/mnt/") {
             "Removable Drive (USB/External)".to_string()
@@ -229,6 +370,20 @@ pub fn get_system_drives() -> io::Result<Vec<DriveInfo>> {
     ));
 }
 
+/// Disk details from WMI, used when ATA IDENTIFY can't reach the drive (USB bridges,
+/// RAID-presented disks). Always `None` off Windows.
+#[cfg_attr(not(windows), allow(dead_code))]
+pub fn query_wmi_disk(drive: &str) -> Option<WmiDiskInfo> {
+    #[cfg(windows)]
+    return windows_impl::query_wmi_disk(drive);
+    
+    #[cfg(not(windows))]
+    {
+        let _ = drive;
+        None
+    }
+}
+
 // Cross-platform device access functions
 pub fn can_access_device_directly(device_path: &str) -> bool {
     #[cfg(windows)]
@@ -281,6 +436,12 @@ pub fn can_access_device_directly(device_path: &str) -> bool {
 }
 
 pub fn get_device_path_for_sanitization(drive_info: &DriveInfo) -> String {
+    // A virtual device is the file itself; on Windows its path would otherwise be taken
+    // for the volume it lives on
+    if crate::devices::virtual_device::sector_size_of(&drive_info.path).is_some() {
+        return drive_info.path.clone();
+    }
+    
     #[cfg(windows)]
     {
         // On Windows, convert drive letter to physical device path
@@ -0,0 +1,127 @@
+use serde::{Deserialize, Serialize};
+use chrono::{DateTime, Local};
+
+use crate::ui::DriveInfo;
+
+/// Output format for the sanitization report saved from the Report tab
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ReportFormat {
+    Text,
+    Json,
+}
+
+impl ReportFormat {
+    pub fn all() -> &'static [ReportFormat] {
+        &[ReportFormat::Text, ReportFormat::Json]
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ReportFormat::Text => "Text (.txt)",
+            ReportFormat::Json => "JSON (.json)",
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ReportFormat::Text => "txt",
+            ReportFormat::Json => "json",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DriveReport {
+    pub name: String,
+    pub path: String,
+    pub size: String,
+    #[serde(default)]
+    pub size_bytes: u64,
+    pub status: String,
+    pub completed: bool,
+    pub bytes_total: u64,
+    pub bytes_processed: u64,
+    pub duration_seconds: Option<u64>,
+}
+
+impl DriveReport {
+    pub fn from_drive(drive: &DriveInfo) -> Self {
+        let duration_seconds = match (drive.start_time, drive.last_update) {
+            (Some(start), Some(last)) => Some(last.duration_since(start).as_secs()),
+            _ => None,
+        };
+
+        Self {
+            name: drive.name.clone(),
+            path: drive.path.clone(),
+            size: drive.size.clone(),
+            size_bytes: drive.size_bytes,
+            status: drive.status.clone(),
+            completed: drive.progress >= 1.0,
+            bytes_total: drive.bytes_total,
+            bytes_processed: drive.bytes_processed,
+            duration_seconds,
+        }
+    }
+}
+
+/// Machine-readable summary of a sanitization run, covering every selected drive
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SanitizationReport {
+    pub generated_at: DateTime<Local>,
+    pub erasure_method: String,
+    /// Certificate formats written for each drive
+    pub certificate_formats: String,
+    pub standards: Vec<String>,
+    pub drives: Vec<DriveReport>,
+}
+
+impl SanitizationReport {
+    /// `standards` are those met by the methods that actually ran on the drives
+    pub fn new(erasure_method: &str, certificate_formats: &str, standards: Vec<String>, drives: &[DriveInfo]) -> Self {
+        Self {
+            generated_at: Local::now(),
+            erasure_method: erasure_method.to_string(),
+            certificate_formats: certificate_formats.to_string(),
+            standards,
+            drives: drives.iter()
+                .filter(|drive| drive.selected)
+                .map(DriveReport::from_drive)
+                .collect(),
+        }
+    }
+
+    pub fn to_text(&self) -> String {
+        let mut report = String::new();
+        report.push_str("SHREDX - Sanitization Report\n");
+        report.push_str(&format!("Generated: {}\n", self.generated_at.format("%Y-%m-%d %H:%M:%S")));
+        report.push_str(&format!("Erasure Method: {}\n", self.erasure_method));
+        report.push_str(&format!("Certificates: {}\n", self.certificate_formats));
+        report.push_str("\n=== SANITIZED DRIVES ===\n");
+
+        for drive in self.drives.iter().filter(|d| d.completed) {
+            report.push_str(&format!("✅ {} ({}): Complete\n", drive.name, drive.path));
+            report.push_str(&format!("   Size: {}\n", drive.size));
+            report.push_str(&format!("   Status: {}\n", drive.status));
+        }
+
+        report.push_str("\n=== COMPLIANCE ===\n");
+        report.push_str("This sanitization process complies with:\n");
+        for standard in &self.standards {
+            report.push_str(&format!("- {}\n", standard));
+        }
+
+        report
+    }
+
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+
+    pub fn render(&self, format: ReportFormat) -> Result<String, Box<dyn std::error::Error>> {
+        match format {
+            ReportFormat::Text => Ok(self.to_text()),
+            ReportFormat::Json => Ok(self.to_json()?),
+        }
+    }
+}
@@ -1,11 +1,15 @@
+use std::collections::VecDeque;
 use std::fs::{File, OpenOptions, read_dir, remove_file, create_dir_all};
 use std::io::{self, Read, Seek, SeekFrom, Write, BufWriter};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex, mpsc};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::thread;
 use std::time::Instant;
 use rand::Rng;
 use rayon::prelude::*;
+use crate::advanced_wiper::{ErrorPolicy, ThermalThrottle, WipingAlgorithm};
+use crate::certificate::SectorSample;
 // use crate::hpa_dco::{HpaDcoDetector, ComprehensiveDriveInfo}; // Temporarily disabled
 
 #[derive(Debug, Clone)]
@@ -17,15 +21,195 @@ pub enum SanitizationMethod {
     ComprehensiveClean, // Full HPA/DCO detection and removal + sanitization
 }
 
+impl SanitizationMethod {
+    /// Patterns written when the method runs as a software overwrite. The ATA erases
+    /// overwrite once with random data when the drive's own command isn't available.
+    pub fn patterns(&self) -> Vec<SanitizationPattern> {
+        match self {
+            SanitizationMethod::Clear => vec![SanitizationPattern::Zeros],
+            SanitizationMethod::Purge => vec![
+                SanitizationPattern::Random,
+                SanitizationPattern::Ones,
+                SanitizationPattern::Zeros,
+            ],
+            SanitizationMethod::SecureErase |
+            SanitizationMethod::EnhancedSecureErase => vec![SanitizationPattern::Random],
+            SanitizationMethod::ComprehensiveClean => vec![
+                SanitizationPattern::Random,
+                SanitizationPattern::DoD5220,
+                SanitizationPattern::Zeros,
+            ],
+        }
+    }
+}
+
+/// The device erasers' counterpart of a sanitizer method. HPA/DCO removal has no
+/// algorithm of its own, so a comprehensive clean maps to the purge it runs afterwards.
+impl From<SanitizationMethod> for WipingAlgorithm {
+    fn from(method: SanitizationMethod) -> Self {
+        match method {
+            SanitizationMethod::Clear => WipingAlgorithm::NistClear,
+            SanitizationMethod::Purge | SanitizationMethod::ComprehensiveClean => WipingAlgorithm::NistPurge,
+            SanitizationMethod::SecureErase => WipingAlgorithm::AtaSecureErase,
+            SanitizationMethod::EnhancedSecureErase => WipingAlgorithm::AtaEnhancedSecureErase,
+        }
+    }
+}
+
+impl TryFrom<&WipingAlgorithm> for SanitizationMethod {
+    type Error = String;
+
+    fn try_from(algorithm: &WipingAlgorithm) -> Result<Self, Self::Error> {
+        match algorithm {
+            WipingAlgorithm::NistClear => Ok(SanitizationMethod::Clear),
+            WipingAlgorithm::NistPurge => Ok(SanitizationMethod::Purge),
+            WipingAlgorithm::AtaSecureErase => Ok(SanitizationMethod::SecureErase),
+            WipingAlgorithm::AtaEnhancedSecureErase => Ok(SanitizationMethod::EnhancedSecureErase),
+            other => Err(format!("{} has no sanitizer method equivalent", other)),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum SanitizationPattern {
     Zeros,      // 0x00
     Ones,       // 0xFF
     Random,     // Random data
     DoD5220,    // DoD 5220.22-M pattern
+    DoD5220Complement, // Complement of the DoD 5220.22-M pattern
     Custom(u8), // Custom byte pattern
 }
 
+/// Byte ranges of the partitions listed in the MBR or, for a protective MBR, the GPT
+pub(crate) fn read_partition_extents(device: &mut File, device_size: u64) -> io::Result<Vec<(u64, u64)>> {
+    let mut mbr = [0u8; 512];
+    device.seek(SeekFrom::Start(0))?;
+    device.read_exact(&mut mbr)?;
+    if mbr[510] != 0x55 || mbr[511] != 0xAA {
+        return Ok(Vec::new());
+    }
+    
+    let mut extents = Vec::new();
+    for entry in mbr[446..510].chunks_exact(16) {
+        let partition_type = entry[4];
+        let start_lba = u32::from_le_bytes([entry[8], entry[9], entry[10], entry[11]]) as u64;
+        let sectors = u32::from_le_bytes([entry[12], entry[13], entry[14], entry[15]]) as u64;
+        if partition_type == 0xEE {
+            return read_gpt_extents(device, device_size);
+        }
+        if partition_type != 0 && sectors > 0 {
+            extents.push((start_lba * 512, (start_lba + sectors) * 512));
+        }
+    }
+    Ok(extents)
+}
+
+fn read_gpt_extents(device: &mut File, device_size: u64) -> io::Result<Vec<(u64, u64)>> {
+    let corrupt = |what: &str| io::Error::new(io::ErrorKind::InvalidData, format!("Corrupt GPT: {}", what));
+    
+    // The GPT header sits in LBA 1, so its offset gives away the logical sector size
+    for sector_size in [512u64, 4096] {
+        let mut header = [0u8; 92];
+        device.seek(SeekFrom::Start(sector_size))?;
+        device.read_exact(&mut header)?;
+        if &header[0..8] != b"EFI PART" {
+            continue;
+        }
+        
+        let entries_lba = u64::from_le_bytes(header[72..80].try_into().unwrap());
+        let entry_count = u32::from_le_bytes(header[80..84].try_into().unwrap()).min(1024) as u64;
+        let entry_size = u32::from_le_bytes(header[84..88].try_into().unwrap()) as u64;
+        // The spec only allows 128 * 2^n byte entries; anything outside that is not a real table
+        if !(128..=4096).contains(&entry_size) || !entry_size.is_power_of_two() {
+            return Err(corrupt("invalid partition entry size"));
+        }
+        let entries_start = entries_lba.checked_mul(sector_size)
+            .filter(|&start| start >= 2 * sector_size)
+            .ok_or_else(|| corrupt("partition entry array offset out of range"))?;
+        let entries_len = entry_count * entry_size;
+        if entries_start.checked_add(entries_len).is_none_or(|end| end > device_size) {
+            return Err(corrupt("partition entry array extends past the end of the device"));
+        }
+        
+        let mut entries = vec![0u8; entries_len as usize];
+        device.seek(SeekFrom::Start(entries_start))?;
+        device.read_exact(&mut entries)?;
+        
+        return entries.chunks_exact(entry_size as usize)
+            .filter(|entry| entry[0..16].iter().any(|&b| b != 0))
+            .map(|entry| {
+                let first = u64::from_le_bytes(entry[32..40].try_into().unwrap());
+                let last = u64::from_le_bytes(entry[40..48].try_into().unwrap());
+                let start = first.checked_mul(sector_size);
+                let end = last.checked_add(1).and_then(|lba| lba.checked_mul(sector_size));
+                match (start, end) {
+                    (Some(start), Some(end)) if start < end && end <= device_size => Ok((start, end)),
+                    _ => Err(corrupt("partition entry outside the device")),
+                }
+            })
+            .collect();
+    }
+    Ok(Vec::new())
+}
+
+/// Read exactly `buffer.len()` bytes at `offset` without using the handle's seek
+/// position, so several threads can read through one handle at once
+fn read_exact_at(file: &File, buffer: &mut [u8], offset: u64) -> io::Result<()> {
+    #[cfg(unix)]
+    {
+        std::os::unix::fs::FileExt::read_exact_at(file, buffer, offset)
+    }
+    #[cfg(windows)]
+    {
+        let mut filled = 0;
+        while filled < buffer.len() {
+            match std::os::windows::fs::FileExt::seek_read(file, &mut buffer[filled..], offset + filled as u64)? {
+                0 => return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "failed to fill whole buffer")),
+                n => filled += n,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Sorted, merged byte ranges overwritten by quick sanitize: both ends of the device
+/// plus the head and tail of every partition
+fn quick_sanitize_regions(device_size: u64, partitions: &[(u64, u64)]) -> Vec<(u64, u64)> {
+    let mut regions = vec![
+        (0, QUICK_EDGE_SIZE.min(device_size)),
+        (device_size.saturating_sub(QUICK_EDGE_SIZE), device_size),
+    ];
+    for &(start, end) in partitions {
+        let end = end.min(device_size);
+        if start >= end {
+            continue;
+        }
+        regions.push((start, (start + QUICK_PARTITION_HEAD).min(end)));
+        regions.push((end.saturating_sub(QUICK_PARTITION_TAIL).max(start), end));
+    }
+    
+    regions.sort();
+    let mut merged: Vec<(u64, u64)> = Vec::new();
+    for (start, end) in regions {
+        match merged.last_mut() {
+            Some(last) if start <= last.1 => last.1 = last.1.max(end),
+            _ => merged.push((start, end)),
+        }
+    }
+    merged
+}
+
+/// Removes a temporary directory and its contents when dropped
+struct TempDirGuard(PathBuf);
+
+impl Drop for TempDirGuard {
+    fn drop(&mut self) {
+        if self.0.exists() {
+            let _ = std::fs::remove_dir_all(&self.0);
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct SanitizationProgress {
     pub bytes_processed: u64,
@@ -39,14 +223,30 @@ pub struct SanitizationProgress {
 
 // Performance optimization constants
 const OPTIMAL_BUFFER_SIZE: usize = 16 * 1024 * 1024;  // 16MB for optimal throughput
-const SECTOR_SIZE: usize = 4096;                       // 4KB sector alignment
+const BUFFER_ALIGNMENT: usize = 4096;                  // A whole number of sectors on 512-byte and 4Kn drives alike
+const VERIFY_SAMPLE_SIZE: u64 = crate::advanced_wiper::SECTOR_SAMPLE_SIZE; // Minimum read per verification sample, rounded up to whole sectors
 const MAX_THREADS: usize = 4;                          // Parallel processing threads
 const CHUNK_SIZE: usize = 64 * 1024 * 1024;          // 64MB chunks for threading
+const PURGE_CHUNK_SIZE: usize = 64 * 1024 * 1024;    // Whole-disk purge write size, one buffer for all passes
+pub(crate) const PASS_VERIFY_SAMPLES: usize = 64;                 // Locations read back after a pass when a verify interval is set
+const ETA_WINDOW: std::time::Duration = std::time::Duration::from_secs(10); // Throughput window for ETA
+pub const FREE_SPACE_PASSES: u32 = 3;                  // Passes for the free-space-only mode
+pub const QUICK_EDGE_SIZE: u64 = 256 * 1024 * 1024;    // Overwritten at each end of the device by quick sanitize
+const QUICK_PARTITION_HEAD: u64 = 16 * 1024 * 1024;    // Boot sector / superblock area at the start of each partition
+const QUICK_PARTITION_TAIL: u64 = 1024 * 1024;         // Backup boot sector area at the end of each partition
+const GPT_SECTORS: u64 = 34;                           // Protective MBR + GPT header + 32 sectors of entries
+const GPT_MAX_SECTOR_SIZE: u64 = 4096;                 // Zero enough for 4Kn drives as well as 512-byte ones
 
 pub struct DataSanitizer {
     buffer_size: usize,
     // pub hpa_dco_detector: HpaDcoDetector, // Temporarily disabled
     thread_count: usize,
+    error_policy: ErrorPolicy,
+    errors: Mutex<Vec<String>>,
+    sampled_sectors: Mutex<Vec<SectorSample>>,
+    file_level_fallback: AtomicBool,
+    thermal_limit_c: Option<u32>,
+    verify_interval: Option<u32>,
 }
 
 impl DataSanitizer {
@@ -55,17 +255,29 @@ impl DataSanitizer {
             buffer_size: OPTIMAL_BUFFER_SIZE,
             // hpa_dco_detector: HpaDcoDetector::new(), // Temporarily disabled
             thread_count: std::cmp::min(MAX_THREADS, num_cpus::get()),
+            error_policy: ErrorPolicy::default(),
+            errors: Mutex::new(Vec::new()),
+            sampled_sectors: Mutex::new(Vec::new()),
+            file_level_fallback: AtomicBool::new(false),
+            thermal_limit_c: None,
+            verify_interval: None,
         }
     }
 
     pub fn with_buffer_size(buffer_size: usize) -> Self {
         // Ensure buffer size is sector-aligned for optimal performance
-        let aligned_buffer_size = ((buffer_size + SECTOR_SIZE - 1) / SECTOR_SIZE) * SECTOR_SIZE;
+        let aligned_buffer_size = buffer_size.div_ceil(BUFFER_ALIGNMENT) * BUFFER_ALIGNMENT;
         
         Self { 
             buffer_size: std::cmp::max(aligned_buffer_size, OPTIMAL_BUFFER_SIZE),
             // hpa_dco_detector: HpaDcoDetector::new(), // Temporarily disabled
             thread_count: std::cmp::min(MAX_THREADS, num_cpus::get()),
+            error_policy: ErrorPolicy::default(),
+            errors: Mutex::new(Vec::new()),
+            sampled_sectors: Mutex::new(Vec::new()),
+            file_level_fallback: AtomicBool::new(false),
+            thermal_limit_c: None,
+            verify_interval: None,
         }
     }
 
@@ -77,6 +289,100 @@ impl DataSanitizer {
             buffer_size: optimal_buffer,
             // hpa_dco_detector: HpaDcoDetector::new(), // Temporarily disabled
             thread_count: num_cpus::get(), // Use all available cores
+            error_policy: ErrorPolicy::default(),
+            errors: Mutex::new(Vec::new()),
+            sampled_sectors: Mutex::new(Vec::new()),
+            file_level_fallback: AtomicBool::new(false),
+            thermal_limit_c: None,
+            verify_interval: None,
+        }
+    }
+
+    pub fn with_error_policy(mut self, error_policy: ErrorPolicy) -> Self {
+        self.error_policy = error_policy;
+        self
+    }
+
+    /// Pause whole-device overwrites while the drive is hotter than `thermal_limit_c`
+    pub fn with_thermal_limit(mut self, thermal_limit_c: Option<u32>) -> Self {
+        self.thermal_limit_c = thermal_limit_c;
+        self
+    }
+
+    /// Read back a sample of the device after every `verify_interval`th pass of a
+    /// multi-pass overwrite, so a failing drive stops the job early instead of at the end
+    pub fn with_verify_interval(mut self, verify_interval: Option<u32>) -> Self {
+        self.verify_interval = verify_interval.filter(|&interval| interval > 0);
+        self
+    }
+
+    /// Whether the pass just written is one the verify interval asks to read back
+    fn verify_due(&self, pass: u32) -> bool {
+        self.verify_interval.is_some_and(|interval| pass % interval == 0)
+    }
+
+    /// Read back `PASS_VERIFY_SAMPLES` random sector-aligned locations and check they hold
+    /// what the pass wrote. Random passes can only be told apart from a stuck or skipped
+    /// write, so they fail when a sample is one byte repeated.
+    fn verify_pass(&self, device_file: &File, device_size: u64, sector_size: u32,
+                   pattern: &SanitizationPattern, pass: u32) -> io::Result<()> {
+        let sector_size = u64::from(sector_size.max(1));
+        let sample_size = VERIFY_SAMPLE_SIZE.div_ceil(sector_size) * sector_size;
+        let sample_size = sample_size.min(device_size / sector_size * sector_size);
+        if sample_size == 0 {
+            return Ok(());
+        }
+        let last_sample_sector = (device_size - sample_size) / sector_size;
+        
+        log::info!("🔍 Reading back {} locations after pass {}", PASS_VERIFY_SAMPLES, pass);
+        let expected = self.generate_pattern_buffer(pattern, sample_size as usize);
+        let mut buffer = vec![0u8; sample_size as usize];
+        let mut rng = rand::thread_rng();
+        for _ in 0..PASS_VERIFY_SAMPLES {
+            let position = rng.gen_range(0..=last_sample_sector) * sector_size;
+            read_exact_at(device_file, &mut buffer, position)?;
+            let matches = match pattern {
+                SanitizationPattern::Random => buffer.iter().any(|&b| b != buffer[0]),
+                _ => buffer == expected,
+            };
+            if !matches {
+                return Err(io::Error::new(io::ErrorKind::InvalidData,
+                    format!("Pass {} did not read back as {:?} at offset {}", pass, pattern, position)));
+            }
+        }
+        log::info!("✅ Pass {} read back correctly", pass);
+        Ok(())
+    }
+
+    /// Errors logged so far; under `ContinueAndReport` these are the regions/files that were skipped
+    pub fn errors(&self) -> Vec<String> {
+        self.errors.lock().map(|errors| errors.clone()).unwrap_or_default()
+    }
+
+    /// Sectors the last purge verification read, hashed as the purge left them
+    pub fn sampled_sectors(&self) -> Vec<SectorSample> {
+        self.sampled_sectors.lock().map(|samples| samples.clone()).unwrap_or_default()
+    }
+
+    /// Whether the last disk purge couldn't open the device and overwrote only the
+    /// files and free space instead
+    pub fn fell_back_to_files(&self) -> bool {
+        self.file_level_fallback.load(Ordering::Relaxed)
+    }
+
+    /// Record an error and decide whether the job stops (`Err`) or carries on (`Ok`)
+    fn handle_error(&self, message: String, error: io::Error) -> io::Result<()> {
+        log::error!("❌ {}", message);
+        if let Ok(mut errors) = self.errors.lock() {
+            errors.push(message);
+        }
+        // There is nothing left to carry on with once the device has gone
+        if crate::advanced_wiper::is_device_removed_error(&error) {
+            return Err(error);
+        }
+        match self.error_policy {
+            ErrorPolicy::AbortOnFirst => Err(error),
+            ErrorPolicy::ContinueAndReport => Ok(()),
         }
     }
 
@@ -106,6 +412,25 @@ impl DataSanitizer {
         self.sanitize_device(device_path, patterns, progress_callback)
     }
 
+    /// DoD 5220.22-M ECE - 7 passes: a DoD 3-pass, one random pass, then another DoD 3-pass
+    pub fn dod_ece<P: AsRef<Path>>(
+        &self,
+        device_path: P,
+        progress_callback: Option<Box<dyn Fn(SanitizationProgress)>>,
+    ) -> io::Result<()> {
+        let patterns = vec![
+            SanitizationPattern::DoD5220,
+            SanitizationPattern::DoD5220Complement,
+            SanitizationPattern::Random,
+            SanitizationPattern::Random,
+            SanitizationPattern::DoD5220,
+            SanitizationPattern::DoD5220Complement,
+            SanitizationPattern::Random,
+        ];
+        
+        self.sanitize_device(device_path, patterns, progress_callback)
+    }
+
     /// Enhanced Purge method with more passes for highly sensitive data
     pub fn enhanced_purge<P: AsRef<Path>>(
         &self,
@@ -134,10 +459,10 @@ impl DataSanitizer {
     ) -> io::Result<String> {  // Changed return type to String temporarily
         let _path = device_path.as_ref();
         
-        println!("🔍 Starting comprehensive drive analysis...");
+        log::info!("🔍 Starting comprehensive drive analysis...");
         
         // Temporarily disabled HPA/DCO detection - would require additional module
-        println!("📊 Comprehensive clean temporarily using standard purge method");
+        log::info!("📊 Comprehensive clean temporarily using standard purge method");
         
         self.purge(device_path, progress_callback)?;
         
@@ -153,9 +478,9 @@ impl DataSanitizer {
     ) -> io::Result<()> {
         let device_path = device_path.as_ref();
         
-        println!("🚨 CRITICAL: Starting NIST SP 800-88 PURGE operation on ENTIRE DISK");
-        println!("📝 This will PERMANENTLY DESTROY ALL DATA on {}", device_path.display());
-        println!("🔒 Data will be UNRECOVERABLE after this operation");
+        log::info!("🚨 CRITICAL: Starting NIST SP 800-88 PURGE operation on ENTIRE DISK");
+        log::info!("📝 This will PERMANENTLY DESTROY ALL DATA on {}", device_path.display());
+        log::info!("🔒 Data will be UNRECOVERABLE after this operation");
         
         // Try to open device for direct access
         let device_file = match std::fs::OpenOptions::new()
@@ -164,8 +489,8 @@ impl DataSanitizer {
             .open(device_path) {
             Ok(file) => file,
             Err(e) => {
-                println!("❌ Cannot access device directly: {}", e);
-                println!("🔄 Falling back to file-system level sanitization");
+                log::error!("❌ Cannot access device directly: {}", e);
+                log::info!("🔄 Falling back to file-system level sanitization");
                 
                 // Try to determine mount point for fallback
                 let fallback_path = if cfg!(windows) {
@@ -181,6 +506,7 @@ impl DataSanitizer {
                     device_path.to_path_buf()
                 };
 
+                self.file_level_fallback.store(true, Ordering::Relaxed);
                 return self.sanitize_files_and_free_space_fallback(fallback_path, 3, progress_callback);
             }
         };
@@ -189,12 +515,12 @@ impl DataSanitizer {
         let device_size = match device_file.metadata() {
             Ok(metadata) => metadata.len(),
             Err(e) => {
-                println!("❌ Cannot determine device size: {}", e);
+                log::error!("❌ Cannot determine device size: {}", e);
                 return Err(e);
             }
         };
         
-        println!("📊 Device size: {:.2} GB ({} bytes)", 
+        log::info!("📊 Device size: {:.2} GB ({} bytes)", 
                 device_size as f64 / (1024.0 * 1024.0 * 1024.0), device_size);
         
         // NIST SP 800-88 Purge Method: Multiple passes with different patterns
@@ -204,8 +530,13 @@ impl DataSanitizer {
             ("Pass 3/3: Final Random Pattern", SanitizationPattern::Random),
         ];
         
+        let mut throttle = ThermalThrottle::new(&device_path.to_string_lossy(), self.thermal_limit_c);
+        let mut pattern_buffer = vec![0u8; PURGE_CHUNK_SIZE];
+        let sector_size = crate::devices::logical_sector_size(&device_path.to_string_lossy()).unwrap_or(512);
+        
         for (pass_num, (pass_name, pattern)) in purge_passes.iter().enumerate() {
-            println!("🔄 Starting {}", pass_name);
+            crate::advanced_wiper::check_stop_requested()?;
+            log::info!("🔄 Starting {}", pass_name);
             
             if let Some(ref callback) = progress_callback {
                 callback(SanitizationProgress {
@@ -220,40 +551,62 @@ impl DataSanitizer {
             }
             
             // Perform the pass
-            match self.overwrite_entire_device(&device_file, device_size, pattern, 
-                                                                                           (pass_num + 1) as u32, 3, progress_callback.as_ref()) {
-                Ok(_) => println!("✅ {} completed", pass_name),
+            match self.overwrite_entire_device(&device_file, device_size, pattern, &mut pattern_buffer,
+                                                                                           (pass_num + 1) as u32, 3, &mut throttle, progress_callback.as_ref()) {
+                Ok(_) => log::info!("✅ {} completed", pass_name),
                 Err(e) => {
-                    println!("❌ {} failed: {}", pass_name, e);
+                    log::error!("❌ {} failed: {}", pass_name, e);
+                    return Err(e);
+                }
+            }
+            
+            if self.verify_due((pass_num + 1) as u32) {
+                if let Err(e) = device_file.sync_data()
+                    .and_then(|_| self.verify_pass(&device_file, device_size, sector_size, pattern, (pass_num + 1) as u32)) {
+                    log::error!("❌ {} failed verification: {}", pass_name, e);
                     return Err(e);
                 }
             }
         }
         
         // Final verification pass (read-only)
-        println!("🔍 Performing final verification...");
-        match self.verify_disk_sanitization(&device_file, device_size) {
-            Ok(true) => println!("✅ NIST SP 800-88 Purge verification PASSED"),
+        log::info!("🔍 Performing final verification...");
+        match self.verify_disk_sanitization(&device_file, device_size, sector_size) {
+            Ok(true) => log::info!("✅ NIST SP 800-88 Purge verification PASSED"),
             Ok(false) => {
-                println!("⚠️  Verification found potential data remnants");
-                println!("🔄 Performing additional sanitization pass...");
+                log::warn!("⚠️  Verification found potential data remnants");
+                log::info!("🔄 Performing additional sanitization pass...");
                 
                 // Additional security pass
                 if let Err(e) = self.overwrite_entire_device(&device_file, device_size, 
-                                                           &SanitizationPattern::Random, 4, 4, 
-                                                           progress_callback.as_ref()) {
-                    println!("❌ Additional sanitization pass failed: {}", e);
+                                                           &SanitizationPattern::Random, &mut pattern_buffer, 4, 4, 
+                                                           &mut throttle, progress_callback.as_ref()) {
+                    log::error!("❌ Additional sanitization pass failed: {}", e);
                     return Err(e);
                 }
             },
             Err(e) => {
-                println!("❌ Verification failed: {}", e);
+                log::error!("❌ Verification failed: {}", e);
                 return Err(e);
             }
         }
         
-        println!("🎯 NIST SP 800-88 PURGE operation completed successfully");
-        println!("🔒 All data has been permanently destroyed and is unrecoverable");
+        // Leave no partition table behind so the drive comes up as blank
+        if let Err(e) = self.zero_partition_tables(&device_file, device_size) {
+            log::error!("❌ Partition table wipe failed: {}", e);
+            return Err(e);
+        }
+        
+        // The extra pass and the partition table wipe may have rewritten sampled sectors;
+        // the certificate has to carry the hashes of what is on the device now
+        if let Ok(mut samples) = self.sampled_sectors.lock() {
+            let offsets: Vec<u64> = samples.iter().map(|sample| sample.offset).collect();
+            let length = samples.first().map_or(0, |sample| sample.length);
+            *samples = crate::advanced_wiper::read_sector_samples(&device_file, &offsets, length)?;
+        }
+        
+        log::info!("🎯 NIST SP 800-88 PURGE operation completed successfully");
+        log::info!("🔒 All data has been permanently destroyed and is unrecoverable");
         
         // Generate compliance report
         self.generate_nist_compliance_report(device_path, device_size)?;
@@ -261,6 +614,89 @@ impl DataSanitizer {
         Ok(())
     }
     
+    /// Quick sanitize for triage of low-sensitivity drives. Overwrites the partition
+    /// tables (MBR, primary and backup GPT), the first and last `QUICK_EDGE_SIZE`
+    /// bytes of the device and the boot sector / superblock area of every partition
+    /// so the drive no longer mounts. This is NOT a full overwrite - data between
+    /// those regions is still recoverable with carving tools.
+    pub fn quick_sanitize<P: AsRef<Path>>(
+        &self,
+        device_path: P,
+        progress_callback: Option<Box<dyn Fn(SanitizationProgress)>>,
+    ) -> io::Result<()> {
+        let device_path = device_path.as_ref();
+        let start_time = Instant::now();
+        
+        log::info!("⚡ Quick sanitize on {} (partition tables and metadata only, NOT a full overwrite)", device_path.display());
+        
+        let mut device = OpenOptions::new().read(true).write(true).open(device_path)?;
+        let device_size = device.seek(SeekFrom::End(0))?;
+        
+        // Read the partition layout before it gets destroyed
+        let partitions = read_partition_extents(&mut device, device_size).unwrap_or_else(|e| {
+            log::warn!("⚠️  Could not read partition table: {}", e);
+            Vec::new()
+        });
+        log::info!("📊 Found {} partition(s)", partitions.len());
+        
+        let regions = quick_sanitize_regions(device_size, &partitions);
+        let total_bytes: u64 = regions.iter().map(|(start, end)| end - start).sum();
+        let buffer = self.generate_pattern_buffer(&SanitizationPattern::Random, OPTIMAL_BUFFER_SIZE);
+        let mut bytes_processed = 0u64;
+        
+        for (start, end) in regions {
+            device.seek(SeekFrom::Start(start))?;
+            let mut offset = start;
+            while offset < end {
+                let len = ((end - offset) as usize).min(buffer.len());
+                if let Err(e) = device.write_all(&buffer[..len]) {
+                    self.handle_error(format!("Quick sanitize write failed at offset {}: {}", offset, e), e)?;
+                    device.seek(SeekFrom::Start(offset + len as u64))?;
+                }
+                offset += len as u64;
+                bytes_processed += len as u64;
+                
+                if let Some(ref cb) = progress_callback {
+                    cb(SanitizationProgress {
+                        bytes_processed,
+                        total_bytes,
+                        current_pass: 1,
+                        total_passes: 1,
+                        percentage: bytes_processed as f64 / total_bytes as f64 * 100.0,
+                        estimated_time_remaining: std::time::Duration::from_secs(0),
+                        current_operation: "Quick sanitize: destroying partition tables and metadata".to_string(),
+                    });
+                }
+            }
+        }
+        device.sync_all()?;
+        
+        log::info!("⚡ Quick sanitize completed in {:.2}s ({:.2} MB overwritten)",
+                start_time.elapsed().as_secs_f64(), bytes_processed as f64 / (1024.0 * 1024.0));
+        Ok(())
+    }
+    
+    /// Zero the protective MBR, the primary GPT (LBA 0-33) and the backup GPT in the
+    /// last 33 LBAs so partitioning tools see the drive as blank
+    pub fn wipe_partition_table<P: AsRef<Path>>(&self, device_path: P) -> io::Result<()> {
+        let device = OpenOptions::new().read(true).write(true).open(device_path.as_ref())?;
+        let device_size = (&device).seek(SeekFrom::End(0))?;
+        self.zero_partition_tables(&device, device_size)
+    }
+
+    fn zero_partition_tables(&self, device: &File, device_size: u64) -> io::Result<()> {
+        let table_len = (GPT_SECTORS * GPT_MAX_SECTOR_SIZE).min(device_size);
+        let zeros = vec![0u8; table_len as usize];
+        let mut device = device;
+        
+        log::info!("🧹 Zeroing MBR and primary/backup GPT");
+        device.seek(SeekFrom::Start(0))?;
+        device.write_all(&zeros)?;
+        device.seek(SeekFrom::Start(device_size - table_len))?;
+        device.write_all(&zeros)?;
+        device.sync_all()
+    }
+    
     /// Fallback method that calls the original file-level sanitization
     pub fn sanitize_files_and_free_space_fallback<P: AsRef<Path>>(
         &self,
@@ -268,10 +704,31 @@ impl DataSanitizer {
         passes: u32,
         progress_callback: Option<Box<dyn Fn(SanitizationProgress)>>,
     ) -> io::Result<()> {
-        println!("🔄 Using file-system level sanitization as fallback");
+        log::info!("🔄 Using file-system level sanitization as fallback");
         self.sanitize_files_and_free_space(drive_root, passes, progress_callback)
     }
 
+    /// Overwrite only the unallocated space on a mounted filesystem, leaving existing
+    /// files untouched. `free_space_hint` is the free space reported for the volume
+    /// and is only used for progress reporting.
+    pub fn wipe_free_space<P: AsRef<Path>>(
+        &self,
+        drive_root: P,
+        passes: u32,
+        free_space_hint: u64,
+        progress_callback: Option<Box<dyn Fn(SanitizationProgress)>>,
+    ) -> io::Result<()> {
+        let drive_path = drive_root.as_ref();
+
+        if !drive_path.is_dir() {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput,
+                format!("Path {} is not a mounted directory", drive_path.display())));
+        }
+
+        log::info!("💾 Wiping free space on {} ({} passes), existing files are preserved", drive_path.display(), passes);
+        self.fill_free_space_with_hint(drive_path, passes, free_space_hint, &progress_callback)
+    }
+
     /// File-level sanitization for when direct device access fails
     /// This method overwrites all files on the drive and fills free space
     pub fn sanitize_files_and_free_space<P: AsRef<Path>>(
@@ -282,7 +739,7 @@ impl DataSanitizer {
     ) -> io::Result<()> {
         let drive_path = drive_root.as_ref();
         
-        println!("🔧 Starting file-level sanitization on {}", drive_path.display());
+        log::info!("🔧 Starting file-level sanitization on {}", drive_path.display());
         
         // Check if the drive path exists and is accessible
         if !drive_path.exists() {
@@ -296,42 +753,42 @@ impl DataSanitizer {
         }
         
         // Step 1: Overwrite all existing files
-        println!("🗂️  Phase 1: Overwriting all existing files...");
+        log::info!("🗂️  Phase 1: Overwriting all existing files...");
         match self.overwrite_all_files(drive_path, passes, &progress_callback) {
-            Ok(_) => println!("✅ File overwriting completed"),
+            Ok(_) => log::info!("✅ File overwriting completed"),
             Err(e) => {
-                println!("❌ File overwriting failed: {}", e);
+                log::error!("❌ File overwriting failed: {}", e);
                 return Err(e);
             }
         }
         
         // Step 2: Fill free space with random data
-        println!("💾 Phase 2: Filling free space with random data...");
+        log::info!("💾 Phase 2: Filling free space with random data...");
         match self.fill_free_space(drive_path, passes, &progress_callback) {
-            Ok(_) => println!("✅ Free space filling completed"),
+            Ok(_) => log::info!("✅ Free space filling completed"),
             Err(e) => {
-                println!("❌ Free space filling failed: {}", e);
+                log::error!("❌ Free space filling failed: {}", e);
                 return Err(e);
             }
         }
         
-        println!("✅ File-level sanitization completed");
+        log::info!("✅ File-level sanitization completed");
         Ok(())
     }
 
     /// Recursively overwrite all files in a directory
     fn overwrite_all_files(&self, dir: &Path, passes: u32, progress_callback: &Option<Box<dyn Fn(SanitizationProgress)>>) -> io::Result<()> {
         if !dir.is_dir() {
-            println!("❌ Path is not a directory: {}", dir.display());
+            log::error!("❌ Path is not a directory: {}", dir.display());
             return Ok(());
         }
 
-        println!("🔍 Scanning directory: {}", dir.display());
+        log::info!("🔍 Scanning directory: {}", dir.display());
         
         let entries = match read_dir(dir) {
             Ok(entries) => entries,
             Err(e) => {
-                println!("❌ Failed to read directory {}: {}", dir.display(), e);
+                log::error!("❌ Failed to read directory {}: {}", dir.display(), e);
                 return Err(e);
             }
         };
@@ -343,7 +800,7 @@ impl DataSanitizer {
             let entry = match entry {
                 Ok(entry) => entry,
                 Err(e) => {
-                    println!("❌ Failed to read directory entry: {}", e);
+                    log::error!("❌ Failed to read directory entry: {}", e);
                     continue;
                 }
             };
@@ -352,18 +809,21 @@ impl DataSanitizer {
 
             if path.is_dir() {
                 dir_count += 1;
-                println!("📁 Processing subdirectory: {}", path.display());
+                log::info!("📁 Processing subdirectory: {}", path.display());
                 // Recursively process subdirectories
                 if let Err(e) = self.overwrite_all_files(&path, passes, progress_callback) {
-                    println!("❌ Failed to process subdirectory {}: {}", path.display(), e);
+                    if self.error_policy == ErrorPolicy::AbortOnFirst {
+                        return Err(e);
+                    }
+                    log::error!("❌ Failed to process subdirectory {}: {}", path.display(), e);
                 }
             } else if path.is_file() {
                 file_count += 1;
-                println!("📄 Found file: {}", path.display());
+                log::info!("📄 Found file: {}", path.display());
                 
                 // Overwrite the file multiple times
                 for pass in 1..=passes {
-                    println!("  🔄 Pass {}/{}: Overwriting {}", pass, passes, path.display());
+                    log::info!("  🔄 Pass {}/{}: Overwriting {}", pass, passes, path.display());
                     
                     // Update progress
                     if let Some(cb) = progress_callback {
@@ -379,20 +839,20 @@ impl DataSanitizer {
                     }
 
                     if let Err(e) = self.overwrite_single_file(&path) {
-                        println!("  ❌ Failed to overwrite {}: {}", path.display(), e);
+                        self.handle_error(format!("Failed to overwrite {} (pass {}): {}", path.display(), pass, e), e)?;
                         continue;
                     }
                 }
                 
                 // Delete the file after overwriting
                 match remove_file(&path) {
-                    Ok(_) => println!("  ✅ Deleted: {}", path.display()),
-                    Err(e) => println!("  ❌ Failed to delete {}: {}", path.display(), e),
+                    Ok(_) => log::info!("  ✅ Deleted: {}", path.display()),
+                    Err(e) => log::error!("  ❌ Failed to delete {}: {}", path.display(), e),
                 }
             }
         }
         
-        println!("📊 Directory scan complete: {} files, {} subdirectories processed", file_count, dir_count);
+        log::info!("📊 Directory scan complete: {} files, {} subdirectories processed", file_count, dir_count);
         Ok(())
     }
 
@@ -439,27 +899,38 @@ impl DataSanitizer {
     /// Fill free space with random data
     /// Optimized free space filling with better performance
     fn fill_free_space(&self, drive_path: &Path, passes: u32, progress_callback: &Option<Box<dyn Fn(SanitizationProgress)>>) -> io::Result<()> {
+        self.fill_free_space_with_hint(drive_path, passes, 0, progress_callback)
+    }
+
+    fn fill_free_space_with_hint(&self, drive_path: &Path, passes: u32, free_space_hint: u64, progress_callback: &Option<Box<dyn Fn(SanitizationProgress)>>) -> io::Result<()> {
         let start_time = Instant::now();
+        let total_bytes = free_space_hint * passes as u64;
         
         for pass in 1..=passes {
-            println!("🚀 Pass {}/{}: Optimized free space filling on {}", pass, passes, drive_path.display());
+            crate::advanced_wiper::check_stop_requested()?;
+            log::info!("🚀 Pass {}/{}: Optimized free space filling on {}", pass, passes, drive_path.display());
             
             // Update progress
+            // A fill pass can't report until the disk is full, so progress advances per pass
+            let bytes_done = free_space_hint * (pass - 1) as u64;
             if let Some(cb) = progress_callback {
                 cb(SanitizationProgress {
-                    bytes_processed: 0,
-                    total_bytes: 0,
+                    bytes_processed: bytes_done,
+                    total_bytes,
                     current_pass: pass,
                     total_passes: passes,
-                    percentage: 0.0,
+                    percentage: if total_bytes > 0 { bytes_done as f64 / total_bytes as f64 * 100.0 } else { 0.0 },
                     estimated_time_remaining: std::time::Duration::from_secs(0),
                     current_operation: format!("Filling free space (Pass {}/{})", pass, passes),
                 });
             }
             
             // Create a temporary directory for our fill files
+            // The guard removes the directory and every fill file in it when it goes out
+            // of scope, so nothing is left behind on an early return or panic
             let temp_dir = drive_path.join("__sanitize_temp__");
-            let _ = create_dir_all(&temp_dir);
+            create_dir_all(&temp_dir)?;
+            let temp_guard = TempDirGuard(temp_dir.clone());
 
             let _file_counter = 0;
             let optimal_chunk_size = OPTIMAL_BUFFER_SIZE; // Use optimized buffer size
@@ -509,14 +980,26 @@ impl DataSanitizer {
             }).collect();
             
             let total_files: usize = results.iter().sum();
-            println!("    ✅ Created {} fill files in {:.2}s", total_files, start_time.elapsed().as_secs_f64());
+            log::info!("    ✅ Created {} fill files in {:.2}s", total_files, start_time.elapsed().as_secs_f64());
 
-            // Clean up temporary files (also parallelized)
+            drop(temp_guard);
             if temp_dir.exists() {
-                let _ = std::fs::remove_dir_all(&temp_dir);
+                return Err(io::Error::new(io::ErrorKind::Other,
+                    format!("Could not remove fill files in {}", temp_dir.display())));
             }
         }
-        println!("🎯 Free space filling completed in {:.2}s", start_time.elapsed().as_secs_f64());
+        if let Some(cb) = progress_callback {
+            cb(SanitizationProgress {
+                bytes_processed: total_bytes,
+                total_bytes,
+                current_pass: passes,
+                total_passes: passes,
+                percentage: 100.0,
+                estimated_time_remaining: std::time::Duration::from_secs(0),
+                current_operation: "Free space filling complete".to_string(),
+            });
+        }
+        log::info!("🎯 Free space filling completed in {:.2}s", start_time.elapsed().as_secs_f64());
         Ok(())
     }
 
@@ -548,7 +1031,7 @@ impl DataSanitizer {
         
         let total_passes = patterns.len() as u32;
         
-        println!("🚀 Starting optimized sanitization (Target size: {:.2} GB)", 
+        log::info!("🚀 Starting optimized sanitization (Target size: {:.2} GB)", 
                 device_size as f64 / (1024.0 * 1024.0 * 1024.0));
         
         // Open device with optimized flags
@@ -556,26 +1039,37 @@ impl DataSanitizer {
             .write(true)
             .read(true)
             .open(path)?;
+        
+        // One buffer for every pass, refilled with each pass's pattern, in whole sectors of this device
+        let sector_size = crate::devices::logical_sector_size(&path.to_string_lossy()).unwrap_or(512) as usize;
+        let aligned_buffer_size = (self.buffer_size / sector_size).max(1) * sector_size;
+        let mut buffer = vec![0u8; aligned_buffer_size];
 
         for (pass_num, pattern) in patterns.iter().enumerate() {
+            crate::advanced_wiper::check_stop_requested()?;
             let current_pass = (pass_num + 1) as u32;
             let pass_start = Instant::now();
             
-            println!("📝 Pass {}/{}: {:?}", current_pass, total_passes, pattern);
+            log::info!("📝 Pass {}/{}: {:?}", current_pass, total_passes, pattern);
             
             // Use optimized writing strategy
             if device_size > CHUNK_SIZE as u64 && self.thread_count > 1 {
                 // Large device: use parallel chunk processing
-                self.sanitize_device_parallel(&mut device, device_size, pattern, current_pass, total_passes, &progress_callback)?;
+                self.sanitize_device_parallel(&mut device, device_size, pattern, &mut buffer, current_pass, total_passes, &progress_callback)?;
             } else {
                 // Small device or single thread: use optimized sequential writing
-                self.sanitize_device_sequential(&mut device, device_size, pattern, current_pass, total_passes, &progress_callback)?;
+                self.sanitize_device_sequential(&mut device, device_size, pattern, &mut buffer, current_pass, total_passes, &progress_callback)?;
             }
             
-            println!("✅ Pass {} completed in {:.2}s", current_pass, pass_start.elapsed().as_secs_f64());
+            log::info!("✅ Pass {} completed in {:.2}s", current_pass, pass_start.elapsed().as_secs_f64());
+            
+            if self.verify_due(current_pass) {
+                device.sync_data()?;
+                self.verify_pass(&device, device_size, sector_size as u32, pattern, current_pass)?;
+            }
         }
         
-        println!("🎯 Total sanitization completed in {:.2}s", start_time.elapsed().as_secs_f64());
+        log::info!("🎯 Total sanitization completed in {:.2}s", start_time.elapsed().as_secs_f64());
         Ok(())
     }
 
@@ -585,6 +1079,7 @@ impl DataSanitizer {
         device: &mut File,
         device_size: u64,
         pattern: &SanitizationPattern,
+        buffer: &mut [u8],
         current_pass: u32,
         total_passes: u32,
         progress_callback: &Option<Box<dyn Fn(SanitizationProgress)>>,
@@ -592,9 +1087,8 @@ impl DataSanitizer {
         // Seek to beginning
         device.seek(SeekFrom::Start(0))?;
         
-        // Pre-allocate aligned buffer for optimal I/O
-        let aligned_buffer_size = (self.buffer_size / SECTOR_SIZE) * SECTOR_SIZE;
-        let mut buffer = self.generate_pattern_buffer(pattern, aligned_buffer_size);
+        let aligned_buffer_size = buffer.len();
+        self.fill_pattern_buffer(pattern, buffer);
         let mut buffered_writer = BufWriter::with_capacity(aligned_buffer_size * 2, device);
         
         let mut bytes_written = 0u64;
@@ -607,7 +1101,7 @@ impl DataSanitizer {
             
             // For random patterns, regenerate buffer periodically for better security
             if matches!(pattern, SanitizationPattern::Random) && bytes_written % (16 * 1024 * 1024) == 0 {
-                self.fill_random(&mut buffer);
+                self.fill_random(buffer);
             }
             
             // Write with optimal chunk size
@@ -644,11 +1138,12 @@ impl DataSanitizer {
         device: &mut File,
         device_size: u64,
         pattern: &SanitizationPattern,
+        buffer: &mut [u8],
         current_pass: u32,
         total_passes: u32,
         progress_callback: &Option<Box<dyn Fn(SanitizationProgress)>>,
     ) -> io::Result<()> {
-        println!("🔄 Using parallel processing with {} threads", self.thread_count);
+        log::info!("🔄 Using parallel processing with {} threads", self.thread_count);
         
         // Calculate optimal chunk distribution
         let chunks_count = (device_size + CHUNK_SIZE as u64 - 1) / CHUNK_SIZE as u64;
@@ -735,7 +1230,7 @@ impl DataSanitizer {
         }
         
         // For now, fall back to sequential for actual writing (parallel positioned I/O requires more complex implementation)
-        self.sanitize_device_sequential(device, device_size, pattern, current_pass, total_passes, progress_callback)?;
+        self.sanitize_device_sequential(device, device_size, pattern, buffer, current_pass, total_passes, progress_callback)?;
         
         Ok(())
     }
@@ -749,16 +1244,22 @@ impl DataSanitizer {
     /// Generate a buffer filled with the specified pattern
     fn generate_pattern_buffer(&self, pattern: &SanitizationPattern, size: usize) -> Vec<u8> {
         let mut buffer = vec![0u8; size];
-        
+        self.fill_pattern_buffer(pattern, &mut buffer);
+        buffer
+    }
+
+    /// Refill an existing buffer with the specified pattern, so one buffer can serve
+    /// every pass; random patterns get fresh data on each call
+    fn fill_pattern_buffer(&self, pattern: &SanitizationPattern, buffer: &mut [u8]) {
         match pattern {
             SanitizationPattern::Zeros => {
-                // Buffer is already filled with zeros
+                buffer.fill(0x00);
             }
             SanitizationPattern::Ones => {
                 buffer.fill(0xFF);
             }
             SanitizationPattern::Random => {
-                self.fill_random(&mut buffer);
+                self.fill_random(buffer);
             }
             SanitizationPattern::Custom(byte) => {
                 buffer.fill(*byte);
@@ -769,9 +1270,12 @@ impl DataSanitizer {
                     *byte = if i % 2 == 0 { 0x55 } else { 0xAA };
                 }
             }
+            SanitizationPattern::DoD5220Complement => {
+                for (i, byte) in buffer.iter_mut().enumerate() {
+                    *byte = if i % 2 == 0 { 0xAA } else { 0x55 };
+                }
+            }
         }
-        
-        buffer
     }
 
     /// Fill buffer with cryptographically secure random data
@@ -818,6 +1322,11 @@ impl DataSanitizer {
                     if i % 2 == 0 { b == 0x55 } else { b == 0xAA }
                 }))
             }
+            SanitizationPattern::DoD5220Complement => {
+                Ok(buffer.iter().enumerate().all(|(i, &b)| {
+                    if i % 2 == 0 { b == 0xAA } else { b == 0x55 }
+                }))
+            }
         }
     }
     
@@ -827,26 +1336,45 @@ impl DataSanitizer {
         device_file: &std::fs::File,
         device_size: u64,
         pattern: &SanitizationPattern,
+        pattern_buffer: &mut [u8],
         current_pass: u32,
         total_passes: u32,
+        throttle: &mut ThermalThrottle,
         progress_callback: Option<&Box<dyn Fn(SanitizationProgress)>>,
     ) -> io::Result<()> {
         use std::io::{Write, Seek, SeekFrom};
         
         let mut file = device_file;
-        let chunk_size = 64 * 1024 * 1024; // 64MB chunks for better performance
-        let pattern_buffer = self.generate_pattern_buffer(pattern, chunk_size);
+        let chunk_size = pattern_buffer.len();
+        self.fill_pattern_buffer(pattern, pattern_buffer);
         let mut bytes_written = 0u64;
         let start_time = std::time::Instant::now();
+        // Recent (time, bytes) samples so the ETA follows current throughput
+        let mut throughput_samples: VecDeque<(Instant, u64)> = VecDeque::new();
+        throughput_samples.push_back((start_time, 0));
         
         // Seek to beginning of device
         file.seek(SeekFrom::Start(0))?;
         
-        println!("📝 Pass {}/{}: Writing pattern to {} bytes in {} chunks", 
+        log::info!("📝 Pass {}/{}: Writing pattern to {} bytes in {} chunks", 
                 current_pass, total_passes, device_size, 
                 (device_size + chunk_size as u64 - 1) / chunk_size as u64);
         
         while bytes_written < device_size {
+            throttle.wait_if_hot(|temperature| {
+                if let Some(callback) = progress_callback {
+                    callback(SanitizationProgress {
+                        current_pass,
+                        total_passes,
+                        percentage: (bytes_written as f64 / device_size as f64) * 100.0,
+                        bytes_processed: bytes_written,
+                        total_bytes: device_size,
+                        estimated_time_remaining: std::time::Duration::from_secs(0),
+                        current_operation: format!("Pass {}/{}: Cooling down ({}°C)", current_pass, total_passes, temperature),
+                    });
+                }
+            });
+            
             let remaining = device_size - bytes_written;
             let write_size = std::cmp::min(chunk_size as u64, remaining) as usize;
             
@@ -855,6 +1383,17 @@ impl DataSanitizer {
                 Ok(_) => {
                     bytes_written += write_size as u64;
                     
+                    let now = Instant::now();
+                    throughput_samples.push_back((now, bytes_written));
+                    while throughput_samples.len() > 2 {
+                        match throughput_samples.front() {
+                            Some((time, _)) if now.duration_since(*time) > ETA_WINDOW => {
+                                throughput_samples.pop_front();
+                            }
+                            _ => break,
+                        }
+                    }
+                    
                     // Force sync every 512MB to ensure data is written
                     if bytes_written % (512 * 1024 * 1024) == 0 {
                         file.sync_data()?;
@@ -863,11 +1402,16 @@ impl DataSanitizer {
                     // Update progress every 100MB
                     if bytes_written % (100 * 1024 * 1024) == 0 || bytes_written == device_size {
                         let percentage = (bytes_written as f64 / device_size as f64) * 100.0;
-                        let elapsed = start_time.elapsed();
-                        let speed_mbps = if elapsed.as_secs() > 0 {
-                            (bytes_written as f64) / (1024.0 * 1024.0) / elapsed.as_secs_f64()
-                        } else {
-                            0.0
+                        // Speed over the recent window; the whole-run average overestimates
+                        // early on and reacts slowly when throughput changes
+                        let speed_mbps = match (throughput_samples.front(), throughput_samples.back()) {
+                            (Some((first_time, first_bytes)), Some((last_time, last_bytes)))
+                                if last_time.duration_since(*first_time).as_secs_f64() > 0.0 =>
+                            {
+                                (last_bytes - first_bytes) as f64 / (1024.0 * 1024.0)
+                                    / last_time.duration_since(*first_time).as_secs_f64()
+                            }
+                            _ => 0.0,
                         };
                         
                         let eta = if bytes_written > 0 && speed_mbps > 0.0 {
@@ -877,7 +1421,7 @@ impl DataSanitizer {
                             std::time::Duration::from_secs(0)
                         };
                         
-                        println!("📊 Pass {}/{}: {:.1}% complete - {:.2} GB processed - {:.1} MB/s - ETA: {:?}", 
+                        log::debug!("📊 Pass {}/{}: {:.1}% complete - {:.2} GB processed - {:.1} MB/s - ETA: {:?}", 
                                 current_pass, total_passes, percentage, 
                                 bytes_written as f64 / (1024.0 * 1024.0 * 1024.0),
                                 speed_mbps, eta);
@@ -896,8 +1440,10 @@ impl DataSanitizer {
                     }
                 }
                 Err(e) => {
-                    println!("❌ Write failed at byte {}: {}", bytes_written, e);
-                    return Err(e);
+                    self.handle_error(format!("Write failed at byte {} ({} bytes): {}", bytes_written, write_size, e), e)?;
+                    // Skip past the failed region and keep going
+                    bytes_written += write_size as u64;
+                    file.seek(SeekFrom::Start(bytes_written))?;
                 }
             }
         }
@@ -905,69 +1451,105 @@ impl DataSanitizer {
         // Final sync to ensure all data is written to disk
         file.sync_all()?;
         
-        println!("✅ Pass {}/{} completed: {} bytes overwritten", 
+        log::info!("✅ Pass {}/{} completed: {} bytes overwritten", 
                 current_pass, total_passes, bytes_written);
         
         Ok(())
     }
     
-    /// Verify disk sanitization by sampling random sectors
-    fn verify_disk_sanitization(&self, device_file: &std::fs::File, device_size: u64) -> io::Result<bool> {
-        use std::io::{Read, Seek, SeekFrom};
+    /// Verify disk sanitization by sampling random sectors. The samples are split across
+    /// `thread_count` workers doing positioned reads on the shared handle.
+    fn verify_disk_sanitization(&self, device_file: &std::fs::File, device_size: u64, sector_size: u32) -> io::Result<bool> {
+        use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
         
-        let mut file = device_file;
         let verification_samples = 1000; // Sample 1000 random locations
-        let sample_size = 4096; // 4KB per sample
-        let mut buffer = vec![0u8; sample_size];
-        let mut suspicious_patterns = 0;
-        
-        println!("🔍 Verifying sanitization by sampling {} random locations...", verification_samples);
+        let give_up_after = 10;          // Suspicious samples that decide the outcome on their own
+        // Each sample is whole sectors starting on a sector boundary, so 4Kn drives are
+        // never asked for a partial or misaligned sector
+        let sector_size = u64::from(sector_size.max(1));
+        let sample_size = VERIFY_SAMPLE_SIZE.div_ceil(sector_size) * sector_size;
+        if device_size < sample_size {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput,
+                format!("Device of {} bytes is smaller than one {}-byte verification sample", device_size, sample_size)));
+        }
+        let last_sample_sector = (device_size - sample_size) / sector_size;
         
-        use rand::Rng;
+        // Random sector-aligned positions with the whole sample inside the device, sorted
+        // so each worker sweeps its own stretch of the drive in one direction
         let mut rng = rand::thread_rng();
-        
-        for i in 0..verification_samples {
-            // Generate random position (aligned to 4KB boundaries)
-            let max_position = (device_size / sample_size as u64) * sample_size as u64;
-            let position = (rng.r#gen::<u64>() % (max_position / sample_size as u64)) * sample_size as u64;
-            
-            // Seek to position and read
-            file.seek(SeekFrom::Start(position))?;
-            match file.read_exact(&mut buffer) {
-                Ok(_) => {
+        let mut positions: Vec<u64> = (0..verification_samples)
+            .map(|_| rng.gen_range(0..=last_sample_sector) * sector_size)
+            .collect();
+        positions.sort_unstable();
+        
+        let workers = self.thread_count.clamp(1, verification_samples);
+        log::info!("🔍 Verifying sanitization by sampling {} random locations on {} thread(s)...", verification_samples, workers);
+        
+        let checked = AtomicUsize::new(0);
+        let suspicious = AtomicUsize::new(0);
+        let failed = AtomicBool::new(false);
+        let results: Vec<io::Result<Vec<SectorSample>>> = positions
+            .par_chunks(positions.len().div_ceil(workers))
+            .map(|chunk| {
+                let mut buffer = vec![0u8; sample_size as usize];
+                let mut samples = Vec::with_capacity(chunk.len());
+                for &position in chunk {
+                    // Another worker already decided the outcome
+                    if failed.load(Ordering::Relaxed) || suspicious.load(Ordering::Relaxed) > give_up_after {
+                        break;
+                    }
+                    if let Err(e) = read_exact_at(device_file, &mut buffer, position) {
+                        log::error!("❌ Read verification failed at position {}: {}", position, e);
+                        failed.store(true, Ordering::Relaxed);
+                        return Err(e);
+                    }
+                    samples.push(SectorSample::of(position, &buffer));
                     // Analyze the data for patterns that might indicate incomplete sanitization
                     if self.contains_suspicious_patterns(&buffer) {
-                        suspicious_patterns += 1;
-                        if suspicious_patterns > 10 { // Allow some tolerance for normal random data
-                            println!("⚠️  Verification failed: Found {} suspicious patterns in {} samples", 
-                                    suspicious_patterns, i + 1);
-                            return Ok(false);
-                        }
+                        suspicious.fetch_add(1, Ordering::Relaxed);
                     }
-                }
-                Err(e) => {
-                    println!("❌ Read verification failed at position {}: {}", position, e);
-                    // Don't fail verification for read errors near end of device
-                    if position < device_size - sample_size as u64 {
-                        return Err(e);
+                    
+                    // Progress update every 100 samples
+                    let done = checked.fetch_add(1, Ordering::Relaxed) + 1;
+                    if done % 100 == 0 {
+                        log::debug!("🔍 Verification progress: {}/{} samples checked, {} suspicious patterns found", 
+                                done, verification_samples, suspicious.load(Ordering::Relaxed));
                     }
                 }
-            }
-            
-            // Progress update every 100 samples
-            if (i + 1) % 100 == 0 {
-                println!("🔍 Verification progress: {}/{} samples checked, {} suspicious patterns found", 
-                        i + 1, verification_samples, suspicious_patterns);
-            }
+                Ok(samples)
+            })
+            .collect();
+        
+        let mut samples = Vec::with_capacity(verification_samples);
+        for result in results {
+            samples.extend(result?);
+        }
+        let checked = checked.into_inner();
+        let suspicious_patterns = suspicious.into_inner();
+        self.record_samples(samples);
+        
+        if suspicious_patterns > give_up_after { // Allow some tolerance for normal random data
+            log::warn!("⚠️  Verification failed: Found {} suspicious patterns in {} samples", 
+                    suspicious_patterns, checked);
+            return Ok(false);
         }
         
-        println!("✅ Verification completed: {}/{} samples checked, {} suspicious patterns found", 
-                verification_samples, verification_samples, suspicious_patterns);
+        log::info!("✅ Verification completed: {}/{} samples checked, {} suspicious patterns found", 
+                checked, verification_samples, suspicious_patterns);
         
         // Pass verification if we found very few suspicious patterns
         Ok(suspicious_patterns <= 5)
     }
     
+    /// Keep the verification samples for the certificate, in offset order
+    fn record_samples(&self, mut samples: Vec<SectorSample>) {
+        samples.sort_by_key(|sample| sample.offset);
+        samples.dedup_by_key(|sample| sample.offset);
+        if let Ok(mut sampled_sectors) = self.sampled_sectors.lock() {
+            *sampled_sectors = samples;
+        }
+    }
+    
     /// Check if a buffer contains patterns that might indicate incomplete sanitization
     fn contains_suspicious_patterns(&self, buffer: &[u8]) -> bool {
         // Check for common file system signatures
@@ -1070,7 +1652,7 @@ impl DataSanitizer {
         writeln!(report_file, "Report saved as: {}", report_filename)?;
         writeln!(report_file, "================================================")?;
         
-        println!("📋 NIST SP 800-88 compliance report generated: {}", report_filename);
+        log::info!("📋 NIST SP 800-88 compliance report generated: {}", report_filename);
         
         Ok(())
     }
@@ -1120,7 +1702,7 @@ pub mod ssd_sanitization {
             // On Linux, use hdparm for SSD secure erase
             use std::process::Command;
             
-            println!("🔧 Attempting SSD secure erase using hdparm...");
+            log::info!("🔧 Attempting SSD secure erase using hdparm...");
             
             // First, check if the device supports secure erase
             let output = Command::new("hdparm")
@@ -1150,7 +1732,7 @@ pub mod ssd_sanitization {
                 .arg(drive_path)
                 .status()?;
             
-            println!("✅ SSD secure erase completed");
+            log::info!("✅ SSD secure erase completed");
             Ok(())
         }
         
@@ -1171,30 +1753,13 @@ pub fn sanitize_device_with_size<P: AsRef<Path>>(
     let sanitizer = DataSanitizer::high_performance();
     let device_size = size_in_sectors * 512; // Convert sectors to bytes
     
-    let patterns = match method {
-        SanitizationMethod::Clear => vec![SanitizationPattern::Zeros],
-        SanitizationMethod::Purge => vec![
-            SanitizationPattern::Random,
-            SanitizationPattern::Ones,
-            SanitizationPattern::Zeros,
-        ],
-        SanitizationMethod::SecureErase | 
-        SanitizationMethod::EnhancedSecureErase => {
-            // For ATA Secure Erase, we still need to overwrite as fallback
-            vec![SanitizationPattern::Random]
-        },
-        SanitizationMethod::ComprehensiveClean => vec![
-            SanitizationPattern::Random,
-            SanitizationPattern::DoD5220,
-            SanitizationPattern::Zeros,
-        ],
-    };
+    let patterns = method.patterns();
     
-    println!("📝 Starting sanitization of {:.2} GB using native capacity", 
+    log::info!("📝 Starting sanitization of {:.2} GB using native capacity", 
              device_size as f64 / (1024.0 * 1024.0 * 1024.0));
     
     let progress_callback = Some(Box::new(|progress: SanitizationProgress| {
-        println!("Progress: {:.1}% - Pass {}/{} - {:.2} GB processed", 
+        log::debug!("Progress: {:.1}% - Pass {}/{} - {:.2} GB processed", 
                 progress.percentage, 
                 progress.current_pass, 
                 progress.total_passes,
@@ -1210,6 +1775,39 @@ mod tests {
     use std::fs;
     use tempfile::NamedTempFile;
 
+    #[test]
+    fn test_method_conversions_agree_on_passes() {
+        let methods = [
+            SanitizationMethod::Clear,
+            SanitizationMethod::Purge,
+            SanitizationMethod::SecureErase,
+            SanitizationMethod::EnhancedSecureErase,
+            SanitizationMethod::ComprehensiveClean,
+        ];
+        for method in methods {
+            let algorithm = WipingAlgorithm::from(method.clone());
+            assert_eq!(algorithm.pass_count() as usize, method.patterns().len(), "{:?}", method);
+            assert!(SanitizationMethod::try_from(&algorithm).is_ok());
+        }
+        assert!(SanitizationMethod::try_from(&WipingAlgorithm::Gutmann).is_err());
+    }
+
+    #[test]
+    fn test_multi_pass_settings() {
+        use crate::advanced_wiper::OverwritePattern;
+
+        let algorithm = WipingAlgorithm::MultiPass { passes: 5, pattern: OverwritePattern::RandomThenZeros };
+        assert_eq!(algorithm.pass_count(), 5);
+        assert_eq!(algorithm.to_string().parse::<WipingAlgorithm>(), Ok(algorithm));
+        assert!("Multi-Pass Overwrite (36 passes, Random)".parse::<WipingAlgorithm>().is_err());
+
+        let patterns = OverwritePattern::RandomThenZeros.patterns(5);
+        assert_eq!(patterns.len(), 5);
+        assert!(matches!(patterns[3], SanitizationPattern::Random));
+        assert!(matches!(patterns[4], SanitizationPattern::Zeros));
+        assert!(matches!(OverwritePattern::Alternating.patterns(2)[..], [SanitizationPattern::Zeros, SanitizationPattern::Ones]));
+    }
+
     #[test]
     fn test_clear_method() {
         let mut temp_file = NamedTempFile::new().unwrap();
@@ -1240,4 +1838,22 @@ mod tests {
         let custom = sanitizer.generate_pattern_buffer(&SanitizationPattern::Custom(0x42), 100);
         assert!(custom.iter().all(|&b| b == 0x42));
     }
+
+    #[test]
+    fn test_quick_sanitize_regions() {
+        let mb = 1024 * 1024;
+        let size = 4096 * mb;
+        // One partition from 1 MiB to the end of the disk
+        let regions = quick_sanitize_regions(size, &[(mb, size - mb)]);
+        assert_eq!(regions, vec![(0, QUICK_EDGE_SIZE), (size - QUICK_EDGE_SIZE, size)]);
+
+        // A partition in the middle gets its head and tail overwritten
+        let regions = quick_sanitize_regions(size, &[(2048 * mb, 3072 * mb)]);
+        assert_eq!(regions.len(), 4);
+        assert_eq!(regions[1], (2048 * mb, 2048 * mb + QUICK_PARTITION_HEAD));
+        assert_eq!(regions[2], (3072 * mb - QUICK_PARTITION_TAIL, 3072 * mb));
+
+        // Tiny devices are covered by a single region
+        assert_eq!(quick_sanitize_regions(mb, &[]), vec![(0, mb)]);
+    }
 }
\ No newline at end of file
@@ -7,8 +7,12 @@ use chrono::{DateTime, Utc};
 use uuid::Uuid;
 use std::fs::{self};
 use std::path::Path;
+use x509_parser::certificate::X509Certificate;
+use x509_parser::extensions::KeyUsage;
+use x509_parser::oid_registry::*;
+use x509_parser::pem::Pem;
 
-use crate::core::{WipeResult, WipeRequest, SecurityFeatures, WipeError, WipeErrorCode, WipeResult2};
+use crate::core::{WipeResult, WipeRequest, SecurityFeatures, WipeError, WipeErrorCode, WipeResult2, OrganizationSigning};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ErasureCertificate {
@@ -24,6 +28,20 @@ pub struct ErasureCertificate {
     pub signature: String,
     pub public_key: String,
     pub certificate_hash: String,
+    /// Base64 DER X.509 certificates binding `public_key` to the issuing organization's
+    /// CA, the signing key's own first; empty when the certificate is self-signed
+    #[serde(default)]
+    pub certificate_chain: Vec<String>,
+}
+
+impl ErasureCertificate {
+    /// Subjects of the chain certificates, signing certificate first, for display
+    pub fn chain_subjects(&self) -> Vec<String> {
+        self.certificate_chain.iter()
+            .filter_map(|der| general_purpose::STANDARD.decode(der).ok())
+            .filter_map(|der| x509_parser::parse_x509_certificate(&der).ok().map(|(_, certificate)| certificate.subject().to_string()))
+            .collect()
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -63,9 +81,32 @@ pub struct VerificationDetails {
 pub struct CertificateAuthority {
     pub name: String,
     pub organization: String,
+    /// Self-signed key; empty when signing with an organization's key
     pub private_key_pem: String,
     pub public_key_pem: String,
     pub certificate_counter: u64,
+    /// File holding the organization's signing key. The key is read from here, never
+    /// written into the CA file next to the issued certificates.
+    #[serde(default)]
+    pub signing_key_path: Option<String>,
+    #[serde(skip)]
+    organization_key_pem: Option<String>,
+    /// Base64 DER certificates of the signing key and any intermediate CAs, copied into
+    /// every issued certificate; empty for a self-signed authority
+    #[serde(default)]
+    pub certificate_chain: Vec<String>,
+    /// PEM certificate of the organizational root CA. When set, `verify_certificate` only
+    /// accepts certificates whose chain leads to it.
+    #[serde(default)]
+    pub trust_anchor_pem: Option<String>,
+}
+
+fn signing_error(message: String) -> WipeError {
+    WipeError {
+        code: WipeErrorCode::UnknownError,
+        message,
+        sector: None,
+    }
 }
 
 impl CertificateAuthority {
@@ -102,6 +143,42 @@ impl CertificateAuthority {
             private_key_pem: private_key_pem.to_string(),
             public_key_pem: public_key_pem.to_string(),
             certificate_counter: 0,
+            signing_key_path: None,
+            organization_key_pem: None,
+            certificate_chain: Vec::new(),
+            trust_anchor_pem: None,
+        })
+    }
+
+    /// An authority that signs with an organization's key, so issued certificates chain to
+    /// the CA its auditors already trust. The chain is checked against the trust anchor
+    /// here, so a mismatched key or expired certificate fails at startup, not at audit.
+    pub fn from_organization_signing(name: String, organization: String, signing: &OrganizationSigning) -> WipeResult2<Self> {
+        let read = |path: &str| fs::read_to_string(path)
+            .map_err(|e| signing_error(format!("Failed to read {}: {}", path, e)));
+        let private_key_pem = read(&signing.signing_key_path)?;
+        let chain_pem = read(&signing.certificate_chain_path)?;
+        let trust_anchor_pem = read(&signing.trust_anchor_path)?;
+
+        let private_key: RsaPrivateKey = rsa::pkcs8::DecodePrivateKey::from_pkcs8_pem(&private_key_pem)
+            .map_err(|e| signing_error(format!("Failed to decode signing key: {}", e)))?;
+        let public_key_pem = rsa::pkcs8::EncodePublicKey::to_public_key_pem(&RsaPublicKey::from(&private_key), rsa::pkcs8::LineEnding::LF)
+            .map_err(|e| signing_error(format!("Failed to encode public key: {}", e)))?;
+
+        let certificate_chain = chain_from_pem(&chain_pem)?;
+        verify_chain(&certificate_chain, &trust_anchor_pem, &public_key_pem, Utc::now())
+            .map_err(|reason| signing_error(format!("Signing certificate does not chain to the trust anchor: {}", reason)))?;
+
+        Ok(Self {
+            name,
+            organization,
+            private_key_pem: String::new(),
+            public_key_pem,
+            certificate_counter: 0,
+            signing_key_path: Some(signing.signing_key_path.clone()),
+            organization_key_pem: Some(private_key_pem),
+            certificate_chain,
+            trust_anchor_pem: Some(trust_anchor_pem),
         })
     }
 
@@ -113,12 +190,17 @@ impl CertificateAuthority {
                 sector: None,
             })?;
 
-        serde_json::from_str(&content)
+        let mut ca: Self = serde_json::from_str(&content)
             .map_err(|e| WipeError {
                 code: WipeErrorCode::UnknownError,
                 message: format!("Failed to parse CA file: {}", e),
                 sector: None,
-            })
+            })?;
+        if let Some(ref key_path) = ca.signing_key_path {
+            ca.organization_key_pem = Some(fs::read_to_string(key_path)
+                .map_err(|e| signing_error(format!("Failed to read {}: {}", key_path, e)))?);
+        }
+        Ok(ca)
     }
 
     pub fn save_to_file<P: AsRef<Path>>(&self, path: P) -> WipeResult2<()> {
@@ -206,6 +288,7 @@ impl CertificateAuthority {
             signature: String::new(),
             public_key: self.public_key_pem.clone(),
             certificate_hash: String::new(),
+            certificate_chain: self.certificate_chain.clone(),
         };
 
         // Calculate hash of certificate content (excluding signature and hash fields)
@@ -254,7 +337,8 @@ impl CertificateAuthority {
     }
 
     fn sign_data(&self, data: &[u8]) -> WipeResult2<Vec<u8>> {
-        let private_key = rsa::pkcs8::DecodePrivateKey::from_pkcs8_pem(&self.private_key_pem)
+        let private_key_pem = self.organization_key_pem.as_deref().unwrap_or(&self.private_key_pem);
+        let private_key = rsa::pkcs8::DecodePrivateKey::from_pkcs8_pem(private_key_pem)
             .map_err(|e| WipeError {
                 code: WipeErrorCode::UnknownError,
                 message: format!("Failed to decode private key: {}", e),
@@ -302,9 +386,21 @@ impl CertificateAuthority {
                 sector: None,
             })?;
 
-        match verifying_key.verify(&content_hash, &signature) {
-            Ok(_) => Ok(true),
-            Err(_) => Ok(false),
+        if verifying_key.verify(&content_hash, &signature).is_err() {
+            return Ok(false);
+        }
+
+        // A valid signature only proves the key signed it; with a trust anchor the key
+        // must also belong to the organization
+        let Some(ref trust_anchor_pem) = self.trust_anchor_pem else {
+            return Ok(true);
+        };
+        match verify_chain(&certificate.certificate_chain, trust_anchor_pem, &certificate.public_key, certificate.issued_at) {
+            Ok(()) => Ok(true),
+            Err(reason) => {
+                log::warn!("Certificate {} does not chain to the trust anchor: {}", certificate.certificate_id, reason);
+                Ok(false)
+            }
         }
     }
 
@@ -365,4 +461,147 @@ impl CertificateAuthority {
             }
         }
     }
-}
\ No newline at end of file
+}
+
+/// Certificates in a PEM file as base64 DER, in file order
+fn chain_from_pem(chain_pem: &str) -> WipeResult2<Vec<String>> {
+    let mut chain = Vec::new();
+    for pem in Pem::iter_from_buffer(chain_pem.as_bytes()) {
+        let pem = pem.map_err(|e| signing_error(format!("Failed to read certificate chain: {}", e)))?;
+        if pem.label == "CERTIFICATE" {
+            chain.push(general_purpose::STANDARD.encode(&pem.contents));
+        }
+    }
+    Ok(chain)
+}
+
+/// Check that `chain` (base64 DER, signing certificate first) certifies `public_key_pem`
+/// for signing, that each certificate was issued by the next and the last by the trust
+/// anchor, each issuer being allowed to issue certificates, and that all of them were
+/// valid at `at`. Returns why not otherwise.
+fn verify_chain(chain: &[String], trust_anchor_pem: &str, public_key_pem: &str, at: DateTime<Utc>) -> Result<(), String> {
+    let (_, anchor_pem) = x509_parser::pem::parse_x509_pem(trust_anchor_pem.as_bytes())
+        .map_err(|e| format!("unreadable trust anchor: {}", e))?;
+    let anchor = anchor_pem.parse_x509()
+        .map_err(|e| format!("unreadable trust anchor: {}", e))?;
+
+    let chain_der = chain.iter()
+        .map(|der| general_purpose::STANDARD.decode(der))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("undecodable chain certificate: {}", e))?;
+    let mut certificates = chain_der.iter()
+        .map(|der| x509_parser::parse_x509_certificate(der).map(|(_, certificate)| certificate))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("unreadable chain certificate: {}", e))?;
+    // The anchor itself may be included at the end of the chain
+    if certificates.last().is_some_and(|last| last.as_ref() == anchor.as_ref()) {
+        certificates.pop();
+    }
+    let Some(signing_certificate) = certificates.first() else {
+        return Err("no signing certificate".to_string());
+    };
+
+    let public_key: RsaPublicKey = rsa::pkcs8::DecodePublicKey::from_public_key_pem(public_key_pem)
+        .map_err(|e| format!("undecodable public key: {}", e))?;
+    let public_key_der = rsa::pkcs8::EncodePublicKey::to_public_key_der(&public_key)
+        .map_err(|e| format!("unencodable public key: {}", e))?;
+    if signing_certificate.public_key().raw != public_key_der.as_bytes() {
+        return Err(format!("the signing certificate ({}) is for a different key", signing_certificate.subject()));
+    }
+    if !key_usage_allows(signing_certificate, |usage| usage.digital_signature()) {
+        return Err(format!("{} may not be used for signing", signing_certificate.subject()));
+    }
+
+    let issuers = certificates.iter().skip(1).chain(std::iter::once(&anchor));
+    for (position, (certificate, issuer)) in certificates.iter().zip(issuers).enumerate() {
+        let validity = certificate.validity();
+        if at.timestamp() < validity.not_before.timestamp() || at.timestamp() > validity.not_after.timestamp() {
+            return Err(format!("{} was not valid at {}", certificate.subject(), at.to_rfc3339()));
+        }
+        if position > 0 && !certificate.is_ca() {
+            return Err(format!("{} is not a CA", certificate.subject()));
+        }
+        if !key_usage_allows(issuer, |usage| usage.key_cert_sign()) {
+            return Err(format!("{} may not issue certificates", issuer.subject()));
+        }
+        if certificate.issuer().as_raw() != issuer.subject().as_raw() || !signed_by(certificate, issuer) {
+            return Err(format!("{} was not issued by {}", certificate.subject(), issuer.subject()));
+        }
+    }
+    Ok(())
+}
+
+/// Whether the certificate's keyUsage extension permits a use; no extension permits all
+fn key_usage_allows(certificate: &X509Certificate, allowed: impl Fn(&KeyUsage) -> bool) -> bool {
+    match certificate.key_usage() {
+        Ok(Some(usage)) => allowed(usage.value),
+        Ok(None) => true,
+        Err(_) => false,
+    }
+}
+
+/// Whether `issuer`'s key made the signature on `certificate`
+fn signed_by(certificate: &X509Certificate, issuer: &X509Certificate) -> bool {
+    use ring::signature;
+
+    let issuer_key = issuer.public_key();
+    let curve = issuer_key.algorithm.parameters.as_ref().and_then(|parameters| parameters.as_oid().ok());
+    let algorithm: &dyn signature::VerificationAlgorithm = match &certificate.signature_algorithm.algorithm {
+        oid if *oid == OID_PKCS1_SHA256WITHRSA => &signature::RSA_PKCS1_2048_8192_SHA256,
+        oid if *oid == OID_PKCS1_SHA384WITHRSA => &signature::RSA_PKCS1_2048_8192_SHA384,
+        oid if *oid == OID_PKCS1_SHA512WITHRSA => &signature::RSA_PKCS1_2048_8192_SHA512,
+        oid if *oid == OID_SIG_ECDSA_WITH_SHA256 && curve == Some(OID_EC_P256) => &signature::ECDSA_P256_SHA256_ASN1,
+        oid if *oid == OID_SIG_ECDSA_WITH_SHA384 && curve == Some(OID_EC_P256) => &signature::ECDSA_P256_SHA384_ASN1,
+        oid if *oid == OID_SIG_ECDSA_WITH_SHA256 && curve == Some(OID_NIST_EC_P384) => &signature::ECDSA_P384_SHA256_ASN1,
+        oid if *oid == OID_SIG_ECDSA_WITH_SHA384 && curve == Some(OID_NIST_EC_P384) => &signature::ECDSA_P384_SHA384_ASN1,
+        oid if *oid == OID_SIG_ED25519 => &signature::ED25519,
+        _ => return false,
+    };
+    signature::UnparsedPublicKey::new(algorithm, &issuer_key.subject_public_key.data)
+        .verify(certificate.tbs_certificate.as_ref(), &certificate.signature_value.data)
+        .is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Example Org Root CA (P-256) → Example Org Issuing CA (RSA) → ShredX Certificate Signing
+    const ROOT: &str = include_str!("testdata/root.pem");
+    const INTERMEDIATE: &str = include_str!("testdata/inter.pem");
+    const SIGNING: &str = include_str!("testdata/signing.pem");
+    const SIGNING_PUBLIC_KEY: &str = include_str!("testdata/signing_public.pem");
+    const OTHER_PUBLIC_KEY: &str = include_str!("testdata/other_public.pem");
+    // A different root with the same name as ROOT
+    const OTHER_ROOT: &str = include_str!("testdata/other_root.pem");
+    // Same signing key, but certified without digitalSignature
+    const SIGNING_NO_DIGITAL_SIGNATURE: &str = include_str!("testdata/signing_no_digital_signature.pem");
+    // An end-entity certificate of the issuing CA, used as if it were an intermediate
+    const NOT_A_CA: &str = include_str!("testdata/not_a_ca.pem");
+    const SIGNING_UNDER_NOT_A_CA: &str = include_str!("testdata/signing_under_not_a_ca.pem");
+
+    fn chain(pems: &[&str]) -> Vec<String> {
+        chain_from_pem(&pems.concat()).unwrap()
+    }
+
+    #[test]
+    fn test_verify_chain() {
+        let now = Utc::now();
+
+        assert_eq!(verify_chain(&chain(&[SIGNING, INTERMEDIATE]), ROOT, SIGNING_PUBLIC_KEY, now), Ok(()));
+        // The anchor may be included at the end of the chain
+        assert_eq!(verify_chain(&chain(&[SIGNING, INTERMEDIATE, ROOT]), ROOT, SIGNING_PUBLIC_KEY, now), Ok(()));
+
+        assert!(verify_chain(&chain(&[SIGNING, INTERMEDIATE]), ROOT, OTHER_PUBLIC_KEY, now).is_err());
+        assert!(verify_chain(&chain(&[SIGNING]), ROOT, SIGNING_PUBLIC_KEY, now).is_err());
+        assert!(verify_chain(&[], ROOT, SIGNING_PUBLIC_KEY, now).is_err());
+        assert!(verify_chain(&chain(&[SIGNING, INTERMEDIATE]), OTHER_ROOT, SIGNING_PUBLIC_KEY, now).is_err());
+
+        // Long after every certificate in the chain expired
+        let expired = "2200-01-01T00:00:00Z".parse().unwrap();
+        assert!(verify_chain(&chain(&[SIGNING, INTERMEDIATE]), ROOT, SIGNING_PUBLIC_KEY, expired).is_err());
+
+        assert!(verify_chain(&chain(&[SIGNING_UNDER_NOT_A_CA, NOT_A_CA, INTERMEDIATE]), ROOT, SIGNING_PUBLIC_KEY, now).is_err());
+        assert!(verify_chain(&chain(&[SIGNING_NO_DIGITAL_SIGNATURE, INTERMEDIATE]), ROOT, SIGNING_PUBLIC_KEY, now).is_err());
+    }
+}
@@ -315,6 +315,17 @@ impl ReportGenerator {
             certificate.signature.clone()
         };
         layer.use_text(&sig_display, 8.0, Mm(20.0), Mm(y_pos as f32), font);
+        y_pos -= 15.0;
+
+        layer.use_text("Certificate Chain:", 10.0, Mm(20.0), Mm(y_pos as f32), font_bold);
+        let chain = certificate.chain_subjects();
+        if chain.is_empty() {
+            layer.use_text("Self-signed", 8.0, Mm(70.0), Mm(y_pos as f32), font);
+        }
+        for subject in chain {
+            layer.use_text(&subject, 8.0, Mm(70.0), Mm(y_pos as f32), font);
+            y_pos -= 10.0;
+        }
 
         Ok(y_pos - 20.0)
     }
@@ -1,17 +1,30 @@
 use warp::{Filter, Reply};
 use std::sync::Arc;
 use uuid::Uuid;
-use crate::server::{DatabaseManager, models::*};
+use crate::server::{DatabaseManager, metrics, notify, password, pdf, session, models::*};
+use crate::server::notify::NotifyConfig;
 use sha2::{Sha256, Digest};
 
 pub async fn start_server(database_url: String, port: u16) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
     let db = Arc::new(DatabaseManager::new(&database_url).await?);
+    let notify_config = NotifyConfig::from_env(port).map(Arc::new);
+    
+    // Public servers can require new accounts to confirm their email before they can sign in
+    let require_verification = std::env::var("REQUIRE_EMAIL_VERIFICATION")
+        .map(|v| v == "true" || v == "1")
+        .unwrap_or(false);
+    let verification_config = if require_verification {
+        // Refuse to start rather than let anyone register unverified
+        let config = NotifyConfig::smtp_from_env(port)
+            .ok_or("REQUIRE_EMAIL_VERIFICATION is set but SMTP_HOST is missing")?;
+        println!("📧 New accounts must verify their email address before signing in");
+        Some(Arc::new(config))
+    } else {
+        None
+    };
     
     // CORS configuration
-    let cors = warp::cors()
-        .allow_any_origin()
-        .allow_headers(vec!["content-type", "authorization"])
-        .allow_methods(vec!["GET", "POST", "PUT", "DELETE"]);
+    let cors = cors_filter(port);
     
     // Static files route for dashboard
     let dashboard = warp::path::end()
@@ -25,8 +38,18 @@ pub async fn start_server(database_url: String, port: u16) -> Result<(), Box<dyn
         .and(warp::post())
         .and(warp::body::json())
         .and(with_db(db.clone()))
+        .and(warp::any().map(move || verification_config.clone()))
         .and_then(register_user);
     
+    let verify_email = warp::path("api")
+        .and(warp::path("auth"))
+        .and(warp::path("verify"))
+        .and(warp::path::end())
+        .and(warp::get())
+        .and(warp::query::<VerifyEmailQuery>())
+        .and(with_db(db.clone()))
+        .and_then(verify_email);
+    
     let login = warp::path("api")
         .and(warp::path("auth"))
         .and(warp::path("login"))
@@ -35,26 +58,121 @@ pub async fn start_server(database_url: String, port: u16) -> Result<(), Box<dyn
         .and(with_db(db.clone()))
         .and_then(login_user);
     
+    let reset_password = warp::path("api")
+        .and(warp::path("auth"))
+        .and(warp::path("reset"))
+        .and(warp::post())
+        .and(warp::body::json())
+        .and(with_db(db.clone()))
+        .and_then(reset_password);
+    
+    let issue_reset_token = warp::path("api")
+        .and(warp::path("admin"))
+        .and(warp::path("users"))
+        .and(warp::path::param::<Uuid>())
+        .and(warp::path("password-reset"))
+        .and(warp::post())
+        .and(with_credentials())
+        .and(with_db(db.clone()))
+        .and_then(issue_password_reset_token);
+    
+    let create_organization = warp::path("api")
+        .and(warp::path("admin"))
+        .and(warp::path("organizations"))
+        .and(warp::path::end())
+        .and(warp::post())
+        .and(with_credentials())
+        .and(warp::body::json())
+        .and(with_db(db.clone()))
+        .and_then(create_organization);
+    
+    let list_organizations = warp::path("api")
+        .and(warp::path("admin"))
+        .and(warp::path("organizations"))
+        .and(warp::path::end())
+        .and(warp::get())
+        .and(with_credentials())
+        .and(with_db(db.clone()))
+        .and_then(list_organizations);
+    
+    let assign_organization = warp::path("api")
+        .and(warp::path("admin"))
+        .and(warp::path("users"))
+        .and(warp::path::param::<Uuid>())
+        .and(warp::path("organization"))
+        .and(warp::put())
+        .and(with_credentials())
+        .and(warp::body::json())
+        .and(with_db(db.clone()))
+        .and_then(assign_user_organization);
+    
+    let create_api_key = warp::path("api")
+        .and(warp::path("keys"))
+        .and(warp::path::end())
+        .and(warp::post())
+        .and(with_credentials())
+        .and(warp::body::json())
+        .and(with_db(db.clone()))
+        .and_then(create_api_key);
+    
+    let list_api_keys = warp::path("api")
+        .and(warp::path("keys"))
+        .and(warp::path::end())
+        .and(warp::get())
+        .and(with_credentials())
+        .and(with_db(db.clone()))
+        .and_then(list_api_keys);
+    
+    let revoke_api_key = warp::path("api")
+        .and(warp::path("keys"))
+        .and(warp::path::param::<Uuid>())
+        .and(warp::path::end())
+        .and(warp::delete())
+        .and(with_credentials())
+        .and(with_db(db.clone()))
+        .and_then(revoke_api_key);
+    
+    let revoke_cert = warp::path("api")
+        .and(warp::path("admin"))
+        .and(warp::path("certificates"))
+        .and(warp::path::param::<Uuid>())
+        .and(warp::path("revoke"))
+        .and(warp::post())
+        .and(with_credentials())
+        .and(warp::body::json())
+        .and(with_db(db.clone()))
+        .and_then(revoke_certificate);
+    
+    // Public: anyone holding a certificate can check it is genuine and not revoked
+    let verify_cert = warp::path("api")
+        .and(warp::path("certificates"))
+        .and(warp::path::param::<Uuid>())
+        .and(warp::path("verify"))
+        .and(warp::get())
+        .and(with_db(db.clone()))
+        .and_then(verify_certificate);
+    
     let submit_cert = warp::path("api")
         .and(warp::path("certificates"))
         .and(warp::post())
-        .and(warp::header::<String>("authorization"))
+        .and(with_credentials())
         .and(warp::body::json())
         .and(with_db(db.clone()))
+        .and(warp::any().map(move || notify_config.clone()))
         .and_then(submit_certificate);
     
     let get_certs = warp::path("api")
         .and(warp::path("certificates"))
         .and(warp::get())
-        .and(warp::header::<String>("authorization"))
-        .and(warp::query::<PaginationQuery>())
+        .and(with_credentials())
+        .and(warp::query::<CertificateQuery>())
         .and(with_db(db.clone()))
         .and_then(get_certificates);
     
     let get_logs = warp::path("api")
         .and(warp::path("logs"))
         .and(warp::get())
-        .and(warp::header::<String>("authorization"))
+        .and(with_credentials())
         .and(warp::query::<PaginationQuery>())
         .and(with_db(db.clone()))
         .and_then(get_sanitization_logs);
@@ -65,28 +183,134 @@ pub async fn start_server(database_url: String, port: u16) -> Result<(), Box<dyn
         .and(warp::path::param::<Uuid>())
         .and(warp::path("download"))
         .and(warp::get())
-        .and(warp::header::<String>("authorization"))
+        .and(with_credentials())
         .and(with_db(db.clone()))
         .and_then(download_certificate);
     
+    let certificate_pdf = warp::path("api")
+        .and(warp::path("certificates"))
+        .and(warp::path::param::<Uuid>())
+        .and(warp::path("pdf"))
+        .and(warp::get())
+        .and(with_credentials())
+        .and(with_db(db.clone()))
+        .and_then(download_certificate_pdf);
+    
+    // Device registry routes
+    let register_device = warp::path("api")
+        .and(warp::path("devices"))
+        .and(warp::path::end())
+        .and(warp::post())
+        .and(with_credentials())
+        .and(warp::body::json())
+        .and(with_db(db.clone()))
+        .and_then(register_device);
+    
+    let list_devices = warp::path("api")
+        .and(warp::path("devices"))
+        .and(warp::path::end())
+        .and(warp::get())
+        .and(with_credentials())
+        .and(warp::query::<PaginationQuery>())
+        .and(with_db(db.clone()))
+        .and_then(list_devices);
+    
+    let get_device = warp::path("api")
+        .and(warp::path("devices"))
+        .and(warp::path::param::<String>())
+        .and(warp::path::end())
+        .and(warp::get())
+        .and(with_credentials())
+        .and(with_db(db.clone()))
+        .and_then(get_device);
+    
+    let update_device = warp::path("api")
+        .and(warp::path("devices"))
+        .and(warp::path::param::<String>())
+        .and(warp::path::end())
+        .and(warp::put())
+        .and(with_credentials())
+        .and(warp::body::json())
+        .and(with_db(db.clone()))
+        .and_then(update_device);
+    
+    let delete_device = warp::path("api")
+        .and(warp::path("devices"))
+        .and(warp::path::param::<String>())
+        .and(warp::path::end())
+        .and(warp::delete())
+        .and(with_credentials())
+        .and(with_db(db.clone()))
+        .and_then(delete_device);
+    
+    let device_history = warp::path("api")
+        .and(warp::path("devices"))
+        .and(warp::path::param::<String>())
+        .and(warp::path("certificates"))
+        .and(warp::path::end())
+        .and(warp::get())
+        .and(with_credentials())
+        .and(with_db(db.clone()))
+        .and_then(get_device_history);
+    
+    // Prometheus scrape endpoint
+    let metrics_route = warp::path("metrics")
+        .and(warp::path::end())
+        .and(warp::get())
+        .and(with_db(db.clone()))
+        .and_then(serve_metrics);
+    
     let routes = dashboard
+        .or(metrics_route)
         .or(register)
+        .or(verify_email)
         .or(login)
+        .or(reset_password)
+        .or(issue_reset_token)
+        .or(create_organization)
+        .or(list_organizations)
+        .or(assign_organization)
+        .or(create_api_key)
+        .or(list_api_keys)
+        .or(revoke_api_key)
+        .or(revoke_cert)
+        .or(verify_cert)
         .or(submit_cert)
         .or(get_certs)
         .or(download_cert)
+        .or(certificate_pdf)
         .or(get_logs)
+        .or(register_device)
+        .or(list_devices)
+        .or(device_history)
+        .or(get_device)
+        .or(update_device)
+        .or(delete_device)
         .with(cors);
     
     println!("🚀 HDD Tool Server starting on port {}", port);
     println!("📊 Dashboard available at: http://localhost:{}/", port);
+    println!("📈 Prometheus metrics at: http://localhost:{}/metrics", port);
     println!("🔗 API endpoints:");
     println!("   POST /api/auth/register - Create user account");
+    println!("   GET  /api/auth/verify?token= - Activate a registered account");
     println!("   POST /api/auth/login - User login");
+    println!("   POST /api/auth/reset - Reset password with a one-time token");
+    println!("   POST /api/admin/users/:id/password-reset - Issue reset token (admin)");
+    println!("   GET|POST /api/admin/organizations - List or create organizations (admin)");
+    println!("   PUT  /api/admin/users/:id/organization - Assign a user's organization and role (admin)");
+    println!("   GET|POST /api/keys, DELETE /api/keys/:id - Manage API keys (admin)");
     println!("   POST /api/certificates - Submit certificate");
-    println!("   GET  /api/certificates - Get user certificates");
+    println!("   GET  /api/certificates - Get user certificates (filters: device_type, method, success, serial, from, to)");
     println!("   GET  /api/certificates/:id/download - Download certificate");
+    println!("   GET  /api/certificates/:id/pdf - Download certificate as PDF");
+    println!("   GET  /api/certificates/:id/verify - Public certificate verification");
+    println!("   POST /api/admin/certificates/:id/revoke - Revoke a certificate (admin)");
     println!("   GET  /api/logs - Get sanitization logs");
+    println!("   POST /api/devices - Register a device");
+    println!("   GET  /api/devices - List registered devices");
+    println!("   GET|PUT|DELETE /api/devices/:serial - Manage a registered device");
+    println!("   GET  /api/devices/:serial/certificates - Device sanitization history");
     
     warp::serve(routes)
         .run(([0, 0, 0, 0], port))
@@ -95,10 +319,78 @@ pub async fn start_server(database_url: String, port: u16) -> Result<(), Box<dyn
     Ok(())
 }
 
+/// Build the CORS policy from `ALLOWED_ORIGINS` (comma-separated), defaulting to the
+/// dashboard served by this process. Any origin is only allowed when
+/// `CORS_ALLOW_ANY_ORIGIN=true` is set for local development.
+fn cors_filter(port: u16) -> warp::cors::Builder {
+    let cors = warp::cors()
+        .allow_headers(vec!["content-type", "authorization", "x-api-key"])
+        .allow_methods(vec!["GET", "POST", "PUT", "DELETE"]);
+    
+    let allow_any = std::env::var("CORS_ALLOW_ANY_ORIGIN")
+        .map(|v| v == "true" || v == "1")
+        .unwrap_or(false);
+    if allow_any {
+        println!("⚠️  CORS_ALLOW_ANY_ORIGIN is set - accepting requests from any origin (development only)");
+        return cors.allow_any_origin();
+    }
+    
+    let mut origins: Vec<String> = Vec::new();
+    if let Ok(configured) = std::env::var("ALLOWED_ORIGINS") {
+        for origin in configured.split(',').map(|o| o.trim().trim_end_matches('/')).filter(|o| !o.is_empty()) {
+            match parse_origin(origin) {
+                Some(origin) => origins.push(origin),
+                None => println!("⚠️  Ignoring invalid CORS origin: {}", origin),
+            }
+        }
+    }
+    if origins.is_empty() {
+        origins.push(format!("http://localhost:{}", port));
+    }
+    
+    println!("🔒 CORS allowed origins: {}", origins.join(", "));
+    cors.allow_origins(origins.iter().map(String::as_str))
+}
+
+/// Normalise a configured origin to `scheme://host[:port]`, or `None` if it is not a
+/// plain http(s) origin. Anything with a path, query or credentials is rejected here
+/// because `allow_origins` panics on values it cannot parse.
+fn parse_origin(origin: &str) -> Option<String> {
+    let uri: warp::http::Uri = origin.parse().ok()?;
+    let scheme = uri.scheme_str()?.to_ascii_lowercase();
+    let authority = uri.authority()?;
+    let (_, rest) = origin.split_once("://")?;
+    if !(scheme == "http" || scheme == "https")
+        || authority.as_str() != rest
+        || rest.contains('@')
+        || authority.host().is_empty()
+        || (authority.port().is_none() && authority.as_str() != authority.host())
+    {
+        return None;
+    }
+    Some(format!("{}://{}", scheme, authority.as_str().to_ascii_lowercase()))
+}
+
+/// Credentials for a request: the `X-API-Key` header if present, otherwise the
+/// `Authorization` header. Handlers resolve either form with `authenticate`.
+fn with_credentials() -> impl Filter<Extract = (String,), Error = warp::Rejection> + Clone {
+    warp::header::optional::<String>("x-api-key")
+        .and(warp::header::optional::<String>("authorization"))
+        .map(|api_key: Option<String>, authorization: Option<String>| match api_key {
+            Some(key) => format!("ApiKey {}", key.trim()),
+            None => authorization.unwrap_or_default(),
+        })
+}
+
 fn with_db(db: Arc<DatabaseManager>) -> impl Filter<Extract = (Arc<DatabaseManager>,), Error = std::convert::Infallible> + Clone {
     warp::any().map(move || db.clone())
 }
 
+#[derive(serde::Deserialize)]
+struct VerifyEmailQuery {
+    token: String,
+}
+
 #[derive(serde::Deserialize)]
 struct PaginationQuery {
     #[serde(default = "default_limit")]
@@ -109,30 +401,153 @@ struct PaginationQuery {
 
 fn default_limit() -> i64 { 50 }
 
-// Extract user ID from Bearer token (simplified - in production use JWT)
+/// Pagination plus the optional certificate filters, e.g.
+/// `?device_type=SSD&success=false&from=2024-01-01T00:00:00Z`
+#[derive(serde::Deserialize)]
+struct CertificateQuery {
+    #[serde(default = "default_limit")]
+    limit: i64,
+    #[serde(default)]
+    offset: i64,
+    device_type: Option<String>,
+    method: Option<String>,
+    success: Option<bool>,
+    serial: Option<String>,
+    from: Option<chrono::DateTime<chrono::Utc>>,
+    to: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// The user behind a signed Bearer token issued by `login_user`/`register_user`
 fn extract_user_id(auth_header: &str) -> Result<Uuid, String> {
-    if let Some(token) = auth_header.strip_prefix("Bearer ") {
-        Uuid::parse_str(token).map_err(|_| "Invalid token format".to_string())
+    let result = if let Some(token) = auth_header.strip_prefix("Bearer ") {
+        session::verify(token.trim())
     } else {
         Err("Invalid authorization header".to_string())
+    };
+    if result.is_err() {
+        metrics::record_auth_failure();
+    }
+    result
+}
+
+/// Resolve the user behind a request's credentials, either an API key or a Bearer token
+async fn authenticate(auth_header: &str, db: &DatabaseManager) -> Result<Uuid, String> {
+    let Some(key) = auth_header.strip_prefix("ApiKey ") else {
+        return extract_user_id(auth_header);
+    };
+    match db.resolve_api_key(key).await {
+        Ok(Some(user_id)) => Ok(user_id),
+        Ok(None) => {
+            metrics::record_auth_failure();
+            Err("Invalid or revoked API key".to_string())
+        }
+        Err(e) => Err(format!("Failed to verify API key: {}", e)),
+    }
+}
+
+/// Load the active user behind the request's credentials; their organization and
+/// role decide which certificates they can see
+async fn require_user(auth_header: &str, db: &DatabaseManager) -> Result<ServerUser, String> {
+    let user_id = authenticate(auth_header, db).await?;
+    match db.get_user_by_id(user_id).await {
+        Ok(Some(user)) if user.is_active => Ok(user),
+        Ok(_) => {
+            metrics::record_auth_failure();
+            Err("Unknown or inactive user".to_string())
+        }
+        Err(e) => Err(format!("Failed to verify user: {}", e)),
+    }
+}
+
+async fn require_admin(auth_header: &str, db: &DatabaseManager) -> Result<ServerUser, String> {
+    let user = require_user(auth_header, db).await?;
+    if user.is_admin() {
+        Ok(user)
+    } else {
+        metrics::record_auth_failure();
+        Err("Admin privileges required".to_string())
     }
 }
 
 async fn register_user(
     req: CreateUserRequest,
     db: Arc<DatabaseManager>,
+    verification_config: Option<Arc<NotifyConfig>>,
 ) -> Result<impl warp::Reply, warp::Rejection> {
+    // Refused accounts get a 400 naming the rule they broke
+    let invalid = if req.username.trim().len() < 3 {
+        Some("Username must be at least 3 characters".to_string())
+    } else {
+        password::validate_password_strength(&req.password, Some(&req.username)).err()
+    };
+    if let Some(reason) = invalid {
+        let response: ApiResponse<()> = ApiResponse::error(format!("Registration failed: {}", reason));
+        return Ok(warp::reply::with_status(warp::reply::json(&response), warp::http::StatusCode::BAD_REQUEST));
+    }
+    
+    // The account stays inactive, and no session is handed out, until the emailed link is opened
+    if let Some(config) = verification_config {
+        let (user, token) = match db.create_unverified_user(req).await {
+            Ok(created) => created,
+            Err(e) => {
+                let response: ApiResponse<()> = ApiResponse::error(format!("Registration failed: {}", e));
+                return Ok(warp::reply::with_status(warp::reply::json(&response), warp::http::StatusCode::OK));
+            }
+        };
+        return Ok(match notify::send_verification_email(&config, &user.username, &user.email, &token).await {
+            Ok(()) => {
+                println!("📧 Verification email sent to {} for {}", user.email, user.username);
+                let response = ApiResponse::success(format!("Account created - open the link sent to {} to activate it", user.email));
+                warp::reply::with_status(warp::reply::json(&response), warp::http::StatusCode::OK)
+            }
+            Err(e) => {
+                eprintln!("⚠️ Verification email to {} failed: {}", user.email, e);
+                if let Err(e) = db.delete_unverified_user(user.id).await {
+                    eprintln!("⚠️ Could not remove unverified account {}: {}", user.username, e);
+                }
+                let response: ApiResponse<()> = ApiResponse::error("Registration failed: the verification email could not be sent".to_string());
+                warp::reply::with_status(warp::reply::json(&response), warp::http::StatusCode::SERVICE_UNAVAILABLE)
+            }
+        });
+    }
+    
     match db.create_user(req).await {
-        Ok(user) => {
-            let response = ApiResponse::success(LoginResponse {
-                token: user.id.to_string(), // Simplified - use JWT in production
-                user_id: user.id,
-                username: user.username,
-            });
+        Ok(user) => match session::issue(user.id) {
+            Ok(token) => {
+                let response = ApiResponse::success(LoginResponse {
+                    token,
+                    user_id: user.id,
+                    username: user.username,
+                });
+                Ok(warp::reply::with_status(warp::reply::json(&response), warp::http::StatusCode::OK))
+            }
+            Err(e) => {
+                let response: ApiResponse<()> = ApiResponse::error(format!("Registration failed: {}", e));
+                Ok(warp::reply::with_status(warp::reply::json(&response), warp::http::StatusCode::INTERNAL_SERVER_ERROR))
+            }
+        },
+        Err(e) => {
+            let response: ApiResponse<()> = ApiResponse::error(format!("Registration failed: {}", e));
+            Ok(warp::reply::with_status(warp::reply::json(&response), warp::http::StatusCode::OK))
+        }
+    }
+}
+
+async fn verify_email(
+    query: VerifyEmailQuery,
+    db: Arc<DatabaseManager>,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    match db.verify_email(&query.token).await {
+        Ok(true) => {
+            let response = ApiResponse::success("Email verified - the account is active and can sign in");
+            Ok(warp::reply::json(&response))
+        }
+        Ok(false) => {
+            let response: ApiResponse<()> = ApiResponse::error("Invalid or expired verification link".to_string());
             Ok(warp::reply::json(&response))
         }
         Err(e) => {
-            let response: ApiResponse<()> = ApiResponse::error(format!("Registration failed: {}", e));
+            let response: ApiResponse<()> = ApiResponse::error(format!("Email verification failed: {}", e));
             Ok(warp::reply::json(&response))
         }
     }
@@ -142,21 +557,276 @@ async fn login_user(
     req: LoginRequest,
     db: Arc<DatabaseManager>,
 ) -> Result<impl warp::Reply, warp::Rejection> {
-    match db.authenticate_user(req).await {
-        Ok(Some(user)) => {
-            let response = ApiResponse::success(LoginResponse {
-                token: user.id.to_string(), // Simplified - use JWT in production
-                user_id: user.id,
-                username: user.username,
+    match db.authenticate_user(&req).await {
+        Ok(Some(user)) => match session::issue(user.id) {
+            Ok(token) => {
+                let response = ApiResponse::success(LoginResponse {
+                    token,
+                    user_id: user.id,
+                    username: user.username,
+                });
+                Ok(warp::reply::json(&response))
+            }
+            Err(e) => {
+                let response: ApiResponse<()> = ApiResponse::error(format!("Login failed: {}", e));
+                Ok(warp::reply::json(&response))
+            }
+        },
+        Ok(None) => {
+            metrics::record_auth_failure();
+            let message = if db.is_awaiting_verification(&req).await.unwrap_or(false) {
+                "Email address not verified - open the link sent when the account was registered"
+            } else {
+                "Invalid credentials"
+            };
+            let response: ApiResponse<()> = ApiResponse::error(message.to_string());
+            Ok(warp::reply::json(&response))
+        }
+        Err(e) => {
+            let response: ApiResponse<()> = ApiResponse::error(format!("Login failed: {}", e));
+            Ok(warp::reply::json(&response))
+        }
+    }
+}
+
+async fn issue_password_reset_token(
+    user_id: Uuid,
+    auth_header: String,
+    db: Arc<DatabaseManager>,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    let admin = match require_admin(&auth_header, &db).await {
+        Ok(admin) => admin,
+        Err(e) => {
+            let response: ApiResponse<()> = ApiResponse::error(e);
+            return Ok(warp::reply::json(&response));
+        }
+    };
+    
+    match db.get_user_by_id(user_id).await {
+        Ok(Some(_)) => {}
+        Ok(None) => {
+            let response: ApiResponse<()> = ApiResponse::error("User not found".to_string());
+            return Ok(warp::reply::json(&response));
+        }
+        Err(e) => {
+            let response: ApiResponse<()> = ApiResponse::error(format!("Failed to look up user: {}", e));
+            return Ok(warp::reply::json(&response));
+        }
+    }
+    
+    match db.create_password_reset_token(user_id, admin.id).await {
+        Ok((token, expires_at)) => {
+            let response = ApiResponse::success(PasswordResetTokenResponse {
+                user_id,
+                token,
+                expires_at,
             });
             Ok(warp::reply::json(&response))
         }
+        Err(e) => {
+            let response: ApiResponse<()> = ApiResponse::error(format!("Failed to issue reset token: {}", e));
+            Ok(warp::reply::json(&response))
+        }
+    }
+}
+
+async fn reset_password(
+    req: ResetPasswordRequest,
+    db: Arc<DatabaseManager>,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    if let Err(reason) = password::validate_password_strength(&req.new_password, None) {
+        let response: ApiResponse<()> = ApiResponse::error(reason);
+        return Ok(warp::reply::json(&response));
+    }
+    
+    match db.reset_password_with_token(&req.token, &req.new_password).await {
+        Ok(true) => {
+            let response = ApiResponse::success("Password has been reset");
+            Ok(warp::reply::json(&response))
+        }
+        Ok(false) => {
+            let response: ApiResponse<()> = ApiResponse::error("Invalid or expired reset token".to_string());
+            Ok(warp::reply::json(&response))
+        }
+        Err(e) => {
+            let response: ApiResponse<()> = ApiResponse::error(format!("Password reset failed: {}", e));
+            Ok(warp::reply::json(&response))
+        }
+    }
+}
+
+async fn create_organization(
+    auth_header: String,
+    req: CreateOrganizationRequest,
+    db: Arc<DatabaseManager>,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    if let Err(e) = require_admin(&auth_header, &db).await {
+        let response: ApiResponse<()> = ApiResponse::error(e);
+        return Ok(warp::reply::json(&response));
+    }
+    
+    if req.name.trim().is_empty() {
+        let response: ApiResponse<()> = ApiResponse::error("Organization name is required".to_string());
+        return Ok(warp::reply::json(&response));
+    }
+    
+    match db.create_organization(req).await {
+        Ok(organization) => {
+            let response = ApiResponse::success(organization);
+            Ok(warp::reply::json(&response))
+        }
+        Err(e) => {
+            let response: ApiResponse<()> = ApiResponse::error(format!("Failed to create organization: {}", e));
+            Ok(warp::reply::json(&response))
+        }
+    }
+}
+
+async fn list_organizations(
+    auth_header: String,
+    db: Arc<DatabaseManager>,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    if let Err(e) = require_admin(&auth_header, &db).await {
+        let response: ApiResponse<()> = ApiResponse::error(e);
+        return Ok(warp::reply::json(&response));
+    }
+    
+    match db.list_organizations().await {
+        Ok(organizations) => {
+            let response = ApiResponse::success(organizations);
+            Ok(warp::reply::json(&response))
+        }
+        Err(e) => {
+            let response: ApiResponse<()> = ApiResponse::error(format!("Failed to list organizations: {}", e));
+            Ok(warp::reply::json(&response))
+        }
+    }
+}
+
+async fn assign_user_organization(
+    user_id: Uuid,
+    auth_header: String,
+    req: AssignOrganizationRequest,
+    db: Arc<DatabaseManager>,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    if let Err(e) = require_admin(&auth_header, &db).await {
+        let response: ApiResponse<()> = ApiResponse::error(e);
+        return Ok(warp::reply::json(&response));
+    }
+    
+    // Server-wide admin is not handed out through this endpoint
+    if let Some(role) = &req.role {
+        if role != "user" && role != "org_admin" {
+            let response: ApiResponse<()> = ApiResponse::error(format!("Unknown role: {}", role));
+            return Ok(warp::reply::json(&response));
+        }
+    }
+    
+    match db.assign_user_organization(user_id, req).await {
+        Ok(Some(user)) => {
+            let response = ApiResponse::success(serde_json::json!({
+                "user_id": user.id,
+                "organization_id": user.organization_id,
+                "role": user.role,
+            }));
+            Ok(warp::reply::json(&response))
+        }
         Ok(None) => {
-            let response: ApiResponse<()> = ApiResponse::error("Invalid credentials".to_string());
+            let response: ApiResponse<()> = ApiResponse::error("User not found".to_string());
             Ok(warp::reply::json(&response))
         }
         Err(e) => {
-            let response: ApiResponse<()> = ApiResponse::error(format!("Login failed: {}", e));
+            let response: ApiResponse<()> = ApiResponse::error(format!("Failed to assign organization: {}", e));
+            Ok(warp::reply::json(&response))
+        }
+    }
+}
+
+async fn create_api_key(
+    auth_header: String,
+    req: CreateApiKeyRequest,
+    db: Arc<DatabaseManager>,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    let admin = match require_admin(&auth_header, &db).await {
+        Ok(admin) => admin,
+        Err(e) => {
+            let response: ApiResponse<()> = ApiResponse::error(e);
+            return Ok(warp::reply::json(&response));
+        }
+    };
+    
+    if req.name.trim().is_empty() {
+        let response: ApiResponse<()> = ApiResponse::error("API key name is required".to_string());
+        return Ok(warp::reply::json(&response));
+    }
+    
+    let user_id = req.user_id.unwrap_or(admin.id);
+    match db.get_user_by_id(user_id).await {
+        Ok(Some(_)) => {}
+        Ok(None) => {
+            let response: ApiResponse<()> = ApiResponse::error("User not found".to_string());
+            return Ok(warp::reply::json(&response));
+        }
+        Err(e) => {
+            let response: ApiResponse<()> = ApiResponse::error(format!("Failed to look up user: {}", e));
+            return Ok(warp::reply::json(&response));
+        }
+    }
+    
+    match db.create_api_key(req.name.trim(), user_id, admin.id).await {
+        Ok((key, api_key)) => {
+            let response = ApiResponse::success(ApiKeyResponse { key, api_key });
+            Ok(warp::reply::json(&response))
+        }
+        Err(e) => {
+            let response: ApiResponse<()> = ApiResponse::error(format!("Failed to create API key: {}", e));
+            Ok(warp::reply::json(&response))
+        }
+    }
+}
+
+async fn list_api_keys(
+    auth_header: String,
+    db: Arc<DatabaseManager>,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    if let Err(e) = require_admin(&auth_header, &db).await {
+        let response: ApiResponse<()> = ApiResponse::error(e);
+        return Ok(warp::reply::json(&response));
+    }
+    
+    match db.list_api_keys().await {
+        Ok(keys) => {
+            let response = ApiResponse::success(keys);
+            Ok(warp::reply::json(&response))
+        }
+        Err(e) => {
+            let response: ApiResponse<()> = ApiResponse::error(format!("Failed to list API keys: {}", e));
+            Ok(warp::reply::json(&response))
+        }
+    }
+}
+
+async fn revoke_api_key(
+    key_id: Uuid,
+    auth_header: String,
+    db: Arc<DatabaseManager>,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    if let Err(e) = require_admin(&auth_header, &db).await {
+        let response: ApiResponse<()> = ApiResponse::error(e);
+        return Ok(warp::reply::json(&response));
+    }
+    
+    match db.revoke_api_key(key_id).await {
+        Ok(true) => {
+            let response = ApiResponse::success("API key revoked");
+            Ok(warp::reply::json(&response))
+        }
+        Ok(false) => {
+            let response: ApiResponse<()> = ApiResponse::error("API key not found or already revoked".to_string());
+            Ok(warp::reply::json(&response))
+        }
+        Err(e) => {
+            let response: ApiResponse<()> = ApiResponse::error(format!("Failed to revoke API key: {}", e));
             Ok(warp::reply::json(&response))
         }
     }
@@ -166,19 +836,27 @@ async fn submit_certificate(
     auth_header: String,
     req: SubmitCertificateRequest,
     db: Arc<DatabaseManager>,
+    notify_config: Option<Arc<NotifyConfig>>,
 ) -> Result<impl warp::Reply, warp::Rejection> {
-    match extract_user_id(&auth_header) {
-        Ok(user_id) => {
+    match require_user(&auth_header, &db).await {
+        Ok(user) => {
             let file_hash = format!("{:x}", Sha256::digest(req.certificate_data.as_bytes()));
+            let device_id = resolve_device_id(&req, &user, &db).await;
             let store_req = StoreCertificateRequest {
-                user_id,
+                user_id: user.id,
                 certificate_data: req.certificate_data,
                 device_info: req.device_info,
                 sanitization_method: req.sanitization_method,
                 file_hash,
+                device_id,
+                organization_id: user.organization_id,
             };
             match db.store_certificate(store_req).await {
                 Ok(certificate) => {
+                    metrics::record_certificate_received();
+                    if let Some(config) = notify_config {
+                        spawn_certificate_notification(config, certificate.clone(), db.clone());
+                    }
                     let response = ApiResponse::success(certificate);
                     Ok(warp::reply::json(&response))
                 }
@@ -195,14 +873,122 @@ async fn submit_certificate(
     }
 }
 
+async fn revoke_certificate(
+    cert_id: Uuid,
+    auth_header: String,
+    req: RevokeCertificateRequest,
+    db: Arc<DatabaseManager>,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    let admin = match require_admin(&auth_header, &db).await {
+        Ok(admin) => admin,
+        Err(e) => {
+            let response: ApiResponse<()> = ApiResponse::error(e);
+            return Ok(warp::reply::json(&response));
+        }
+    };
+    
+    if req.reason.trim().is_empty() {
+        let response: ApiResponse<()> = ApiResponse::error("A revocation reason is required".to_string());
+        return Ok(warp::reply::json(&response));
+    }
+    
+    match db.revoke_certificate(cert_id, req.reason.trim(), admin.id).await {
+        Ok(Some(certificate)) => {
+            println!("🚫 Certificate {} revoked by {}: {}", cert_id, admin.username, req.reason.trim());
+            pdf::invalidate(cert_id);
+            let response = ApiResponse::success(certificate);
+            Ok(warp::reply::json(&response))
+        }
+        Ok(None) => {
+            let response: ApiResponse<()> = ApiResponse::error("Certificate not found or already revoked".to_string());
+            Ok(warp::reply::json(&response))
+        }
+        Err(e) => {
+            let response: ApiResponse<()> = ApiResponse::error(format!("Failed to revoke certificate: {}", e));
+            Ok(warp::reply::json(&response))
+        }
+    }
+}
+
+async fn verify_certificate(
+    cert_id: Uuid,
+    db: Arc<DatabaseManager>,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    match db.find_certificate(cert_id).await {
+        Ok(Some(certificate)) => {
+            let response = ApiResponse::success(CertificateVerification::from(certificate));
+            Ok(warp::reply::json(&response))
+        }
+        Ok(None) => {
+            let response: ApiResponse<()> = ApiResponse::error("Certificate not found".to_string());
+            Ok(warp::reply::json(&response))
+        }
+        Err(e) => {
+            let response: ApiResponse<()> = ApiResponse::error(format!("Failed to verify certificate: {}", e));
+            Ok(warp::reply::json(&response))
+        }
+    }
+}
+
+/// Email the configured recipient, or the uploading user, in the background so the
+/// upload response isn't held up by the SMTP server
+fn spawn_certificate_notification(config: Arc<NotifyConfig>, certificate: Certificate, db: Arc<DatabaseManager>) {
+    tokio::spawn(async move {
+        let recipient = match &config.recipient {
+            Some(recipient) => recipient.clone(),
+            None => match db.get_user_by_id(certificate.user_id).await {
+                Ok(Some(user)) => user.email,
+                _ => {
+                    eprintln!("⚠️ No recipient for certificate {} notification", certificate.id);
+                    return;
+                }
+            },
+        };
+        match notify::send_certificate_notification(&config, &certificate, &recipient).await {
+            Ok(()) => println!("📧 Certificate {} notification sent to {}", certificate.id, recipient),
+            Err(e) => eprintln!("⚠️ Certificate {} notification failed: {}", certificate.id, e),
+        }
+    });
+}
+
+/// Link an uploaded certificate to a device registered where `user` can see it, using
+/// the explicit serial if given or the serial recorded inside the certificate JSON otherwise
+async fn resolve_device_id(req: &SubmitCertificateRequest, user: &ServerUser, db: &DatabaseManager) -> Option<Uuid> {
+    let serial = req.device_serial.clone().or_else(|| {
+        serde_json::from_str::<serde_json::Value>(&req.certificate_data)
+            .ok()
+            .and_then(|cert| cert["device_info"]["serial_number"].as_str().map(|s| s.to_string()))
+    })?;
+    
+    if serial.trim().is_empty() || serial == "N/A" || serial == "Unknown" {
+        return None;
+    }
+    
+    match db.get_device_by_serial(&serial, user).await {
+        Ok(device) => device.map(|d| d.id),
+        Err(e) => {
+            eprintln!("⚠️ Failed to look up device {}: {}", serial, e);
+            None
+        }
+    }
+}
+
 async fn get_certificates(
     auth_header: String,
-    query: PaginationQuery,
+    query: CertificateQuery,
     db: Arc<DatabaseManager>,
 ) -> Result<impl warp::Reply, warp::Rejection> {
-    match extract_user_id(&auth_header) {
-        Ok(user_id) => {
-            match db.get_user_certificates(user_id, query.limit, query.offset).await {
+    match require_user(&auth_header, &db).await {
+        Ok(user) => {
+            let filter = CertificateFilter {
+                device_type: query.device_type,
+                method: query.method,
+                success: query.success,
+                serial: query.serial,
+                from: query.from,
+                to: query.to,
+            };
+            match db.get_user_certificates(&user, &filter, query.limit, query.offset).await {
                 Ok(certificates) => {
                     let response = ApiResponse::success(certificates);
                     Ok(warp::reply::json(&response))
@@ -225,7 +1011,7 @@ async fn get_sanitization_logs(
     query: PaginationQuery,
     db: Arc<DatabaseManager>,
 ) -> Result<impl warp::Reply, warp::Rejection> {
-    match extract_user_id(&auth_header) {
+    match authenticate(&auth_header, &db).await {
         Ok(user_id) => {
             match db.get_sanitization_logs(user_id, query.limit, query.offset).await {
                 Ok(logs) => {
@@ -245,6 +1031,18 @@ async fn get_sanitization_logs(
     }
 }
 
+async fn serve_metrics(db: Arc<DatabaseManager>) -> Result<impl warp::Reply, warp::Rejection> {
+    let active_operations = db.count_active_operations().await.unwrap_or_else(|e| {
+        eprintln!("⚠️ Failed to count active operations: {}", e);
+        0
+    });
+    Ok(warp::reply::with_header(
+        metrics::render(active_operations),
+        "content-type",
+        "text/plain; version=0.0.4",
+    ))
+}
+
 async fn serve_dashboard() -> Result<impl warp::Reply, warp::Rejection> {
     let dashboard_html = include_str!("dashboard.html");
     Ok(warp::reply::html(dashboard_html))
@@ -255,9 +1053,9 @@ async fn download_certificate(
     auth_header: String,
     db: Arc<DatabaseManager>,
 ) -> Result<Box<dyn warp::Reply>, warp::Rejection> {
-    match extract_user_id(&auth_header) {
-        Ok(user_id) => {
-            match db.get_certificate_by_id(cert_id, user_id).await {
+    match require_user(&auth_header, &db).await {
+        Ok(user) => {
+            match db.get_certificate_by_id(cert_id, &user).await {
                 Ok(Some(certificate)) => {
                     let filename = format!("certificate_{}.json", cert_id);
                     
@@ -292,4 +1090,238 @@ async fn download_certificate(
             )))
         }
     }
-}
\ No newline at end of file
+}
+
+async fn download_certificate_pdf(
+    cert_id: Uuid,
+    auth_header: String,
+    db: Arc<DatabaseManager>,
+) -> Result<Box<dyn warp::Reply>, warp::Rejection> {
+    let user = match require_user(&auth_header, &db).await {
+        Ok(user) => user,
+        Err(e) => {
+            return Ok(Box::new(warp::reply::with_status(e, warp::http::StatusCode::UNAUTHORIZED)));
+        }
+    };
+    
+    let certificate = match db.get_certificate_by_id(cert_id, &user).await {
+        Ok(Some(certificate)) => certificate,
+        Ok(None) => {
+            return Ok(Box::new(warp::reply::with_status(
+                "Certificate not found".to_string(),
+                warp::http::StatusCode::NOT_FOUND
+            )));
+        }
+        Err(e) => {
+            return Ok(Box::new(warp::reply::with_status(
+                format!("Database error: {}", e),
+                warp::http::StatusCode::INTERNAL_SERVER_ERROR
+            )));
+        }
+    };
+    
+    let revocation = certificate.revoked.then(|| format!("REVOKED{}: {}",
+        certificate.revoked_at.map(|at| format!(" on {}", at.format("%Y-%m-%d"))).unwrap_or_default(),
+        certificate.revocation_reason.as_deref().unwrap_or("no reason given")));
+    
+    // Rendering and the cache are file/CPU work, keep them off the async workers
+    let rendered = tokio::task::spawn_blocking(move || pdf::certificate_pdf(cert_id, &certificate.certificate_data, revocation.as_deref()))
+        .await
+        .unwrap_or_else(|e| Err(format!("PDF rendering panicked: {}", e)));
+    
+    match rendered {
+        Ok(bytes) => Ok(Box::new(warp::reply::with_header(
+            warp::reply::with_header(
+                bytes,
+                "content-disposition",
+                format!("attachment; filename=certificate_{}.pdf", cert_id)
+            ),
+            "content-type",
+            "application/pdf"
+        ))),
+        Err(e) => Ok(Box::new(warp::reply::with_status(
+            e,
+            warp::http::StatusCode::INTERNAL_SERVER_ERROR
+        ))),
+    }
+}
+
+async fn register_device(
+    auth_header: String,
+    req: RegisterDeviceRequest,
+    db: Arc<DatabaseManager>,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    let user = match require_user(&auth_header, &db).await {
+        Ok(user) => user,
+        Err(e) => {
+            let response: ApiResponse<()> = ApiResponse::error(e);
+            return Ok(warp::reply::json(&response));
+        }
+    };
+    
+    if req.serial_number.trim().is_empty() {
+        let response: ApiResponse<()> = ApiResponse::error("Serial number is required".to_string());
+        return Ok(warp::reply::json(&response));
+    }
+    
+    match db.register_device(req, &user).await {
+        Ok(device) => {
+            let response = ApiResponse::success(device);
+            Ok(warp::reply::json(&response))
+        }
+        Err(e) => {
+            let response: ApiResponse<()> = ApiResponse::error(format!("Failed to register device: {}", e));
+            Ok(warp::reply::json(&response))
+        }
+    }
+}
+
+async fn list_devices(
+    auth_header: String,
+    query: PaginationQuery,
+    db: Arc<DatabaseManager>,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    let user = match require_user(&auth_header, &db).await {
+        Ok(user) => user,
+        Err(e) => {
+            let response: ApiResponse<()> = ApiResponse::error(e);
+            return Ok(warp::reply::json(&response));
+        }
+    };
+    
+    match db.list_devices(&user, query.limit, query.offset).await {
+        Ok(devices) => {
+            let response = ApiResponse::success(devices);
+            Ok(warp::reply::json(&response))
+        }
+        Err(e) => {
+            let response: ApiResponse<()> = ApiResponse::error(format!("Failed to list devices: {}", e));
+            Ok(warp::reply::json(&response))
+        }
+    }
+}
+
+async fn get_device(
+    serial_number: String,
+    auth_header: String,
+    db: Arc<DatabaseManager>,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    let user = match require_user(&auth_header, &db).await {
+        Ok(user) => user,
+        Err(e) => {
+            let response: ApiResponse<()> = ApiResponse::error(e);
+            return Ok(warp::reply::json(&response));
+        }
+    };
+    
+    match db.get_device_by_serial(&serial_number, &user).await {
+        Ok(Some(device)) => {
+            let response = ApiResponse::success(device);
+            Ok(warp::reply::json(&response))
+        }
+        Ok(None) => {
+            let response: ApiResponse<()> = ApiResponse::error("Device not found".to_string());
+            Ok(warp::reply::json(&response))
+        }
+        Err(e) => {
+            let response: ApiResponse<()> = ApiResponse::error(format!("Failed to get device: {}", e));
+            Ok(warp::reply::json(&response))
+        }
+    }
+}
+
+async fn update_device(
+    serial_number: String,
+    auth_header: String,
+    req: UpdateDeviceRequest,
+    db: Arc<DatabaseManager>,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    let user = match require_user(&auth_header, &db).await {
+        Ok(user) => user,
+        Err(e) => {
+            let response: ApiResponse<()> = ApiResponse::error(e);
+            return Ok(warp::reply::json(&response));
+        }
+    };
+    
+    match db.update_device(&serial_number, req, &user).await {
+        Ok(Some(device)) => {
+            let response = ApiResponse::success(device);
+            Ok(warp::reply::json(&response))
+        }
+        Ok(None) => {
+            let response: ApiResponse<()> = ApiResponse::error("Device not found".to_string());
+            Ok(warp::reply::json(&response))
+        }
+        Err(e) => {
+            let response: ApiResponse<()> = ApiResponse::error(format!("Failed to update device: {}", e));
+            Ok(warp::reply::json(&response))
+        }
+    }
+}
+
+async fn delete_device(
+    serial_number: String,
+    auth_header: String,
+    db: Arc<DatabaseManager>,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    let user = match require_admin(&auth_header, &db).await {
+        Ok(user) => user,
+        Err(e) => {
+            let response: ApiResponse<()> = ApiResponse::error(e);
+            return Ok(warp::reply::json(&response));
+        }
+    };
+    
+    match db.delete_device(&serial_number, &user).await {
+        Ok(true) => {
+            let response = ApiResponse::success("Device removed from registry");
+            Ok(warp::reply::json(&response))
+        }
+        Ok(false) => {
+            let response: ApiResponse<()> = ApiResponse::error("Device not found".to_string());
+            Ok(warp::reply::json(&response))
+        }
+        Err(e) => {
+            let response: ApiResponse<()> = ApiResponse::error(format!("Failed to delete device: {}", e));
+            Ok(warp::reply::json(&response))
+        }
+    }
+}
+
+async fn get_device_history(
+    serial_number: String,
+    auth_header: String,
+    db: Arc<DatabaseManager>,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    let user = match require_user(&auth_header, &db).await {
+        Ok(user) => user,
+        Err(e) => {
+            let response: ApiResponse<()> = ApiResponse::error(e);
+            return Ok(warp::reply::json(&response));
+        }
+    };
+    
+    let device = match db.get_device_by_serial(&serial_number, &user).await {
+        Ok(Some(device)) => device,
+        Ok(None) => {
+            let response: ApiResponse<()> = ApiResponse::error("Device not found".to_string());
+            return Ok(warp::reply::json(&response));
+        }
+        Err(e) => {
+            let response: ApiResponse<()> = ApiResponse::error(format!("Failed to get device: {}", e));
+            return Ok(warp::reply::json(&response));
+        }
+    };
+    
+    match db.get_device_certificates(device.id, &user).await {
+        Ok(certificates) => {
+            let response = ApiResponse::success(DeviceHistoryResponse { device, certificates });
+            Ok(warp::reply::json(&response))
+        }
+        Err(e) => {
+            let response: ApiResponse<()> = ApiResponse::error(format!("Failed to get device history: {}", e));
+            Ok(warp::reply::json(&response))
+        }
+    }
+}
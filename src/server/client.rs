@@ -8,6 +8,7 @@ pub struct ServerClient {
     base_url: String,
     token: Option<String>,
     user_id: Option<Uuid>,
+    api_key: Option<String>,
 }
 
 impl ServerClient {
@@ -17,9 +18,25 @@ impl ServerClient {
             base_url: server_url.to_string(),
             token: None,
             user_id: None,
+            api_key: None,
         }
     }
     
+    /// Authenticate with an API key instead of logging in, for headless wipe stations
+    pub fn with_api_key(mut self, api_key: &str) -> Self {
+        self.api_key = Some(api_key.to_string());
+        self
+    }
+    
+    /// Attach the API key if one is set, otherwise the session token from `login`
+    fn authorize(&self, request: reqwest::RequestBuilder) -> Result<reqwest::RequestBuilder, Box<dyn std::error::Error + Send + Sync>> {
+        if let Some(api_key) = &self.api_key {
+            return Ok(request.header("X-API-Key", api_key));
+        }
+        let token = self.token.as_ref().ok_or("Not logged in")?;
+        Ok(request.header("Authorization", format!("Bearer {}", token)))
+    }
+    
     pub async fn register(&mut self, username: &str, email: &str, password: &str) -> Result<LoginResponse, Box<dyn std::error::Error + Send + Sync>> {
         let req = CreateUserRequest {
             username: username.to_string(),
@@ -81,17 +98,14 @@ impl ServerClient {
         device_info: &str,
         sanitization_method: &str,
     ) -> Result<Certificate, Box<dyn std::error::Error + Send + Sync>> {
-        let token = self.token.as_ref().ok_or("Not logged in")?;
-        
         let req = SubmitCertificateRequest {
             certificate_data: certificate_data.to_string(),
             device_info: device_info.to_string(),
             sanitization_method: sanitization_method.to_string(),
+            device_serial: None,
         };
         
-        let response = self.client
-            .post(&format!("{}/api/certificates", self.base_url))
-            .header("Authorization", format!("Bearer {}", token))
+        let response = self.authorize(self.client.post(&format!("{}/api/certificates", self.base_url)))?
             .json(&req)
             .send()
             .await?;
@@ -106,11 +120,7 @@ impl ServerClient {
     }
     
     pub async fn get_certificates(&self, limit: i64, offset: i64) -> Result<CertificateResponse, Box<dyn std::error::Error + Send + Sync>> {
-        let token = self.token.as_ref().ok_or("Not logged in")?;
-        
-        let response = self.client
-            .get(&format!("{}/api/certificates?limit={}&offset={}", self.base_url, limit, offset))
-            .header("Authorization", format!("Bearer {}", token))
+        let response = self.authorize(self.client.get(&format!("{}/api/certificates?limit={}&offset={}", self.base_url, limit, offset)))?
             .send()
             .await?;
         
@@ -124,11 +134,7 @@ impl ServerClient {
     }
     
     pub async fn get_sanitization_logs(&self, limit: i64, offset: i64) -> Result<SanitizationLogResponse, Box<dyn std::error::Error + Send + Sync>> {
-        let token = self.token.as_ref().ok_or("Not logged in")?;
-        
-        let response = self.client
-            .get(&format!("{}/api/logs?limit={}&offset={}", self.base_url, limit, offset))
-            .header("Authorization", format!("Bearer {}", token))
+        let response = self.authorize(self.client.get(&format!("{}/api/logs?limit={}&offset={}", self.base_url, limit, offset)))?
             .send()
             .await?;
         
@@ -142,7 +148,7 @@ impl ServerClient {
     }
     
     pub fn is_logged_in(&self) -> bool {
-        self.token.is_some() && self.user_id.is_some()
+        self.api_key.is_some() || (self.token.is_some() && self.user_id.is_some())
     }
     
     pub fn get_user_id(&self) -> Option<Uuid> {
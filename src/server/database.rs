@@ -1,6 +1,16 @@
 use sqlx::PgPool;
+use chrono::{DateTime, Utc};
 use crate::server::models::*;
-use sha2::{Sha256, Digest};
+use crate::server::password;
+
+/// How long an admin-issued password reset token stays valid
+const RESET_TOKEN_TTL_MINUTES: i64 = 60;
+
+/// How long the link emailed to a newly registered user stays valid
+pub const VERIFICATION_TOKEN_TTL_HOURS: i64 = 24;
+
+/// Prefix on minted API keys so they are recognisable in config files and secret scanners
+const API_KEY_PREFIX: &str = "hddk_";
 
 pub struct DatabaseManager {
     pool: PgPool,
@@ -25,21 +35,19 @@ impl DatabaseManager {
         Ok(())
     }
     
-    fn hash_password(password: &str) -> String {
-        let mut hasher = Sha256::new();
-        hasher.update(password.as_bytes());
-        format!("{:x}", hasher.finalize())
+    fn hash_password(password: &str) -> Result<String, sqlx::Error> {
+        password::hash_password(password).map_err(sqlx::Error::Protocol)
     }
     
     pub async fn create_user(&self, req: CreateUserRequest) -> Result<ServerUser, sqlx::Error> {
-        let password_hash = Self::hash_password(&req.password);
+        let password_hash = Self::hash_password(&req.password)?;
         let user_id = uuid::Uuid::new_v4();
         
         let user = sqlx::query_as::<_, ServerUser>(
             r#"
             INSERT INTO users (id, username, email, password_hash)
             VALUES ($1, $2, $3, $4)
-            RETURNING id, username, email, password_hash, created_at, last_login, is_active
+            RETURNING id, username, email, password_hash, created_at, last_login, is_active, role, organization_id
             "#
         )
         .bind(&user_id)
@@ -52,40 +60,248 @@ impl DatabaseManager {
         Ok(user)
     }
     
-    pub async fn authenticate_user(&self, req: LoginRequest) -> Result<Option<ServerUser>, sqlx::Error> {
-        let password_hash = Self::hash_password(&req.password);
+    /// Create a self-registered account inactive, with a one-time token that activates
+    /// it once the email address is confirmed. Only the token's hash is stored. An earlier
+    /// registration of the same username or address whose link expired unused is purged
+    /// first, so registering again is how a new link is sent.
+    pub async fn create_unverified_user(&self, req: CreateUserRequest) -> Result<(ServerUser, String), sqlx::Error> {
+        let password_hash = Self::hash_password(&req.password)?;
+        let token = password::generate_token();
+        let expires_at = Utc::now() + chrono::Duration::hours(VERIFICATION_TOKEN_TTL_HOURS);
+        
+        let mut tx = self.pool.begin().await?;
+        
+        sqlx::query(
+            r#"
+            DELETE FROM users u
+            WHERE (u.username = $1 OR u.email = $2) AND u.is_active = FALSE AND u.last_login IS NULL
+              AND EXISTS (SELECT 1 FROM email_verification_tokens t WHERE t.user_id = u.id)
+              AND NOT EXISTS (SELECT 1 FROM email_verification_tokens t
+                              WHERE t.user_id = u.id AND (t.used_at IS NOT NULL OR t.expires_at > NOW()))
+            "#
+        )
+        .bind(&req.username)
+        .bind(&req.email)
+        .execute(&mut *tx)
+        .await?;
         
         let user = sqlx::query_as::<_, ServerUser>(
             r#"
-            SELECT id, username, email, password_hash, created_at, last_login, is_active
-            FROM users 
-            WHERE username = $1 AND password_hash = $2 AND is_active = TRUE
+            INSERT INTO users (id, username, email, password_hash, is_active)
+            VALUES ($1, $2, $3, $4, FALSE)
+            RETURNING id, username, email, password_hash, created_at, last_login, is_active, role, organization_id
             "#
         )
+        .bind(uuid::Uuid::new_v4())
         .bind(&req.username)
+        .bind(&req.email)
         .bind(&password_hash)
+        .fetch_one(&mut *tx)
+        .await?;
+        
+        sqlx::query(
+            r#"
+            INSERT INTO email_verification_tokens (id, user_id, token_hash, expires_at)
+            VALUES ($1, $2, $3, $4)
+            "#
+        )
+        .bind(uuid::Uuid::new_v4())
+        .bind(&user.id)
+        .bind(password::hash_token(&token))
+        .bind(&expires_at)
+        .execute(&mut *tx)
+        .await?;
+        
+        tx.commit().await?;
+        
+        Ok((user, token))
+    }
+    
+    /// Consume a verification token and activate its account.
+    /// Returns `false` if the token is unknown, expired or already used.
+    pub async fn verify_email(&self, token: &str) -> Result<bool, sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+        
+        let user_id = sqlx::query_scalar::<_, uuid::Uuid>(
+            r#"
+            UPDATE email_verification_tokens SET used_at = NOW()
+            WHERE token_hash = $1 AND used_at IS NULL AND expires_at > NOW()
+            RETURNING user_id
+            "#
+        )
+        .bind(password::hash_token(token))
+        .fetch_optional(&mut *tx)
+        .await?;
+        
+        let Some(user_id) = user_id else {
+            return Ok(false);
+        };
+        
+        sqlx::query("UPDATE users SET is_active = TRUE WHERE id = $1")
+            .bind(&user_id)
+            .execute(&mut *tx)
+            .await?;
+        
+        tx.commit().await?;
+        
+        Ok(true)
+    }
+    
+    /// Remove an account that never got verified, e.g. when its verification email
+    /// could not be sent, so the username and address can be registered again
+    pub async fn delete_unverified_user(&self, user_id: uuid::Uuid) -> Result<(), sqlx::Error> {
+        sqlx::query("DELETE FROM users WHERE id = $1 AND is_active = FALSE AND last_login IS NULL")
+            .bind(&user_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+    
+    /// Whether the credentials belong to an account still waiting for its email to be
+    /// verified with a link that hasn't expired, so login can say so instead of
+    /// "Invalid credentials"
+    pub async fn is_awaiting_verification(&self, req: &LoginRequest) -> Result<bool, sqlx::Error> {
+        let password_hash = sqlx::query_scalar::<_, String>(
+            r#"
+            SELECT u.password_hash FROM users u
+            WHERE u.username = $1 AND u.is_active = FALSE
+              AND EXISTS (SELECT 1 FROM email_verification_tokens t
+                          WHERE t.user_id = u.id AND t.used_at IS NULL AND t.expires_at > NOW())
+            "#
+        )
+        .bind(&req.username)
         .fetch_optional(&self.pool)
         .await?;
         
-        if user.is_some() {
+        Ok(password_hash.is_some_and(|hash| password::verify_password(&req.password, &hash)))
+    }
+    
+    pub async fn authenticate_user(&self, req: &LoginRequest) -> Result<Option<ServerUser>, sqlx::Error> {
+        let user = sqlx::query_as::<_, ServerUser>(
+            r#"
+            SELECT id, username, email, password_hash, created_at, last_login, is_active, role, organization_id
+            FROM users 
+            WHERE username = $1 AND is_active = TRUE
+            "#
+        )
+        .bind(&req.username)
+        .fetch_optional(&self.pool)
+        .await?
+        .filter(|user| password::verify_password(&req.password, &user.password_hash));
+        
+        if let Some(user) = &user {
             // Update last login
             sqlx::query("UPDATE users SET last_login = NOW() WHERE username = $1")
                 .bind(&req.username)
                 .execute(&self.pool)
                 .await?;
+            
+            // Move legacy SHA-256 accounts to Argon2 now that the password is known
+            if password::needs_rehash(&user.password_hash) {
+                let password_hash = Self::hash_password(&req.password)?;
+                sqlx::query("UPDATE users SET password_hash = $1 WHERE id = $2")
+                    .bind(&password_hash)
+                    .bind(&user.id)
+                    .execute(&self.pool)
+                    .await?;
+                println!("🔐 Upgraded password hash for {} to Argon2", user.username);
+            }
         }
         
         Ok(user)
     }
     
+    pub async fn get_user_by_id(&self, user_id: uuid::Uuid) -> Result<Option<ServerUser>, sqlx::Error> {
+        let user = sqlx::query_as::<_, ServerUser>(
+            r#"
+            SELECT id, username, email, password_hash, created_at, last_login, is_active, role, organization_id
+            FROM users 
+            WHERE id = $1
+            "#
+        )
+        .bind(&user_id)
+        .fetch_optional(&self.pool)
+        .await?;
+        
+        Ok(user)
+    }
+    
+    /// Issue a one-time password reset token for `user_id`. Any earlier unused
+    /// tokens for the same user are invalidated. Only the hash is stored.
+    pub async fn create_password_reset_token(&self, user_id: uuid::Uuid, created_by: uuid::Uuid) -> Result<(String, DateTime<Utc>), sqlx::Error> {
+        let token = password::generate_token();
+        let expires_at = Utc::now() + chrono::Duration::minutes(RESET_TOKEN_TTL_MINUTES);
+        
+        let mut tx = self.pool.begin().await?;
+        
+        sqlx::query("UPDATE password_reset_tokens SET used_at = NOW() WHERE user_id = $1 AND used_at IS NULL")
+            .bind(&user_id)
+            .execute(&mut *tx)
+            .await?;
+        
+        sqlx::query(
+            r#"
+            INSERT INTO password_reset_tokens (id, user_id, token_hash, created_by, expires_at)
+            VALUES ($1, $2, $3, $4, $5)
+            "#
+        )
+        .bind(uuid::Uuid::new_v4())
+        .bind(&user_id)
+        .bind(password::hash_token(&token))
+        .bind(&created_by)
+        .bind(&expires_at)
+        .execute(&mut *tx)
+        .await?;
+        
+        tx.commit().await?;
+        
+        Ok((token, expires_at))
+    }
+    
+    /// Consume a reset token and set a new Argon2-hashed password.
+    /// Returns `false` if the token is unknown, expired or already used.
+    pub async fn reset_password_with_token(&self, token: &str, new_password: &str) -> Result<bool, sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+        
+        let user_id = sqlx::query_scalar::<_, uuid::Uuid>(
+            r#"
+            UPDATE password_reset_tokens SET used_at = NOW()
+            WHERE token_hash = $1 AND used_at IS NULL AND expires_at > NOW()
+            RETURNING user_id
+            "#
+        )
+        .bind(password::hash_token(token))
+        .fetch_optional(&mut *tx)
+        .await?;
+        
+        let Some(user_id) = user_id else {
+            return Ok(false);
+        };
+        
+        let password_hash = Self::hash_password(new_password)?;
+        sqlx::query("UPDATE users SET password_hash = $1 WHERE id = $2")
+            .bind(&password_hash)
+            .bind(&user_id)
+            .execute(&mut *tx)
+            .await?;
+        
+        tx.commit().await?;
+        
+        Ok(true)
+    }
+    
     pub async fn store_certificate(&self, req: StoreCertificateRequest) -> Result<Certificate, sqlx::Error> {
         let certificate_id = uuid::Uuid::new_v4();
+        let fields = CertificateFields::from_certificate_data(&req.certificate_data);
         
         let certificate = sqlx::query_as::<_, Certificate>(
             r#"
-            INSERT INTO certificates (id, user_id, certificate_data, device_info, sanitization_method, file_hash)
-            VALUES ($1, $2, $3, $4, $5, $6)
-            RETURNING id, user_id, certificate_data, device_info, sanitization_method, created_at, file_hash
+            INSERT INTO certificates (id, user_id, certificate_data, device_info, sanitization_method, file_hash, device_id,
+                                      device_type, serial_number, success, issued_at, organization_id)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, COALESCE($11, NOW()), $12)
+            RETURNING id, user_id, certificate_data, device_info, sanitization_method, created_at, file_hash, device_id,
+                      device_type, serial_number, success, issued_at, organization_id,
+                      revoked, revoked_at, revocation_reason
             "#
         )
         .bind(&certificate_id)
@@ -94,32 +310,76 @@ impl DatabaseManager {
         .bind(&req.device_info)
         .bind(&req.sanitization_method)
         .bind(&req.file_hash)
+        .bind(&req.device_id)
+        .bind(&fields.device_type)
+        .bind(&fields.serial_number)
+        .bind(fields.success)
+        .bind(fields.issued_at)
+        .bind(&req.organization_id)
         .fetch_one(&self.pool)
         .await?;
         
         Ok(certificate)
     }
     
-    pub async fn get_user_certificates(&self, user_id: uuid::Uuid, limit: i64, offset: i64) -> Result<PaginatedResponse<Certificate>, sqlx::Error> {
+    /// Certificates visible to `user`, newest first, narrowed by any filters that are set.
+    /// Only certificates from the user's own organization are ever returned; org admins
+    /// see all of them, everyone else only their own uploads.
+    pub async fn get_user_certificates(&self, user: &ServerUser, filter: &CertificateFilter, limit: i64, offset: i64) -> Result<PaginatedResponse<Certificate>, sqlx::Error> {
         let certificates = sqlx::query_as::<_, Certificate>(
             r#"
-            SELECT id, user_id, certificate_data, device_info, sanitization_method, created_at, file_hash
+            SELECT id, user_id, certificate_data, device_info, sanitization_method, created_at, file_hash, device_id,
+                   device_type, serial_number, success, issued_at, organization_id,
+                   revoked, revoked_at, revocation_reason
             FROM certificates 
-            WHERE user_id = $1 
-            ORDER BY created_at DESC 
+            WHERE organization_id IS NOT DISTINCT FROM $10
+              AND ($11 OR user_id = $1)
+              AND ($4::text IS NULL OR device_type = $4)
+              AND ($5::text IS NULL OR sanitization_method = $5)
+              AND ($6::boolean IS NULL OR success = $6)
+              AND ($7::text IS NULL OR serial_number = $7)
+              AND ($8::timestamptz IS NULL OR issued_at >= $8)
+              AND ($9::timestamptz IS NULL OR issued_at < $9)
+            ORDER BY issued_at DESC 
             LIMIT $2 OFFSET $3
             "#
         )
-        .bind(&user_id)
+        .bind(&user.id)
         .bind(limit)
         .bind(offset)
+        .bind(&filter.device_type)
+        .bind(&filter.method)
+        .bind(filter.success)
+        .bind(&filter.serial)
+        .bind(filter.from)
+        .bind(filter.to)
+        .bind(&user.organization_id)
+        .bind(user.is_org_admin())
         .fetch_all(&self.pool)
         .await?;
         
         let total = sqlx::query_scalar::<_, i64>(
-            "SELECT COUNT(*) FROM certificates WHERE user_id = $1"
+            r#"
+            SELECT COUNT(*) FROM certificates
+            WHERE organization_id IS NOT DISTINCT FROM $8
+              AND ($9 OR user_id = $1)
+              AND ($2::text IS NULL OR device_type = $2)
+              AND ($3::text IS NULL OR sanitization_method = $3)
+              AND ($4::boolean IS NULL OR success = $4)
+              AND ($5::text IS NULL OR serial_number = $5)
+              AND ($6::timestamptz IS NULL OR issued_at >= $6)
+              AND ($7::timestamptz IS NULL OR issued_at < $7)
+            "#
         )
-        .bind(&user_id)
+        .bind(&user.id)
+        .bind(&filter.device_type)
+        .bind(&filter.method)
+        .bind(filter.success)
+        .bind(&filter.serial)
+        .bind(filter.from)
+        .bind(filter.to)
+        .bind(&user.organization_id)
+        .bind(user.is_org_admin())
         .fetch_one(&self.pool)
         .await?;
         
@@ -162,6 +422,15 @@ impl DatabaseManager {
         Ok(result)
     }
     
+    /// Number of sanitization operations still reported as in progress, across all users
+    pub async fn count_active_operations(&self) -> Result<i64, sqlx::Error> {
+        sqlx::query_scalar::<_, i64>(
+            "SELECT COUNT(*) FROM sanitization_logs WHERE status = 'in_progress'"
+        )
+        .fetch_one(&self.pool)
+        .await
+    }
+    
     pub async fn get_sanitization_logs(&self, user_id: uuid::Uuid, limit: i64, offset: i64) -> Result<PaginatedResponse<SanitizationLog>, sqlx::Error> {
         let logs = sqlx::query_as::<_, SanitizationLog>(
             r#"
@@ -194,20 +463,324 @@ impl DatabaseManager {
         })
     }
     
-    pub async fn get_certificate_by_id(&self, cert_id: uuid::Uuid, user_id: uuid::Uuid) -> Result<Option<Certificate>, sqlx::Error> {
+    /// A single certificate, if `user` is allowed to see it (same rules as `get_user_certificates`)
+    pub async fn get_certificate_by_id(&self, cert_id: uuid::Uuid, user: &ServerUser) -> Result<Option<Certificate>, sqlx::Error> {
         let certificate = sqlx::query_as::<_, Certificate>(
             r#"
-            SELECT id, user_id, device_info, sanitization_method, start_time, end_time, 
-                   passes_completed, verification_status, certificate_data, created_at
+            SELECT id, user_id, certificate_data, device_info, sanitization_method, created_at, file_hash, device_id,
+                   device_type, serial_number, success, issued_at, organization_id,
+                   revoked, revoked_at, revocation_reason
             FROM certificates 
-            WHERE id = $1 AND user_id = $2
+            WHERE id = $1
+              AND organization_id IS NOT DISTINCT FROM $3
+              AND ($4 OR user_id = $2)
             "#
         )
         .bind(&cert_id)
-        .bind(&user_id)
+        .bind(&user.id)
+        .bind(&user.organization_id)
+        .bind(user.is_org_admin())
         .fetch_optional(&self.pool)
         .await?;
         
         Ok(certificate)
     }
-}
\ No newline at end of file
+    
+    /// Register a device under `registered_by`'s organization
+    pub async fn register_device(&self, req: RegisterDeviceRequest, registered_by: &ServerUser) -> Result<Device, sqlx::Error> {
+        let device = sqlx::query_as::<_, Device>(
+            r#"
+            INSERT INTO devices (id, serial_number, manufacturer, model, device_type, capacity_bytes, asset_tag, notes, registered_by, organization_id)
+            VALUES ($1, $2, COALESCE($3, 'Unknown'), COALESCE($4, 'Unknown'), COALESCE($5, 'Unknown'), $6, $7, $8, $9, $10)
+            RETURNING id, serial_number, manufacturer, model, device_type, capacity_bytes, asset_tag, notes, registered_by, created_at, updated_at, organization_id
+            "#
+        )
+        .bind(uuid::Uuid::new_v4())
+        .bind(req.serial_number.trim())
+        .bind(&req.manufacturer)
+        .bind(&req.model)
+        .bind(&req.device_type)
+        .bind(&req.capacity_bytes)
+        .bind(&req.asset_tag)
+        .bind(&req.notes)
+        .bind(&registered_by.id)
+        .bind(&registered_by.organization_id)
+        .fetch_one(&self.pool)
+        .await?;
+        
+        Ok(device)
+    }
+    
+    /// Devices visible to `user`: those registered in the user's own organization; org
+    /// admins see all of them, everyone else only the ones they registered
+    pub async fn list_devices(&self, user: &ServerUser, limit: i64, offset: i64) -> Result<PaginatedResponse<Device>, sqlx::Error> {
+        let devices = sqlx::query_as::<_, Device>(
+            r#"
+            SELECT id, serial_number, manufacturer, model, device_type, capacity_bytes, asset_tag, notes, registered_by, created_at, updated_at, organization_id
+            FROM devices 
+            WHERE organization_id IS NOT DISTINCT FROM $3
+              AND ($5 OR registered_by = $4)
+            ORDER BY updated_at DESC 
+            LIMIT $1 OFFSET $2
+            "#
+        )
+        .bind(limit)
+        .bind(offset)
+        .bind(&user.organization_id)
+        .bind(&user.id)
+        .bind(user.is_org_admin())
+        .fetch_all(&self.pool)
+        .await?;
+        
+        let total = sqlx::query_scalar::<_, i64>(
+            "SELECT COUNT(*) FROM devices WHERE organization_id IS NOT DISTINCT FROM $1 AND ($3 OR registered_by = $2)"
+        )
+            .bind(&user.organization_id)
+            .bind(&user.id)
+            .bind(user.is_org_admin())
+            .fetch_one(&self.pool)
+            .await?;
+        
+        Ok(PaginatedResponse {
+            data: devices,
+            total: total as u64,
+            page: (offset / limit + 1) as u64,
+            per_page: limit as u64,
+        })
+    }
+    
+    /// A device by serial, if `user` is allowed to see it (same rules as `list_devices`)
+    pub async fn get_device_by_serial(&self, serial_number: &str, user: &ServerUser) -> Result<Option<Device>, sqlx::Error> {
+        let device = sqlx::query_as::<_, Device>(
+            r#"
+            SELECT id, serial_number, manufacturer, model, device_type, capacity_bytes, asset_tag, notes, registered_by, created_at, updated_at, organization_id
+            FROM devices 
+            WHERE serial_number = $1
+              AND organization_id IS NOT DISTINCT FROM $2
+              AND ($4 OR registered_by = $3)
+            "#
+        )
+        .bind(serial_number.trim())
+        .bind(&user.organization_id)
+        .bind(&user.id)
+        .bind(user.is_org_admin())
+        .fetch_optional(&self.pool)
+        .await?;
+        
+        Ok(device)
+    }
+    
+    /// Update the fields present in `req`, leaving the others untouched. Only devices
+    /// `user` can see are changed.
+    pub async fn update_device(&self, serial_number: &str, req: UpdateDeviceRequest, user: &ServerUser) -> Result<Option<Device>, sqlx::Error> {
+        let device = sqlx::query_as::<_, Device>(
+            r#"
+            UPDATE devices SET
+                manufacturer = COALESCE($2, manufacturer),
+                model = COALESCE($3, model),
+                device_type = COALESCE($4, device_type),
+                capacity_bytes = COALESCE($5, capacity_bytes),
+                asset_tag = COALESCE($6, asset_tag),
+                notes = COALESCE($7, notes),
+                updated_at = NOW()
+            WHERE serial_number = $1
+              AND organization_id IS NOT DISTINCT FROM $8
+              AND ($10 OR registered_by = $9)
+            RETURNING id, serial_number, manufacturer, model, device_type, capacity_bytes, asset_tag, notes, registered_by, created_at, updated_at, organization_id
+            "#
+        )
+        .bind(serial_number.trim())
+        .bind(&req.manufacturer)
+        .bind(&req.model)
+        .bind(&req.device_type)
+        .bind(&req.capacity_bytes)
+        .bind(&req.asset_tag)
+        .bind(&req.notes)
+        .bind(&user.organization_id)
+        .bind(&user.id)
+        .bind(user.is_org_admin())
+        .fetch_optional(&self.pool)
+        .await?;
+        
+        Ok(device)
+    }
+    
+    /// Remove a device from the registry if `user` can see it. Its certificates are kept
+    /// and simply unlinked.
+    pub async fn delete_device(&self, serial_number: &str, user: &ServerUser) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query(
+            "DELETE FROM devices WHERE serial_number = $1 AND organization_id IS NOT DISTINCT FROM $2 AND ($4 OR registered_by = $3)"
+        )
+            .bind(serial_number.trim())
+            .bind(&user.organization_id)
+            .bind(&user.id)
+            .bind(user.is_org_admin())
+            .execute(&self.pool)
+            .await?;
+        
+        Ok(result.rows_affected() > 0)
+    }
+    
+    /// A device's sanitization history, newest first, limited to the certificates `user`
+    /// can see (same rules as `get_user_certificates`)
+    pub async fn get_device_certificates(&self, device_id: uuid::Uuid, user: &ServerUser) -> Result<Vec<Certificate>, sqlx::Error> {
+        let certificates = sqlx::query_as::<_, Certificate>(
+            r#"
+            SELECT id, user_id, certificate_data, device_info, sanitization_method, created_at, file_hash, device_id,
+                   device_type, serial_number, success, issued_at, organization_id,
+                   revoked, revoked_at, revocation_reason
+            FROM certificates 
+            WHERE device_id = $1
+              AND organization_id IS NOT DISTINCT FROM $2
+              AND ($4 OR user_id = $3)
+            ORDER BY created_at DESC
+            "#
+        )
+        .bind(&device_id)
+        .bind(&user.organization_id)
+        .bind(&user.id)
+        .bind(user.is_org_admin())
+        .fetch_all(&self.pool)
+        .await?;
+        
+        Ok(certificates)
+    }
+    
+    pub async fn create_organization(&self, req: CreateOrganizationRequest) -> Result<Organization, sqlx::Error> {
+        let organization = sqlx::query_as::<_, Organization>(
+            r#"
+            INSERT INTO organizations (id, name)
+            VALUES ($1, $2)
+            RETURNING id, name, created_at
+            "#
+        )
+        .bind(uuid::Uuid::new_v4())
+        .bind(req.name.trim())
+        .fetch_one(&self.pool)
+        .await?;
+        
+        Ok(organization)
+    }
+    
+    pub async fn list_organizations(&self) -> Result<Vec<Organization>, sqlx::Error> {
+        sqlx::query_as::<_, Organization>(
+            "SELECT id, name, created_at FROM organizations ORDER BY name"
+        )
+        .fetch_all(&self.pool)
+        .await
+    }
+    
+    /// Move a user into (or out of) an organization, optionally changing their role.
+    /// Certificates they already uploaded stay with the old organization.
+    pub async fn assign_user_organization(&self, user_id: uuid::Uuid, req: AssignOrganizationRequest) -> Result<Option<ServerUser>, sqlx::Error> {
+        let user = sqlx::query_as::<_, ServerUser>(
+            r#"
+            UPDATE users
+            SET organization_id = $2, role = COALESCE($3, role)
+            WHERE id = $1
+            RETURNING id, username, email, password_hash, created_at, last_login, is_active, role, organization_id
+            "#
+        )
+        .bind(&user_id)
+        .bind(&req.organization_id)
+        .bind(&req.role)
+        .fetch_optional(&self.pool)
+        .await?;
+        
+        Ok(user)
+    }
+    
+    /// Mint an API key that authenticates as `user_id`. Returns the plaintext key,
+    /// which is not stored and cannot be shown again.
+    pub async fn create_api_key(&self, name: &str, user_id: uuid::Uuid, created_by: uuid::Uuid) -> Result<(String, ApiKey), sqlx::Error> {
+        let key = format!("{}{}", API_KEY_PREFIX, password::generate_token());
+        
+        let api_key = sqlx::query_as::<_, ApiKey>(
+            r#"
+            INSERT INTO api_keys (id, name, key_hash, user_id, created_by)
+            VALUES ($1, $2, $3, $4, $5)
+            RETURNING id, name, user_id, created_by, created_at, last_used_at, revoked_at
+            "#
+        )
+        .bind(uuid::Uuid::new_v4())
+        .bind(name)
+        .bind(password::hash_token(&key))
+        .bind(&user_id)
+        .bind(&created_by)
+        .fetch_one(&self.pool)
+        .await?;
+        
+        Ok((key, api_key))
+    }
+    
+    pub async fn list_api_keys(&self) -> Result<Vec<ApiKey>, sqlx::Error> {
+        sqlx::query_as::<_, ApiKey>(
+            r#"
+            SELECT id, name, user_id, created_by, created_at, last_used_at, revoked_at
+            FROM api_keys
+            ORDER BY created_at DESC
+            "#
+        )
+        .fetch_all(&self.pool)
+        .await
+    }
+    
+    pub async fn revoke_api_key(&self, key_id: uuid::Uuid) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query("UPDATE api_keys SET revoked_at = NOW() WHERE id = $1 AND revoked_at IS NULL")
+            .bind(&key_id)
+            .execute(&self.pool)
+            .await?;
+        
+        Ok(result.rows_affected() > 0)
+    }
+    
+    /// The user an unrevoked API key acts as, recording the key as used
+    pub async fn resolve_api_key(&self, key: &str) -> Result<Option<uuid::Uuid>, sqlx::Error> {
+        sqlx::query_scalar::<_, uuid::Uuid>(
+            r#"
+            UPDATE api_keys SET last_used_at = NOW()
+            WHERE key_hash = $1 AND revoked_at IS NULL
+            RETURNING user_id
+            "#
+        )
+        .bind(password::hash_token(key))
+        .fetch_optional(&self.pool)
+        .await
+    }
+    
+    /// Any certificate by ID, regardless of owner; for the public verification endpoint
+    pub async fn find_certificate(&self, cert_id: uuid::Uuid) -> Result<Option<Certificate>, sqlx::Error> {
+        sqlx::query_as::<_, Certificate>(
+            r#"
+            SELECT id, user_id, certificate_data, device_info, sanitization_method, created_at, file_hash, device_id,
+                   device_type, serial_number, success, issued_at, organization_id,
+                   revoked, revoked_at, revocation_reason
+            FROM certificates
+            WHERE id = $1
+            "#
+        )
+        .bind(&cert_id)
+        .fetch_optional(&self.pool)
+        .await
+    }
+    
+    /// Mark a certificate as revoked. Returns `None` if it doesn't exist or was
+    /// already revoked, so the original reason is never overwritten.
+    pub async fn revoke_certificate(&self, cert_id: uuid::Uuid, reason: &str, revoked_by: uuid::Uuid) -> Result<Option<Certificate>, sqlx::Error> {
+        sqlx::query_as::<_, Certificate>(
+            r#"
+            UPDATE certificates
+            SET revoked = TRUE, revoked_at = NOW(), revocation_reason = $2, revoked_by = $3
+            WHERE id = $1 AND NOT revoked
+            RETURNING id, user_id, certificate_data, device_info, sanitization_method, created_at, file_hash, device_id,
+                      device_type, serial_number, success, issued_at, organization_id,
+                      revoked, revoked_at, revocation_reason
+            "#
+        )
+        .bind(&cert_id)
+        .bind(reason)
+        .bind(&revoked_by)
+        .fetch_optional(&self.pool)
+        .await
+    }
+}
@@ -0,0 +1,34 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+
+// Process-wide counters; they reset when the server restarts, which Prometheus handles
+static CERTIFICATES_RECEIVED: AtomicU64 = AtomicU64::new(0);
+static AUTH_FAILURES: AtomicU64 = AtomicU64::new(0);
+
+pub fn record_certificate_received() {
+    CERTIFICATES_RECEIVED.fetch_add(1, Ordering::Relaxed);
+}
+
+pub fn record_auth_failure() {
+    AUTH_FAILURES.fetch_add(1, Ordering::Relaxed);
+}
+
+/// Render all metrics in the Prometheus text exposition format.
+///
+/// `active_operations` is a gauge read from the database at scrape time.
+pub fn render(active_operations: i64) -> String {
+    let mut output = String::new();
+
+    output.push_str("# HELP certificates_received_total Sanitization certificates stored by the server.\n");
+    output.push_str("# TYPE certificates_received_total counter\n");
+    output.push_str(&format!("certificates_received_total {}\n", CERTIFICATES_RECEIVED.load(Ordering::Relaxed)));
+
+    output.push_str("# HELP active_operations Sanitization operations currently reported as in progress.\n");
+    output.push_str("# TYPE active_operations gauge\n");
+    output.push_str(&format!("active_operations {}\n", active_operations));
+
+    output.push_str("# HELP auth_failures_total Rejected logins and invalid authorization headers.\n");
+    output.push_str("# TYPE auth_failures_total counter\n");
+    output.push_str(&format!("auth_failures_total {}\n", AUTH_FAILURES.load(Ordering::Relaxed)));
+
+    output
+}
@@ -2,6 +2,11 @@ pub mod database;
 pub mod api;
 pub mod client;
 pub mod models;
+pub mod password;
+pub mod metrics;
+pub mod pdf;
+pub mod notify;
+pub mod session;
 
 pub use database::DatabaseManager;
 pub use api::start_server;
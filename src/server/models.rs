@@ -11,6 +11,26 @@ pub struct ServerUser {
     pub created_at: DateTime<Utc>,
     pub last_login: Option<DateTime<Utc>>,
     pub is_active: bool,
+    pub role: String,             // "user", "org_admin" or "admin"
+    pub organization_id: Option<Uuid>,
+}
+
+impl ServerUser {
+    pub fn is_admin(&self) -> bool {
+        self.role == "admin"
+    }
+    
+    /// Org admins see every certificate in their organization, not just their own
+    pub fn is_org_admin(&self) -> bool {
+        self.role == "org_admin" && self.organization_id.is_some()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct Organization {
+    pub id: Uuid,
+    pub name: String,
+    pub created_at: DateTime<Utc>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
@@ -22,6 +42,99 @@ pub struct Certificate {
     pub sanitization_method: String,
     pub created_at: DateTime<Utc>,
     pub file_hash: String,        // Hash of the certificate for integrity
+    pub device_id: Option<Uuid>,  // Registered device this certificate belongs to
+    // Queryable copies of fields inside certificate_data
+    pub device_type: Option<String>,
+    pub serial_number: Option<String>,
+    pub success: Option<bool>,
+    pub issued_at: Option<DateTime<Utc>>,
+    pub organization_id: Option<Uuid>,
+    #[serde(default)]
+    pub revoked: bool,
+    pub revoked_at: Option<DateTime<Utc>>,
+    pub revocation_reason: Option<String>,
+}
+
+/// What the public verification endpoint discloses about a certificate: enough to
+/// check a copy's hash and status, nothing identifying the owner or the drive
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CertificateVerification {
+    pub id: Uuid,
+    pub file_hash: String,
+    pub sanitization_method: String,
+    pub issued_at: Option<DateTime<Utc>>,
+    pub success: Option<bool>,
+    pub revoked: bool,
+    pub revoked_at: Option<DateTime<Utc>>,
+    pub revocation_reason: Option<String>,
+}
+
+impl From<Certificate> for CertificateVerification {
+    fn from(certificate: Certificate) -> Self {
+        Self {
+            id: certificate.id,
+            file_hash: certificate.file_hash,
+            sanitization_method: certificate.sanitization_method,
+            issued_at: certificate.issued_at,
+            success: certificate.success,
+            revoked: certificate.revoked,
+            revoked_at: certificate.revoked_at,
+            revocation_reason: certificate.revocation_reason,
+        }
+    }
+}
+
+/// Searchable fields pulled out of a certificate's JSON when it is stored
+#[derive(Debug, Clone, Default)]
+pub struct CertificateFields {
+    pub device_type: Option<String>,
+    pub serial_number: Option<String>,
+    pub success: Option<bool>,
+    pub issued_at: Option<DateTime<Utc>>,
+}
+
+impl CertificateFields {
+    /// Missing or malformed fields stay `None`; the certificate is still stored
+    pub fn from_certificate_data(certificate_data: &str) -> Self {
+        let Ok(cert) = serde_json::from_str::<serde_json::Value>(certificate_data) else {
+            return Self::default();
+        };
+        Self {
+            device_type: cert["device_info"]["device_type"].as_str().map(|s| s.to_string()),
+            serial_number: cert["device_info"]["serial_number"].as_str().map(|s| s.to_string()),
+            success: cert["sanitization_info"]["success"].as_bool(),
+            issued_at: cert["timestamp"].as_str()
+                .and_then(|ts| DateTime::parse_from_rfc3339(ts).ok())
+                .map(|ts| ts.with_timezone(&Utc)),
+        }
+    }
+}
+
+/// Optional filters for listing certificates; every field that is set must match
+#[derive(Debug, Clone, Default)]
+pub struct CertificateFilter {
+    pub device_type: Option<String>,
+    pub method: Option<String>,
+    pub success: Option<bool>,
+    pub serial: Option<String>,
+    pub from: Option<DateTime<Utc>>,
+    pub to: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct Device {
+    pub id: Uuid,
+    pub serial_number: String,
+    pub manufacturer: String,
+    pub model: String,
+    pub device_type: String,
+    pub capacity_bytes: Option<i64>,
+    pub asset_tag: Option<String>,
+    pub notes: Option<String>,
+    pub registered_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub organization_id: Option<Uuid>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
@@ -41,6 +154,31 @@ pub struct SanitizationLog {
     pub created_at: DateTime<Utc>,
 }
 
+/// An API key as listed to admins; the key itself is only shown when it is minted
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct ApiKey {
+    pub id: Uuid,
+    pub name: String,
+    pub user_id: Uuid,            // Identity the key authenticates as
+    pub created_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub revoked_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CreateApiKeyRequest {
+    pub name: String,
+    #[serde(default)]
+    pub user_id: Option<Uuid>,    // Service account to act as; defaults to the admin minting the key
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ApiKeyResponse {
+    pub key: String,              // Only returned once, at creation
+    pub api_key: ApiKey,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct CreateUserRequest {
     pub username: String,
@@ -61,11 +199,82 @@ pub struct LoginResponse {
     pub username: String,
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PasswordResetTokenResponse {
+    pub user_id: Uuid,
+    pub token: String,            // One-time token, only returned once to the admin
+    pub expires_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ResetPasswordRequest {
+    pub token: String,
+    pub new_password: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CreateOrganizationRequest {
+    pub name: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AssignOrganizationRequest {
+    pub organization_id: Option<Uuid>, // None takes the user out of any organization
+    #[serde(default)]
+    pub role: Option<String>,          // "user" or "org_admin"; unchanged if omitted
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RevokeCertificateRequest {
+    pub reason: String,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct SubmitCertificateRequest {
     pub certificate_data: String,
     pub device_info: String,
     pub sanitization_method: String,
+    #[serde(default)]
+    pub device_serial: Option<String>, // Falls back to the serial inside certificate_data
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RegisterDeviceRequest {
+    pub serial_number: String,
+    #[serde(default)]
+    pub manufacturer: Option<String>,
+    #[serde(default)]
+    pub model: Option<String>,
+    #[serde(default)]
+    pub device_type: Option<String>,
+    #[serde(default)]
+    pub capacity_bytes: Option<i64>,
+    #[serde(default)]
+    pub asset_tag: Option<String>,
+    #[serde(default)]
+    pub notes: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UpdateDeviceRequest {
+    #[serde(default)]
+    pub manufacturer: Option<String>,
+    #[serde(default)]
+    pub model: Option<String>,
+    #[serde(default)]
+    pub device_type: Option<String>,
+    #[serde(default)]
+    pub capacity_bytes: Option<i64>,
+    #[serde(default)]
+    pub asset_tag: Option<String>,
+    #[serde(default)]
+    pub notes: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DeviceHistoryResponse {
+    pub device: Device,
+    pub certificates: Vec<Certificate>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -87,6 +296,8 @@ pub struct StoreCertificateRequest {
     pub device_info: String,
     pub sanitization_method: String,
     pub file_hash: String,
+    pub device_id: Option<Uuid>,
+    pub organization_id: Option<Uuid>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
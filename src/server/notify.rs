@@ -0,0 +1,144 @@
+use lettre::message::header::ContentType;
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor};
+use crate::server::models::Certificate;
+
+/// SMTP settings for certificate notifications and verification emails, read from the environment.
+///
+/// Notifications are off unless `CERT_NOTIFY_ENABLED=true` and `SMTP_HOST` is set.
+#[derive(Debug, Clone)]
+pub struct NotifyConfig {
+    pub smtp_host: String,
+    pub smtp_port: u16,
+    /// `starttls` (default), `tls` for implicit TLS, or `none` for a local relay
+    pub smtp_security: String,
+    pub smtp_username: Option<String>,
+    pub smtp_password: Option<String>,
+    pub from: String,
+    /// Fixed recipient such as a compliance manager; the uploading user otherwise
+    pub recipient: Option<String>,
+    /// Base URL of the dashboard used for the verification link
+    pub public_url: String,
+}
+
+impl NotifyConfig {
+    pub fn from_env(port: u16) -> Option<Self> {
+        let enabled = std::env::var("CERT_NOTIFY_ENABLED")
+            .map(|v| v == "true" || v == "1")
+            .unwrap_or(false);
+        if !enabled {
+            return None;
+        }
+        let config = Self::smtp_from_env(port);
+        if config.is_none() {
+            eprintln!("⚠️ CERT_NOTIFY_ENABLED is set but SMTP_HOST is missing - certificate emails are disabled");
+        }
+        config
+    }
+
+    /// SMTP settings alone, whether or not certificate notifications are on; `None`
+    /// without `SMTP_HOST`
+    pub fn smtp_from_env(port: u16) -> Option<Self> {
+        let smtp_host = std::env::var("SMTP_HOST").ok()?;
+
+        Some(Self {
+            smtp_host,
+            smtp_port: std::env::var("SMTP_PORT").ok().and_then(|p| p.parse().ok()).unwrap_or(587),
+            smtp_security: std::env::var("SMTP_SECURITY").unwrap_or_else(|_| "starttls".to_string()),
+            smtp_username: std::env::var("SMTP_USERNAME").ok(),
+            smtp_password: std::env::var("SMTP_PASSWORD").ok(),
+            from: std::env::var("SMTP_FROM").unwrap_or_else(|_| "hdd-tool@localhost".to_string()),
+            recipient: std::env::var("CERT_NOTIFY_RECIPIENT").ok().filter(|r| !r.trim().is_empty()),
+            public_url: std::env::var("SERVER_PUBLIC_URL")
+                .map(|url| url.trim_end_matches('/').to_string())
+                .unwrap_or_else(|_| format!("http://localhost:{}", port)),
+        })
+    }
+
+    fn transport(&self) -> Result<AsyncSmtpTransport<Tokio1Executor>, String> {
+        let builder = match self.smtp_security.as_str() {
+            "tls" => AsyncSmtpTransport::<Tokio1Executor>::relay(&self.smtp_host).map_err(|e| e.to_string())?,
+            "none" => AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(&self.smtp_host),
+            _ => AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&self.smtp_host).map_err(|e| e.to_string())?,
+        };
+        let builder = builder.port(self.smtp_port);
+        let builder = match (&self.smtp_username, &self.smtp_password) {
+            (Some(username), Some(password)) => builder.credentials(Credentials::new(username.clone(), password.clone())),
+            _ => builder,
+        };
+        Ok(builder.build())
+    }
+}
+
+/// Email a newly registered user the link that activates their account
+pub async fn send_verification_email(config: &NotifyConfig, username: &str, recipient: &str, token: &str) -> Result<(), String> {
+    let link = format!("{}/api/auth/verify?token={}", config.public_url, token);
+    let body = format!(
+        "An account named {} was registered on the sanitization server with this address.\n\n\
+         Open this link within {} hours to activate it:\n{}\n\n\
+         If you did not register, ignore this email and the account will never be activated.\n",
+        username,
+        crate::server::database::VERIFICATION_TOKEN_TTL_HOURS,
+        link,
+    );
+
+    let email = Message::builder()
+        .from(config.from.parse().map_err(|e| format!("Invalid SMTP_FROM address: {}", e))?)
+        .to(recipient.parse().map_err(|e| format!("Invalid email address {}: {}", recipient, e))?)
+        .subject("Verify your sanitization server account")
+        .header(ContentType::TEXT_PLAIN)
+        .body(body)
+        .map_err(|e| format!("Failed to build email: {}", e))?;
+
+    config.transport()?
+        .send(email)
+        .await
+        .map(|_| ())
+        .map_err(|e| format!("SMTP send failed: {}", e))
+}
+
+/// Email a summary of a newly stored certificate with a link to it on the dashboard
+pub async fn send_certificate_notification(config: &NotifyConfig, certificate: &Certificate, recipient: &str) -> Result<(), String> {
+    let status = match certificate.success {
+        Some(true) => "Successful",
+        Some(false) => "Completed with errors",
+        None => "Unknown",
+    };
+    let link = format!("{}/?certificate={}", config.public_url, certificate.id);
+
+    let body = format!(
+        "A sanitization certificate was received.\n\n\
+         Certificate ID: {}\n\
+         Device: {}\n\
+         Device Type: {}\n\
+         Serial Number: {}\n\
+         Method: {}\n\
+         Result: {}\n\
+         Issued: {}\n\
+         Certificate Hash: {}\n\n\
+         Verify it on the dashboard: {}\n",
+        certificate.id,
+        certificate.device_info,
+        certificate.device_type.as_deref().unwrap_or("Unknown"),
+        certificate.serial_number.as_deref().unwrap_or("Unknown"),
+        certificate.sanitization_method,
+        status,
+        certificate.issued_at.unwrap_or(certificate.created_at).format("%Y-%m-%d %H:%M:%S UTC"),
+        certificate.file_hash,
+        link,
+    );
+
+    let email = Message::builder()
+        .from(config.from.parse().map_err(|e| format!("Invalid SMTP_FROM address: {}", e))?)
+        .to(recipient.parse().map_err(|e| format!("Invalid recipient address {}: {}", recipient, e))?)
+        .subject(format!("Sanitization certificate received: {} ({})", certificate.device_info, status))
+        .header(ContentType::TEXT_PLAIN)
+        .body(body)
+        .map_err(|e| format!("Failed to build email: {}", e))?;
+
+    config.transport()?
+        .send(email)
+        .await
+        .map(|_| ())
+        .map_err(|e| format!("SMTP send failed: {}", e))
+}
@@ -0,0 +1,103 @@
+use argon2::{Argon2, PasswordHash, PasswordHasher, PasswordVerifier};
+use argon2::password_hash::SaltString;
+use rand::RngCore;
+use sha2::{Sha256, Digest};
+use subtle::ConstantTimeEq;
+
+/// Hash a password with Argon2id, returning the PHC string stored in `users.password_hash`
+pub fn hash_password(password: &str) -> Result<String, String> {
+    let mut salt_bytes = [0u8; 16];
+    rand::thread_rng().fill_bytes(&mut salt_bytes);
+    let salt = SaltString::encode_b64(&salt_bytes)
+        .map_err(|e| format!("Failed to encode salt: {}", e))?;
+
+    Argon2::default()
+        .hash_password(password.as_bytes(), &salt)
+        .map(|hash| hash.to_string())
+        .map_err(|e| format!("Failed to hash password: {}", e))
+}
+
+/// Check a password against a stored hash.
+///
+/// Accounts created before Argon2 was introduced still hold an unsalted SHA-256
+/// hex digest, so those are verified the old way until the next successful login
+/// rehashes them (see [`needs_rehash`]).
+pub fn verify_password(password: &str, stored_hash: &str) -> bool {
+    if stored_hash.starts_with("$argon2") {
+        match PasswordHash::new(stored_hash) {
+            Ok(parsed) => Argon2::default()
+                .verify_password(password.as_bytes(), &parsed)
+                .is_ok(),
+            Err(_) => false,
+        }
+    } else {
+        legacy_sha256(password).as_bytes().ct_eq(stored_hash.as_bytes()).into()
+    }
+}
+
+/// Whether a stored hash predates Argon2 and should be replaced once the password is known
+pub fn needs_rehash(stored_hash: &str) -> bool {
+    !stored_hash.starts_with("$argon2")
+}
+
+/// Shortest password the server accepts; well above the desktop client's 6
+pub const MIN_PASSWORD_LENGTH: usize = 12;
+
+/// Passwords (and bases of passwords, before trailing digits and symbols) that are
+/// guessed first, whatever their length or character mix
+const COMMON_PASSWORDS: &[&str] = &[
+    "password", "passw0rd", "p@ssword", "p@ssw0rd", "123456789012", "1234567890", "qwerty",
+    "qwertyuiop", "asdfghjkl", "letmein", "welcome", "iloveyou", "admin", "administrator",
+    "changeme", "secret", "monkey", "dragon", "football", "baseball", "sunshine", "princess",
+    "trustno1", "master", "superman", "starwars", "whatever", "shredx", "hddtool",
+];
+
+/// Check a new password against the server's strength rules, returning the reason it
+/// was refused. `username`, when known, must not appear in the password.
+pub fn validate_password_strength(password: &str, username: Option<&str>) -> Result<(), String> {
+    if password.chars().count() < MIN_PASSWORD_LENGTH {
+        return Err(format!("Password must be at least {} characters long", MIN_PASSWORD_LENGTH));
+    }
+
+    let classes = [
+        password.chars().any(|c| c.is_lowercase()),
+        password.chars().any(|c| c.is_uppercase()),
+        password.chars().any(|c| c.is_ascii_digit()),
+        password.chars().any(|c| !c.is_alphanumeric()),
+    ];
+    if classes.iter().filter(|&&present| present).count() < 3 {
+        return Err("Password must contain at least three of: lowercase letters, uppercase letters, digits and symbols".to_string());
+    }
+
+    let lowered = password.to_lowercase();
+    let base = lowered.trim_end_matches(|c: char| !c.is_alphabetic());
+    if COMMON_PASSWORDS.contains(&lowered.as_str()) || COMMON_PASSWORDS.contains(&base) {
+        return Err("Password is too common".to_string());
+    }
+
+    if let Some(username) = username.map(str::trim).filter(|name| name.len() >= 3) {
+        if lowered.contains(&username.to_lowercase()) {
+            return Err("Password must not contain the username".to_string());
+        }
+    }
+
+    Ok(())
+}
+
+/// Generate a random one-time token, returned as hex
+pub fn generate_token() -> String {
+    let mut bytes = [0u8; 32];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    hex::encode(bytes)
+}
+
+/// Tokens are stored hashed so a database leak does not expose usable tokens
+pub fn hash_token(token: &str) -> String {
+    format!("{:x}", Sha256::digest(token.as_bytes()))
+}
+
+fn legacy_sha256(password: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(password.as_bytes());
+    format!("{:x}", hasher.finalize())
+}
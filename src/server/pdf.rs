@@ -0,0 +1,53 @@
+use std::path::PathBuf;
+use uuid::Uuid;
+use crate::certificate::{render_certificate_pdf, SanitizationCertificate};
+
+/// Rendered PDFs are kept in `PDF_CACHE_DIR` (default `./pdf_cache`), one file per certificate
+fn cache_dir() -> PathBuf {
+    std::env::var("PDF_CACHE_DIR")
+        .unwrap_or_else(|_| "./pdf_cache".to_string())
+        .into()
+}
+
+/// PDF for a stored certificate. Stored certificates never change, so each one is
+/// rendered once and served from the cache afterwards. A revoked certificate is always
+/// rendered afresh with `revocation` printed on it, and never cached.
+pub fn certificate_pdf(cert_id: Uuid, certificate_data: &str, revocation: Option<&str>) -> Result<Vec<u8>, String> {
+    let dir = cache_dir();
+    let path = dir.join(format!("{}.pdf", cert_id));
+    if revocation.is_none() {
+        if let Ok(bytes) = std::fs::read(&path) {
+            return Ok(bytes);
+        }
+    }
+
+    let certificate: SanitizationCertificate = serde_json::from_str(certificate_data)
+        .map_err(|e| format!("Stored certificate is not a valid certificate document: {}", e))?;
+    let bytes = render_certificate_pdf(&certificate, revocation)
+        .map_err(|e| format!("Failed to render PDF: {}", e))?;
+    if revocation.is_some() {
+        invalidate(cert_id);
+        return Ok(bytes);
+    }
+
+    // Write to a temporary name first so a concurrent request never reads a partial file
+    let temp_path = dir.join(format!("{}.pdf.tmp", cert_id));
+    let cached = std::fs::create_dir_all(&dir)
+        .and_then(|_| std::fs::write(&temp_path, &bytes))
+        .and_then(|_| std::fs::rename(&temp_path, &path));
+    if let Err(e) = cached {
+        eprintln!("⚠️ Could not cache PDF for certificate {}: {}", cert_id, e);
+    }
+
+    Ok(bytes)
+}
+
+/// Drop the cached PDF of a certificate, e.g. once it has been revoked
+pub fn invalidate(cert_id: Uuid) {
+    let path = cache_dir().join(format!("{}.pdf", cert_id));
+    if let Err(e) = std::fs::remove_file(&path) {
+        if e.kind() != std::io::ErrorKind::NotFound {
+            eprintln!("⚠️ Could not remove the cached PDF of certificate {}: {}", cert_id, e);
+        }
+    }
+}
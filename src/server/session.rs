@@ -0,0 +1,59 @@
+use jsonwebtoken::{decode, encode, DecodingKey, EncodingKey, Header, Validation};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use std::sync::OnceLock;
+use uuid::Uuid;
+
+/// Token lifetime when `JWT_EXPIRY_SECONDS` is unset or invalid (24 hours)
+const DEFAULT_SESSION_SECONDS: i64 = 24 * 60 * 60;
+
+#[derive(Debug, Serialize, Deserialize)]
+struct SessionClaims {
+    sub: String,
+    exp: usize,
+}
+
+/// Signing key from `JWT_SECRET`. Without one a random key is used, so sessions
+/// only last until the server restarts.
+fn secret() -> &'static [u8] {
+    static SECRET: OnceLock<Vec<u8>> = OnceLock::new();
+    SECRET.get_or_init(|| match std::env::var("JWT_SECRET") {
+        Ok(secret) if !secret.trim().is_empty() => secret.into_bytes(),
+        _ => {
+            println!("⚠️  JWT_SECRET is not set - sessions will not survive a server restart");
+            let mut bytes = vec![0u8; 32];
+            rand::thread_rng().fill_bytes(&mut bytes);
+            bytes
+        }
+    })
+}
+
+fn session_seconds() -> i64 {
+    std::env::var("JWT_EXPIRY_SECONDS")
+        .ok()
+        .and_then(|value| value.parse::<i64>().ok())
+        .filter(|seconds| *seconds > 0)
+        .unwrap_or(DEFAULT_SESSION_SECONDS)
+}
+
+/// Issue the signed bearer token handed out at login
+pub fn issue(user_id: Uuid) -> Result<String, String> {
+    let claims = SessionClaims {
+        sub: user_id.to_string(),
+        exp: (chrono::Utc::now() + chrono::Duration::seconds(session_seconds())).timestamp() as usize,
+    };
+    encode(&Header::default(), &claims, &EncodingKey::from_secret(secret()))
+        .map_err(|e| format!("Failed to create session token: {}", e))
+}
+
+/// The user a bearer token was issued to, if its signature and expiry check out
+pub fn verify(token: &str) -> Result<Uuid, String> {
+    let mut validation = Validation::default();
+    validation.leeway = 0;
+    validation.set_required_spec_claims(&["exp", "sub"]);
+
+    let claims = decode::<SessionClaims>(token, &DecodingKey::from_secret(secret()), &validation)
+        .map_err(|_| "Invalid or expired token".to_string())?
+        .claims;
+    Uuid::parse_str(&claims.sub).map_err(|_| "Invalid token subject".to_string())
+}
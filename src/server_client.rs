@@ -1,5 +1,11 @@
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+
+use crate::app_config::AppConfig;
+use crate::auth::SecretString;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UserSession {
@@ -17,10 +23,17 @@ pub struct CreateAccountRequest {
     pub confirm_password: String,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// The password stays zeroized until the request body is written; there is no Debug
+/// so it can't end up in a log
+#[derive(Clone, Serialize)]
 pub struct LoginRequest {
     pub username: String,
-    pub password: String,
+    #[serde(serialize_with = "serialize_secret")]
+    pub password: SecretString,
+}
+
+fn serialize_secret<S: serde::Serializer>(secret: &SecretString, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(secret)
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -30,11 +43,33 @@ pub struct ApiResponse<T> {
     pub message: String,
 }
 
+/// Failure to reach the server through the configured proxy, kept apart from
+/// server-side errors so users know to check their proxy settings
+#[derive(Debug)]
+pub struct ProxyError {
+    pub proxy_url: String,
+    pub source: reqwest::Error,
+}
+
+impl std::fmt::Display for ProxyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Could not connect through proxy {}: {}", self.proxy_url, self.source)
+    }
+}
+
+impl std::error::Error for ProxyError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.source)
+    }
+}
+
 #[derive(Clone)]
 pub struct ServerClient {
     server_url: String,
     client: reqwest::Client,
-    current_session: Option<UserSession>,
+    proxy_url: Option<String>,
+    // Shared by every clone, so a login made from a background task counts everywhere
+    current_session: Arc<Mutex<Option<UserSession>>>,
 }
 
 impl ServerClient {
@@ -42,7 +77,43 @@ impl ServerClient {
         Self {
             server_url,
             client: reqwest::Client::new(),
-            current_session: None,
+            proxy_url: None,
+            current_session: Arc::default(),
+        }
+    }
+
+    /// Create a client using the proxy (http, https, socks5 or socks5h) and certificate pin from the config
+    pub fn from_config(config: &AppConfig) -> Result<Self, Box<dyn std::error::Error>> {
+        let proxy_url = config.proxy_url.clone().filter(|p| !p.trim().is_empty());
+        let mut builder = reqwest::Client::builder();
+
+        if let Some(ref proxy) = proxy_url {
+            builder = builder.proxy(reqwest::Proxy::all(proxy)?);
+        }
+
+        if let Some(ref fingerprint) = config.pinned_cert_sha256 {
+            if !config.server_url.starts_with("https://") {
+                log::warn!("Warning: Certificate pinning is configured but the server URL is not https");
+            }
+            builder = builder.use_preconfigured_tls(pinned_tls_config(fingerprint)?);
+        }
+
+        Ok(Self {
+            server_url: config.server_url.clone(),
+            client: builder.build()?,
+            proxy_url,
+            current_session: Arc::default(),
+        })
+    }
+
+    /// Connection failures go to the proxy first when one is configured, so report them as proxy errors
+    fn map_send_error(&self, error: reqwest::Error) -> Box<dyn std::error::Error> {
+        match self.proxy_url {
+            Some(ref proxy_url) if error.is_connect() => Box::new(ProxyError {
+                proxy_url: proxy_url.clone(),
+                source: error,
+            }),
+            _ => Box::new(error),
         }
     }
 
@@ -60,7 +131,8 @@ impl ServerClient {
             .post(&url)
             .json(&request)
             .send()
-            .await?;
+            .await
+            .map_err(|e| self.map_send_error(e))?;
 
         let result: ApiResponse<UserSession> = response.json().await?;
         Ok(result)
@@ -72,13 +144,14 @@ impl ServerClient {
             .post(&url)
             .json(&request)
             .send()
-            .await?;
+            .await
+            .map_err(|e| self.map_send_error(e))?;
 
         let result: ApiResponse<UserSession> = response.json().await?;
         
         if result.success {
             if let Some(ref session) = result.data {
-                self.current_session = Some(session.clone());
+                self.set_session(Some(session.clone()));
             }
         }
 
@@ -86,7 +159,7 @@ impl ServerClient {
     }
 
     pub async fn upload_certificate(&self, certificate_data: String, device_info: String, method: String) -> Result<ApiResponse<Certificate>, Box<dyn std::error::Error>> {
-        if let Some(ref session) = self.current_session {
+        if let Some(ref session) = self.get_current_user() {
             let url = format!("{}/api/certificates", self.server_url);
             
             let request = UploadCertificateRequest {
@@ -100,7 +173,8 @@ impl ServerClient {
                 .header("Authorization", format!("Bearer {}", session.token))
                 .json(&request)
                 .send()
-                .await?;
+                .await
+                .map_err(|e| self.map_send_error(e))?;
 
             let result: ApiResponse<Certificate> = response.json().await?;
             Ok(result)
@@ -114,16 +188,21 @@ impl ServerClient {
     }
 
     pub async fn get_user_certificates(&self) -> Result<ApiResponse<Vec<Certificate>>, Box<dyn std::error::Error>> {
-        if let Some(ref session) = self.current_session {
+        if let Some(ref session) = self.get_current_user() {
             let url = format!("{}/api/certificates", self.server_url);
 
             let response = self.client
                 .get(&url)
                 .header("Authorization", format!("Bearer {}", session.token))
                 .send()
-                .await?;
+                .await
+                .map_err(|e| self.map_send_error(e))?;
 
             let result: ApiResponse<Vec<Certificate>> = response.json().await?;
+            for certificate in result.data.iter().flatten().filter(|c| c.revoked) {
+                log::info!("🚫 Certificate {} has been revoked: {}", certificate.id,
+                    certificate.revocation_reason.as_deref().unwrap_or("no reason given"));
+            }
             Ok(result)
         } else {
             Ok(ApiResponse {
@@ -134,25 +213,83 @@ impl ServerClient {
         }
     }
 
+    /// Check a certificate against the server, including whether it has been revoked.
+    /// No login is needed.
+    pub async fn verify_certificate(&self, certificate_id: &str) -> Result<ApiResponse<CertificateVerification>, Box<dyn std::error::Error>> {
+        let url = format!("{}/api/certificates/{}/verify", self.server_url, certificate_id);
+        let response = self.client.get(&url).send().await.map_err(|e| self.map_send_error(e))?;
+        let result: ApiResponse<CertificateVerification> = response.json().await?;
+        Ok(result)
+    }
+
     pub fn is_authenticated(&self) -> bool {
-        self.current_session.as_ref().map(|s| s.is_authenticated).unwrap_or(false)
+        self.get_current_user().map(|s| s.is_authenticated).unwrap_or(false)
+    }
+
+    pub fn get_current_user(&self) -> Option<UserSession> {
+        self.current_session.lock().ok().and_then(|session| session.clone())
     }
 
-    pub fn get_current_user(&self) -> Option<&UserSession> {
-        self.current_session.as_ref()
+    fn set_session(&self, session: Option<UserSession>) {
+        if let Ok(mut current) = self.current_session.lock() {
+            *current = session;
+        }
     }
 
     pub fn logout(&mut self) {
-        self.current_session = None;
+        self.set_session(None);
     }
 
     pub async fn test_connection(&self) -> Result<bool, Box<dyn std::error::Error>> {
         let url = format!("{}/api/health", self.server_url);
-        let response = self.client.get(&url).send().await?;
+        let response = self.client.get(&url).send().await.map_err(|e| self.map_send_error(e))?;
         Ok(response.status().is_success())
     }
 }
 
+/// Trusts only the server certificate whose SHA-256 matches the pinned fingerprint.
+///
+/// The pin replaces CA chain validation, so self-signed on-premise servers work;
+/// handshake signatures are still checked by rustls.
+struct PinnedCertVerifier {
+    fingerprint: Vec<u8>,
+}
+
+impl rustls::client::ServerCertVerifier for PinnedCertVerifier {
+    fn verify_server_cert(
+        &self,
+        end_entity: &rustls::Certificate,
+        _intermediates: &[rustls::Certificate],
+        _server_name: &rustls::ServerName,
+        _scts: &mut dyn Iterator<Item = &[u8]>,
+        _ocsp_response: &[u8],
+        _now: SystemTime,
+    ) -> Result<rustls::client::ServerCertVerified, rustls::Error> {
+        let actual = Sha256::digest(&end_entity.0);
+        if actual.as_slice() == self.fingerprint.as_slice() {
+            Ok(rustls::client::ServerCertVerified::assertion())
+        } else {
+            log::error!("❌ Server certificate fingerprint {} does not match the pinned fingerprint", hex::encode(actual));
+            Err(rustls::Error::InvalidCertificate(rustls::CertificateError::ApplicationVerificationFailure))
+        }
+    }
+}
+
+fn pinned_tls_config(fingerprint: &str) -> Result<rustls::ClientConfig, Box<dyn std::error::Error>> {
+    let normalized: String = fingerprint.chars()
+        .filter(|c| *c != ':' && !c.is_whitespace())
+        .collect();
+    let fingerprint = hex::decode(&normalized)?;
+    if fingerprint.len() != 32 {
+        return Err("Pinned certificate fingerprint must be a SHA-256 hash (64 hex characters)".into());
+    }
+
+    Ok(rustls::ClientConfig::builder()
+        .with_safe_defaults()
+        .with_custom_certificate_verifier(Arc::new(PinnedCertVerifier { fingerprint }))
+        .with_no_client_auth())
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UploadCertificateRequest {
     pub certificate_data: String,
@@ -169,4 +306,21 @@ pub struct Certificate {
     pub sanitization_method: String,
     pub created_at: String,
     pub file_hash: String,
+    #[serde(default)]
+    pub revoked: bool,
+    #[serde(default)]
+    pub revocation_reason: Option<String>,
+}
+
+/// Public verification result for a certificate, see `ServerClient::verify_certificate`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CertificateVerification {
+    pub id: String,
+    pub file_hash: String,
+    pub sanitization_method: String,
+    pub issued_at: Option<String>,
+    pub success: Option<bool>,
+    pub revoked: bool,
+    pub revoked_at: Option<String>,
+    pub revocation_reason: Option<String>,
 }
\ No newline at end of file
@@ -0,0 +1,50 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+/// Running totals across every session, kept so managers can report how much was
+/// erased without going through the certificates
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LifetimeStats {
+    /// Bytes overwritten or purged by wipes that succeeded
+    #[serde(default)]
+    pub bytes_erased: u64,
+    /// Drives whose wipe succeeded
+    #[serde(default)]
+    pub drives_erased: u64,
+    /// Drives wiped, whether or not the wipe succeeded
+    #[serde(default)]
+    pub drives_processed: u64,
+    /// When counting started, i.e. the first wipe after the last reset
+    #[serde(default)]
+    pub since: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl LifetimeStats {
+    pub fn load(path: &Path) -> Self {
+        fs::read_to_string(path).ok()
+            .and_then(|stats_str| serde_json::from_str(&stats_str).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+        let stats_str = serde_json::to_string_pretty(self)?;
+        fs::write(path, stats_str)?;
+        Ok(())
+    }
+
+    /// Count one finished drive; only a successful wipe adds to the bytes erased
+    pub fn record(&mut self, success: bool, bytes_erased: u64) {
+        self.since.get_or_insert_with(chrono::Utc::now);
+        self.drives_processed += 1;
+        if success {
+            self.drives_erased += 1;
+            self.bytes_erased += bytes_erased;
+        }
+    }
+
+    /// Start counting again from zero, e.g. at the start of a reporting period
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
@@ -1,5 +1,7 @@
 use eframe::egui;
 use std::collections::HashMap;
+use zeroize::Zeroize;
+use crate::auth::{secret_input, SecretString};
 use serde::{Deserialize, Serialize};
 use sha2::{Sha256, Digest};
 use std::fs;
@@ -30,13 +32,13 @@ pub struct AuthWidget {
     
     // Login form
     login_username: String,
-    login_password: String,
+    login_password: SecretString,
     
     // Register form
     register_username: String,
     register_email: String,
-    register_password: String,
-    register_confirm_password: String,
+    register_password: SecretString,
+    register_confirm_password: SecretString,
     
     // Status messages
     status_message: String,
@@ -49,6 +51,9 @@ pub struct AuthWidget {
     
     // Stored users
     stored_users: HashMap<String, StoredUser>,
+    
+    // Credentials of the last successful login, kept until the server client takes them
+    server_credentials: Option<(String, SecretString)>,
 }
 
 impl Default for AuthWidget {
@@ -56,17 +61,18 @@ impl Default for AuthWidget {
         let mut widget = Self {
             state: AuthState::NotConnected,
             login_username: String::new(),
-            login_password: String::new(),
+            login_password: secret_input(),
             register_username: String::new(),
             register_email: String::new(),
-            register_password: String::new(),
-            register_confirm_password: String::new(),
+            register_password: secret_input(),
+            register_confirm_password: secret_input(),
             status_message: String::new(),
             error_message: String::new(),
             is_connecting: false,
             is_logging_in: false,
             is_registering: false,
             stored_users: HashMap::new(),
+            server_credentials: None,
         };
         
         // Load stored users from file
@@ -197,7 +203,7 @@ impl AuthWidget {
             
             ui.horizontal(|ui| {
                 ui.label("Password:");
-                ui.add(egui::TextEdit::singleline(&mut self.login_password)
+                ui.add(egui::TextEdit::singleline(&mut *self.login_password)
                     .password(true)
                     .desired_width(200.0));
             });
@@ -261,7 +267,7 @@ impl AuthWidget {
             
             ui.horizontal(|ui| {
                 ui.label("Password:");
-                ui.add(egui::TextEdit::singleline(&mut self.register_password)
+                ui.add(egui::TextEdit::singleline(&mut *self.register_password)
                     .password(true)
                     .desired_width(200.0));
             });
@@ -270,7 +276,7 @@ impl AuthWidget {
             
             ui.horizontal(|ui| {
                 ui.label("Confirm:");
-                ui.add(egui::TextEdit::singleline(&mut self.register_confirm_password)
+                ui.add(egui::TextEdit::singleline(&mut *self.register_confirm_password)
                     .password(true)
                     .desired_width(200.0));
             });
@@ -350,10 +356,11 @@ impl AuthWidget {
             }
             
             // Hash the provided password and compare
-            let password_hash = self.hash_password(&self.login_password);
-            if password_hash == user.password_hash {
+            let password_hash = zeroize::Zeroizing::new(self.hash_password(&self.login_password));
+            if crate::auth::password_hashes_match(&user.password_hash, &password_hash) {
                 // Successful login
                 let username = self.login_username.clone();
+                self.server_credentials = Some((username.clone(), self.login_password.clone()));
                 self.state = AuthState::Authenticated(username.clone());
                 self.status_message = format!("Welcome back, {}!", username);
                 self.update_last_login(&username);
@@ -397,11 +404,11 @@ impl AuthWidget {
 
     fn clear_forms(&mut self) {
         self.login_username.clear();
-        self.login_password.clear();
+        self.login_password.zeroize();
         self.register_username.clear();
         self.register_email.clear();
-        self.register_password.clear();
-        self.register_confirm_password.clear();
+        self.register_password.zeroize();
+        self.register_confirm_password.zeroize();
     }
 
     pub fn is_authenticated(&self) -> bool {
@@ -443,7 +450,14 @@ impl AuthWidget {
         }
     }
     
+    /// Username and password of the login that just succeeded, for signing in to the
+    /// server as well; returns them only once
+    pub fn take_server_credentials(&mut self) -> Option<(String, SecretString)> {
+        self.server_credentials.take()
+    }
+    
     pub fn logout(&mut self) {
+        self.server_credentials = None;
         self.state = AuthState::Connected;
         self.clear_forms();
         self.clear_error();
@@ -1,5 +1,26 @@
 use eframe::egui;
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
 use crate::ui::themes::SecureTheme;
+use crate::advanced_wiper::{ErrorPolicy, OverwritePattern, Sensitivity, WipingAlgorithm, MAX_OVERWRITE_PASSES};
+
+/// Window over which the displayed speed (and derived ETA) is averaged
+const SPEED_WINDOW: Duration = Duration::from_secs(5);
+
+/// Format a byte count for display in decimal units, as drive capacities are sold.
+/// Display only; sizes are carried as byte counts and never parsed back.
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: &[&str] = &["B", "KB", "MB", "GB", "TB"];
+    let mut size = bytes as f64;
+    let mut unit_index = 0;
+
+    while size >= 1000.0 && unit_index < UNITS.len() - 1 {
+        size /= 1000.0;
+        unit_index += 1;
+    }
+
+    format!("{:.2} {}", size, UNITS[unit_index])
+}
 
 #[derive(Clone, Debug)]
 pub struct DriveInfo {
@@ -8,6 +29,8 @@ pub struct DriveInfo {
     pub path: String,
     pub size: String,
     pub used: String,
+    pub size_bytes: u64,        // Raw capacity; `size` is only its display form
+    pub used_bytes: u64,
     pub progress: f32,          // Progress as 0.0 to 1.0
     pub time_left: String,      // Calculated time remaining
     pub speed: String,          // Current processing speed
@@ -16,16 +39,21 @@ pub struct DriveInfo {
     pub bytes_processed: u64,   // Bytes processed so far
     pub start_time: Option<std::time::Instant>, // When processing started
     pub last_update: Option<std::time::Instant>, // Last progress update
+    pub interrupted: bool,      // Stopped short of completion, e.g. the device was removed
+    pub benchmark_speed: Option<f64>, // Bytes per second a benchmark measured, for the ETA before the wipe has its own
+    speed_samples: VecDeque<(Instant, u64)>,      // Recent (time, bytes) samples for the moving average
 }
 
 impl DriveInfo {
-    pub fn new(name: String, path: String, size: String, used: String) -> Self {
+    pub fn new(name: String, path: String, size_bytes: u64, used_bytes: u64) -> Self {
         Self {
             selected: false,
             name,
             path,
-            size,
-            used,
+            size: format_bytes(size_bytes),
+            used: format_bytes(used_bytes),
+            size_bytes,
+            used_bytes,
             progress: 0.0,
             time_left: "-".to_string(),
             speed: "-".to_string(),
@@ -34,6 +62,9 @@ impl DriveInfo {
             bytes_processed: 0,
             start_time: None,
             last_update: None,
+            interrupted: false,
+            benchmark_speed: None,
+            speed_samples: VecDeque::new(),
         }
     }
     
@@ -44,6 +75,74 @@ impl DriveInfo {
         self.start_time = Some(std::time::Instant::now());
         self.last_update = Some(std::time::Instant::now());
         self.status = "Processing...".to_string();
+        self.interrupted = false;
+        self.speed_samples.clear();
+        if let Some(bytes_per_second) = self.benchmark_speed.filter(|&speed| speed > 0.0) {
+            self.time_left = format_duration(total_bytes as f64 / bytes_per_second);
+        }
+    }
+    
+    /// Whether the drive is being wiped: started, not finished and not cut short
+    pub fn is_active(&self) -> bool {
+        self.start_time.is_some() && self.progress < 1.0 && !self.interrupted
+    }
+    
+    /// How long the drive has been wiping; stops counting once it finishes or is cut short
+    pub fn elapsed(&self) -> Option<std::time::Duration> {
+        let start = self.start_time?;
+        let end = if self.is_active() { Instant::now() } else { self.last_update.unwrap_or(start) };
+        Some(end.saturating_duration_since(start))
+    }
+    
+    /// Stop the drive where it is, short of completion; the bar keeps showing how far
+    /// the wipe got instead of running on to 100%
+    pub fn interrupt(&mut self, status: &str) {
+        self.interrupted = true;
+        self.status = status.to_string();
+        self.speed = "-".to_string();
+        self.time_left = "-".to_string();
+    }
+    
+    /// Replace the size estimate with the device's real size once the wipe reports it,
+    /// keeping the fraction done so the bar doesn't jump
+    pub fn reconcile_total(&mut self, total_bytes: u64) {
+        if total_bytes == 0 || total_bytes == self.bytes_total {
+            return;
+        }
+        let fraction = if self.bytes_total > 0 {
+            (self.bytes_processed as f64 / self.bytes_total as f64).min(1.0)
+        } else {
+            0.0
+        };
+        self.bytes_total = total_bytes;
+        self.bytes_processed = (fraction * total_bytes as f64) as u64;
+        // Samples counted against the old total would skew the speed
+        self.speed_samples.clear();
+    }
+    
+    /// Bytes per second over the last `SPEED_WINDOW`, falling back to the
+    /// overall average until the window holds at least a second of samples
+    fn windowed_speed(&mut self, now: Instant, overall_elapsed: f64) -> f64 {
+        self.speed_samples.push_back((now, self.bytes_processed));
+        while self.speed_samples.len() > 2 {
+            match self.speed_samples.front() {
+                Some((time, _)) if now.duration_since(*time) > SPEED_WINDOW => {
+                    self.speed_samples.pop_front();
+                }
+                _ => break,
+            }
+        }
+        
+        if let (Some((first_time, first_bytes)), Some((last_time, last_bytes))) =
+            (self.speed_samples.front(), self.speed_samples.back())
+        {
+            let window = last_time.duration_since(*first_time).as_secs_f64();
+            if window >= 1.0 {
+                return last_bytes.saturating_sub(*first_bytes) as f64 / window;
+            }
+        }
+        
+        self.bytes_processed as f64 / overall_elapsed
     }
     
     pub fn update_progress(&mut self, bytes_processed: u64) {
@@ -60,8 +159,8 @@ impl DriveInfo {
             let elapsed = now.duration_since(start).as_secs_f64();
             
             if elapsed > 1.0 { // Only calculate after 1 second to avoid division issues
-                // Calculate current speed (bytes per second)
-                let bytes_per_second = self.bytes_processed as f64 / elapsed;
+                // Calculate current speed (bytes per second) as a moving average
+                let bytes_per_second = self.windowed_speed(now, elapsed);
                 
                 // Format speed display
                 self.speed = if bytes_per_second >= 1_000_000_000.0 {
@@ -85,6 +184,10 @@ impl DriveInfo {
                 } else {
                     self.time_left = "Calculating...".to_string();
                 }
+            } else if let Some(bytes_per_second) = self.benchmark_speed.filter(|&speed| speed > 0.0) {
+                // Too early to measure; go by the benchmark until the wipe has a speed of its own
+                self.speed = "Calculating...".to_string();
+                self.time_left = format_duration(self.bytes_total.saturating_sub(self.bytes_processed) as f64 / bytes_per_second);
             } else {
                 self.speed = "Calculating...".to_string();
                 self.time_left = "Calculating...".to_string();
@@ -160,9 +263,22 @@ impl TabWidget {
     }
 }
 
+/// Quick action picked from a drive row's context menu, with the row index
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DriveRowAction {
+    Analyze(usize),
+    Benchmark(usize),
+    ViewDetails(usize),
+    Wipe(usize),
+}
+
 pub struct DriveTableWidget {
     pub drives: Vec<DriveInfo>,
     pub select_all: bool,
+    /// Row of the last click, the anchor for shift-click range selection
+    selection_anchor: Option<usize>,
+    /// State being applied to every row the pointer crosses while drag-selecting
+    drag_select: Option<bool>,
 }
 
 impl DriveTableWidget {
@@ -170,6 +286,8 @@ impl DriveTableWidget {
         Self {
             drives: Vec::new(),
             select_all: false,
+            selection_anchor: None,
+            drag_select: None,
         }
     }
     
@@ -177,7 +295,18 @@ impl DriveTableWidget {
         self.drives.push(drive);
     }
     
-    pub fn show(&mut self, ui: &mut egui::Ui) {
+    /// Select just the drive at `index`, for actions that target a single row
+    pub fn select_only(&mut self, index: usize) {
+        for (i, drive) in self.drives.iter_mut().enumerate() {
+            drive.selected = i == index;
+        }
+        self.select_all = false;
+    }
+    
+    /// Draw the table; returns the context menu action the operator picked, if any
+    pub fn show(&mut self, ui: &mut egui::Ui) -> Option<DriveRowAction> {
+        let mut row_action = None;
+        
         // Header
         ui.horizontal(|ui| {
             ui.label("DRIVES");
@@ -186,7 +315,7 @@ impl DriveTableWidget {
         ui.add_space(10.0);
         
         // Define column widths for consistent alignment
-        let col_widths = [60.0, 100.0, 80.0, 80.0, 80.0, 100.0, 80.0, 80.0];
+        let col_widths = [60.0, 100.0, 80.0, 80.0, 80.0, 100.0, 80.0, 80.0, 80.0];
         
         // Column headers with fixed widths
         ui.horizontal(|ui| {
@@ -232,16 +361,23 @@ impl DriveTableWidget {
                 |ui| { ui.label("Progress"); }
             );
             
-            // Time left column header
+            // Elapsed column header
             ui.allocate_ui_with_layout(
                 egui::vec2(col_widths[6], 20.0),
                 egui::Layout::centered_and_justified(egui::Direction::LeftToRight),
+                |ui| { ui.label("Elapsed"); }
+            );
+            
+            // Time left column header
+            ui.allocate_ui_with_layout(
+                egui::vec2(col_widths[7], 20.0),
+                egui::Layout::centered_and_justified(egui::Direction::LeftToRight),
                 |ui| { ui.label("Time left"); }
             );
             
             // Speed column header
             ui.allocate_ui_with_layout(
-                egui::vec2(col_widths[7], 20.0),
+                egui::vec2(col_widths[8], 20.0),
                 egui::Layout::centered_and_justified(egui::Direction::LeftToRight),
                 |ui| { ui.label("Speed"); }
             );
@@ -251,6 +387,10 @@ impl DriveTableWidget {
         
         // Drive rows
         let mut rows_to_update = Vec::new();
+        let shift_held = ui.input(|i| i.modifiers.shift);
+        let pointer_pos = ui.input(|i| i.pointer.interact_pos());
+        let mut clicked_row = None;
+        let mut drag_started = None;
         for (i, drive) in self.drives.iter().enumerate() {
             let row_bg = if i % 2 == 0 { 
                 SecureTheme::TABLE_ROW 
@@ -258,11 +398,48 @@ impl DriveTableWidget {
                 SecureTheme::TABLE_ROW_ALT 
             };
             
+            // Clicking a row toggles it like its checkbox; dragging paints the new state across rows
             let response = ui.allocate_response(
                 egui::vec2(ui.available_width(), 30.0),
-                egui::Sense::hover()
+                egui::Sense::click_and_drag()
             );
             
+            if response.clicked() {
+                clicked_row = Some((i, !drive.selected));
+            }
+            if response.drag_started() {
+                drag_started = Some((i, !drive.selected));
+            }
+            if let Some(state) = self.drag_select {
+                if drive.selected != state && pointer_pos.is_some_and(|pos| response.rect.contains(pos)) {
+                    rows_to_update.push((i, state));
+                }
+            }
+            
+            response.context_menu(|ui| {
+                if ui.button("🔍 Analyze").clicked() {
+                    row_action = Some(DriveRowAction::Analyze(i));
+                    ui.close_menu();
+                }
+                if ui.button("⏱ Benchmark").on_hover_text("Measure throughput without touching existing data").clicked() {
+                    row_action = Some(DriveRowAction::Benchmark(i));
+                    ui.close_menu();
+                }
+                if ui.button("ℹ View Details").clicked() {
+                    row_action = Some(DriveRowAction::ViewDetails(i));
+                    ui.close_menu();
+                }
+                if ui.button("🗑 Wipe this drive").clicked() {
+                    row_action = Some(DriveRowAction::Wipe(i));
+                    ui.close_menu();
+                }
+                ui.separator();
+                if ui.button("📋 Copy path").clicked() {
+                    ui.output_mut(|o| o.copied_text = drive.path.clone());
+                    ui.close_menu();
+                }
+            });
+            
             if response.hovered() {
                 ui.painter().rect_filled(
                     response.rect,
@@ -287,7 +464,7 @@ impl DriveTableWidget {
                         |ui| {
                             let mut selected = drive.selected;
                             if ui.checkbox(&mut selected, "").changed() {
-                                rows_to_update.push((i, selected));
+                                clicked_row = Some((i, selected));
                             }
                         }
                     );
@@ -359,16 +536,28 @@ impl DriveTableWidget {
                         }
                     );
                     
-                    // Time left column
+                    // Elapsed column
                     ui.allocate_ui_with_layout(
                         egui::vec2(col_widths[6], 25.0),
                         egui::Layout::centered_and_justified(egui::Direction::LeftToRight),
+                        |ui| {
+                            match drive.elapsed() {
+                                Some(elapsed) => ui.label(format_duration(elapsed.as_secs_f64())),
+                                None => ui.label("-"),
+                            };
+                        }
+                    );
+                    
+                    // Time left column
+                    ui.allocate_ui_with_layout(
+                        egui::vec2(col_widths[7], 25.0),
+                        egui::Layout::centered_and_justified(egui::Direction::LeftToRight),
                         |ui| { ui.label(&drive.time_left); }
                     );
                     
                     // Speed column
                     ui.allocate_ui_with_layout(
-                        egui::vec2(col_widths[7], 25.0),
+                        egui::vec2(col_widths[8], 25.0),
                         egui::Layout::centered_and_justified(egui::Direction::LeftToRight),
                         |ui| { ui.label(&drive.speed); }
                     );
@@ -376,6 +565,28 @@ impl DriveTableWidget {
             });
         }
         
+        // Shift-click applies the clicked row's new state to everything since the anchor
+        if let Some((index, selected)) = clicked_row {
+            match self.selection_anchor {
+                Some(anchor) if shift_held => {
+                    for row in anchor.min(index)..=anchor.max(index) {
+                        rows_to_update.push((row, selected));
+                    }
+                }
+                _ => rows_to_update.push((index, selected)),
+            }
+            self.selection_anchor = Some(index);
+        }
+        
+        if let Some((index, selected)) = drag_started {
+            self.drag_select = Some(selected);
+            self.selection_anchor = Some(index);
+            rows_to_update.push((index, selected));
+        }
+        if !ui.input(|i| i.pointer.primary_down()) {
+            self.drag_select = None;
+        }
+        
         // Apply updates
         for (index, selected) in rows_to_update {
             if let Some(drive) = self.drives.get_mut(index) {
@@ -394,31 +605,49 @@ impl DriveTableWidget {
                     drive.selected = new_state;
                 }
             }
+            ui.label(egui::RichText::new("Shift-click selects a range, drag across rows to select several, right-click for actions").weak().small());
         });
+        
+        row_action
     }
 }
 
 pub struct AdvancedOptionsWidget {
-    pub eraser_method: String,
-    pub verification: String,
+    pub algorithm: WipingAlgorithm,
     pub confirm_erase: bool,
+    pub hash_device: bool,
+    pub error_policy: ErrorPolicy,
+    /// Only used by the NIST SP 800-88 Auto method
+    pub sensitivity: Sensitivity,
 }
 
 impl AdvancedOptionsWidget {
     pub fn new() -> Self {
         Self {
-            eraser_method: "NIST SP 800-88 and DoD 5220.22-M".to_string(),
-            verification: "json".to_string(),
+            algorithm: WipingAlgorithm::NistClear,
             confirm_erase: false,
+            hash_device: false,
+            error_policy: ErrorPolicy::default(),
+            sensitivity: Sensitivity::default(),
         }
     }
     
+    /// Wiping algorithm selected in the eraser method dropdown
+    pub fn algorithm(&self) -> WipingAlgorithm {
+        self.algorithm.clone()
+    }
+    
+    /// Name of the selected method, as recorded on reports and certificates
+    pub fn eraser_method(&self) -> String {
+        self.algorithm.to_string()
+    }
+    
     pub fn show(&mut self, ui: &mut egui::Ui) -> bool {
         self.show_with_permissions(ui, true, "Admin")
     }
     
     pub fn show_with_permissions(&mut self, ui: &mut egui::Ui, can_sanitize: bool, user_role: &str) -> bool {
-        println!("🔐 AUTH STATUS: can_sanitize={}, user_role={}", can_sanitize, user_role);
+        log::debug!("🔐 AUTH STATUS: can_sanitize={}, user_role={}", can_sanitize, user_role);
         
         ui.horizontal(|ui| {
             ui.label("ADVANCE OPTIONS");
@@ -430,30 +659,76 @@ impl AdvancedOptionsWidget {
             // Eraser method dropdown
             ui.label("Eraser method :");
             egui::ComboBox::from_id_salt("eraser_method")
-                .selected_text(&self.eraser_method)
+                .selected_text(self.algorithm.name())
                 .width(250.0)
                 .show_ui(ui, |ui| {
-                    ui.selectable_value(&mut self.eraser_method, "NIST SP 800-88 and DoD 5220.22-M".to_string(), "NIST SP 800-88 and DoD 5220.22-M");
-                    ui.selectable_value(&mut self.eraser_method, "NIST SP 800-88".to_string(), "NIST SP 800-88");
-                    ui.selectable_value(&mut self.eraser_method, "DoD 5220.22-M".to_string(), "DoD 5220.22-M");
-                    ui.selectable_value(&mut self.eraser_method, "DoD 5220.22-M ECE".to_string(), "DoD 5220.22-M ECE");
-                    ui.selectable_value(&mut self.eraser_method, "Gutmann".to_string(), "Gutmann");
-                    ui.selectable_value(&mut self.eraser_method, "Random".to_string(), "Random");
-                    ui.selectable_value(&mut self.eraser_method, "ATA Secure Erase".to_string(), "ATA Secure Erase");
-                    ui.selectable_value(&mut self.eraser_method, "Enhanced Secure Erase".to_string(), "Enhanced Secure Erase");
+                    for algorithm in WipingAlgorithm::SELECTABLE {
+                        // Picking the method the operator already configured keeps its settings
+                        let selected = self.algorithm.same_method(algorithm);
+                        if ui.selectable_label(selected, algorithm.name()).clicked() && !selected {
+                            self.algorithm = algorithm.clone();
+                        }
+                    }
                 });
-            
-            ui.add_space(50.0);
-            
-            // Verification dropdown
-            ui.label("Verification :");
-            egui::ComboBox::from_id_salt("verification")
-                .selected_text(&self.verification)
-                .width(100.0)
+        });
+        
+        if let WipingAlgorithm::MultiPass { passes, pattern } = &mut self.algorithm {
+            ui.add_space(10.0);
+            ui.horizontal(|ui| {
+                ui.label("Passes :");
+                ui.add(egui::DragValue::new(passes).range(1..=MAX_OVERWRITE_PASSES));
+                ui.add_space(20.0);
+                ui.label("Pattern :");
+                egui::ComboBox::from_id_salt("overwrite_pattern")
+                    .selected_text(pattern.label())
+                    .width(160.0)
+                    .show_ui(ui, |ui| {
+                        for choice in OverwritePattern::all() {
+                            ui.selectable_value(pattern, *choice, choice.label());
+                        }
+                    });
+                ui.label(egui::RichText::new("The final pass is read back; no named standard is claimed").weak().small());
+            });
+        }
+        
+        let preview = crate::devices::pass_preview(&self.algorithm);
+        if !preview.is_empty() {
+            ui.add_space(5.0);
+            let passes: Vec<String> = preview.iter().enumerate()
+                .map(|(pass, name)| format!("Pass {}: {}", pass + 1, name))
+                .collect();
+            ui.label(egui::RichText::new(passes.join(", ")).weak().small());
+        }
+        
+        if self.algorithm == WipingAlgorithm::NistAuto {
+            ui.add_space(10.0);
+            ui.horizontal(|ui| {
+                ui.label("Data sensitivity :");
+                egui::ComboBox::from_id_salt("sensitivity")
+                    .selected_text(self.sensitivity.label())
+                    .width(120.0)
+                    .show_ui(ui, |ui| {
+                        for sensitivity in Sensitivity::all() {
+                            ui.selectable_value(&mut self.sensitivity, *sensitivity, sensitivity.label());
+                        }
+                    });
+                ui.label(egui::RichText::new("Low clears HDDs and flash media; Moderate/High and SSDs are purged").weak().small());
+            });
+        }
+        
+        ui.add_space(10.0);
+        
+        ui.checkbox(&mut self.hash_device, "🔐 Hash entire device before and after wipe (doubles I/O time)");
+        
+        ui.horizontal(|ui| {
+            ui.label("On write error :");
+            egui::ComboBox::from_id_salt("error_policy")
+                .selected_text(self.error_policy.label())
+                .width(200.0)
                 .show_ui(ui, |ui| {
-                    ui.selectable_value(&mut self.verification, "json".to_string(), "json");
-                    ui.selectable_value(&mut self.verification, "xml".to_string(), "xml");
-                    ui.selectable_value(&mut self.verification, "pdf".to_string(), "pdf");
+                    for policy in ErrorPolicy::all() {
+                        ui.selectable_value(&mut self.error_policy, *policy, policy.label());
+                    }
                 });
         });
         
@@ -466,7 +741,7 @@ impl AdvancedOptionsWidget {
             ui.add_space(10.0);
             
             let can_erase = self.confirm_erase && can_sanitize;
-            println!("🔧 ERASE STATUS: confirm_erase={}, can_sanitize={}, can_erase={}", 
+            log::debug!("🔧 ERASE STATUS: confirm_erase={}, can_sanitize={}, can_erase={}", 
                     self.confirm_erase, can_sanitize, can_erase);
             
             let erase_button = egui::Button::new("ERASE")
@@ -475,7 +750,7 @@ impl AdvancedOptionsWidget {
                 
             let mut erase_clicked = false;
             if ui.add_enabled(can_erase, erase_button).clicked() {
-                println!("🚨 ERASE BUTTON CLICKED!");
+                log::debug!("🚨 ERASE BUTTON CLICKED!");
                 erase_clicked = true;
             }
             
@@ -61,8 +61,8 @@ impl SanitizationValidator {
 
     /// Perform pre-sanitization analysis and validation
     pub fn pre_sanitization_check(&self, drive_path: &str) -> io::Result<ComprehensiveDriveInfo> {
-        println!("🔍 PRE-SANITIZATION VALIDATION");
-        println!("Drive: {}", drive_path);
+        log::info!("🔍 PRE-SANITIZATION VALIDATION");
+        log::info!("Drive: {}", drive_path);
         
         let geometry = self.detect_drive_info(drive_path).unwrap_or_else(|_| DriveGeometry {
             model: "Unknown".to_string(),